@@ -1,4 +1,4 @@
-use super::*;
+use {super::*, crate::okx::datastore::ord::btc_name::DomainConfig};
 
 #[derive(Deserialize, Default, PartialEq, Debug)]
 #[serde(deny_unknown_fields)]
@@ -6,6 +6,8 @@ pub(crate) struct Config {
   pub(crate) hidden: HashSet<InscriptionId>,
   pub(crate) bitcoin_rpc_pass: Option<String>,
   pub(crate) bitcoin_rpc_user: Option<String>,
+  #[serde(default)]
+  pub(crate) domains: DomainConfig,
 }
 
 impl Config {
@@ -1,19 +1,35 @@
 use crate::okx::datastore::brc20::redb::table::{
-  get_balance, get_balances, get_token_info, get_tokens_info, get_transaction_receipts,
-  get_transferable_assets_by_account, get_transferable_assets_by_account_ticker,
-  get_transferable_assets_by_outpoint,
+  get_all_balances, get_all_transferable_assets, get_balance, get_balance_at_height, get_balances,
+  get_balances_page, get_block_checksum, get_tick_holders_count, get_tick_holders_page,
+  get_token_info, get_tokens_info, get_tokens_info_page, get_transaction_receipts,
+  get_transactions_by_address_page, get_transferable_assets_by_account_page,
+  get_transferable_assets_by_account_ticker, get_transferable_assets_by_outpoint,
+  insert_token_info, insert_transferable_asset, save_balance_history, save_block_checksum,
+  update_token_balance,
 };
 use crate::okx::datastore::ord::redb::table::{
-  get_collection_inscription_id, get_collections_of_inscription, get_transaction_operations,
+  get_collection_count, get_collection_counts, get_collection_inscription_id,
+  get_collection_inscription_ids_by_prefix, get_collection_members_page,
+  get_collections_of_inscription, get_district_block, get_transaction_operations,
   get_txout_by_outpoint,
 };
-use crate::okx::datastore::{brc20, ScriptKey};
+use crate::okx::datastore::undo::{
+  journal::{undo_multimap_table_writes, undo_table_writes},
+  redb::table::{get_rolled_back_blocks, get_undo_log},
+  Journal, RolledBackBlock, StateChange, TableWrite,
+};
+use crate::okx::datastore::{brc20, KeyPage, ScriptKey};
+use crate::okx::protocol::{registry::ProtocolRegistry, ProtocolConfig};
 
 use {
   self::{
-    entry::{Entry, HeaderValue, RuneEntryValue, RuneIdValue, SatPointValue, SatRange},
+    entry::{
+      Entry, HeaderValue, RuneEntryValue, RuneIdValue, RuneSupplyEntryValue, SatPointValue,
+      SatRange,
+    },
+    event::BlockEvent,
     reorg::*,
-    runes::{Rune, RuneId},
+    runes::{Rune, RuneEvent, RuneId},
     updater::Updater,
   },
   super::*,
@@ -26,7 +42,11 @@ use {
   chrono::SubsecRound,
   indicatif::{ProgressBar, ProgressStyle},
   log::log_enabled,
-  okx::datastore::ord::{self, bitmap::District, collections::CollectionKind},
+  okx::datastore::ord::{
+    self,
+    bitmap::{District, DistrictBlock, Parcel},
+    collections::CollectionKind,
+  },
   redb::{
     Database, DatabaseError, MultimapTable, MultimapTableDefinition, MultimapTableHandle,
     ReadOnlyTable, ReadableMultimapTable, ReadableTable, RedbKey, RedbValue, RepairSession,
@@ -35,28 +55,34 @@ use {
   std::{
     collections::{BTreeSet, HashMap},
     io::{BufWriter, Write},
-    sync::{Mutex, Once},
+    sync::{Mutex, Once, RwLock},
   },
+  tokio::sync::broadcast,
 };
 
-pub use self::entry::RuneEntry;
 pub(super) use self::entry::{
   InscriptionEntry, InscriptionEntryValue, InscriptionIdValue, OutPointValue, TxidValue,
 };
+pub use self::entry::{RuneEntry, RuneSupplyEntry};
 pub(super) use self::updater::BlockData;
 
 pub(crate) mod entry;
+pub(crate) mod event;
 mod fetcher;
 mod reorg;
 pub(crate) mod rtx;
+mod rune_holders;
 pub(crate) mod updater;
 
 mod extend;
+mod mempool;
 
 #[cfg(test)]
 pub(crate) mod testing;
 
-const SCHEMA_VERSION: u64 = 16;
+const SCHEMA_VERSION: u64 = 35;
+
+const BLOCK_EVENTS_CAPACITY: usize = 1024;
 
 macro_rules! define_table {
   ($name:ident, $key:ty, $value:ty) => {
@@ -71,38 +97,80 @@ macro_rules! define_multimap_table {
   };
 }
 
-define_multimap_table! { SATPOINT_TO_SEQUENCE_NUMBER, &SatPointValue, u32 }
-define_multimap_table! { SAT_TO_SEQUENCE_NUMBER, u64, u32 }
-define_multimap_table! { SEQUENCE_NUMBER_TO_CHILDREN, u32, u32 }
-define_table! { HEIGHT_TO_BLOCK_HEADER, u32, &HeaderValue }
-define_table! { HEIGHT_TO_LAST_SEQUENCE_NUMBER, u32, u32 }
-define_table! { HOME_INSCRIPTIONS, u32, InscriptionIdValue }
-define_table! { INSCRIPTION_ID_TO_SEQUENCE_NUMBER, InscriptionIdValue, u32 }
-define_table! { INSCRIPTION_NUMBER_TO_SEQUENCE_NUMBER, i32, u32 }
-define_table! { OUTPOINT_TO_RUNE_BALANCES, &OutPointValue, &[u8] }
-define_table! { OUTPOINT_TO_SAT_RANGES, &OutPointValue, &[u8] }
-define_table! { OUTPOINT_TO_ENTRY, &OutPointValue, &[u8]}
-define_table! { RUNE_ID_TO_RUNE_ENTRY, RuneIdValue, RuneEntryValue }
-define_table! { RUNE_TO_RUNE_ID, u128, RuneIdValue }
-define_table! { SAT_TO_SATPOINT, u64, &SatPointValue }
-define_table! { SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY, u32, InscriptionEntryValue }
-define_table! { SEQUENCE_NUMBER_TO_RUNE_ID, u32, RuneIdValue }
-define_table! { SEQUENCE_NUMBER_TO_SATPOINT, u32, &SatPointValue }
-define_table! { STATISTIC_TO_COUNT, u64, u64 }
-define_table! { TRANSACTION_ID_TO_RUNE, &TxidValue, u128 }
-define_table! { TRANSACTION_ID_TO_TRANSACTION, &TxidValue, &[u8] }
-define_table! { WRITE_TRANSACTION_STARTING_BLOCK_COUNT_TO_TIMESTAMP, u32, u128 }
+/// Declares the tables written while indexing blocks, and `undo_writes`, which undoes the journaled
+/// writes of one of them by name. Every write of these tables goes through the block's `Journal`.
+macro_rules! define_journaled_tables {
+  (
+    tables { $($table:ident: $key:ty => $value:ty,)* }
+    multimap_tables { $($multimap:ident: $multimap_key:ty => $multimap_value:ty,)* }
+  ) => {
+    $(define_table! { $table, $key, $value })*
+    $(define_multimap_table! { $multimap, $multimap_key, $multimap_value })*
+
+    /// Undoes the `writes` made to `table` in reverse order.
+    pub(crate) fn undo_writes(wtx: &WriteTransaction, table: &str, writes: &[TableWrite]) -> Result {
+      match table {
+        $(stringify!($table) => undo_table_writes(wtx, $table, writes),)*
+        $(stringify!($multimap) => undo_multimap_table_writes(wtx, $multimap, writes),)*
+        table => Err(anyhow!("no undo of writes to table {table}")),
+      }
+    }
+  };
+}
 
-// new
-define_table! { ORD_TX_TO_OPERATIONS, &TxidValue, &[u8] }
-define_table! { COLLECTIONS_KEY_TO_INSCRIPTION_ID, &str, InscriptionIdValue }
-define_multimap_table! { COLLECTIONS_INSCRIPTION_ID_TO_KINDS, InscriptionIdValue, &[u8] }
+define_journaled_tables! {
+  tables {
+    HEIGHT_TO_BLOCK_HEADER: u32 => &HeaderValue,
+    HEIGHT_TO_LAST_SEQUENCE_NUMBER: u32 => u32,
+    HOME_INSCRIPTIONS: u32 => InscriptionIdValue,
+    INSCRIPTION_ID_TO_SEQUENCE_NUMBER: InscriptionIdValue => u32,
+    INSCRIPTION_NUMBER_TO_SEQUENCE_NUMBER: i32 => u32,
+    OUTPOINT_TO_RUNE_BALANCES: &OutPointValue => &[u8],
+    RUNE_EVENTS: &TxidValue => &[u8],
+    OUTPOINT_TO_SAT_RANGES: &OutPointValue => &[u8],
+    OUTPOINT_TO_ENTRY: &OutPointValue => &[u8],
+    RUNE_ID_HEIGHT_TO_RUNE_SUPPLY: (RuneIdValue, u32) => RuneSupplyEntryValue,
+    RUNE_ID_TO_HOLDERS: &str => &[u8],
+    RUNE_ID_TO_RUNE_ENTRY: RuneIdValue => RuneEntryValue,
+    RUNE_ID_TO_SCRIPT_KEY_BALANCE: &str => u128,
+    RUNE_TO_RUNE_ID: u128 => RuneIdValue,
+    SAT_TO_SATPOINT: u64 => &SatPointValue,
+    SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY: u32 => InscriptionEntryValue,
+    SEQUENCE_NUMBER_TO_RUNE_ID: u32 => RuneIdValue,
+    SEQUENCE_NUMBER_TO_SATPOINT: u32 => &SatPointValue,
+    STATISTIC_TO_COUNT: u64 => u64,
+    TRANSACTION_ID_TO_RUNE: &TxidValue => u128,
+    TRANSACTION_ID_TO_TRANSACTION: &TxidValue => &[u8],
+    ORD_TX_TO_OPERATIONS: &TxidValue => &[u8],
+    COLLECTIONS_KEY_TO_INSCRIPTION_ID: &str => InscriptionIdValue,
+    COLLECTION_KIND_TO_COUNT: &str => u64,
+    BITMAP_DISTRICT_BLOCKS: u32 => &[u8],
+    BRC20_BALANCES: &str => &[u8],
+    BRC20_BALANCE_HISTORY: &str => &[u8],
+    BRC20_TOKEN: &str => &[u8],
+    BRC20_TICK_HOLDERS: &str => &[u8],
+    BRC20_EVENTS: &TxidValue => &[u8],
+    BRC20_BLOCK_CHECKSUMS: u32 => &[u8; 32],
+    BRC20_SCRIPT_KEY_TO_EVENTS: &str => &TxidValue,
+    BRC20_SATPOINT_TO_TRANSFERABLE_ASSETS: &SatPointValue => &[u8],
+    BRC20_ADDRESS_INSCRIPTION_NUMBER_TO_TRANSFERABLE_ASSETS: &str => &SatPointValue,
+  }
+  multimap_tables {
+    SATPOINT_TO_SEQUENCE_NUMBER: &SatPointValue => u32,
+    SCRIPT_KEY_TO_OUTPOINTS: &str => &OutPointValue,
+    SCRIPT_KEY_TO_RUNE_OUTPOINTS: &str => &OutPointValue,
+    SCRIPT_KEY_TO_UNSPENT_OUTPOINTS: &str => &OutPointValue,
+    SAT_TO_SEQUENCE_NUMBER: u64 => u32,
+    SEQUENCE_NUMBER_TO_CHILDREN: u32 => u32,
+    COLLECTIONS_INSCRIPTION_ID_TO_KINDS: InscriptionIdValue => &[u8],
+    COLLECTION_KIND_TO_SEQUENCE_NUMBERS: &str => u32,
+    BRC20_ADDRESS_TICKER_TO_TRANSFERABLE_ASSETS: &str => &SatPointValue,
+  }
+}
 
-define_table! { BRC20_BALANCES, &str, &[u8] }
-define_table! { BRC20_TOKEN, &str, &[u8] }
-define_table! { BRC20_EVENTS, &TxidValue, &[u8] }
-define_table! { BRC20_SATPOINT_TO_TRANSFERABLE_ASSETS, &SatPointValue, &[u8] }
-define_multimap_table! { BRC20_ADDRESS_TICKER_TO_TRANSFERABLE_ASSETS, &str, &SatPointValue }
+define_table! { WRITE_TRANSACTION_STARTING_BLOCK_COUNT_TO_TIMESTAMP, u32, u128 }
+define_table! { UNDO_LOGS, u32, &[u8] }
+define_table! { REORG_UNDO_LOGS, (u32, u64), &[u8] }
 
 #[derive(Debug, PartialEq)]
 pub enum List {
@@ -125,6 +193,7 @@ pub(crate) enum Statistic {
   SatRanges = 10,
   UnboundInscriptions = 11,
   IndexTransactions = 12,
+  IndexAddresses = 13,
 }
 
 impl Statistic {
@@ -150,6 +219,7 @@ pub(crate) struct Info {
   metadata_bytes: u64,
   outputs_traversed: u64,
   page_size: usize,
+  protocols: BTreeMap<String, Vec<String>>,
   sat_ranges: u64,
   stored_bytes: u64,
   tables: BTreeMap<String, TableInfo>,
@@ -214,6 +284,8 @@ impl<T> BitcoinCoreRpcResultExt<T> for Result<T, bitcoincore_rpc::Error> {
 }
 
 pub struct Index {
+  block_events: broadcast::Sender<BlockEvent>,
+  brc20_mempool: Option<mempool::Brc20Mempool>,
   client: Client,
   database: Database,
   durability: redb::Durability,
@@ -221,11 +293,13 @@ pub struct Index {
   genesis_block_coinbase_transaction: Transaction,
   genesis_block_coinbase_txid: Txid,
   height_limit: Option<u32>,
+  index_addresses: bool,
   index_runes: bool,
   index_sats: bool,
   index_transactions: bool,
   options: Options,
   path: PathBuf,
+  protocol_config: ProtocolConfig,
   started: DateTime<Utc>,
   unrecoverably_reorged: AtomicBool,
 }
@@ -263,6 +337,7 @@ impl Index {
       redb::Durability::Immediate
     };
 
+    let index_addresses;
     let index_runes;
     let index_sats;
     let index_transactions;
@@ -320,6 +395,7 @@ impl Index {
             }
           }
 
+          index_addresses = Self::is_statistic_set(&statistics, Statistic::IndexAddresses)?;
           index_runes = Self::is_statistic_set(&statistics, Statistic::IndexRunes)?;
           index_sats = Self::is_statistic_set(&statistics, Statistic::IndexSats)?;
           index_transactions = Self::is_statistic_set(&statistics, Statistic::IndexTransactions)?;
@@ -340,6 +416,9 @@ impl Index {
 
         tx.open_multimap_table(SATPOINT_TO_SEQUENCE_NUMBER)?;
         tx.open_multimap_table(SAT_TO_SEQUENCE_NUMBER)?;
+        tx.open_multimap_table(SCRIPT_KEY_TO_OUTPOINTS)?;
+        tx.open_multimap_table(SCRIPT_KEY_TO_RUNE_OUTPOINTS)?;
+        tx.open_multimap_table(SCRIPT_KEY_TO_UNSPENT_OUTPOINTS)?;
         tx.open_multimap_table(SEQUENCE_NUMBER_TO_CHILDREN)?;
         tx.open_table(HEIGHT_TO_BLOCK_HEADER)?;
         tx.open_table(HEIGHT_TO_LAST_SEQUENCE_NUMBER)?;
@@ -347,8 +426,12 @@ impl Index {
         tx.open_table(INSCRIPTION_ID_TO_SEQUENCE_NUMBER)?;
         tx.open_table(INSCRIPTION_NUMBER_TO_SEQUENCE_NUMBER)?;
         tx.open_table(OUTPOINT_TO_RUNE_BALANCES)?;
+        tx.open_table(RUNE_EVENTS)?;
         tx.open_table(OUTPOINT_TO_ENTRY)?;
+        tx.open_table(RUNE_ID_HEIGHT_TO_RUNE_SUPPLY)?;
+        tx.open_table(RUNE_ID_TO_HOLDERS)?;
         tx.open_table(RUNE_ID_TO_RUNE_ENTRY)?;
+        tx.open_table(RUNE_ID_TO_SCRIPT_KEY_BALANCE)?;
         tx.open_table(RUNE_TO_RUNE_ID)?;
         tx.open_table(SAT_TO_SATPOINT)?;
         tx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
@@ -361,14 +444,26 @@ impl Index {
         tx.open_table(ORD_TX_TO_OPERATIONS)?;
         tx.open_table(COLLECTIONS_KEY_TO_INSCRIPTION_ID)?;
         tx.open_multimap_table(COLLECTIONS_INSCRIPTION_ID_TO_KINDS)?;
+        tx.open_multimap_table(COLLECTION_KIND_TO_SEQUENCE_NUMBERS)?;
+        tx.open_table(COLLECTION_KIND_TO_COUNT)?;
+        tx.open_table(BITMAP_DISTRICT_BLOCKS)?;
 
         // brc20 tables
         tx.open_multimap_table(BRC20_ADDRESS_TICKER_TO_TRANSFERABLE_ASSETS)?;
+        tx.open_table(BRC20_ADDRESS_INSCRIPTION_NUMBER_TO_TRANSFERABLE_ASSETS)?;
         tx.open_table(BRC20_BALANCES)?;
+        tx.open_table(BRC20_BALANCE_HISTORY)?;
         tx.open_table(BRC20_TOKEN)?;
+        tx.open_table(BRC20_TICK_HOLDERS)?;
         tx.open_table(BRC20_EVENTS)?;
+        tx.open_table(BRC20_BLOCK_CHECKSUMS)?;
+        tx.open_table(BRC20_SCRIPT_KEY_TO_EVENTS)?;
         tx.open_table(BRC20_SATPOINT_TO_TRANSFERABLE_ASSETS)?;
 
+        // undo tables
+        tx.open_table(UNDO_LOGS)?;
+        tx.open_table(REORG_UNDO_LOGS)?;
+
         {
           let mut outpoint_to_sat_ranges = tx.open_table(OUTPOINT_TO_SAT_RANGES)?;
           let mut statistics = tx.open_table(STATISTIC_TO_COUNT)?;
@@ -377,10 +472,16 @@ impl Index {
             outpoint_to_sat_ranges.insert(&OutPoint::null().store(), [].as_slice())?;
           }
 
+          index_addresses = options.index_addresses;
           index_runes = options.index_runes();
           index_sats = options.index_sats;
           index_transactions = options.index_transactions;
 
+          Self::set_statistic(
+            &mut statistics,
+            Statistic::IndexAddresses,
+            u64::from(index_addresses),
+          )?;
           Self::set_statistic(
             &mut statistics,
             Statistic::IndexRunes,
@@ -409,26 +510,45 @@ impl Index {
 
     Ok(Self {
       genesis_block_coinbase_txid: genesis_block_coinbase_transaction.txid(),
+      block_events: broadcast::channel(BLOCK_EVENTS_CAPACITY).0,
+      brc20_mempool: options
+        .enable_brc20_mempool
+        .then(mempool::Brc20Mempool::default),
       client,
       database,
       durability,
       first_inscription_height: options.first_inscription_height(),
       genesis_block_coinbase_transaction,
       height_limit: options.height_limit,
+      index_addresses,
       index_runes,
       index_sats,
       index_transactions,
       options: options.clone(),
       path,
+      protocol_config: ProtocolConfig::new_with_options(options)?,
       started: Utc::now(),
       unrecoverably_reorged: AtomicBool::new(false),
     })
   }
 
+  pub(crate) fn subscribe_block_events(&self) -> broadcast::Receiver<BlockEvent> {
+    self.block_events.subscribe()
+  }
+
+  fn notify_block_event(&self, event: BlockEvent) {
+    // it's fine that nobody is subscribed.
+    self.block_events.send(event).ok();
+  }
+
   pub(crate) fn get_chain(&self) -> Chain {
     self.options.chain()
   }
 
+  pub(crate) fn protocol_config(&self) -> &ProtocolConfig {
+    &self.protocol_config
+  }
+
   #[cfg(test)]
   fn set_durability(&mut self, durability: redb::Durability) {
     self.durability = durability;
@@ -448,10 +568,18 @@ impl Index {
     Ok(true)
   }
 
+  pub(crate) fn has_address_index(&self) -> bool {
+    self.index_addresses
+  }
+
   pub(crate) fn has_rune_index(&self) -> bool {
     self.index_runes
   }
 
+  pub(crate) fn has_brc20_index(&self) -> bool {
+    self.options.enable_index_brc20
+  }
+
   pub(crate) fn has_transactions_index(&self) -> bool {
     self.index_transactions
   }
@@ -581,6 +709,14 @@ impl Index {
 
     insert_multimap_table_info(&mut tables, &wtx, total_bytes, SATPOINT_TO_SEQUENCE_NUMBER);
     insert_multimap_table_info(&mut tables, &wtx, total_bytes, SAT_TO_SEQUENCE_NUMBER);
+    insert_multimap_table_info(&mut tables, &wtx, total_bytes, SCRIPT_KEY_TO_OUTPOINTS);
+    insert_multimap_table_info(&mut tables, &wtx, total_bytes, SCRIPT_KEY_TO_RUNE_OUTPOINTS);
+    insert_multimap_table_info(
+      &mut tables,
+      &wtx,
+      total_bytes,
+      SCRIPT_KEY_TO_UNSPENT_OUTPOINTS,
+    );
     insert_multimap_table_info(&mut tables, &wtx, total_bytes, SEQUENCE_NUMBER_TO_CHILDREN);
     insert_table_info(&mut tables, &wtx, total_bytes, HEIGHT_TO_BLOCK_HEADER);
     insert_table_info(
@@ -603,9 +739,23 @@ impl Index {
       INSCRIPTION_NUMBER_TO_SEQUENCE_NUMBER,
     );
     insert_table_info(&mut tables, &wtx, total_bytes, OUTPOINT_TO_RUNE_BALANCES);
+    insert_table_info(&mut tables, &wtx, total_bytes, RUNE_EVENTS);
     insert_table_info(&mut tables, &wtx, total_bytes, OUTPOINT_TO_SAT_RANGES);
     insert_table_info(&mut tables, &wtx, total_bytes, OUTPOINT_TO_ENTRY);
+    insert_table_info(
+      &mut tables,
+      &wtx,
+      total_bytes,
+      RUNE_ID_HEIGHT_TO_RUNE_SUPPLY,
+    );
+    insert_table_info(&mut tables, &wtx, total_bytes, RUNE_ID_TO_HOLDERS);
     insert_table_info(&mut tables, &wtx, total_bytes, RUNE_ID_TO_RUNE_ENTRY);
+    insert_table_info(
+      &mut tables,
+      &wtx,
+      total_bytes,
+      RUNE_ID_TO_SCRIPT_KEY_BALANCE,
+    );
     insert_table_info(&mut tables, &wtx, total_bytes, RUNE_TO_RUNE_ID);
     insert_table_info(&mut tables, &wtx, total_bytes, SAT_TO_SATPOINT);
     insert_table_info(
@@ -644,9 +794,21 @@ impl Index {
       total_bytes,
       COLLECTIONS_INSCRIPTION_ID_TO_KINDS,
     );
+    insert_multimap_table_info(
+      &mut tables,
+      &wtx,
+      total_bytes,
+      COLLECTION_KIND_TO_SEQUENCE_NUMBERS,
+    );
+    insert_table_info(&mut tables, &wtx, total_bytes, COLLECTION_KIND_TO_COUNT);
+    insert_table_info(&mut tables, &wtx, total_bytes, BITMAP_DISTRICT_BLOCKS);
     insert_table_info(&mut tables, &wtx, total_bytes, BRC20_BALANCES);
+    insert_table_info(&mut tables, &wtx, total_bytes, BRC20_BALANCE_HISTORY);
     insert_table_info(&mut tables, &wtx, total_bytes, BRC20_TOKEN);
+    insert_table_info(&mut tables, &wtx, total_bytes, BRC20_TICK_HOLDERS);
     insert_table_info(&mut tables, &wtx, total_bytes, BRC20_EVENTS);
+    insert_table_info(&mut tables, &wtx, total_bytes, BRC20_BLOCK_CHECKSUMS);
+    insert_table_info(&mut tables, &wtx, total_bytes, BRC20_SCRIPT_KEY_TO_EVENTS);
     insert_table_info(
       &mut tables,
       &wtx,
@@ -659,6 +821,14 @@ impl Index {
       total_bytes,
       BRC20_ADDRESS_TICKER_TO_TRANSFERABLE_ASSETS,
     );
+    insert_table_info(
+      &mut tables,
+      &wtx,
+      total_bytes,
+      BRC20_ADDRESS_INSCRIPTION_NUMBER_TO_TRANSFERABLE_ASSETS,
+    );
+    insert_table_info(&mut tables, &wtx, total_bytes, UNDO_LOGS);
+    insert_table_info(&mut tables, &wtx, total_bytes, REORG_UNDO_LOGS);
 
     for table in wtx.list_tables()? {
       assert!(tables.contains_key(table.name()));
@@ -694,6 +864,19 @@ impl Index {
         metadata_bytes,
         outputs_traversed,
         page_size: stats.page_size(),
+        protocols: ProtocolRegistry::new(&self.protocol_config)
+          .enabled()
+          .map(|protocol| {
+            (
+              protocol.name().to_string(),
+              protocol
+                .tables()
+                .iter()
+                .map(|table| table.to_string())
+                .collect(),
+            )
+          })
+          .collect(),
         sat_ranges,
         stored_bytes,
         tables,
@@ -718,6 +901,12 @@ impl Index {
     Ok(info)
   }
 
+  /// Rolls the index back so that `height` is the last block indexed, and returns the height of the next block to
+  /// index.
+  pub(crate) fn rollback(&self, height: u32) -> Result<u32> {
+    Reorg::rollback(self, height)
+  }
+
   pub(crate) fn update(&self) -> Result {
     let mut updater = Updater::new(self)?;
 
@@ -814,6 +1003,176 @@ impl Index {
     Ok(())
   }
 
+  /// Takes a snapshot of the BRC20 state at the indexed chain tip.
+  pub(crate) fn export_brc20_snapshot(&self) -> Result<brc20::Snapshot> {
+    let rtx = self.database.begin_read()?;
+
+    let (height, block_hash) = rtx
+      .open_table(HEIGHT_TO_BLOCK_HEADER)?
+      .range(0..)?
+      .next_back()
+      .transpose()?
+      .map(|(height, header)| (height.value(), Header::load(*header.value()).block_hash()))
+      .ok_or_else(|| anyhow!("no blocks indexed"))?;
+
+    log::info!("exporting BRC20 state at block height {height}");
+
+    let block_checksum = get_block_checksum(&rtx.open_table(BRC20_BLOCK_CHECKSUMS)?, height)?;
+
+    let tokens = get_tokens_info(&rtx.open_table(BRC20_TOKEN)?)?;
+
+    let balances = get_all_balances(&rtx.open_table(BRC20_BALANCES)?)?
+      .into_iter()
+      .map(|(script_key, balance)| brc20::SnapshotBalance {
+        script_key,
+        balance,
+      })
+      .collect();
+
+    let transferable_assets =
+      get_all_transferable_assets(&rtx.open_table(BRC20_SATPOINT_TO_TRANSFERABLE_ASSETS)?)?
+        .into_iter()
+        .map(|(satpoint, asset)| brc20::SnapshotTransferableAsset { satpoint, asset })
+        .collect();
+
+    Ok(brc20::Snapshot::new(
+      height,
+      block_hash,
+      block_checksum,
+      tokens,
+      balances,
+      transferable_assets,
+    ))
+  }
+
+  /// Seeds the BRC20 tables of an index without BRC20 state from a snapshot taken at the indexed chain tip.
+  pub(crate) fn import_brc20_snapshot(&self, snapshot: &brc20::Snapshot) -> Result {
+    if !snapshot.is_valid() {
+      bail!("snapshot state hash {} does not match", snapshot.state_hash);
+    }
+
+    // the checksum of the next block chains onto the checksum of the snapshot block.
+    if snapshot.block_checksum.is_none() && snapshot.height >= self.options.first_brc20_height() {
+      bail!(
+        "snapshot at height {} has no block checksum",
+        snapshot.height
+      );
+    }
+
+    let wtx = self.begin_write()?;
+
+    {
+      let height_to_block_header = wtx.open_table(HEIGHT_TO_BLOCK_HEADER)?;
+
+      let block_count = height_to_block_header
+        .range(0..)?
+        .next_back()
+        .transpose()?
+        .map(|(height, _header)| height.value() + 1)
+        .unwrap_or(0);
+
+      if block_count != snapshot.height + 1 {
+        bail!(
+          "snapshot is at height {} but index is at height {}",
+          snapshot.height,
+          i64::from(block_count) - 1
+        );
+      }
+
+      let block_hash = height_to_block_header
+        .get(snapshot.height)?
+        .map(|header| Header::load(*header.value()).block_hash());
+
+      if block_hash != Some(snapshot.block_hash) {
+        bail!(
+          "snapshot block {} is not in the index at height {}",
+          snapshot.block_hash,
+          snapshot.height
+        );
+      }
+    }
+
+    let mut token_table = wtx.open_table(BRC20_TOKEN)?;
+    let mut balance_table = wtx.open_table(BRC20_BALANCES)?;
+    let mut holder_table = wtx.open_table(BRC20_TICK_HOLDERS)?;
+    let mut balance_history_table = wtx.open_table(BRC20_BALANCE_HISTORY)?;
+    let mut satpoint_table = wtx.open_table(BRC20_SATPOINT_TO_TRANSFERABLE_ASSETS)?;
+    let mut address_table = wtx.open_multimap_table(BRC20_ADDRESS_TICKER_TO_TRANSFERABLE_ASSETS)?;
+    let mut number_table =
+      wtx.open_table(BRC20_ADDRESS_INSCRIPTION_NUMBER_TO_TRANSFERABLE_ASSETS)?;
+
+    if !token_table.is_empty()? || !balance_table.is_empty()? || !satpoint_table.is_empty()? {
+      bail!("index already has BRC20 state");
+    }
+
+    log::info!(
+      "importing BRC20 state at block height {} with state hash {}",
+      snapshot.height,
+      snapshot.state_hash
+    );
+
+    // the imported state stands in for the blocks up to the snapshot, which are never disconnected.
+    let journal = Journal::disabled();
+
+    if let Some(block_checksum) = &snapshot.block_checksum {
+      save_block_checksum(
+        &journal,
+        &mut wtx.open_table(BRC20_BLOCK_CHECKSUMS)?,
+        snapshot.height,
+        block_checksum,
+      )?;
+    }
+
+    for token in &snapshot.tokens {
+      insert_token_info(&journal, &mut token_table, &token.tick, token)?;
+    }
+
+    for brc20::SnapshotBalance {
+      script_key,
+      balance,
+    } in &snapshot.balances
+    {
+      update_token_balance(
+        &journal,
+        &mut balance_table,
+        &mut holder_table,
+        script_key,
+        balance.clone(),
+      )?;
+      save_balance_history(
+        &journal,
+        &mut balance_history_table,
+        script_key,
+        snapshot.height,
+        balance,
+      )?;
+    }
+
+    for brc20::SnapshotTransferableAsset { satpoint, asset } in &snapshot.transferable_assets {
+      insert_transferable_asset(
+        &journal,
+        &mut satpoint_table,
+        &mut address_table,
+        &mut number_table,
+        *satpoint,
+        asset,
+      )?;
+    }
+
+    drop(token_table);
+    drop(balance_table);
+    drop(holder_table);
+    drop(balance_history_table);
+    drop(satpoint_table);
+    drop(address_table);
+    drop(number_table);
+
+    Self::increment_statistic(&wtx, Statistic::Commits, 1)?;
+    wtx.commit()?;
+
+    Ok(())
+  }
+
   pub(crate) fn begin_read(&self) -> Result<rtx::Rtx> {
     Ok(rtx::Rtx(self.database.begin_read()?))
   }
@@ -822,6 +1181,15 @@ impl Index {
     self.options.bitcoin_rpc_client(None)
   }
 
+  /// The client the index is updated with, shared by readers that outlive a single request.
+  pub(crate) fn client(&self) -> &Client {
+    &self.client
+  }
+
+  pub(crate) fn max_reorg_depth(&self) -> u32 {
+    self.options.max_reorg_depth()
+  }
+
   fn begin_write(&self) -> Result<WriteTransaction> {
     let mut tx = self.database.begin_write()?;
     tx.set_durability(self.durability);
@@ -1080,6 +1448,48 @@ impl Index {
     Ok(runic)
   }
 
+  pub(crate) fn get_brc20_transferable_inscriptions(
+    &self,
+    outpoints: &[OutPoint],
+  ) -> Result<BTreeSet<SatPoint>> {
+    let rtx = self.begin_read()?;
+
+    let mut transferable = BTreeSet::new();
+
+    for outpoint in outpoints {
+      for (satpoint, _) in rtx.brc20_transferable_assets_on_output_with_satpoints(*outpoint)? {
+        transferable.insert(satpoint);
+      }
+    }
+
+    Ok(transferable)
+  }
+
+  /// The unspent outputs of the addresses, as tracked by an index created with
+  /// `--index-addresses`.
+  pub(crate) fn get_address_unspent_outputs(
+    &self,
+    addresses: &[Address],
+  ) -> Result<BTreeMap<OutPoint, Amount>> {
+    let rtx = self.begin_read()?;
+
+    let mut utxos = BTreeMap::new();
+
+    for address in addresses {
+      let script_key = ScriptKey::from_address(address.clone());
+
+      for outpoint in rtx.script_key_to_unspent_outpoints(&script_key.to_string())? {
+        let tx_out = rtx
+          .outpoint_to_entry(outpoint)?
+          .ok_or_else(|| anyhow!("index has not seen {outpoint}"))?;
+
+        utxos.insert(outpoint, Amount::from_sat(tx_out.value));
+      }
+    }
+
+    Ok(utxos)
+  }
+
   pub(crate) fn get_rune_balance_map(&self) -> Result<BTreeMap<Rune, BTreeMap<OutPoint, u128>>> {
     let outpoint_balances = self.get_rune_balances()?;
 
@@ -4633,6 +5043,374 @@ mod tests {
     }
   }
 
+  #[test]
+  fn block_events_are_sent_on_commit_and_reorg() {
+    let mut context = Context::builder().build();
+    context.index.set_durability(redb::Durability::Immediate);
+
+    let mut block_events = context.index.subscribe_block_events();
+
+    context.mine_blocks(1);
+    context.mine_blocks(6);
+    context.rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, Default::default())],
+      ..Default::default()
+    });
+    context.mine_blocks(1);
+
+    for height in 1..9 {
+      assert_eq!(
+        block_events.try_recv().unwrap(),
+        BlockEvent::Connected {
+          height,
+          block_hash: context.index.block_hash(Some(height)).unwrap().unwrap(),
+        }
+      );
+    }
+
+    let block_hash = context.index.block_hash(Some(8)).unwrap().unwrap();
+
+    context.rpc_server.invalidate_tip();
+    context.mine_blocks(2);
+
+    let mut events = Vec::new();
+    while let Ok(event) = block_events.try_recv() {
+      events.push(event);
+    }
+
+    assert!(events.contains(&BlockEvent::Disconnected {
+      height: 8,
+      block_hash
+    }));
+    assert_eq!(
+      events.last(),
+      Some(&BlockEvent::Connected {
+        height: 9,
+        block_hash: context.index.block_hash(Some(9)).unwrap().unwrap(),
+      })
+    );
+  }
+
+  #[test]
+  fn reorg_saves_undo_logs_of_rolled_back_blocks() {
+    let mut context = Context::builder().build();
+    context.index.set_durability(redb::Durability::Immediate);
+
+    context.mine_blocks(1);
+    context.mine_blocks(6);
+    context.rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, Default::default())],
+      ..Default::default()
+    });
+    context.mine_blocks(1);
+
+    assert_eq!(
+      context.index.begin_read().unwrap().undo_log(8).unwrap(),
+      Some(Vec::new())
+    );
+
+    let block_hash = context.index.block_hash(Some(8)).unwrap().unwrap();
+
+    context.rpc_server.invalidate_tip();
+    context.mine_blocks(2);
+
+    let reorgs = context
+      .index
+      .begin_read()
+      .unwrap()
+      .rolled_back_blocks(8)
+      .unwrap();
+
+    assert_eq!(reorgs.len(), 1);
+    assert_eq!(
+      reorgs[0]
+        .iter()
+        .map(|block| block.height)
+        .collect::<Vec<u32>>(),
+      vec![8]
+    );
+    assert_eq!(reorgs[0][0].block_hash, block_hash);
+  }
+
+  #[test]
+  fn rollback_undoes_blocks_above_height() {
+    let context = Context::builder().build();
+
+    for _ in 0..12 {
+      context.mine_blocks(1);
+    }
+
+    let block_hash = context.index.block_hash(Some(6)).unwrap().unwrap();
+
+    assert_eq!(
+      context.index.rollback(1).unwrap_err().to_string(),
+      "cannot roll back to height 1, the undo log of block 2 is pruned"
+    );
+
+    let rolled_back_height = context.index.rollback(4).unwrap();
+    assert_eq!(rolled_back_height, 5);
+    assert_eq!(context.index.block_count().unwrap(), 5);
+
+    let reorgs = context
+      .index
+      .begin_read()
+      .unwrap()
+      .rolled_back_blocks(5)
+      .unwrap();
+    assert_eq!(reorgs.len(), 1);
+    assert_eq!(
+      reorgs[0]
+        .iter()
+        .map(|block| block.height)
+        .collect::<Vec<u32>>(),
+      (5..=12).collect::<Vec<u32>>()
+    );
+    assert_eq!(reorgs[0][1].block_hash, block_hash);
+
+    context.index.update().unwrap();
+    assert_eq!(context.index.block_count().unwrap(), 13);
+  }
+
+  #[test]
+  fn rollback_undoes_traversal_statistics() {
+    let context = Context::builder().arg("--index-sats").build();
+
+    context.mine_blocks(4);
+
+    let statistics = || {
+      (
+        context.index.statistic(Statistic::OutputsTraversed),
+        context.index.statistic(Statistic::SatRanges),
+      )
+    };
+
+    let before = statistics();
+
+    context.mine_blocks(3);
+    assert_ne!(statistics(), before);
+
+    context.index.rollback(4).unwrap();
+    assert_eq!(statistics(), before);
+  }
+
+  #[test]
+  fn blocks_within_max_reorg_depth_of_the_chain_tip_can_be_rolled_back() {
+    let context = Context::builder().build();
+
+    context.rpc_server.mine_blocks(20);
+    context.index.update().unwrap();
+
+    assert_eq!(
+      context.index.rollback(9).unwrap_err().to_string(),
+      "cannot roll back to height 9, the undo log of block 10 is pruned"
+    );
+    assert_eq!(context.index.rollback(10).unwrap(), 11);
+  }
+
+  #[test]
+  fn every_table_written_by_indexing_can_be_undone() {
+    let context = Context::builder()
+      .args([
+        "--index-sats",
+        "--index-addresses",
+        "--enable-index-brc20",
+        "--enable-index-bitmap",
+        "--enable-index-domain",
+        "--enable-save-ord-receipts",
+      ])
+      .build();
+
+    context.mine_blocks(1);
+    context.rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "1.bitmap").to_witness())],
+      ..Default::default()
+    });
+    context.mine_blocks(1);
+
+    // the tables that aren't written by `Updater::index_block`, and so have no journaled writes.
+    let unjournaled = [
+      WRITE_TRANSACTION_STARTING_BLOCK_COUNT_TO_TIMESTAMP.name(),
+      UNDO_LOGS.name(),
+      REORG_UNDO_LOGS.name(),
+    ];
+
+    let wtx = context.index.begin_write().unwrap();
+
+    let tables = wtx
+      .list_tables()
+      .unwrap()
+      .map(|table| table.name().to_string())
+      .chain(
+        wtx
+          .list_multimap_tables()
+          .unwrap()
+          .map(|table| table.name().to_string()),
+      )
+      .collect::<Vec<String>>();
+
+    assert!(tables.len() > unjournaled.len());
+
+    for table in tables {
+      if !unjournaled.contains(&table.as_str()) {
+        undo_writes(&wtx, &table, &[]).unwrap();
+      }
+    }
+  }
+
+  #[test]
+  fn info_lists_the_tables_of_enabled_protocols() {
+    let context = Context::builder()
+      .args(["--enable-index-brc20", "--enable-index-bitmap"])
+      .build();
+
+    let info = context.index.info().unwrap();
+
+    assert_eq!(
+      info.protocols.keys().collect::<Vec<_>>(),
+      vec!["bitmap", "brc20"]
+    );
+    for table in info.protocols.values().flatten() {
+      assert!(info.tables.contains_key(table), "unknown table {table}");
+    }
+  }
+
+  #[test]
+  fn brc20_snapshot_export_and_import() {
+    let context = Context::builder().arg("--enable-index-brc20").build();
+
+    context.mine_blocks(1);
+    context.rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(
+        1,
+        0,
+        0,
+        inscription(
+          "text/plain;charset=utf-8",
+          r#"{"p":"brc-20","op":"deploy","tick":"ordi","max":"1000","lim":"10"}"#,
+        )
+        .to_witness(),
+      )],
+      ..Default::default()
+    });
+    context.mine_blocks(1);
+    context.rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(
+        2,
+        0,
+        0,
+        inscription(
+          "text/plain;charset=utf-8",
+          r#"{"p":"brc-20","op":"mint","tick":"ordi","amt":"10"}"#,
+        )
+        .to_witness(),
+      )],
+      ..Default::default()
+    });
+    context.mine_blocks(1);
+
+    let snapshot = context.index.export_brc20_snapshot().unwrap();
+    assert_eq!(snapshot.height, 3);
+    assert!(snapshot.block_checksum.is_some());
+    assert_eq!(snapshot.tokens.len(), 1);
+    assert_eq!(snapshot.balances.len(), 1);
+    assert_eq!(
+      snapshot.balances[0].balance.overall_balance,
+      10_000_000_000_000_000_000
+    );
+    assert!(snapshot.is_valid());
+
+    let index = Index::open(&Options {
+      index: Some(context.tempdir.path().join("replica.redb")),
+      enable_index_brc20: false,
+      ..context.options.clone()
+    })
+    .unwrap();
+    index.update().unwrap();
+
+    assert_eq!(
+      index
+        .import_brc20_snapshot(&brc20::Snapshot {
+          block_checksum: None,
+          ..snapshot.clone()
+        })
+        .unwrap_err()
+        .to_string(),
+      "snapshot at height 3 has no block checksum"
+    );
+
+    index.import_brc20_snapshot(&snapshot).unwrap();
+    assert_eq!(index.export_brc20_snapshot().unwrap(), snapshot);
+    assert!(index.import_brc20_snapshot(&snapshot).is_err());
+  }
+
+  #[test]
+  fn reorg_deeper_than_ten_blocks_matches_a_fresh_index() {
+    let context = Context::builder()
+      .args(["--enable-index-brc20", "--max-reorg-depth", "30"])
+      .build();
+
+    context.mine_blocks(1);
+    context.rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(
+        1,
+        0,
+        0,
+        inscription(
+          "text/plain;charset=utf-8",
+          r#"{"p":"brc-20","op":"deploy","tick":"ordi","max":"1000","lim":"10"}"#,
+        )
+        .to_witness(),
+      )],
+      ..Default::default()
+    });
+    context.mine_blocks(1);
+
+    for block in [2, 3] {
+      context.mine_blocks(10);
+      context.rpc_server.broadcast_tx(TransactionTemplate {
+        inputs: &[(
+          block,
+          0,
+          0,
+          inscription(
+            "text/plain;charset=utf-8",
+            r#"{"p":"brc-20","op":"mint","tick":"ordi","amt":"10"}"#,
+          )
+          .to_witness(),
+        )],
+        ..Default::default()
+      });
+    }
+    context.mine_blocks(5);
+
+    assert_eq!(
+      context.index.export_brc20_snapshot().unwrap().balances[0]
+        .balance
+        .overall_balance,
+      20_000_000_000_000_000_000
+    );
+
+    for _ in 0..14 {
+      context.rpc_server.invalidate_tip();
+    }
+    context.mine_blocks(15);
+
+    let snapshot = context.index.export_brc20_snapshot().unwrap();
+    assert_eq!(
+      snapshot.balances[0].balance.overall_balance,
+      10_000_000_000_000_000_000
+    );
+
+    let index = Index::open(&Options {
+      index: Some(context.tempdir.path().join("fresh.redb")),
+      ..context.options.clone()
+    })
+    .unwrap();
+    index.update().unwrap();
+
+    assert_eq!(index.export_brc20_snapshot().unwrap(), snapshot);
+  }
+
   #[test]
   fn recover_from_3_block_deep_and_consecutive_reorg() {
     for mut context in Context::configurations() {
@@ -5893,4 +6671,49 @@ mod tests {
       assert_eq!(sat, entry.sat);
     }
   }
+
+  #[test]
+  fn address_index_tracks_unspent_outputs() {
+    // the outputs are tracked whether or not the inscriptions of the blocks are indexed
+    for args in [
+      vec!["--index-addresses"],
+      vec!["--index-addresses", "--first-inscription-height", "10"],
+      vec!["--index-addresses", "--no-index-inscriptions"],
+    ] {
+      let context = Context::builder().args(args).build();
+
+      let coinbase = context.mine_blocks(1)[0].txdata[0].txid();
+
+      // the test outputs pay to the empty script, which has no address
+      let script_key = ScriptKey::from_script(&ScriptBuf::new(), Chain::Mainnet).to_string();
+
+      let unspent_outpoints = || {
+        context
+          .index
+          .begin_read()
+          .unwrap()
+          .script_key_to_unspent_outpoints(&script_key)
+          .unwrap()
+      };
+
+      assert_eq!(unspent_outpoints(), [OutPoint::new(coinbase, 0)]);
+
+      let txid = context.rpc_server.broadcast_tx(TransactionTemplate {
+        inputs: &[(1, 0, 0, Default::default())],
+        outputs: 2,
+        ..Default::default()
+      });
+
+      let coinbase = context.mine_blocks(1)[0].txdata[0].txid();
+
+      let mut expected = vec![
+        OutPoint::new(coinbase, 0),
+        OutPoint::new(txid, 0),
+        OutPoint::new(txid, 1),
+      ];
+      expected.sort();
+
+      assert_eq!(unspent_outpoints(), expected);
+    }
+  }
 }
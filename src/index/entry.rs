@@ -167,6 +167,79 @@ impl Entry for RuneEntry {
   }
 }
 
+/// The supply of a rune at the end of a block that etched, minted or burned it.
+#[derive(Debug, PartialEq, Copy, Clone, Default)]
+pub struct RuneSupplyEntry {
+  /// The amount burned up to and including the block.
+  pub burned: u128,
+  /// The amount burned in the block by runestones with unrecognized fields.
+  pub burned_invalid_runestone: u128,
+  /// The amount burned in the block by allocating it to OP_RETURN outputs.
+  pub burned_op_return: u128,
+  /// The amount burned in the block for lack of an output to receive it.
+  pub burned_unallocated: u128,
+  /// The amount minted in the block.
+  pub minted: u128,
+  /// The number of mints up to and including the block.
+  pub mints: u64,
+  /// The supply up to and including the block.
+  pub supply: u128,
+}
+
+pub(super) type RuneSupplyEntryValue = (
+  u128, // burned
+  u128, // burned_invalid_runestone
+  u128, // burned_op_return
+  u128, // burned_unallocated
+  u128, // minted
+  u64,  // mints
+  u128, // supply
+);
+
+impl RuneSupplyEntry {
+  pub(crate) fn circulating(&self) -> u128 {
+    self.supply - self.burned
+  }
+}
+
+impl Entry for RuneSupplyEntry {
+  type Value = RuneSupplyEntryValue;
+
+  fn load(
+    (
+      burned,
+      burned_invalid_runestone,
+      burned_op_return,
+      burned_unallocated,
+      minted,
+      mints,
+      supply,
+    ): RuneSupplyEntryValue,
+  ) -> Self {
+    Self {
+      burned,
+      burned_invalid_runestone,
+      burned_op_return,
+      burned_unallocated,
+      minted,
+      mints,
+      supply,
+    }
+  }
+
+  fn store(self) -> Self::Value {
+    (
+      self.burned,
+      self.burned_invalid_runestone,
+      self.burned_op_return,
+      self.burned_unallocated,
+      self.minted,
+      self.mints,
+      self.supply,
+    )
+  }
+}
+
 pub(super) type RuneIdValue = (u32, u16);
 
 impl Entry for RuneId {
@@ -474,6 +547,24 @@ mod tests {
     assert_eq!(RuneEntry::load(value), entry);
   }
 
+  #[test]
+  fn rune_supply_entry() {
+    let entry = RuneSupplyEntry {
+      burned: 1,
+      burned_invalid_runestone: 2,
+      burned_op_return: 3,
+      burned_unallocated: 4,
+      minted: 5,
+      mints: 6,
+      supply: 7,
+    };
+
+    let value = (1, 2, 3, 4, 5, 6, 7);
+
+    assert_eq!(entry.store(), value);
+    assert_eq!(RuneSupplyEntry::load(value), entry);
+  }
+
   #[test]
   fn rune_id_entry() {
     assert_eq!(
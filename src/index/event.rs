@@ -0,0 +1,8 @@
+use super::*;
+
+/// Notification of a block committed to or rolled back from the index.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum BlockEvent {
+  Connected { height: u32, block_hash: BlockHash },
+  Disconnected { height: u32, block_hash: BlockHash },
+}
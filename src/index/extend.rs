@@ -152,6 +152,31 @@ impl Index {
     })
   }
 
+  pub(crate) fn get_brc20_balance_by_tick_and_address_at_height(
+    tick: brc20::Tick,
+    script_key: ScriptKey,
+    height: u32,
+    rtx: &Rtx,
+  ) -> Result<Option<brc20::Balance>> {
+    let Some(tick_info) = rtx.brc20_get_tick_info(&tick)? else {
+      return Ok(None);
+    };
+
+    if tick_info.deployed_number > height {
+      return Ok(None);
+    }
+
+    Ok(Some(
+      rtx
+        .brc20_get_balance_by_address_at_height(&tick, script_key, height)?
+        .unwrap_or(brc20::Balance {
+          tick,
+          overall_balance: 0,
+          transferable_balance: 0,
+        }),
+    ))
+  }
+
   pub(crate) fn get_brc20_transferable_utxo_by_tick_and_address(
     tick: brc20::Tick,
     script_key: ScriptKey,
@@ -208,11 +233,12 @@ impl Index {
     Ok(Some(receipts))
   }
 
-  pub(crate) fn get_brc20_block_receipts(
+  /// The transactions of the block, provided it is the indexed block at its height.
+  fn get_indexed_block_txids(
     block_hash: BlockHash,
     rtx: &Rtx,
     client: &Client,
-  ) -> Result<Vec<(bitcoin::Txid, Vec<brc20::Receipt>)>> {
+  ) -> Result<Vec<Txid>> {
     // get block from btc client.
     let blockinfo = client.get_block_info(&block_hash)?;
 
@@ -233,14 +259,46 @@ impl Index {
       ));
     }
 
-    let mut result = Vec::new();
-    for txid in blockinfo.tx {
-      let Some(inscriptions) = rtx.brc20_transaction_id_to_transaction_receipt(txid)? else {
-        continue;
-      };
-      result.push((txid, inscriptions));
-    }
-    Ok(result)
+    Ok(blockinfo.tx)
+  }
+
+  /// The BRC20 receipts of the transactions of the block in the page, keyed by transaction position.
+  pub(crate) fn get_brc20_block_receipts(
+    block_hash: BlockHash,
+    rtx: &Rtx,
+    client: &Client,
+    page: &KeyPage,
+  ) -> Result<Vec<(String, (bitcoin::Txid, Vec<brc20::Receipt>))>> {
+    page.read_positions(
+      Self::get_indexed_block_txids(block_hash, rtx, client)?.into_iter(),
+      |txid| {
+        Ok(
+          rtx
+            .brc20_transaction_id_to_transaction_receipt(txid)?
+            .filter(|receipts| !receipts.is_empty())
+            .map(|receipts| (txid, receipts)),
+        )
+      },
+    )
+  }
+
+  /// The rune events of the transactions of the block in the page, keyed by transaction position.
+  pub(crate) fn get_rune_block_events(
+    block_hash: BlockHash,
+    rtx: &Rtx,
+    client: &Client,
+    page: &KeyPage,
+  ) -> Result<Vec<(String, (bitcoin::Txid, Vec<RuneEvent>))>> {
+    page.read_positions(
+      Self::get_indexed_block_txids(block_hash, rtx, client)?.into_iter(),
+      |txid| {
+        Ok(
+          rtx
+            .rune_transaction_events(txid)?
+            .map(|events| (txid, events)),
+        )
+      },
+    )
   }
 
   // Assume these are helper functions defined elsewhere in the module.
@@ -0,0 +1,377 @@
+use {
+  super::{
+    rtx::Rtx,
+    updater::inscription_updater::{float_inscriptions, Origin, SpentOutputs},
+    *,
+  },
+  crate::okx::{
+    datastore::ord::operation::{Action, InscriptionOp},
+    protocol::{
+      brc20::{Simulator, SimulatorState},
+      ChainContext,
+    },
+  },
+};
+
+/// The BRC20 simulation of the mempool, carried over between updates so only the transactions
+/// that entered the mempool since the last one are simulated.
+#[derive(Default)]
+pub(crate) struct Brc20Mempool {
+  simulation: Mutex<Simulation>,
+  /// The receipts of the simulated transactions, in execution order.
+  receipts: RwLock<Vec<(Txid, Vec<brc20::Receipt>)>>,
+}
+
+#[derive(Default)]
+struct Simulation {
+  /// The indexed tip the simulation is on top of.
+  tip: Option<BlockHash>,
+  /// The transactions in the mempool.
+  transactions: HashMap<Txid, Transaction>,
+  /// The transactions simulated or skipped on top of the tip.
+  processed: HashSet<Txid>,
+  pending: PendingState,
+  state: SimulatorState,
+}
+
+/// The state built up while walking the unconfirmed transactions.
+#[derive(Default)]
+struct PendingState {
+  /// The inscriptions on the outputs of unconfirmed transactions.
+  inscriptions: HashMap<OutPoint, Vec<(SatPoint, InscriptionId)>>,
+  /// The inscriptions created by unconfirmed transactions, with their sequence numbers and whether
+  /// they are cursed or vindicated.
+  new_inscriptions: HashMap<InscriptionId, (u32, bool)>,
+  /// The outputs of unconfirmed transactions.
+  outputs: HashMap<OutPoint, TxOut>,
+  blessed_inscription_count: u64,
+  cursed_inscription_count: u64,
+  next_sequence_number: u32,
+}
+
+/// The outputs spent by an unconfirmed transaction, with their prefetched values.
+struct PendingInputs<'a, 'db> {
+  rtx: &'a Rtx<'db>,
+  state: &'a mut PendingState,
+  prevouts: HashMap<OutPoint, TxOut>,
+}
+
+impl SpentOutputs for PendingInputs<'_, '_> {
+  fn spend(&mut self, outpoint: OutPoint) -> Result<(Vec<(SatPoint, InscriptionId)>, u64)> {
+    let inscriptions = match self.state.inscriptions.remove(&outpoint) {
+      Some(inscriptions) => inscriptions,
+      None => self.rtx.inscriptions_on_output_with_satpoints(outpoint)?,
+    };
+
+    Ok((inscriptions, self.prevouts[&outpoint].value))
+  }
+
+  fn is_cursed_or_vindicated(&mut self, inscription_id: InscriptionId) -> Result<bool> {
+    Ok(match self.state.new_inscriptions.get(&inscription_id) {
+      Some((_, cursed_or_vindicated)) => *cursed_or_vindicated,
+      None => Index::get_inscription_entry_with_rtx(inscription_id, self.rtx)?
+        .map(|entry| entry.inscription_number < 0 || Charm::Vindicated.is_set(entry.charms))
+        .unwrap_or_default(),
+    })
+  }
+}
+
+impl Index {
+  pub(crate) fn is_brc20_mempool_enabled(&self) -> bool {
+    self.brc20_mempool.is_some()
+  }
+
+  /// Simulates the BRC20 messages of the transactions that entered the mempool since the last
+  /// call on top of the indexed state. The simulation starts over when the tip changes or a
+  /// simulated transaction leaves the mempool. Transactions that fail to simulate are skipped.
+  pub(crate) fn update_brc20_mempool(&self) -> Result {
+    let Some(mempool) = &self.brc20_mempool else {
+      return Ok(());
+    };
+
+    let mut simulation = mempool.simulation.lock().unwrap();
+
+    let rtx = self.begin_read()?;
+    let height = rtx.block_count()?;
+    let tip = rtx.block_hash(None)?;
+
+    let txids = self
+      .client
+      .get_raw_mempool()?
+      .into_iter()
+      .collect::<HashSet<Txid>>();
+
+    simulation
+      .transactions
+      .retain(|txid, _| txids.contains(txid));
+
+    for txid in txids {
+      if simulation.transactions.contains_key(&txid) {
+        continue;
+      }
+
+      // the transaction may have been mined or evicted since the mempool was listed
+      if let Some(tx) = self.client.get_raw_transaction(&txid, None).into_option()? {
+        simulation.transactions.insert(txid, tx);
+      }
+    }
+
+    let Simulation {
+      tip: simulated_tip,
+      transactions,
+      processed,
+      pending,
+      state,
+    } = &mut *simulation;
+
+    if *simulated_tip != tip
+      || processed
+        .iter()
+        .any(|txid| !transactions.contains_key(txid))
+    {
+      *simulated_tip = tip;
+      processed.clear();
+      *pending = PendingState {
+        blessed_inscription_count: rtx.statistic(Statistic::BlessedInscriptions)?,
+        cursed_inscription_count: rtx.statistic(Statistic::CursedInscriptions)?,
+        next_sequence_number: rtx.next_sequence_number()?,
+        ..Default::default()
+      };
+      *state = SimulatorState::default();
+    }
+
+    let mut ordered = Vec::new();
+    let mut visited = processed.clone();
+    for txid in transactions.keys() {
+      Self::order_by_dependency(*txid, transactions, &mut visited, &mut ordered);
+    }
+
+    let chain_conf = ChainContext {
+      chain: self.options.chain(),
+      blockheight: height,
+      blocktime: u32::try_from(Utc::now().timestamp()).unwrap_or(u32::MAX),
+    };
+
+    let mut simulator = Simulator::resume(&rtx, chain_conf, mem::take(state));
+    let mut skipped = 0;
+    for txid in &ordered {
+      processed.insert(*txid);
+
+      if let Err(err) = self
+        .pending_operations(&rtx, pending, &mut simulator, height, &transactions[txid])
+        .and_then(|operations| simulator.execute_transaction(*txid, &operations))
+      {
+        log::warn!("skipping mempool transaction {txid}: {err}");
+        skipped += 1;
+      }
+    }
+    *state = simulator.into_state();
+
+    log::debug!(
+      "simulated {} new mempool transactions, {} skipped, {} with BRC20 receipts",
+      ordered.len(),
+      skipped,
+      state.receipts().len()
+    );
+
+    *mempool.receipts.write().unwrap() = state.receipts().to_vec();
+
+    Ok(())
+  }
+
+  /// The pending receipts sent from or to the script key, in execution order.
+  pub(crate) fn brc20_mempool_receipts(
+    &self,
+    script_key: &ScriptKey,
+  ) -> Option<Vec<(Txid, brc20::Receipt)>> {
+    let mempool = self.brc20_mempool.as_ref()?.receipts.read().unwrap();
+
+    Some(
+      mempool
+        .iter()
+        .flat_map(|(txid, receipts)| receipts.iter().map(|receipt| (*txid, receipt)))
+        .filter(|(_, receipt)| &receipt.from == script_key || &receipt.to == script_key)
+        .map(|(txid, receipt)| (txid, receipt.clone()))
+        .collect(),
+    )
+  }
+
+  fn order_by_dependency(
+    txid: Txid,
+    transactions: &HashMap<Txid, Transaction>,
+    visited: &mut HashSet<Txid>,
+    ordered: &mut Vec<Txid>,
+  ) {
+    if !visited.insert(txid) {
+      return;
+    }
+
+    for tx_in in &transactions[&txid].input {
+      if transactions.contains_key(&tx_in.previous_output.txid) {
+        Self::order_by_dependency(tx_in.previous_output.txid, transactions, visited, ordered);
+      }
+    }
+
+    ordered.push(txid);
+  }
+
+  /// Derives the inscription operations of an unconfirmed transaction the way the inscription
+  /// updater does for a confirmed one. Inscriptions sent to fees are placed on the null outpoint.
+  /// The spent outputs are fetched before the pending state is touched, so a transaction with an
+  /// unknown input leaves it as it was.
+  fn pending_operations(
+    &self,
+    rtx: &Rtx,
+    state: &mut PendingState,
+    simulator: &mut Simulator,
+    height: u32,
+    tx: &Transaction,
+  ) -> Result<Vec<InscriptionOp>> {
+    let txid = tx.txid();
+
+    let mut prevouts = HashMap::new();
+    for tx_in in &tx.input {
+      let outpoint = tx_in.previous_output;
+      let tx_out = match state.outputs.get(&outpoint) {
+        Some(tx_out) => tx_out.clone(),
+        None => match rtx.outpoint_to_entry(outpoint)? {
+          Some(tx_out) => tx_out,
+          None => self
+            .client
+            .get_raw_transaction(&outpoint.txid, None)?
+            .output
+            .into_iter()
+            .nth(usize::try_from(outpoint.vout).unwrap())
+            .ok_or_else(|| anyhow!("output {outpoint} not found"))?,
+        },
+      };
+      prevouts.insert(outpoint, tx_out);
+    }
+
+    let mut inputs = PendingInputs {
+      rtx,
+      state,
+      prevouts,
+    };
+
+    let (mut floating, _, _) =
+      float_inscriptions(tx, txid, height, self.options.chain(), &mut inputs)?;
+
+    for (outpoint, tx_out) in inputs.prevouts {
+      simulator.insert_tx_out(outpoint, tx_out);
+    }
+
+    floating.sort_by_key(|flotsam| flotsam.offset);
+
+    let mut range_to_vout = BTreeMap::new();
+    let mut output_value = 0;
+    for (vout, tx_out) in tx.output.iter().enumerate() {
+      let outpoint = OutPoint {
+        txid,
+        vout: vout.try_into().unwrap(),
+      };
+      range_to_vout.insert((output_value, output_value + tx_out.value), outpoint);
+      output_value += tx_out.value;
+      state.outputs.insert(outpoint, tx_out.clone());
+      simulator.insert_tx_out(outpoint, tx_out.clone());
+    }
+
+    let mut operations = Vec::new();
+    for flotsam in floating {
+      let offset = match flotsam.origin {
+        Origin::New {
+          pointer: Some(pointer),
+          ..
+        } if pointer < output_value => pointer,
+        _ => flotsam.offset,
+      };
+
+      let new_satpoint = range_to_vout
+        .iter()
+        .find_map(|((start, end), outpoint)| {
+          (offset >= *start && offset < *end).then(|| SatPoint {
+            outpoint: *outpoint,
+            offset: offset - start,
+          })
+        })
+        .unwrap_or(SatPoint {
+          outpoint: OutPoint::null(),
+          offset: flotsam.offset.saturating_sub(output_value),
+        });
+
+      let (action, sequence_number, inscription_number) = match flotsam.origin {
+        Origin::Old => (
+          Action::Transfer,
+          match state.new_inscriptions.get(&flotsam.inscription_id) {
+            Some((sequence_number, _)) => *sequence_number,
+            None => rtx
+              .inscription_id_to_sequence_number(flotsam.inscription_id)?
+              .ok_or_else(|| anyhow!("inscription {} not found", flotsam.inscription_id))?,
+          },
+          None,
+        ),
+        Origin::New {
+          cursed,
+          unbound,
+          inscription,
+          vindicated,
+          parent,
+          ..
+        } => {
+          let inscription_number = if cursed {
+            let number: i32 = state.cursed_inscription_count.try_into().unwrap();
+            state.cursed_inscription_count += 1;
+            -(number + 1)
+          } else {
+            let number: i32 = state.blessed_inscription_count.try_into().unwrap();
+            state.blessed_inscription_count += 1;
+            number
+          };
+
+          let sequence_number = state.next_sequence_number;
+          state.next_sequence_number += 1;
+
+          simulator.insert_inscription_number(sequence_number, inscription_number);
+          state.new_inscriptions.insert(
+            flotsam.inscription_id,
+            (sequence_number, cursed || vindicated),
+          );
+
+          (
+            Action::New {
+              cursed,
+              unbound,
+              inscription,
+              vindicated,
+              parent,
+            },
+            sequence_number,
+            Some(inscription_number),
+          )
+        }
+      };
+
+      let unbound = matches!(action, Action::New { unbound: true, .. });
+
+      if !unbound && new_satpoint.outpoint != OutPoint::null() {
+        state
+          .inscriptions
+          .entry(new_satpoint.outpoint)
+          .or_default()
+          .push((new_satpoint, flotsam.inscription_id));
+      }
+
+      operations.push(InscriptionOp {
+        txid,
+        action,
+        sequence_number,
+        inscription_number,
+        inscription_id: flotsam.inscription_id,
+        old_satpoint: flotsam.old_satpoint,
+        new_satpoint: Some(new_satpoint),
+      });
+    }
+
+    Ok(operations)
+  }
+}
@@ -1,4 +1,11 @@
-use {super::*, updater::BlockData};
+use {
+  super::*,
+  crate::okx::datastore::undo::{
+    redb::table::{get_undo_log, remove_undo_logs, save_rolled_back_blocks},
+    RolledBackBlock, TableWrite,
+  },
+  updater::BlockData,
+};
 
 #[derive(Debug, PartialEq)]
 pub(crate) enum ReorgError {
@@ -19,10 +26,6 @@ impl fmt::Display for ReorgError {
 
 impl std::error::Error for ReorgError {}
 
-const MAX_SAVEPOINTS: u32 = 2;
-const SAVEPOINT_INTERVAL: u32 = 10;
-const CHAIN_TIP_DISTANCE: u32 = 21;
-
 pub(crate) struct Reorg {}
 
 impl Reorg {
@@ -32,10 +35,7 @@ impl Reorg {
     match index.block_hash(height.checked_sub(1))? {
       Some(index_prev_blockhash) if index_prev_blockhash == bitcoind_prev_blockhash => Ok(()),
       Some(index_prev_blockhash) if index_prev_blockhash != bitcoind_prev_blockhash => {
-        let max_recoverable_reorg_depth =
-          (MAX_SAVEPOINTS - 1) * SAVEPOINT_INTERVAL + height % SAVEPOINT_INTERVAL;
-
-        for depth in 1..max_recoverable_reorg_depth {
+        for depth in 1..=index.max_reorg_depth() {
           let index_block_hash = index.block_hash(height.checked_sub(depth))?;
           let bitcoind_block_hash = index
             .client
@@ -56,65 +56,76 @@ impl Reorg {
   pub(crate) fn handle_reorg(index: &Index, height: u32, depth: u32) -> Result {
     log::info!("rolling back database after reorg of depth {depth} at height {height}");
 
-    if let redb::Durability::None = index.durability {
-      panic!("set index durability to `Durability::Immediate` to test reorg handling");
-    }
-
-    let mut wtx = index.begin_write()?;
-
-    let oldest_savepoint =
-      wtx.get_persistent_savepoint(wtx.list_persistent_savepoints()?.min().unwrap())?;
-
-    wtx.restore_savepoint(&oldest_savepoint)?;
-
-    Index::increment_statistic(&wtx, Statistic::Commits, 1)?;
-    wtx.commit()?;
-
-    log::info!(
-      "successfully rolled back database to height {}",
-      index.block_count()?
-    );
+    // the block at `height - depth` is the last one shared with bitcoind.
+    Self::rollback(index, height - depth)?;
 
     Ok(())
   }
 
-  pub(crate) fn update_savepoints(index: &Index, height: u32) -> Result {
-    if let redb::Durability::None = index.durability {
-      return Ok(());
+  /// Disconnects the blocks above `height` by undoing their table writes in reverse, and returns the height of
+  /// the next block to index.
+  ///
+  /// The blocks rolled back are saved with their state changes under the returned height.
+  pub(crate) fn rollback(index: &Index, height: u32) -> Result<u32> {
+    let wtx = index.begin_write()?;
+
+    let tip = wtx
+      .open_table(HEIGHT_TO_BLOCK_HEADER)?
+      .last()?
+      .map(|(height, _header)| height.value())
+      .ok_or_else(|| anyhow!("index has no blocks"))?;
+
+    if tip <= height {
+      bail!("index is at height {tip}, which is not above {height}");
     }
 
-    if (height < SAVEPOINT_INTERVAL || height % SAVEPOINT_INTERVAL == 0)
-      && u32::try_from(
-        index
-          .options
-          .bitcoin_rpc_client(None)?
-          .get_blockchain_info()?
-          .headers,
-      )
-      .unwrap()
-      .saturating_sub(height)
-        <= CHAIN_TIP_DISTANCE
+    let mut blocks = Vec::new();
+    let mut writes = Vec::new();
     {
-      let wtx = index.begin_write()?;
+      let height_to_block_header = wtx.open_table(HEIGHT_TO_BLOCK_HEADER)?;
+      let undo_logs = wtx.open_table(UNDO_LOGS)?;
+      for result in height_to_block_header.range(height + 1..)? {
+        let (block_height, header) = result?;
+        let block_height = block_height.value();
+        let undo_log = get_undo_log(&undo_logs, block_height)?.ok_or_else(|| {
+          anyhow!(
+            "cannot roll back to height {height}, the undo log of block {block_height} is pruned"
+          )
+        })?;
+        blocks.push(RolledBackBlock {
+          height: block_height,
+          block_hash: Header::load(*header.value()).block_hash(),
+          changes: undo_log.changes,
+        });
+        writes.extend(undo_log.writes);
+      }
+    }
 
-      let savepoints = wtx.list_persistent_savepoints()?.collect::<Vec<u64>>();
+    let tables = writes
+      .iter()
+      .map(TableWrite::table)
+      .collect::<BTreeSet<&str>>();
 
-      if savepoints.len() >= usize::try_from(MAX_SAVEPOINTS).unwrap() {
-        wtx.delete_persistent_savepoint(savepoints.into_iter().min().unwrap())?;
-      }
+    for table in tables {
+      undo_writes(&wtx, table, &writes)?;
+    }
 
-      Index::increment_statistic(&wtx, Statistic::Commits, 1)?;
-      wtx.commit()?;
+    remove_undo_logs(&mut wtx.open_table(UNDO_LOGS)?, height + 1..)?;
 
-      let wtx = index.begin_write()?;
+    save_rolled_back_blocks(&mut wtx.open_table(REORG_UNDO_LOGS)?, height + 1, &blocks)?;
 
-      log::debug!("creating savepoint at height {}", height);
-      wtx.persistent_savepoint()?;
+    Index::increment_statistic(&wtx, Statistic::Commits, 1)?;
+    wtx.commit()?;
 
-      Index::increment_statistic(&wtx, Statistic::Commits, 1)?;
-      wtx.commit()?;
+    log::info!("successfully rolled back database to height {height}");
+
+    for block in blocks.into_iter().rev() {
+      index.notify_block_event(BlockEvent::Disconnected {
+        height: block.height,
+        block_hash: block.block_hash,
+      });
     }
 
-    Ok(())
+    Ok(height + 1)
   }
 }
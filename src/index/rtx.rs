@@ -1,5 +1,8 @@
 use super::*;
-use crate::okx::datastore::ord::btc_name::BtcDomain;
+use crate::{
+  index::rune_holders::{get_rune_holders_count, get_rune_holders_page},
+  okx::datastore::ord::btc_name::BtcDomain,
+};
 
 pub(crate) struct Rtx<'a>(pub(crate) redb::ReadTransaction<'a>);
 
@@ -62,6 +65,31 @@ impl Rtx<'_> {
     )
   }
 
+  /// The sequence number the next inscription will be given.
+  pub(crate) fn next_sequence_number(&self) -> Result<u32> {
+    Ok(
+      self
+        .0
+        .open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?
+        .iter()?
+        .next_back()
+        .and_then(|result| result.ok())
+        .map(|(number, _entry)| number.value() + 1)
+        .unwrap_or(0),
+    )
+  }
+
+  pub(crate) fn statistic(&self, statistic: Statistic) -> Result<u64> {
+    Ok(
+      self
+        .0
+        .open_table(STATISTIC_TO_COUNT)?
+        .get(&statistic.key())?
+        .map(|count| count.value())
+        .unwrap_or_default(),
+    )
+  }
+
   pub(crate) fn inscription_id_to_sequence_number(
     &self,
     inscription_id: InscriptionId,
@@ -116,6 +144,63 @@ impl Rtx<'_> {
     )
   }
 
+  /// A page of the inscriptions on the outputs of the script key, in outpoint and offset order. `read` returns `None`
+  /// for the inscriptions to skip.
+  pub(crate) fn script_key_inscriptions_page<R>(
+    &self,
+    script_key: &str,
+    page: &KeyPage,
+    mut read: impl FnMut(SatPoint, InscriptionId) -> Result<Option<R>>,
+  ) -> Result<Vec<(String, R)>> {
+    let script_key_to_outpoints = self.0.open_multimap_table(SCRIPT_KEY_TO_OUTPOINTS)?;
+    let satpoint_to_sequence_number = self.0.open_multimap_table(SATPOINT_TO_SEQUENCE_NUMBER)?;
+    let sequence_number_to_inscription_entry =
+      self.0.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
+
+    // the inscriptions on an output never change, so their position in it is a stable key.
+    let inscriptions = script_key_to_outpoints
+      .get(script_key)?
+      .map(|outpoint| -> Result<_> {
+        let outpoint = *outpoint?.value();
+        let inscriptions = Index::inscriptions_on_output(
+          &satpoint_to_sequence_number,
+          &sequence_number_to_inscription_entry,
+          OutPoint::load(outpoint),
+        )?;
+        Ok(
+          inscriptions
+            .into_iter()
+            .enumerate()
+            .map(move |(position, inscription)| {
+              (
+                format!("{}{position:08x}", hex::encode(outpoint)),
+                inscription,
+              )
+            }),
+        )
+      })
+      .flat_map(|result| match result {
+        Ok(inscriptions) => inscriptions.map(Ok).collect::<Vec<_>>(),
+        Err(err) => vec![Err(err)],
+      });
+
+    page.read_sorted(
+      inscriptions,
+      |(key, _)| key.clone(),
+      |(_, (satpoint, inscription_id))| read(satpoint, inscription_id),
+    )
+  }
+
+  /// The unspent outputs of the script key, in outpoint order.
+  pub(crate) fn script_key_to_unspent_outpoints(&self, script_key: &str) -> Result<Vec<OutPoint>> {
+    self
+      .0
+      .open_multimap_table(SCRIPT_KEY_TO_UNSPENT_OUTPOINTS)?
+      .get(script_key)?
+      .map(|outpoint| Ok(OutPoint::load(*outpoint?.value())))
+      .collect()
+  }
+
   pub(crate) fn sequence_number_to_inscription_entry(
     &self,
     sequence_number: u32,
@@ -156,6 +241,41 @@ impl Rtx<'_> {
     }
   }
 
+  /// A page of the children of the inscription in inscription order, keyed by the hex sequence
+  /// number.
+  pub(crate) fn inscription_children_page(
+    &self,
+    sequence_number: u32,
+    page: &KeyPage,
+  ) -> Result<Vec<(String, InscriptionEntry)>> {
+    page.read_sorted(
+      self
+        .0
+        .open_multimap_table(SEQUENCE_NUMBER_TO_CHILDREN)?
+        .get(sequence_number)?
+        .map(|child| Ok(child?.value())),
+      |child| format!("{child:08x}"),
+      |child| {
+        self
+          .sequence_number_to_inscription_entry(child)?
+          .ok_or_else(|| anyhow!("inscription entry {child} not found"))
+          .map(Some)
+      },
+    )
+  }
+
+  pub(crate) fn inscription_child_count(&self, sequence_number: u32) -> Result<u64> {
+    Ok(
+      self
+        .0
+        .open_multimap_table(SEQUENCE_NUMBER_TO_CHILDREN)?
+        .get(sequence_number)?
+        .count()
+        .try_into()
+        .unwrap(),
+    )
+  }
+
   pub(crate) fn ord_inscription_id_to_collections(
     &self,
     inscription_id: InscriptionId,
@@ -166,6 +286,39 @@ impl Rtx<'_> {
     get_collections_of_inscription(&table, &inscription_id)
   }
 
+  /// The number of members of every collection kind with members, in kind order.
+  pub(crate) fn ord_collection_counts(&self) -> Result<Vec<(String, u64)>> {
+    let table = self.0.open_table(COLLECTION_KIND_TO_COUNT)?;
+    get_collection_counts(&table)
+  }
+
+  pub(crate) fn ord_collection_count(&self, kind: &str) -> Result<Option<u64>> {
+    let table = self.0.open_table(COLLECTION_KIND_TO_COUNT)?;
+    get_collection_count(&table, kind)
+  }
+
+  /// A page of the members of the collection kind in inscription order.
+  pub(crate) fn ord_collection_inscriptions_page(
+    &self,
+    kind: &str,
+    page: &KeyPage,
+  ) -> Result<Vec<(String, InscriptionEntry)>> {
+    let table = self
+      .0
+      .open_multimap_table(COLLECTION_KIND_TO_SEQUENCE_NUMBERS)?;
+    get_collection_members_page(&table, kind, page)?
+      .into_iter()
+      .map(|(key, sequence_number)| {
+        Ok((
+          key,
+          self
+            .sequence_number_to_inscription_entry(sequence_number)?
+            .ok_or_else(|| anyhow!("inscription entry {sequence_number} not found"))?,
+        ))
+      })
+      .collect()
+  }
+
   pub(crate) fn ord_district_to_inscription_id(
     &self,
     number: u32,
@@ -175,9 +328,40 @@ impl Rtx<'_> {
     get_collection_inscription_id(&table, &district.to_collection_key())
   }
 
-  pub(crate) fn btc_name_to_inscription_id(&self, btc_name: &str) -> Result<Option<InscriptionId>> {
-    let btc_name_raw = btc_name.as_bytes().to_vec();
-    let domain = BtcDomain::parse(&btc_name_raw)?;
+  /// The registered parcels of the district in ascending number order.
+  pub(crate) fn bitmap_parcels(&self, district: u32) -> Result<Vec<(u32, InscriptionId)>> {
+    let table = self.0.open_table(COLLECTIONS_KEY_TO_INSCRIPTION_ID)?;
+    let mut parcels =
+      get_collection_inscription_ids_by_prefix(&table, &Parcel::collection_key_prefix(district))?
+        .into_iter()
+        .filter_map(|(key, inscription_id)| {
+          Parcel::from_collection_key(&key).map(|parcel| (parcel.number, inscription_id))
+        })
+        .collect::<Vec<_>>();
+    parcels.sort_by_key(|(number, _)| *number);
+    Ok(parcels)
+  }
+
+  pub(crate) fn bitmap_district_block(
+    &self,
+    height: u32,
+  ) -> Result<Option<(Header, DistrictBlock)>> {
+    let Some(header) = self
+      .0
+      .open_table(HEIGHT_TO_BLOCK_HEADER)?
+      .get(height)?
+      .map(|header| Header::load(*header.value()))
+    else {
+      return Ok(None);
+    };
+    let table = self.0.open_table(BITMAP_DISTRICT_BLOCKS)?;
+    Ok(get_district_block(&table, height)?.map(|district_block| (header, district_block)))
+  }
+
+  pub(crate) fn btc_name_to_inscription_id(
+    &self,
+    domain: &BtcDomain,
+  ) -> Result<Option<InscriptionId>> {
     let table = self.0.open_table(COLLECTIONS_KEY_TO_INSCRIPTION_ID)?;
     get_collection_inscription_id(&table, &domain.to_collection_key())
   }
@@ -190,14 +374,136 @@ impl Rtx<'_> {
     get_transaction_operations(&table, &txid)
   }
 
+  pub(crate) fn rune(&self, rune: Rune) -> Result<Option<(RuneId, RuneEntry)>> {
+    let Some(id) = self
+      .0
+      .open_table(RUNE_TO_RUNE_ID)?
+      .get(rune.0)?
+      .map(|guard| guard.value())
+    else {
+      return Ok(None);
+    };
+
+    Ok(
+      self
+        .0
+        .open_table(RUNE_ID_TO_RUNE_ENTRY)?
+        .get(id)?
+        .map(|entry| (RuneId::load(id), RuneEntry::load(entry.value()))),
+    )
+  }
+
+  pub(crate) fn rune_id_to_rune_entry(&self, id: RuneId) -> Result<Option<RuneEntry>> {
+    Ok(
+      self
+        .0
+        .open_table(RUNE_ID_TO_RUNE_ENTRY)?
+        .get(id.store())?
+        .map(|entry| RuneEntry::load(entry.value())),
+    )
+  }
+
+  pub(crate) fn rune_balances_on_output(&self, outpoint: OutPoint) -> Result<Vec<(RuneId, u128)>> {
+    let outpoint_to_balances = self.0.open_table(OUTPOINT_TO_RUNE_BALANCES)?;
+    let Some(balances) = outpoint_to_balances.get(&outpoint.store())? else {
+      return Ok(Vec::new());
+    };
+
+    let buffer = balances.value();
+    let mut balances = Vec::new();
+    let mut i = 0;
+    while i < buffer.len() {
+      let (id, length) = runes::varint::decode(&buffer[i..]);
+      i += length;
+      let (balance, length) = runes::varint::decode(&buffer[i..]);
+      i += length;
+      balances.push((RuneId::try_from(id)?, balance));
+    }
+
+    Ok(balances)
+  }
+
+  /// The supply of the rune at each height from `from` to `to` inclusive at which it was etched,
+  /// minted or burned.
+  pub(crate) fn rune_supply_history(
+    &self,
+    id: RuneId,
+    from: u32,
+    to: u32,
+  ) -> Result<Vec<(u32, RuneSupplyEntry)>> {
+    self
+      .0
+      .open_table(RUNE_ID_HEIGHT_TO_RUNE_SUPPLY)?
+      .range((id.store(), from)..=(id.store(), to))?
+      .map(|result| {
+        let (key, entry) = result?;
+        Ok((key.value().1, RuneSupplyEntry::load(entry.value())))
+      })
+      .collect()
+  }
+
+  pub(crate) fn rune_transaction_events(&self, txid: Txid) -> Result<Option<Vec<RuneEvent>>> {
+    Ok(
+      self
+        .0
+        .open_table(RUNE_EVENTS)?
+        .get(&txid.store())?
+        .map(|events| rmp_serde::from_slice::<Vec<RuneEvent>>(events.value()).unwrap()),
+    )
+  }
+
+  /// The outputs holding runes of the script key, in outpoint order.
+  pub(crate) fn script_key_to_rune_outpoints(&self, script_key: &str) -> Result<Vec<OutPoint>> {
+    self
+      .0
+      .open_multimap_table(SCRIPT_KEY_TO_RUNE_OUTPOINTS)?
+      .get(script_key)?
+      .map(|outpoint| Ok(OutPoint::load(*outpoint?.value())))
+      .collect()
+  }
+
+  /// A page of the holders of the rune with their balances in descending balance order.
+  pub(crate) fn rune_holders_page(
+    &self,
+    id: RuneId,
+    page: &KeyPage,
+  ) -> Result<Vec<(String, (String, u128))>> {
+    let table = self.0.open_table(RUNE_ID_TO_HOLDERS)?;
+    get_rune_holders_page(&table, id, page)
+  }
+
+  pub(crate) fn rune_holders_count(&self, id: RuneId) -> Result<u64> {
+    let table = self.0.open_table(RUNE_ID_TO_HOLDERS)?;
+    get_rune_holders_count(&table, id)
+  }
+
   pub(crate) fn brc20_get_tick_info(&self, name: &brc20::Tick) -> Result<Option<brc20::TokenInfo>> {
     let table = self.0.open_table(BRC20_TOKEN)?;
     get_token_info(&table, name)
   }
 
-  pub(crate) fn brc20_get_all_tick_info(&self) -> Result<Vec<brc20::TokenInfo>> {
+  pub(crate) fn brc20_get_tick_info_page(
+    &self,
+    page: &KeyPage,
+    filter: impl Fn(&brc20::TokenInfo) -> bool,
+  ) -> Result<Vec<(String, brc20::TokenInfo)>> {
     let table = self.0.open_table(BRC20_TOKEN)?;
-    get_tokens_info(&table)
+    get_tokens_info_page(&table, page, filter)
+  }
+
+  /// A page of the holders of the tick in descending balance order.
+  pub(crate) fn brc20_get_tick_holders_page(
+    &self,
+    tick: &brc20::Tick,
+    page: &KeyPage,
+  ) -> Result<Vec<(String, (ScriptKey, u128))>> {
+    let table = self.0.open_table(BRC20_TICK_HOLDERS)?;
+    get_tick_holders_page(&table, tick, page)
+  }
+
+  pub(crate) fn brc20_get_tick_holders_count(&self, tick: &brc20::Tick) -> Result<u64> {
+    let table = self.0.open_table(BRC20_TICK_HOLDERS)?;
+    get_tick_holders_count(&table, tick)
   }
 
   pub(crate) fn brc20_get_balance_by_address(
@@ -209,6 +515,25 @@ impl Rtx<'_> {
     get_balance(&table, &script_key, tick)
   }
 
+  pub(crate) fn brc20_get_balance_by_address_at_height(
+    &self,
+    tick: &brc20::Tick,
+    script_key: ScriptKey,
+    height: u32,
+  ) -> Result<Option<brc20::Balance>> {
+    let table = self.0.open_table(BRC20_BALANCE_HISTORY)?;
+    get_balance_at_height(&table, &script_key, tick, height)
+  }
+
+  pub(crate) fn brc20_get_balances_page_by_address(
+    &self,
+    script_key: &ScriptKey,
+    page: &KeyPage,
+  ) -> Result<Vec<(String, brc20::Balance)>> {
+    let table = self.0.open_table(BRC20_BALANCES)?;
+    get_balances_page(&table, script_key, page)
+  }
+
   pub(crate) fn brc20_get_all_balance_by_address(
     &self,
     script_key: ScriptKey,
@@ -217,6 +542,19 @@ impl Rtx<'_> {
     get_balances(&table, &script_key)
   }
 
+  /// A page of the transactions of the address in height order, with their receipts that involve
+  /// the address and match `filter`.
+  pub(crate) fn brc20_get_transactions_page_by_address(
+    &self,
+    script_key: &ScriptKey,
+    page: &KeyPage,
+    filter: impl Fn(&brc20::Receipt) -> bool,
+  ) -> Result<Vec<(String, (Txid, Vec<brc20::Receipt>))>> {
+    let address_table = self.0.open_table(BRC20_SCRIPT_KEY_TO_EVENTS)?;
+    let events_table = self.0.open_table(BRC20_EVENTS)?;
+    get_transactions_by_address_page(&address_table, &events_table, script_key, page, filter)
+  }
+
   pub(crate) fn brc20_transaction_id_to_transaction_receipt(
     &self,
     txid: Txid,
@@ -225,6 +563,14 @@ impl Rtx<'_> {
     get_transaction_receipts(&table, &txid)
   }
 
+  pub(crate) fn brc20_get_block_checksum(
+    &self,
+    height: u32,
+  ) -> Result<Option<bitcoin::hashes::sha256::Hash>> {
+    let table = self.0.open_table(BRC20_BLOCK_CHECKSUMS)?;
+    get_block_checksum(&table, height)
+  }
+
   pub(crate) fn brc20_get_tick_transferable_by_address(
     &self,
     tick: &brc20::Tick,
@@ -237,15 +583,16 @@ impl Rtx<'_> {
     get_transferable_assets_by_account_ticker(&address_table, &satpoint_table, &script_key, tick)
   }
 
-  pub(crate) fn brc20_get_all_transferable_by_address(
+  pub(crate) fn brc20_get_transferable_page_by_address(
     &self,
-    script_key: ScriptKey,
-  ) -> Result<Vec<(SatPoint, brc20::TransferableLog)>> {
-    let address_table = self
+    script_key: &ScriptKey,
+    page: &KeyPage,
+  ) -> Result<Vec<(String, (SatPoint, brc20::TransferableLog))>> {
+    let number_table = self
       .0
-      .open_multimap_table(BRC20_ADDRESS_TICKER_TO_TRANSFERABLE_ASSETS)?;
+      .open_table(BRC20_ADDRESS_INSCRIPTION_NUMBER_TO_TRANSFERABLE_ASSETS)?;
     let satpoint_table = self.0.open_table(BRC20_SATPOINT_TO_TRANSFERABLE_ASSETS)?;
-    get_transferable_assets_by_account(&address_table, &satpoint_table, &script_key)
+    get_transferable_assets_by_account_page(&number_table, &satpoint_table, script_key, page)
   }
 
   pub(crate) fn brc20_transferable_assets_on_output_with_satpoints(
@@ -265,4 +612,14 @@ impl Rtx<'_> {
         .map(|outpoint| outpoint.value().to_vec()),
     )
   }
+
+  pub(crate) fn undo_log(&self, height: u32) -> Result<Option<Vec<StateChange>>> {
+    let table = self.0.open_table(UNDO_LOGS)?;
+    Ok(get_undo_log(&table, height)?.map(|undo_log| undo_log.changes))
+  }
+
+  pub(crate) fn rolled_back_blocks(&self, height: u32) -> Result<Vec<Vec<RolledBackBlock>>> {
+    let table = self.0.open_table(REORG_UNDO_LOGS)?;
+    get_rolled_back_blocks(&table, height)
+  }
 }
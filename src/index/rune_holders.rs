@@ -0,0 +1,181 @@
+use {
+  super::*,
+  crate::okx::datastore::{undo::journal::Journal, KeyPage},
+  redb::ReadableTable,
+};
+
+fn script_key_balance_key(id: RuneId, script_key: &str) -> String {
+  format!("{:032x}_{}", u128::from(id), script_key)
+}
+
+fn holder_key(id: RuneId, balance: u128, script_key: &str) -> String {
+  // the balance is inverted so the holders of the rune are in descending balance order.
+  format!(
+    "{:032x}_{:032x}_{}",
+    u128::from(id),
+    u128::MAX - balance,
+    script_key
+  )
+}
+
+fn min_holder_key(id: RuneId) -> String {
+  format!("{:032x}_", u128::from(id))
+}
+
+fn max_holder_key(id: RuneId) -> String {
+  // because '`' is the next character after '_' in bytes order, it's greater than any holder key of the rune.
+  format!("{:032x}`", u128::from(id))
+}
+
+// RUNE_ID_TO_SCRIPT_KEY_BALANCE
+pub(crate) fn get_rune_balance<T>(table: &T, id: RuneId, script_key: &str) -> Result<u128>
+where
+  T: ReadableTable<&'static str, u128>,
+{
+  Ok(
+    table
+      .get(script_key_balance_key(id, script_key).as_str())?
+      .map(|balance| balance.value())
+      .unwrap_or_default(),
+  )
+}
+
+// RUNE_ID_TO_SCRIPT_KEY_BALANCE
+// RUNE_ID_TO_HOLDERS
+pub(crate) fn update_rune_balance(
+  journal: &Journal,
+  balances: &mut Table<'_, '_, &'static str, u128>,
+  holders: &mut Table<'_, '_, &'static str, &'static [u8]>,
+  id: RuneId,
+  script_key: &str,
+  balance: u128,
+) -> Result {
+  // a script key holds the rune as long as its balance is positive.
+  let old_balance = get_rune_balance(balances, id, script_key)?;
+  if old_balance > 0 {
+    journal.remove(holders, holder_key(id, old_balance, script_key).as_str())?;
+  }
+
+  let key = script_key_balance_key(id, script_key);
+  if balance > 0 {
+    journal.insert(
+      holders,
+      holder_key(id, balance, script_key).as_str(),
+      rmp_serde::to_vec(&(script_key, balance))
+        .unwrap()
+        .as_slice(),
+    )?;
+    journal.insert(balances, key.as_str(), balance)?;
+  } else {
+    journal.remove(balances, key.as_str())?;
+  }
+
+  Ok(())
+}
+
+// RUNE_ID_TO_HOLDERS
+pub(crate) fn get_rune_holders_page<T>(
+  table: &T,
+  id: RuneId,
+  page: &KeyPage,
+) -> Result<Vec<(String, (String, u128))>>
+where
+  T: ReadableTable<&'static str, &'static [u8]>,
+{
+  page.read(
+    table,
+    &min_holder_key(id),
+    Some(&max_holder_key(id)),
+    |_, data| Ok(Some(rmp_serde::from_slice::<(String, u128)>(data).unwrap())),
+  )
+}
+
+// RUNE_ID_TO_HOLDERS
+pub(crate) fn get_rune_holders_count<T>(table: &T, id: RuneId) -> Result<u64>
+where
+  T: ReadableTable<&'static str, &'static [u8]>,
+{
+  Ok(
+    table
+      .range(min_holder_key(id).as_str()..max_holder_key(id).as_str())?
+      .count()
+      .try_into()
+      .unwrap(),
+  )
+}
+
+#[cfg(test)]
+mod tests {
+  use {super::*, redb::Database, tempfile::NamedTempFile};
+
+  #[test]
+  fn holders_are_paged_in_descending_balance_order() {
+    let dbfile = NamedTempFile::new().unwrap();
+    let db = Database::create(dbfile.path()).unwrap();
+    let wtx = db.begin_write().unwrap();
+    let mut balances = wtx.open_table(RUNE_ID_TO_SCRIPT_KEY_BALANCE).unwrap();
+    let mut holders = wtx.open_table(RUNE_ID_TO_HOLDERS).unwrap();
+
+    let id = RuneId {
+      height: 2,
+      index: 1,
+    };
+    let other = RuneId {
+      height: 3,
+      index: 1,
+    };
+
+    for (id, script_key, balance) in [
+      (id, "alice", 100),
+      (id, "bob", 20),
+      (other, "bob", 1),
+      (id, "bob", 200),
+      (id, "carol", 0),
+    ] {
+      update_rune_balance(
+        &Journal::default(),
+        &mut balances,
+        &mut holders,
+        id,
+        script_key,
+        balance,
+      )
+      .unwrap();
+    }
+
+    let page = |after: Option<String>| {
+      get_rune_holders_page(
+        &holders,
+        id,
+        &KeyPage {
+          after,
+          limit: 1,
+          reverse: false,
+        },
+      )
+      .unwrap()
+    };
+
+    let first = page(None);
+    assert_eq!(first[0].1, ("bob".into(), 200));
+    assert_eq!(first.len(), 2);
+    let second = page(Some(first[0].0.clone()));
+    assert_eq!(second, vec![first[1].clone()]);
+    assert_eq!(second[0].1, ("alice".into(), 100));
+    assert_eq!(get_rune_holders_count(&holders, id).unwrap(), 2);
+    assert_eq!(get_rune_holders_count(&holders, other).unwrap(), 1);
+
+    update_rune_balance(
+      &Journal::default(),
+      &mut balances,
+      &mut holders,
+      id,
+      "bob",
+      0,
+    )
+    .unwrap();
+    assert_eq!(get_rune_holders_count(&holders, id).unwrap(), 1);
+    assert_eq!(get_rune_balance(&balances, id, "bob").unwrap(), 0);
+    assert_eq!(get_rune_balance(&balances, id, "alice").unwrap(), 100);
+  }
+}
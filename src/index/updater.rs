@@ -1,4 +1,4 @@
-use crate::okx::protocol::{context::Context, ChainContext, ProtocolConfig, ProtocolManager};
+use crate::okx::protocol::{context::Context, ChainContext, ProtocolManager};
 use std::sync::atomic::{AtomicUsize, Ordering};
 use {
   self::{inscription_updater::InscriptionUpdater, rune_updater::RuneUpdater},
@@ -9,19 +9,32 @@ use {
 };
 
 pub(crate) mod inscription_updater;
-use crate::okx::lru::SimpleLru;
+use crate::okx::{
+  datastore::undo::{
+    redb::table::{remove_undo_logs, save_undo_log},
+    BlockUndoLog, Journal, UndoLog,
+  },
+  lru::SimpleLru,
+};
 
 mod rune_updater;
 
 pub(crate) struct BlockData {
   pub(crate) header: Header,
+  /// Empty if only the header was fetched.
   pub(crate) txdata: Vec<(Transaction, Txid)>,
+  /// The number of transactions in the block, whether or not they were fetched.
+  pub(crate) tx_count: u32,
+  /// The serialized size of the block in bytes, whether or not its transactions were fetched.
+  pub(crate) size: u64,
 }
 
 impl From<Block> for BlockData {
   fn from(block: Block) -> Self {
     BlockData {
       header: block.header,
+      tx_count: block.txdata.len().try_into().unwrap(),
+      size: block.size().try_into().unwrap(),
       txdata: block
         .txdata
         .into_iter()
@@ -36,9 +49,13 @@ impl From<Block> for BlockData {
 
 pub(crate) struct Updater<'index> {
   range_cache: HashMap<OutPointValue, Vec<u8>>,
+  uncommitted_blocks: Vec<(u32, BlockHash)>,
   height: u32,
+  /// Blocks below this height are too deep to be disconnected by a reorg, so their writes aren't
+  /// journaled and they have no undo log.
+  first_journaled_height: u32,
   index: &'index Index,
-  sat_ranges_since_flush: u64,
+  sat_ranges_in_block: u64,
   outputs_cached: u64,
   outputs_inserted_since_flush: u64,
   outputs_traversed: u64,
@@ -48,9 +65,11 @@ impl<'index> Updater<'_> {
   pub(crate) fn new(index: &'index Index) -> Result<Updater<'index>> {
     Ok(Updater {
       range_cache: HashMap::new(),
+      uncommitted_blocks: Vec::new(),
       height: index.block_count()?,
+      first_journaled_height: 0,
       index,
-      sat_ranges_since_flush: 0,
+      sat_ranges_in_block: 0,
       outputs_cached: 0,
       outputs_inserted_since_flush: 0,
       outputs_traversed: 0,
@@ -61,6 +80,10 @@ impl<'index> Updater<'_> {
     let mut wtx = self.index.begin_write()?;
     let starting_height = u32::try_from(self.index.client.get_block_count()?).unwrap() + 1;
 
+    self.first_journaled_height = u32::try_from(self.index.client.get_blockchain_info()?.headers)
+      .unwrap()
+      .saturating_sub(self.index.options.max_reorg_depth());
+
     wtx
       .open_table(WRITE_TRANSACTION_STARTING_BLOCK_COUNT_TO_TIMESTAMP)?
       .insert(
@@ -189,7 +212,12 @@ impl<'index> Updater<'_> {
 
     let client = index.options.bitcoin_rpc_client(None)?;
 
-    let first_inscription_height = index.first_inscription_height;
+    // the unspent outputs of addresses are tracked from genesis, which needs every transaction
+    let first_full_block_height = if index.index_addresses {
+      0
+    } else {
+      index.first_inscription_height
+    };
 
     thread::spawn(move || loop {
       if let Some(height_limit) = height_limit {
@@ -198,9 +226,9 @@ impl<'index> Updater<'_> {
         }
       }
 
-      match Self::get_block_with_retries(&client, height, index_sats, first_inscription_height) {
+      match Self::get_block_with_retries(&client, height, index_sats, first_full_block_height) {
         Ok(Some(block)) => {
-          if let Err(err) = tx.send(block.into()) {
+          if let Err(err) = tx.send(block) {
             log::info!("Block receiver disconnected: {err}");
             break;
           }
@@ -221,8 +249,8 @@ impl<'index> Updater<'_> {
     client: &Client,
     height: u32,
     index_sats: bool,
-    first_inscription_height: u32,
-  ) -> Result<Option<Block>> {
+    first_full_block_height: u32,
+  ) -> Result<Option<BlockData>> {
     let mut errors = 0;
     loop {
       match client
@@ -231,12 +259,16 @@ impl<'index> Updater<'_> {
         .and_then(|option| {
           option
             .map(|hash| {
-              if index_sats || height >= first_inscription_height {
-                Ok(client.get_block(&hash)?)
+              if index_sats || height >= first_full_block_height {
+                Ok(client.get_block(&hash)?.into())
               } else {
-                Ok(Block {
+                // the transaction count and size are still needed by the protocols, e.g. for bitmap districts.
+                let info = client.get_block_info(&hash)?;
+                Ok(BlockData {
                   header: client.get_block_header(&hash)?,
                   txdata: Vec::new(),
+                  tx_count: info.n_tx.try_into().unwrap(),
+                  size: info.size.try_into().unwrap(),
                 })
               }
             })
@@ -355,15 +387,24 @@ impl<'index> Updater<'_> {
 
     let mut outpoint_to_entry = wtx.open_table(OUTPOINT_TO_ENTRY)?;
 
+    let journal = if self.height >= self.first_journaled_height {
+      Journal::default()
+    } else {
+      Journal::disabled()
+    };
+
     let index_inscriptions =
       self.height >= index.first_inscription_height && !index.options.no_index_inscriptions;
 
+    // the spent outputs are needed to index inscriptions and to track the unspent outputs of addresses
+    let index_spent_outputs = index_inscriptions || index.index_addresses;
+
     let fetching_outputs_count = AtomicUsize::new(0);
     let total_outputs_count = AtomicUsize::new(0);
     let cache_outputs_count = AtomicUsize::new(0);
     let miss_outputs_count = AtomicUsize::new(0);
     let meet_outputs_count = AtomicUsize::new(0);
-    if index_inscriptions {
+    if index_spent_outputs {
       // Send all missing input outpoints to be fetched right away
       let txids = block
         .txdata
@@ -432,6 +473,9 @@ impl<'index> Updater<'_> {
     let mut sat_to_sequence_number = wtx.open_multimap_table(SAT_TO_SEQUENCE_NUMBER)?;
     let mut satpoint_to_sequence_number = wtx.open_multimap_table(SATPOINT_TO_SEQUENCE_NUMBER)?;
     let mut sequence_number_to_children = wtx.open_multimap_table(SEQUENCE_NUMBER_TO_CHILDREN)?;
+    let mut script_key_to_outpoints = wtx.open_multimap_table(SCRIPT_KEY_TO_OUTPOINTS)?;
+    let mut script_key_to_unspent_outpoints =
+      wtx.open_multimap_table(SCRIPT_KEY_TO_UNSPENT_OUTPOINTS)?;
     let mut sequence_number_to_inscription_entry =
       wtx.open_table(SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY)?;
     let mut sequence_number_to_satpoint = wtx.open_table(SEQUENCE_NUMBER_TO_SATPOINT)?;
@@ -476,12 +520,21 @@ impl<'index> Updater<'_> {
       &mut inscription_id_to_sequence_number,
       self.index.index_transactions,
       &mut inscription_number_to_sequence_number,
+      &journal,
       next_sequence_number,
       lost_sats,
       &mut outpoint_to_entry,
       &mut transaction_id_to_transaction,
       &mut sat_to_sequence_number,
       &mut satpoint_to_sequence_number,
+      self
+        .index
+        .index_addresses
+        .then_some(&mut script_key_to_outpoints),
+      self
+        .index
+        .index_addresses
+        .then_some(&mut script_key_to_unspent_outpoints),
       &mut sequence_number_to_children,
       &mut sequence_number_to_inscription_entry,
       &mut sequence_number_to_satpoint,
@@ -502,7 +555,7 @@ impl<'index> Updater<'_> {
       if h.subsidy() > 0 {
         let start = h.starting_sat();
         coinbase_inputs.push_front((start.n(), (start + h.subsidy()).n()));
-        self.sat_ranges_since_flush += 1;
+        self.sat_ranges_in_block += 1;
       }
 
       for (tx_offset, (tx, txid)) in block.txdata.iter().enumerate().skip(1) {
@@ -516,10 +569,11 @@ impl<'index> Updater<'_> {
           let sat_ranges = match self.range_cache.remove(&key) {
             Some(sat_ranges) => {
               self.outputs_cached += 1;
+              journal.record_buffered(OUTPOINT_TO_SAT_RANGES, &key, Some(&sat_ranges));
               sat_ranges
             }
-            None => outpoint_to_sat_ranges
-              .remove(&key)?
+            None => journal
+              .remove(&mut outpoint_to_sat_ranges, &key)?
               .ok_or_else(|| anyhow!("Could not find outpoint {} in index", input.previous_output))?
               .value()
               .to_vec(),
@@ -539,6 +593,7 @@ impl<'index> Updater<'_> {
           &mut outputs_in_block,
           &mut inscription_updater,
           index_inscriptions,
+          &journal,
         )?;
 
         coinbase_inputs.extend(input_sat_ranges);
@@ -554,18 +609,20 @@ impl<'index> Updater<'_> {
           &mut outputs_in_block,
           &mut inscription_updater,
           index_inscriptions,
+          &journal,
         )?;
       }
 
       if !coinbase_inputs.is_empty() {
-        let mut lost_sat_ranges = outpoint_to_sat_ranges
-          .remove(&OutPoint::null().store())?
+        let mut lost_sat_ranges = journal
+          .remove(&mut outpoint_to_sat_ranges, &OutPoint::null().store())?
           .map(|ranges| ranges.value().to_vec())
           .unwrap_or_default();
 
         for (start, end) in coinbase_inputs {
           if !Sat(start).common() {
-            sat_to_satpoint.insert(
+            journal.insert(
+              &mut sat_to_satpoint,
               &start,
               &SatPoint {
                 outpoint: OutPoint::null(),
@@ -580,21 +637,33 @@ impl<'index> Updater<'_> {
           lost_sats += end - start;
         }
 
-        outpoint_to_sat_ranges.insert(&OutPoint::null().store(), lost_sat_ranges.as_slice())?;
+        journal.insert(
+          &mut outpoint_to_sat_ranges,
+          &OutPoint::null().store(),
+          lost_sat_ranges.as_slice(),
+        )?;
       }
     } else if index_inscriptions {
       for (tx, txid) in block.txdata.iter().skip(1).chain(block.txdata.first()) {
         inscription_updater.index_envelopes(tx, *txid, None)?;
       }
+    } else if self.index.index_addresses {
+      for (tx, txid) in block.txdata.iter().skip(1).chain(block.txdata.first()) {
+        inscription_updater.index_outputs(tx, *txid)?;
+      }
     }
     let ord_cost = start_time.elapsed().as_millis();
 
     if index_inscriptions {
-      height_to_last_sequence_number
-        .insert(&self.height, inscription_updater.next_sequence_number)?;
+      journal.insert(
+        &mut height_to_last_sequence_number,
+        &self.height,
+        inscription_updater.next_sequence_number,
+      )?;
     }
 
-    statistic_to_count.insert(
+    journal.insert(
+      &mut statistic_to_count,
       &Statistic::LostSats.key(),
       &if self.index.index_sats {
         lost_sats
@@ -603,21 +672,42 @@ impl<'index> Updater<'_> {
       },
     )?;
 
-    statistic_to_count.insert(
+    journal.insert(
+      &mut statistic_to_count,
       &Statistic::CursedInscriptions.key(),
       &inscription_updater.cursed_inscription_count,
     )?;
 
-    statistic_to_count.insert(
+    journal.insert(
+      &mut statistic_to_count,
       &Statistic::BlessedInscriptions.key(),
       &inscription_updater.blessed_inscription_count,
     )?;
 
-    statistic_to_count.insert(
+    journal.insert(
+      &mut statistic_to_count,
       &Statistic::UnboundInscriptions.key(),
       &inscription_updater.unbound_inscriptions,
     )?;
 
+    // the traversal statistics are written with the block instead of on commit, so that
+    // disconnecting the block undoes them.
+    for (statistic, n) in [
+      (Statistic::OutputsTraversed, outputs_in_block),
+      (
+        Statistic::SatRanges,
+        mem::take(&mut self.sat_ranges_in_block),
+      ),
+    ] {
+      if n > 0 {
+        let count = statistic_to_count
+          .get(&statistic.key())?
+          .map(|count| count.value())
+          .unwrap_or(0);
+        journal.insert(&mut statistic_to_count, &statistic.key(), count + n)?;
+      }
+    }
+
     inscription_updater.flush_cache()?;
 
     let mut context = Context {
@@ -633,26 +723,50 @@ impl<'index> Updater<'_> {
       COLLECTIONS_KEY_TO_INSCRIPTION_ID: &mut wtx.open_table(COLLECTIONS_KEY_TO_INSCRIPTION_ID)?,
       COLLECTIONS_INSCRIPTION_ID_TO_KINDS: &mut wtx
         .open_multimap_table(COLLECTIONS_INSCRIPTION_ID_TO_KINDS)?,
+      COLLECTION_KIND_TO_SEQUENCE_NUMBERS: &mut wtx
+        .open_multimap_table(COLLECTION_KIND_TO_SEQUENCE_NUMBERS)?,
+      COLLECTION_KIND_TO_COUNT: &mut wtx.open_table(COLLECTION_KIND_TO_COUNT)?,
+      INSCRIPTION_ID_TO_SEQUENCE_NUMBER: &mut inscription_id_to_sequence_number,
       SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY: &mut sequence_number_to_inscription_entry,
       OUTPOINT_TO_ENTRY: &mut outpoint_to_entry,
+      BITMAP_DISTRICT_BLOCKS: &mut wtx.open_table(BITMAP_DISTRICT_BLOCKS)?,
       BRC20_BALANCES: &mut wtx.open_table(BRC20_BALANCES)?,
+      BRC20_BALANCE_HISTORY: &mut wtx.open_table(BRC20_BALANCE_HISTORY)?,
       BRC20_TOKEN: &mut wtx.open_table(BRC20_TOKEN)?,
+      BRC20_TICK_HOLDERS: &mut wtx.open_table(BRC20_TICK_HOLDERS)?,
       BRC20_EVENTS: &mut wtx.open_table(BRC20_EVENTS)?,
+      BRC20_BLOCK_CHECKSUMS: &mut wtx.open_table(BRC20_BLOCK_CHECKSUMS)?,
+      BRC20_SCRIPT_KEY_TO_EVENTS: &mut wtx.open_table(BRC20_SCRIPT_KEY_TO_EVENTS)?,
       BRC20_SATPOINT_TO_TRANSFERABLE_ASSETS: &mut wtx
         .open_table(BRC20_SATPOINT_TO_TRANSFERABLE_ASSETS)?,
       BRC20_ADDRESS_TICKER_TO_TRANSFERABLE_ASSETS: &mut wtx
         .open_multimap_table(BRC20_ADDRESS_TICKER_TO_TRANSFERABLE_ASSETS)?,
+      BRC20_ADDRESS_INSCRIPTION_NUMBER_TO_TRANSFERABLE_ASSETS: &mut wtx
+        .open_table(BRC20_ADDRESS_INSCRIPTION_NUMBER_TO_TRANSFERABLE_ASSETS)?,
+      journal: &journal,
+      undo_log: UndoLog::default(),
     };
 
     // Create a protocol manager to index the block of bitmap data.
-    let config = ProtocolConfig::new_with_options(&index.options);
-    ProtocolManager::new(config).index_block(&mut context, &block, operations)?;
+    ProtocolManager::new(index.protocol_config.clone()).index_block(
+      &mut context,
+      &block,
+      operations,
+    )?;
+    let (hit, miss) = (context.hit, context.miss);
+    let changes = context.undo_log.take_changes();
 
     if index.index_runes && self.height >= self.index.options.first_rune_height() {
       let mut outpoint_to_rune_balances = wtx.open_table(OUTPOINT_TO_RUNE_BALANCES)?;
+      let mut rune_events = wtx.open_table(RUNE_EVENTS)?;
+      let mut rune_id_height_to_rune_supply = wtx.open_table(RUNE_ID_HEIGHT_TO_RUNE_SUPPLY)?;
+      let mut rune_id_to_holders = wtx.open_table(RUNE_ID_TO_HOLDERS)?;
       let mut rune_id_to_rune_entry = wtx.open_table(RUNE_ID_TO_RUNE_ENTRY)?;
+      let mut rune_id_to_script_key_balance = wtx.open_table(RUNE_ID_TO_SCRIPT_KEY_BALANCE)?;
       let mut rune_to_rune_id = wtx.open_table(RUNE_TO_RUNE_ID)?;
       let mut sequence_number_to_rune_id = wtx.open_table(SEQUENCE_NUMBER_TO_RUNE_ID)?;
+      let mut script_key_to_rune_outpoints =
+        wtx.open_multimap_table(SCRIPT_KEY_TO_RUNE_OUTPOINTS)?;
       let mut transaction_id_to_rune = wtx.open_table(TRANSACTION_ID_TO_RUNE)?;
 
       let runes = statistic_to_count
@@ -661,17 +775,26 @@ impl<'index> Updater<'_> {
         .unwrap_or(0);
 
       let mut rune_updater = RuneUpdater {
+        chain: self.index.options.chain(),
+        client: &index.client,
+        events: &mut rune_events,
         height: self.height,
+        holder_balances: &mut rune_id_to_script_key_balance,
+        holders: &mut rune_id_to_holders,
         id_to_entry: &mut rune_id_to_rune_entry,
         inscription_id_to_sequence_number: &mut inscription_id_to_sequence_number,
+        journal: &journal,
         minimum: Rune::minimum_at_height(self.index.options.chain(), Height(self.height)),
         outpoint_to_balances: &mut outpoint_to_rune_balances,
+        outpoint_to_entry: &outpoint_to_entry,
         rune_to_id: &mut rune_to_rune_id,
         runes,
+        script_key_to_outpoints: &mut script_key_to_rune_outpoints,
         sequence_number_to_rune_id: &mut sequence_number_to_rune_id,
         statistic_to_count: &mut statistic_to_count,
         timestamp: block.header.time,
         transaction_id_to_rune: &mut transaction_id_to_rune,
+        tx_out_cache,
         updates: HashMap::new(),
       };
 
@@ -687,16 +810,55 @@ impl<'index> Updater<'_> {
             .value(),
         );
 
-        entry.burned += update.burned;
+        entry.burned += update.burned();
         entry.mints += update.mints;
         entry.supply += update.supply;
 
-        rune_id_to_rune_entry.insert(&rune_id.store(), entry.store())?;
+        journal.insert(&mut rune_id_to_rune_entry, &rune_id.store(), entry.store())?;
+
+        journal.insert(
+          &mut rune_id_height_to_rune_supply,
+          &(rune_id.store(), self.height),
+          RuneSupplyEntry {
+            burned: entry.burned,
+            burned_invalid_runestone: update.burned_invalid_runestone,
+            burned_op_return: update.burned_op_return,
+            burned_unallocated: update.burned_unallocated,
+            minted: update.supply,
+            mints: entry.mints,
+            supply: entry.supply,
+          }
+          .store(),
+        )?;
       }
     }
 
-    height_to_block_header.insert(&self.height, &block.header.store())?;
+    journal.insert(
+      &mut height_to_block_header,
+      &self.height,
+      &block.header.store(),
+    )?;
 
+    if journal.is_enabled() {
+      let mut undo_logs = wtx.open_table(UNDO_LOGS)?;
+      save_undo_log(
+        &mut undo_logs,
+        self.height,
+        &BlockUndoLog {
+          changes,
+          writes: journal.take(),
+        },
+      )?;
+      // a reorg never disconnects blocks deeper than the max reorg depth.
+      remove_undo_logs(
+        &mut undo_logs,
+        ..(self.height + 1).saturating_sub(self.index.options.max_reorg_depth()),
+      )?;
+    }
+
+    self
+      .uncommitted_blocks
+      .push((self.height, block.header.block_hash()));
     self.height += 1;
     self.outputs_traversed += outputs_in_block;
 
@@ -704,8 +866,8 @@ impl<'index> Updater<'_> {
       "Wrote {sat_ranges_written} sat ranges from {outputs_in_block} outputs in {}/{} ms, hit miss: {}/{}",
       ord_cost,
       (Instant::now() - start).as_millis(),
-      context.hit,
-      context.miss,
+      hit,
+      miss,
     );
 
     Ok(())
@@ -721,9 +883,12 @@ impl<'index> Updater<'_> {
     outputs_traversed: &mut u64,
     inscription_updater: &mut InscriptionUpdater,
     index_inscriptions: bool,
+    journal: &Journal,
   ) -> Result {
     if index_inscriptions {
       inscription_updater.index_envelopes(tx, txid, Some(input_sat_ranges))?;
+    } else if self.index.index_addresses {
+      inscription_updater.index_outputs(tx, txid)?;
     }
 
     for (vout, output) in tx.output.iter().enumerate() {
@@ -740,7 +905,8 @@ impl<'index> Updater<'_> {
           .ok_or_else(|| anyhow!("insufficient inputs for transaction outputs"))?;
 
         if !Sat(range.0).common() {
-          sat_to_satpoint.insert(
+          journal.insert(
+            sat_to_satpoint,
             &range.0,
             &SatPoint {
               outpoint,
@@ -753,7 +919,7 @@ impl<'index> Updater<'_> {
         let count = range.1 - range.0;
 
         let assigned = if count > remaining {
-          self.sat_ranges_since_flush += 1;
+          self.sat_ranges_in_block += 1;
           let middle = range.0 + remaining;
           input_sat_ranges.push_front((middle, range.1));
           (range.0, middle)
@@ -770,6 +936,7 @@ impl<'index> Updater<'_> {
 
       *outputs_traversed += 1;
 
+      journal.record_buffered(OUTPOINT_TO_SAT_RANGES, &outpoint.store(), None);
       self.range_cache.insert(outpoint.store(), sats);
       self.outputs_inserted_since_flush += 1;
     }
@@ -803,14 +970,15 @@ impl<'index> Updater<'_> {
       self.outputs_inserted_since_flush = 0;
     }
 
-    Index::increment_statistic(&wtx, Statistic::OutputsTraversed, self.outputs_traversed)?;
     self.outputs_traversed = 0;
-    Index::increment_statistic(&wtx, Statistic::SatRanges, self.sat_ranges_since_flush)?;
-    self.sat_ranges_since_flush = 0;
     Index::increment_statistic(&wtx, Statistic::Commits, 1)?;
     wtx.commit()?;
 
-    Reorg::update_savepoints(self.index, self.height)?;
+    for (height, block_hash) in self.uncommitted_blocks.drain(..) {
+      self
+        .index
+        .notify_block_event(BlockEvent::Connected { height, block_hash });
+    }
 
     Ok(())
   }
@@ -15,17 +15,17 @@ enum Curse {
 }
 
 #[derive(Debug, Clone)]
-pub(super) struct Flotsam {
-  txid: Txid,
-  inscription_id: InscriptionId,
-  offset: u64,
-  old_satpoint: SatPoint,
-  origin: Origin,
+pub(crate) struct Flotsam {
+  pub(crate) txid: Txid,
+  pub(crate) inscription_id: InscriptionId,
+  pub(crate) offset: u64,
+  pub(crate) old_satpoint: SatPoint,
+  pub(crate) origin: Origin,
 }
 
 #[allow(clippy::large_enum_variant)]
 #[derive(Debug, Clone)]
-enum Origin {
+pub(crate) enum Origin {
   New {
     cursed: bool,
     fee: u64,
@@ -40,6 +40,178 @@ enum Origin {
   Old,
 }
 
+/// The outputs spent by a transaction whose inscriptions are floated.
+pub(crate) trait SpentOutputs {
+  /// Spends the output and returns the inscriptions on it with its value.
+  fn spend(&mut self, outpoint: OutPoint) -> Result<(Vec<(SatPoint, InscriptionId)>, u64)>;
+
+  /// Whether the inscription was cursed or vindicated, in which case reinscribing its sat is not a curse.
+  fn is_cursed_or_vindicated(&mut self, inscription_id: InscriptionId) -> Result<bool>;
+}
+
+/// Floats the inscriptions transferred by the inputs of the transaction and the ones its envelopes create, in input
+/// order. Returns them with the total input value and whether the transaction has envelopes.
+pub(crate) fn float_inscriptions(
+  tx: &Transaction,
+  txid: Txid,
+  height: u32,
+  chain: Chain,
+  spent_outputs: &mut impl SpentOutputs,
+) -> Result<(Vec<Flotsam>, u64, bool)> {
+  let mut floating_inscriptions = Vec::new();
+  let mut id_counter = 0;
+  let mut inscribed_offsets = BTreeMap::new();
+  let jubilant = height >= chain.jubilee_height();
+  let mut total_input_value = 0;
+  let total_output_value = tx.output.iter().map(|txout| txout.value).sum::<u64>();
+
+  let envelopes = ParsedEnvelope::from_transaction(tx);
+  let inscriptions = !envelopes.is_empty();
+  let mut envelopes = envelopes.into_iter().peekable();
+
+  for (input_index, tx_in) in tx.input.iter().enumerate() {
+    // skip subsidy since no inscriptions possible
+    if tx_in.previous_output.is_null() {
+      total_input_value += Height(height).subsidy();
+      continue;
+    }
+
+    let (spent_inscriptions, current_input_value) = spent_outputs.spend(tx_in.previous_output)?;
+
+    // find existing inscriptions on input (transfers of inscriptions)
+    for (old_satpoint, inscription_id) in spent_inscriptions {
+      let offset = total_input_value + old_satpoint.offset;
+      floating_inscriptions.push(Flotsam {
+        txid,
+        offset,
+        inscription_id,
+        old_satpoint,
+        origin: Origin::Old,
+      });
+
+      inscribed_offsets
+        .entry(offset)
+        .or_insert((inscription_id, 0))
+        .1 += 1;
+    }
+
+    let offset = total_input_value;
+
+    total_input_value += current_input_value;
+
+    // go through all inscriptions in this input
+    while let Some(inscription) = envelopes.peek() {
+      if inscription.input != u32::try_from(input_index).unwrap() {
+        break;
+      }
+
+      let inscription_id = InscriptionId {
+        txid,
+        index: id_counter,
+      };
+
+      let curse = if inscription.payload.unrecognized_even_field {
+        Some(Curse::UnrecognizedEvenField)
+      } else if inscription.payload.duplicate_field {
+        Some(Curse::DuplicateField)
+      } else if inscription.payload.incomplete_field {
+        Some(Curse::IncompleteField)
+      } else if inscription.input != 0 {
+        Some(Curse::NotInFirstInput)
+      } else if inscription.offset != 0 {
+        Some(Curse::NotAtOffsetZero)
+      } else if inscription.payload.pointer.is_some() {
+        Some(Curse::Pointer)
+      } else if inscription.pushnum {
+        Some(Curse::Pushnum)
+      } else if inscription.stutter {
+        Some(Curse::Stutter)
+      } else if let Some((id, count)) = inscribed_offsets.get(&offset) {
+        if *count > 1 {
+          Some(Curse::Reinscription)
+        } else if spent_outputs.is_cursed_or_vindicated(*id)? {
+          None
+        } else {
+          Some(Curse::Reinscription)
+        }
+      } else {
+        None
+      };
+
+      let unbound = current_input_value == 0
+        || curse == Some(Curse::UnrecognizedEvenField)
+        || inscription.payload.unrecognized_even_field;
+
+      let offset = inscription
+        .payload
+        .pointer()
+        .filter(|&pointer| pointer < total_output_value)
+        .unwrap_or(offset);
+
+      floating_inscriptions.push(Flotsam {
+        txid,
+        inscription_id,
+        offset,
+        old_satpoint: SatPoint {
+          outpoint: tx_in.previous_output,
+          offset: 0,
+        },
+        origin: Origin::New {
+          cursed: curse.is_some() && !jubilant,
+          fee: 0,
+          hidden: inscription.payload.hidden(),
+          parent: inscription.payload.parent(),
+          pointer: inscription.payload.pointer(),
+          reinscription: inscribed_offsets.contains_key(&offset),
+          unbound,
+          inscription: inscription.payload.clone(),
+          vindicated: curse.is_some() && jubilant,
+        },
+      });
+
+      inscribed_offsets
+        .entry(offset)
+        .or_insert((inscription_id, 0))
+        .1 += 1;
+
+      envelopes.next();
+      id_counter += 1;
+    }
+  }
+
+  let potential_parents = floating_inscriptions
+    .iter()
+    .map(|flotsam| flotsam.inscription_id)
+    .collect::<HashSet<InscriptionId>>();
+
+  for flotsam in &mut floating_inscriptions {
+    if let Flotsam {
+      origin: Origin::New { parent, .. },
+      ..
+    } = flotsam
+    {
+      if let Some(purported_parent) = parent {
+        if !potential_parents.contains(purported_parent) {
+          *parent = None;
+        }
+      }
+    }
+  }
+
+  // still have to normalize over inscription size
+  for flotsam in &mut floating_inscriptions {
+    if let Flotsam {
+      origin: Origin::New { ref mut fee, .. },
+      ..
+    } = flotsam
+    {
+      *fee = (total_input_value - total_output_value) / u64::from(id_counter);
+    }
+  }
+
+  Ok((floating_inscriptions, total_input_value, inscriptions))
+}
+
 pub(super) struct InscriptionUpdater<'a, 'db, 'tx> {
   pub(super) operations: &'a mut HashMap<Txid, Vec<InscriptionOp>>,
   pub(super) blessed_inscription_count: u64,
@@ -52,6 +224,7 @@ pub(super) struct InscriptionUpdater<'a, 'db, 'tx> {
   pub(super) id_to_sequence_number: &'a mut Table<'db, 'tx, InscriptionIdValue, u32>,
   pub(super) index_transactions: bool,
   pub(super) inscription_number_to_sequence_number: &'a mut Table<'db, 'tx, i32, u32>,
+  pub(super) journal: &'a Journal,
   pub(super) next_sequence_number: u32,
   pub(super) lost_sats: u64,
   pub(super) outpoint_to_entry: &'a mut Table<'db, 'tx, &'static OutPointValue, &'static [u8]>,
@@ -62,6 +235,10 @@ pub(super) struct InscriptionUpdater<'a, 'db, 'tx> {
   pub(super) sat_to_sequence_number: &'a mut MultimapTable<'db, 'tx, u64, u32>,
   pub(super) satpoint_to_sequence_number:
     &'a mut MultimapTable<'db, 'tx, &'static SatPointValue, u32>,
+  pub(super) script_key_to_outpoints:
+    Option<&'a mut MultimapTable<'db, 'tx, &'static str, &'static OutPointValue>>,
+  pub(super) script_key_to_unspent_outpoints:
+    Option<&'a mut MultimapTable<'db, 'tx, &'static str, &'static OutPointValue>>,
   pub(super) sequence_number_to_children: &'a mut MultimapTable<'db, 'tx, u32, u32>,
   pub(super) sequence_number_to_entry: &'a mut Table<'db, 'tx, u32, InscriptionEntryValue>,
   pub(super) sequence_number_to_satpoint: &'a mut Table<'db, 'tx, u32, &'static SatPointValue>,
@@ -83,12 +260,19 @@ impl<'a, 'db, 'tx> InscriptionUpdater<'a, 'db, 'tx> {
     id_to_sequence_number: &'a mut Table<'db, 'tx, InscriptionIdValue, u32>,
     index_transactions: bool,
     inscription_number_to_sequence_number: &'a mut Table<'db, 'tx, i32, u32>,
+    journal: &'a Journal,
     next_sequence_number: u32,
     lost_sats: u64,
     outpoint_to_entry: &'a mut Table<'db, 'tx, &'static OutPointValue, &'static [u8]>,
     transaction_id_to_transaction: &'a mut Table<'db, 'tx, &'static TxidValue, &'static [u8]>,
     sat_to_sequence_number: &'a mut MultimapTable<'db, 'tx, u64, u32>,
     satpoint_to_sequence_number: &'a mut MultimapTable<'db, 'tx, &'static SatPointValue, u32>,
+    script_key_to_outpoints: Option<
+      &'a mut MultimapTable<'db, 'tx, &'static str, &'static OutPointValue>,
+    >,
+    script_key_to_unspent_outpoints: Option<
+      &'a mut MultimapTable<'db, 'tx, &'static str, &'static OutPointValue>,
+    >,
     sequence_number_to_children: &'a mut MultimapTable<'db, 'tx, u32, u32>,
     sequence_number_to_entry: &'a mut Table<'db, 'tx, u32, InscriptionEntryValue>,
     sequence_number_to_satpoint: &'a mut Table<'db, 'tx, u32, &'static SatPointValue>,
@@ -109,6 +293,7 @@ impl<'a, 'db, 'tx> InscriptionUpdater<'a, 'db, 'tx> {
       id_to_sequence_number,
       index_transactions,
       inscription_number_to_sequence_number,
+      journal,
       next_sequence_number,
       lost_sats,
       outpoint_to_entry,
@@ -117,6 +302,8 @@ impl<'a, 'db, 'tx> InscriptionUpdater<'a, 'db, 'tx> {
       transaction_id_to_transaction,
       sat_to_sequence_number,
       satpoint_to_sequence_number,
+      script_key_to_outpoints,
+      script_key_to_unspent_outpoints,
       sequence_number_to_children,
       sequence_number_to_entry,
       sequence_number_to_satpoint,
@@ -133,206 +320,22 @@ impl<'a, 'db, 'tx> InscriptionUpdater<'a, 'db, 'tx> {
     txid: Txid,
     input_sat_ranges: Option<&VecDeque<(u64, u64)>>,
   ) -> Result {
-    let mut floating_inscriptions = Vec::new();
-    let mut id_counter = 0;
-    let mut inscribed_offsets = BTreeMap::new();
-    let jubilant = self.height >= self.chain.jubilee_height();
-    let mut total_input_value = 0;
-    let total_output_value = tx.output.iter().map(|txout| txout.value).sum::<u64>();
-
-    let envelopes = ParsedEnvelope::from_transaction(tx);
-    let inscriptions = !envelopes.is_empty();
-    let mut envelopes = envelopes.into_iter().peekable();
-
-    for (input_index, tx_in) in tx.input.iter().enumerate() {
-      // skip subsidy since no inscriptions possible
-      if tx_in.previous_output.is_null() {
-        total_input_value += Height(self.height).subsidy();
-        continue;
-      }
-
-      // find existing inscriptions on input (transfers of inscriptions)
-      for (old_satpoint, inscription_id) in Index::inscriptions_on_output(
-        self.satpoint_to_sequence_number,
-        self.sequence_number_to_entry,
-        tx_in.previous_output,
-      )? {
-        let offset = total_input_value + old_satpoint.offset;
-        floating_inscriptions.push(Flotsam {
-          txid,
-          offset,
-          inscription_id,
-          old_satpoint,
-          origin: Origin::Old,
-        });
-
-        inscribed_offsets
-          .entry(offset)
-          .or_insert((inscription_id, 0))
-          .1 += 1;
-      }
-
-      let offset = total_input_value;
-
-      // multi-level cache for UTXO set to get to the input amount
-      let current_input_value = if let Some(tx_out) = self.tx_out_cache.get(&tx_in.previous_output)
-      {
-        tx_out.value
-      } else {
-        let tx_out = self.tx_out_receiver.blocking_recv().ok_or_else(|| {
-          anyhow!(
-            "failed to get transaction for {}",
-            tx_in.previous_output.txid
-          )
-        })?;
-        // received new tx out from chain node, add it to new_outpoints first and persist it in db later.
-        #[cfg(not(feature = "cache"))]
-        self.new_outpoints.push(tx_in.previous_output);
-        self
-          .tx_out_cache
-          .insert(tx_in.previous_output, tx_out.clone());
-        tx_out.value
-      };
-
-      total_input_value += current_input_value;
-
-      // go through all inscriptions in this input
-      while let Some(inscription) = envelopes.peek() {
-        if inscription.input != u32::try_from(input_index).unwrap() {
-          break;
-        }
-
-        let inscription_id = InscriptionId {
-          txid,
-          index: id_counter,
-        };
-
-        let curse = if inscription.payload.unrecognized_even_field {
-          Some(Curse::UnrecognizedEvenField)
-        } else if inscription.payload.duplicate_field {
-          Some(Curse::DuplicateField)
-        } else if inscription.payload.incomplete_field {
-          Some(Curse::IncompleteField)
-        } else if inscription.input != 0 {
-          Some(Curse::NotInFirstInput)
-        } else if inscription.offset != 0 {
-          Some(Curse::NotAtOffsetZero)
-        } else if inscription.payload.pointer.is_some() {
-          Some(Curse::Pointer)
-        } else if inscription.pushnum {
-          Some(Curse::Pushnum)
-        } else if inscription.stutter {
-          Some(Curse::Stutter)
-        } else if let Some((id, count)) = inscribed_offsets.get(&offset) {
-          if *count > 1 {
-            Some(Curse::Reinscription)
-          } else {
-            let initial_inscription_sequence_number =
-              self.id_to_sequence_number.get(id.store())?.unwrap().value();
-
-            let entry = InscriptionEntry::load(
-              self
-                .sequence_number_to_entry
-                .get(initial_inscription_sequence_number)?
-                .unwrap()
-                .value(),
-            );
-
-            let initial_inscription_was_cursed_or_vindicated =
-              entry.inscription_number < 0 || Charm::Vindicated.is_set(entry.charms);
-
-            if initial_inscription_was_cursed_or_vindicated {
-              None
-            } else {
-              Some(Curse::Reinscription)
-            }
-          }
-        } else {
-          None
-        };
-
-        let unbound = current_input_value == 0
-          || curse == Some(Curse::UnrecognizedEvenField)
-          || inscription.payload.unrecognized_even_field;
-
-        let offset = inscription
-          .payload
-          .pointer()
-          .filter(|&pointer| pointer < total_output_value)
-          .unwrap_or(offset);
-
-        floating_inscriptions.push(Flotsam {
-          txid,
-          inscription_id,
-          offset,
-          old_satpoint: SatPoint {
-            outpoint: tx_in.previous_output,
-            offset: 0,
-          },
-          origin: Origin::New {
-            cursed: curse.is_some() && !jubilant,
-            fee: 0,
-            hidden: inscription.payload.hidden(),
-            parent: inscription.payload.parent(),
-            pointer: inscription.payload.pointer(),
-            reinscription: inscribed_offsets.contains_key(&offset),
-            unbound,
-            inscription: inscription.payload.clone(),
-            vindicated: curse.is_some() && jubilant,
-          },
-        });
-
-        inscribed_offsets
-          .entry(offset)
-          .or_insert((inscription_id, 0))
-          .1 += 1;
-
-        envelopes.next();
-        id_counter += 1;
-      }
-    }
+    let (mut floating_inscriptions, total_input_value, inscriptions) =
+      float_inscriptions(tx, txid, self.height, self.chain, self)?;
 
     if self.index_transactions && inscriptions {
       tx.consensus_encode(&mut self.transaction_buffer)
         .expect("in-memory writers don't error");
 
-      self
-        .transaction_id_to_transaction
-        .insert(&txid.store(), self.transaction_buffer.as_slice())?;
+      self.journal.insert(
+        self.transaction_id_to_transaction,
+        &txid.store(),
+        self.transaction_buffer.as_slice(),
+      )?;
 
       self.transaction_buffer.clear();
     }
 
-    let potential_parents = floating_inscriptions
-      .iter()
-      .map(|flotsam| flotsam.inscription_id)
-      .collect::<HashSet<InscriptionId>>();
-
-    for flotsam in &mut floating_inscriptions {
-      if let Flotsam {
-        origin: Origin::New { parent, .. },
-        ..
-      } = flotsam
-      {
-        if let Some(purported_parent) = parent {
-          if !potential_parents.contains(purported_parent) {
-            *parent = None;
-          }
-        }
-      }
-    }
-
-    // still have to normalize over inscription size
-    for flotsam in &mut floating_inscriptions {
-      if let Flotsam {
-        origin: Origin::New { ref mut fee, .. },
-        ..
-      } = flotsam
-      {
-        *fee = (total_input_value - total_output_value) / u64::from(id_counter);
-      }
-    }
-
     let is_coinbase = tx
       .input
       .first()
@@ -352,6 +355,14 @@ impl<'a, 'db, 'tx> InscriptionUpdater<'a, 'db, 'tx> {
     for (vout, tx_out) in tx.output.iter().enumerate() {
       let end = output_value + tx_out.value;
 
+      self.create_output(
+        OutPoint {
+          txid,
+          vout: vout.try_into().unwrap(),
+        },
+        tx_out,
+      )?;
+
       while let Some(flotsam) = inscriptions.peek() {
         if flotsam.offset >= end {
           break;
@@ -371,19 +382,6 @@ impl<'a, 'db, 'tx> InscriptionUpdater<'a, 'db, 'tx> {
       range_to_vout.insert((output_value, end), vout.try_into().unwrap());
 
       output_value = end;
-
-      #[cfg(not(feature = "cache"))]
-      self.new_outpoints.push(OutPoint {
-        vout: vout.try_into().unwrap(),
-        txid,
-      });
-      self.tx_out_cache.insert(
-        OutPoint {
-          vout: vout.try_into().unwrap(),
-          txid,
-        },
-        tx_out.clone(),
-      );
     }
 
     for (new_satpoint, mut flotsam) in new_locations.into_iter() {
@@ -408,6 +406,22 @@ impl<'a, 'db, 'tx> InscriptionUpdater<'a, 'db, 'tx> {
         _ => new_satpoint,
       };
 
+      let unbound = matches!(flotsam.origin, Origin::New { unbound: true, .. });
+
+      if let Some(script_key_to_outpoints) = self.script_key_to_outpoints.as_mut() {
+        if !unbound {
+          let script_key = ScriptKey::from_script(
+            &tx.output[usize::try_from(new_satpoint.outpoint.vout).unwrap()].script_pubkey,
+            self.chain,
+          );
+          self.journal.multimap_insert(
+            script_key_to_outpoints,
+            script_key.to_string().as_str(),
+            &new_satpoint.outpoint.store(),
+          )?;
+        }
+      }
+
       self.update_inscription_location(input_sat_ranges, flotsam, new_satpoint)?;
     }
 
@@ -431,6 +445,48 @@ impl<'a, 'db, 'tx> InscriptionUpdater<'a, 'db, 'tx> {
     }
   }
 
+  /// Spends the inputs and creates the outputs of a transaction whose inscriptions aren't indexed,
+  /// so that the unspent outputs of addresses are tracked at every height.
+  pub(super) fn index_outputs(&mut self, tx: &Transaction, txid: Txid) -> Result {
+    for tx_in in &tx.input {
+      if !tx_in.previous_output.is_null() {
+        self.spend(tx_in.previous_output)?;
+      }
+    }
+
+    for (vout, tx_out) in tx.output.iter().enumerate() {
+      self.create_output(
+        OutPoint {
+          txid,
+          vout: vout.try_into().unwrap(),
+        },
+        tx_out,
+      )?;
+    }
+
+    Ok(())
+  }
+
+  fn create_output(&mut self, outpoint: OutPoint, tx_out: &TxOut) -> Result {
+    if let Some(script_key_to_unspent_outpoints) = self.script_key_to_unspent_outpoints.as_mut() {
+      if !tx_out.script_pubkey.is_op_return() {
+        self.journal.multimap_insert(
+          script_key_to_unspent_outpoints,
+          ScriptKey::from_script(&tx_out.script_pubkey, self.chain)
+            .to_string()
+            .as_str(),
+          &outpoint.store(),
+        )?;
+      }
+    }
+
+    #[cfg(not(feature = "cache"))]
+    self.new_outpoints.push(outpoint);
+    self.tx_out_cache.insert(outpoint, tx_out.clone());
+
+    Ok(())
+  }
+
   // write tx_out to outpoint_to_entry table
   pub(super) fn flush_cache(self) -> Result {
     let start = Instant::now();
@@ -440,8 +496,8 @@ impl<'a, 'db, 'tx> InscriptionUpdater<'a, 'db, 'tx> {
       let tx_out = self.tx_out_cache.get(&outpoint).unwrap();
       tx_out.consensus_encode(&mut entry)?;
       self
-        .outpoint_to_entry
-        .insert(&outpoint.store(), entry.as_slice())?;
+        .journal
+        .insert(self.outpoint_to_entry, &outpoint.store(), entry.as_slice())?;
       entry.clear();
     }
     log::info!(
@@ -481,9 +537,10 @@ impl<'a, 'db, 'tx> InscriptionUpdater<'a, 'db, 'tx> {
     let inscription_id = flotsam.inscription_id;
     let (unbound, sequence_number) = match flotsam.origin {
       Origin::Old => {
-        self
-          .satpoint_to_sequence_number
-          .remove_all(&flotsam.old_satpoint.store())?;
+        self.journal.multimap_remove_all(
+          self.satpoint_to_sequence_number,
+          &flotsam.old_satpoint.store(),
+        )?;
 
         (
           false,
@@ -521,9 +578,11 @@ impl<'a, 'db, 'tx> InscriptionUpdater<'a, 'db, 'tx> {
         let sequence_number = self.next_sequence_number;
         self.next_sequence_number += 1;
 
-        self
-          .inscription_number_to_sequence_number
-          .insert(inscription_number, sequence_number)?;
+        self.journal.insert(
+          self.inscription_number_to_sequence_number,
+          inscription_number,
+          sequence_number,
+        )?;
 
         let sat = if unbound {
           None
@@ -572,7 +631,9 @@ impl<'a, 'db, 'tx> InscriptionUpdater<'a, 'db, 'tx> {
         }
 
         if let Some(Sat(n)) = sat {
-          self.sat_to_sequence_number.insert(&n, &sequence_number)?;
+          self
+            .journal
+            .multimap_insert(self.sat_to_sequence_number, &n, &sequence_number)?;
         }
 
         let parent = match parent {
@@ -582,16 +643,19 @@ impl<'a, 'db, 'tx> InscriptionUpdater<'a, 'db, 'tx> {
               .get(&parent_id.store())?
               .unwrap()
               .value();
-            self
-              .sequence_number_to_children
-              .insert(parent_sequence_number, sequence_number)?;
+            self.journal.multimap_insert(
+              self.sequence_number_to_children,
+              parent_sequence_number,
+              sequence_number,
+            )?;
 
             Some(parent_sequence_number)
           }
           None => None,
         };
 
-        self.sequence_number_to_entry.insert(
+        self.journal.insert(
+          self.sequence_number_to_entry,
           sequence_number,
           &InscriptionEntry {
             charms,
@@ -607,17 +671,21 @@ impl<'a, 'db, 'tx> InscriptionUpdater<'a, 'db, 'tx> {
           .store(),
         )?;
 
-        self
-          .id_to_sequence_number
-          .insert(&inscription_id.store(), sequence_number)?;
+        self.journal.insert(
+          self.id_to_sequence_number,
+          &inscription_id.store(),
+          sequence_number,
+        )?;
 
         if !hidden {
-          self
-            .home_inscriptions
-            .insert(&sequence_number, inscription_id.store())?;
+          self.journal.insert(
+            self.home_inscriptions,
+            &sequence_number,
+            inscription_id.store(),
+          )?;
 
           if self.home_inscription_count == 100 {
-            self.home_inscriptions.pop_first()?;
+            self.journal.pop_first(self.home_inscriptions)?;
           } else {
             self.home_inscription_count += 1;
           }
@@ -675,12 +743,84 @@ impl<'a, 'db, 'tx> InscriptionUpdater<'a, 'db, 'tx> {
       });
 
     self
-      .satpoint_to_sequence_number
-      .insert(&satpoint, sequence_number)?;
+      .journal
+      .multimap_insert(self.satpoint_to_sequence_number, &satpoint, sequence_number)?;
     self
-      .sequence_number_to_satpoint
-      .insert(sequence_number, &satpoint)?;
+      .journal
+      .insert(self.sequence_number_to_satpoint, sequence_number, &satpoint)?;
 
     Ok(())
   }
 }
+
+impl SpentOutputs for InscriptionUpdater<'_, '_, '_> {
+  fn spend(&mut self, outpoint: OutPoint) -> Result<(Vec<(SatPoint, InscriptionId)>, u64)> {
+    let inscriptions = Index::inscriptions_on_output(
+      self.satpoint_to_sequence_number,
+      self.sequence_number_to_entry,
+      outpoint,
+    )?;
+
+    // multi-level cache for UTXO set to get to the input amount
+    let value = if let Some(tx_out) = self.tx_out_cache.get(&outpoint) {
+      tx_out.value
+    } else {
+      let tx_out = self
+        .tx_out_receiver
+        .blocking_recv()
+        .ok_or_else(|| anyhow!("failed to get transaction for {}", outpoint.txid))?;
+      // received new tx out from chain node, add it to new_outpoints first and persist it in db later.
+      #[cfg(not(feature = "cache"))]
+      self.new_outpoints.push(outpoint);
+      self.tx_out_cache.insert(outpoint, tx_out.clone());
+      tx_out.value
+    };
+
+    if let Some(script_key_to_unspent_outpoints) = self.script_key_to_unspent_outpoints.as_mut() {
+      let script_key = ScriptKey::from_script(
+        &self.tx_out_cache.get(&outpoint).unwrap().script_pubkey,
+        self.chain,
+      );
+      self.journal.multimap_remove(
+        script_key_to_unspent_outpoints,
+        script_key.to_string().as_str(),
+        &outpoint.store(),
+      )?;
+    }
+
+    // the spent output no longer holds inscriptions of its address
+    if !inscriptions.is_empty() {
+      if let Some(script_key_to_outpoints) = self.script_key_to_outpoints.as_mut() {
+        let script_key = ScriptKey::from_script(
+          &self.tx_out_cache.get(&outpoint).unwrap().script_pubkey,
+          self.chain,
+        );
+        self.journal.multimap_remove(
+          script_key_to_outpoints,
+          script_key.to_string().as_str(),
+          &outpoint.store(),
+        )?;
+      }
+    }
+
+    Ok((inscriptions, value))
+  }
+
+  fn is_cursed_or_vindicated(&mut self, inscription_id: InscriptionId) -> Result<bool> {
+    let initial_inscription_sequence_number = self
+      .id_to_sequence_number
+      .get(inscription_id.store())?
+      .unwrap()
+      .value();
+
+    let entry = InscriptionEntry::load(
+      self
+        .sequence_number_to_entry
+        .get(initial_inscription_sequence_number)?
+        .unwrap()
+        .value(),
+    );
+
+    Ok(entry.inscription_number < 0 || Charm::Vindicated.is_set(entry.charms))
+  }
+}
@@ -1,6 +1,10 @@
 use {
   super::*,
-  crate::runes::{varint, Edict, Runestone, CLAIM_BIT},
+  crate::{
+    index::rune_holders::{get_rune_balance, update_rune_balance},
+    okx::datastore::ord::redb::table::get_txout_by_outpoint,
+    runes::{varint, BurnReason, Edict, RuneError, RuneEvent, Runestone, SpacedRune, CLAIM_BIT},
+  },
 };
 
 fn claim(id: u128) -> Option<u128> {
@@ -21,40 +25,87 @@ struct Allocation {
 
 #[derive(Default)]
 pub(crate) struct RuneUpdate {
-  pub(crate) burned: u128,
+  pub(crate) burned_invalid_runestone: u128,
+  pub(crate) burned_op_return: u128,
+  pub(crate) burned_unallocated: u128,
   pub(crate) mints: u64,
   pub(crate) supply: u128,
 }
 
+impl RuneUpdate {
+  fn burn(&mut self, reason: BurnReason, amount: u128) {
+    *match reason {
+      BurnReason::InvalidRunestone => &mut self.burned_invalid_runestone,
+      BurnReason::OpReturn => &mut self.burned_op_return,
+      BurnReason::Unallocated => &mut self.burned_unallocated,
+    } += amount;
+  }
+
+  pub(crate) fn burned(&self) -> u128 {
+    self.burned_invalid_runestone + self.burned_op_return + self.burned_unallocated
+  }
+}
+
 pub(super) struct RuneUpdater<'a, 'db, 'tx> {
+  pub(super) chain: Chain,
+  pub(super) client: &'a Client,
   pub(super) height: u32,
+  pub(super) holder_balances: &'a mut Table<'db, 'tx, &'static str, u128>,
+  pub(super) holders: &'a mut Table<'db, 'tx, &'static str, &'static [u8]>,
+  pub(super) events: &'a mut Table<'db, 'tx, &'static TxidValue, &'static [u8]>,
   pub(super) id_to_entry: &'a mut Table<'db, 'tx, RuneIdValue, RuneEntryValue>,
   pub(super) inscription_id_to_sequence_number: &'a Table<'db, 'tx, InscriptionIdValue, u32>,
+  pub(super) journal: &'a Journal,
   pub(super) minimum: Rune,
   pub(super) outpoint_to_balances: &'a mut Table<'db, 'tx, &'static OutPointValue, &'static [u8]>,
+  pub(super) outpoint_to_entry: &'a Table<'db, 'tx, &'static OutPointValue, &'static [u8]>,
   pub(super) rune_to_id: &'a mut Table<'db, 'tx, u128, RuneIdValue>,
   pub(super) runes: u64,
+  pub(super) script_key_to_outpoints:
+    &'a mut MultimapTable<'db, 'tx, &'static str, &'static OutPointValue>,
   pub(super) sequence_number_to_rune_id: &'a mut Table<'db, 'tx, u32, RuneIdValue>,
   pub(super) statistic_to_count: &'a mut Table<'db, 'tx, u64, u64>,
   pub(super) timestamp: u32,
   pub(super) transaction_id_to_rune: &'a mut Table<'db, 'tx, &'static TxidValue, u128>,
+  pub(super) tx_out_cache: &'a SimpleLru<OutPoint, TxOut>,
   pub(super) updates: HashMap<RuneId, RuneUpdate>,
 }
 
 impl<'a, 'db, 'tx> RuneUpdater<'a, 'db, 'tx> {
   pub(super) fn index_runes(&mut self, index: usize, tx: &Transaction, txid: Txid) -> Result<()> {
-    let runestone = Runestone::from_transaction(tx);
+    // The events of the transaction
+    let mut events = Vec::new();
+
+    let runestone = Runestone::decipher(tx).unwrap_or_else(|_| {
+      events.push(RuneEvent::Invalid(RuneError::MalformedRunestone));
+      None
+    });
+
+    if runestone
+      .as_ref()
+      .map(|runestone| runestone.burn)
+      .unwrap_or_default()
+    {
+      events.push(RuneEvent::Invalid(RuneError::UnrecognizedFields));
+    }
 
     // A mapping of rune ID to un-allocated balance of that rune
     let mut unallocated: HashMap<u128, u128> = HashMap::new();
 
+    // The inputs holding runes, with their balances
+    let mut spent = Vec::new();
+
+    // The runes spent and received by each script key
+    let mut holder_changes: HashMap<(String, u128), (u128, u128)> = HashMap::new();
+
     // Increment unallocated runes with the runes in this transaction's inputs
     for input in &tx.input {
       if let Some(guard) = self
-        .outpoint_to_balances
-        .remove(&input.previous_output.store())?
+        .journal
+        .remove(self.outpoint_to_balances, &input.previous_output.store())?
       {
         let buffer = guard.value();
+        let mut balances = Vec::new();
         let mut i = 0;
         while i < buffer.len() {
           let (id, len) = varint::decode(&buffer[i..]);
@@ -62,7 +113,25 @@ impl<'a, 'db, 'tx> RuneUpdater<'a, 'db, 'tx> {
           let (balance, len) = varint::decode(&buffer[i..]);
           i += len;
           *unallocated.entry(id).or_default() += balance;
+          balances.push((id, balance));
         }
+        spent.push((input.previous_output, balances));
+      }
+    }
+
+    // The spent outputs no longer hold runes of their addresses
+    for (outpoint, balances) in spent {
+      let script_key = self.script_key(outpoint)?;
+      self.journal.multimap_remove(
+        self.script_key_to_outpoints,
+        script_key.as_str(),
+        &outpoint.store(),
+      )?;
+      for (id, balance) in balances {
+        holder_changes
+          .entry((script_key.clone(), id))
+          .or_default()
+          .0 += balance;
       }
     }
 
@@ -84,16 +153,17 @@ impl<'a, 'db, 'tx> RuneUpdater<'a, 'db, 'tx> {
       // Determine if this runestone contains a valid issuance
       let mut allocation = match runestone.etching {
         Some(etching) => {
-          if etching
-            .rune
-            .map(|rune| rune < self.minimum || rune.is_reserved())
-            .unwrap_or_default()
-            || etching
-              .rune
-              .and_then(|rune| self.rune_to_id.get(rune.0).transpose())
-              .transpose()?
-              .is_some()
-          {
+          let invalid = match etching.rune {
+            Some(rune) if rune < self.minimum => Some(RuneError::RuneBelowMinimum(rune)),
+            Some(rune) if rune.is_reserved() => Some(RuneError::ReservedRune(rune)),
+            Some(rune) if self.rune_to_id.get(rune.0)?.is_some() => {
+              Some(RuneError::RuneAlreadyEtched(rune))
+            }
+            _ => None,
+          };
+
+          if let Some(error) = invalid {
+            events.push(RuneEvent::Invalid(error));
             None
           } else {
             let rune = if let Some(rune) = etching.rune {
@@ -105,9 +175,11 @@ impl<'a, 'db, 'tx> RuneUpdater<'a, 'db, 'tx> {
                 .map(|entry| entry.value())
                 .unwrap_or_default();
 
-              self
-                .statistic_to_count
-                .insert(&Statistic::ReservedRunes.into(), reserved_runes + 1)?;
+              self.journal.insert(
+                self.statistic_to_count,
+                &Statistic::ReservedRunes.into(),
+                reserved_runes + 1,
+              )?;
 
               Rune::reserved(reserved_runes.into())
             };
@@ -263,15 +335,16 @@ impl<'a, 'db, 'tx> RuneUpdater<'a, 'db, 'tx> {
         }
 
         // increment entries with minted runes
+        let mut mintable = mintable.into_iter().collect::<Vec<(u128, u128)>>();
+        mintable.sort();
         for (id, amount) in mintable {
           let minted = limits[&id] - amount;
           if minted > 0 {
-            let update = self
-              .updates
-              .entry(RuneId::try_from(id).unwrap())
-              .or_default();
+            let id = RuneId::try_from(id).unwrap();
+            let update = self.updates.entry(id).or_default();
             update.mints += 1;
             update.supply += minted;
+            events.push(RuneEvent::Minted { id, amount: minted });
           }
         }
       }
@@ -289,14 +362,35 @@ impl<'a, 'db, 'tx> RuneUpdater<'a, 'db, 'tx> {
       }) = allocation
       {
         let id = RuneId::try_from(id).unwrap();
-        self.rune_to_id.insert(rune.0, id.store())?;
-        self.transaction_id_to_rune.insert(&txid.store(), rune.0)?;
+        // the supply history of a rune starts at its etching
+        self.updates.entry(id).or_default();
+        self.journal.insert(self.rune_to_id, rune.0, id.store())?;
+        self
+          .journal
+          .insert(self.transaction_id_to_rune, &txid.store(), rune.0)?;
         let number = self.runes;
         self.runes += 1;
-        self
-          .statistic_to_count
-          .insert(&Statistic::Runes.into(), self.runes)?;
-        self.id_to_entry.insert(
+        self.journal.insert(
+          self.statistic_to_count,
+          &Statistic::Runes.into(),
+          self.runes,
+        )?;
+        let supply = if let Some(limit) = limit {
+          if end == Some(self.height) {
+            0
+          } else {
+            limit
+          }
+        } else {
+          u128::MAX
+        } - balance;
+        events.push(RuneEvent::Etched {
+          id,
+          rune: SpacedRune { rune, spacers },
+          supply,
+        });
+        self.journal.insert(
+          self.id_to_entry,
           id.store(),
           RuneEntry {
             burned: 0,
@@ -307,15 +401,7 @@ impl<'a, 'db, 'tx> RuneUpdater<'a, 'db, 'tx> {
             number,
             rune,
             spacers,
-            supply: if let Some(limit) = limit {
-              if end == Some(self.height) {
-                0
-              } else {
-                limit
-              }
-            } else {
-              u128::MAX
-            } - balance,
+            supply,
             end: end.and_then(|end| (!burn).then_some(end)),
             symbol,
             limit: limit.and_then(|limit| (!burn).then_some(limit)),
@@ -330,18 +416,22 @@ impl<'a, 'db, 'tx> RuneUpdater<'a, 'db, 'tx> {
           .inscription_id_to_sequence_number
           .get(&inscription_id.store())?
         {
-          self
-            .sequence_number_to_rune_id
-            .insert(sequence_number.value(), id.store())?;
+          self.journal.insert(
+            self.sequence_number_to_rune_id,
+            sequence_number.value(),
+            id.store(),
+          )?;
         }
       }
     }
 
-    let mut burned: HashMap<u128, u128> = HashMap::new();
+    let mut burned: HashMap<(u128, BurnReason), u128> = HashMap::new();
 
     if burn {
       for (id, balance) in unallocated {
-        *burned.entry(id).or_default() += balance;
+        *burned
+          .entry((id, BurnReason::InvalidRunestone))
+          .or_default() += balance;
       }
     } else {
       // assign all un-allocated runes to the default output, or the first non
@@ -365,7 +455,7 @@ impl<'a, 'db, 'tx> RuneUpdater<'a, 'db, 'tx> {
       } else {
         for (id, balance) in unallocated {
           if balance > 0 {
-            *burned.entry(id).or_default() += balance;
+            *burned.entry((id, BurnReason::Unallocated)).or_default() += balance;
           }
         }
       }
@@ -381,7 +471,7 @@ impl<'a, 'db, 'tx> RuneUpdater<'a, 'db, 'tx> {
       // increment burned balances
       if tx.output[vout].script_pubkey.is_op_return() {
         for (id, balance) in &balances {
-          *burned.entry(*id).or_default() += balance;
+          *burned.entry((*id, BurnReason::OpReturn)).or_default() += balance;
         }
         continue;
       }
@@ -393,32 +483,97 @@ impl<'a, 'db, 'tx> RuneUpdater<'a, 'db, 'tx> {
       // Sort balances by id so tests can assert balances in a fixed order
       balances.sort();
 
+      let script_key =
+        ScriptKey::from_script(&tx.output[vout].script_pubkey, self.chain).to_string();
+
       for (id, balance) in balances {
+        holder_changes
+          .entry((script_key.clone(), id))
+          .or_default()
+          .1 += balance;
         varint::encode_to_vec(id, &mut buffer);
         varint::encode_to_vec(balance, &mut buffer);
+        events.push(RuneEvent::Transferred {
+          id: RuneId::try_from(id).unwrap(),
+          output: vout.try_into().unwrap(),
+          amount: balance,
+        });
       }
 
-      self.outpoint_to_balances.insert(
-        &OutPoint {
-          txid,
-          vout: vout.try_into().unwrap(),
-        }
-        .store(),
+      let outpoint = OutPoint {
+        txid,
+        vout: vout.try_into().unwrap(),
+      };
+
+      self.journal.insert(
+        self.outpoint_to_balances,
+        &outpoint.store(),
         buffer.as_slice(),
       )?;
+
+      self.journal.multimap_insert(
+        self.script_key_to_outpoints,
+        script_key.as_str(),
+        &outpoint.store(),
+      )?;
+    }
+
+    // update the balances of the holders, in a fixed order so the undo log is deterministic
+    let mut holder_changes = holder_changes.into_iter().collect::<Vec<_>>();
+    holder_changes.sort();
+    for ((script_key, id), (spent, received)) in holder_changes {
+      let id = RuneId::try_from(id).unwrap();
+      let balance = get_rune_balance(self.holder_balances, id, &script_key)? - spent + received;
+      update_rune_balance(
+        self.journal,
+        self.holder_balances,
+        self.holders,
+        id,
+        &script_key,
+        balance,
+      )?;
     }
 
     // increment entries with burned runes
-    for (id, amount) in burned {
-      self
-        .updates
-        .entry(RuneId::try_from(id).unwrap())
-        .or_default()
-        .burned += amount;
+    let mut burned = burned
+      .into_iter()
+      .collect::<Vec<((u128, BurnReason), u128)>>();
+    burned.sort();
+    for ((id, reason), amount) in burned {
+      let id = RuneId::try_from(id).unwrap();
+      self.updates.entry(id).or_default().burn(reason, amount);
+      events.push(RuneEvent::Burned { id, amount, reason });
+    }
+
+    if !events.is_empty() {
+      self.journal.insert(
+        self.events,
+        &txid.store(),
+        rmp_serde::to_vec(&events).unwrap().as_slice(),
+      )?;
     }
 
     Ok(())
   }
+
+  /// The script key of a spent output, which was fetched for the inscription updater, or is fetched from bitcoind
+  /// when inscriptions are not indexed.
+  fn script_key(&self, outpoint: OutPoint) -> Result<String> {
+    let tx_out = match self.tx_out_cache.get(&outpoint) {
+      Some(tx_out) => tx_out.clone(),
+      None => match get_txout_by_outpoint(self.outpoint_to_entry, &outpoint)? {
+        Some(tx_out) => tx_out,
+        None => self
+          .client
+          .get_raw_transaction(&outpoint.txid, None)
+          .into_option()?
+          .and_then(|tx| tx.output.into_iter().nth(outpoint.vout.try_into().unwrap()))
+          .ok_or_else(|| anyhow!("failed to get tx out of rune outpoint {outpoint}"))?,
+      },
+    };
+
+    Ok(ScriptKey::from_script(&tx_out.script_pubkey, self.chain).to_string())
+  }
 }
 
 #[cfg(test)]
@@ -0,0 +1,183 @@
+use super::*;
+use bitcoin::hashes::{sha256, Hash, HashEngine};
+
+/// Chains the receipts and the new balances of a block onto the checksum of the previous block.
+///
+/// `receipts` are in transaction order and `balances` are ordered by script and ticker, so replicas with the same state
+/// history compute the same checksum. The checksum is the SHA-256 of, in order:
+///
+/// - the checksum of the previous block, or 32 zero bytes for the first BRC20 block,
+/// - the height as 4 little endian bytes,
+/// - a `receipt;<txid>;<inscription id>;<inscription number>;<old satpoint>;<new satpoint>;<from>;<to>;<result>\n`
+///   line for each receipt, where `<result>` is `deploy,<ticker>,<supply>,<limit per mint>,<decimals>,<self mint>`,
+///   `mint,<ticker>,<amount>`, `inscribe-transfer,<ticker>,<amount>`, `transfer,<ticker>,<amount>` or
+///   `failed,<operation>`,
+/// - a `balance;<script key>;<ticker>;<overall balance>;<transferable balance>\n` line for each balance.
+///
+/// Script keys are addresses or hex script hashes, tickers are lowercase and amounts are decimal integers. Messages
+/// and error reasons are left out, they don't change the state.
+pub fn block_checksum(
+  previous: Option<sha256::Hash>,
+  height: u32,
+  receipts: &[(Txid, Vec<Receipt>)],
+  balances: &[(ScriptKey, Balance)],
+) -> sha256::Hash {
+  let mut engine = sha256::Hash::engine();
+  engine.input(
+    previous
+      .unwrap_or_else(sha256::Hash::all_zeros)
+      .as_byte_array(),
+  );
+  engine.input(&height.to_le_bytes());
+  for (txid, tx_receipts) in receipts {
+    for receipt in tx_receipts {
+      engine.input(
+        format!(
+          "receipt;{txid};{};{};{};{};{};{};{}\n",
+          receipt.inscription_id,
+          receipt.inscription_number,
+          receipt.old_satpoint,
+          receipt.new_satpoint,
+          receipt.from,
+          receipt.to,
+          canonical_result(receipt),
+        )
+        .as_bytes(),
+      );
+    }
+  }
+  for (script_key, balance) in balances {
+    engine.input(
+      format!(
+        "balance;{script_key};{};{};{}\n",
+        balance.tick.to_lowercase().as_str(),
+        balance.overall_balance,
+        balance.transferable_balance,
+      )
+      .as_bytes(),
+    );
+  }
+  sha256::Hash::from_engine(engine)
+}
+
+fn canonical_result(receipt: &Receipt) -> String {
+  match &receipt.result {
+    Ok(Event::Deploy(deploy)) => format!(
+      "deploy,{},{},{},{},{}",
+      deploy.tick.to_lowercase().as_str(),
+      deploy.supply,
+      deploy.limit_per_mint,
+      deploy.decimal,
+      deploy.self_mint
+    ),
+    Ok(Event::Mint(mint)) => format!("mint,{},{}", mint.tick.to_lowercase().as_str(), mint.amount),
+    Ok(Event::InscribeTransfer(transfer)) => format!(
+      "inscribe-transfer,{},{}",
+      transfer.tick.to_lowercase().as_str(),
+      transfer.amount
+    ),
+    Ok(Event::Transfer(transfer)) => format!(
+      "transfer,{},{}",
+      transfer.tick.to_lowercase().as_str(),
+      transfer.amount
+    ),
+    Err(_) => format!(
+      "failed,{}",
+      match receipt.op {
+        OperationType::Deploy => "deploy",
+        OperationType::Mint => "mint",
+        OperationType::InscribeTransfer => "inscribe-transfer",
+        OperationType::Transfer => "transfer",
+      }
+    ),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use {super::*, std::str::FromStr};
+
+  #[test]
+  fn block_checksum_chains_previous_checksum() {
+    let balances = vec![(
+      ScriptKey::from_script(
+        &bitcoin::Address::from_str("bc1qhvd6suvqzjcu9pxjhrwhtrlj85ny3n2mqql5w4")
+          .unwrap()
+          .assume_checked()
+          .script_pubkey(),
+        crate::Chain::Mainnet,
+      ),
+      Balance::new(&Tick::from_str("ordi").unwrap()),
+    )];
+
+    let first = block_checksum(None, 1, &[], &balances);
+    assert_eq!(first, block_checksum(None, 1, &[], &balances));
+    assert_ne!(first, block_checksum(None, 1, &[], &[]));
+    assert_ne!(first, block_checksum(None, 2, &[], &balances));
+
+    let second = block_checksum(Some(first), 2, &[], &[]);
+    assert_ne!(second, block_checksum(None, 2, &[], &[]));
+  }
+
+  #[test]
+  fn block_checksum_hashes_canonical_lines() {
+    let script_key = ScriptKey::from_script(
+      &bitcoin::Address::from_str("bc1qhvd6suvqzjcu9pxjhrwhtrlj85ny3n2mqql5w4")
+        .unwrap()
+        .assume_checked()
+        .script_pubkey(),
+      crate::Chain::Mainnet,
+    );
+    let txid = crate::test::txid(1);
+    let satpoint = SatPoint::from_str(&format!("{txid}:0:0")).unwrap();
+    let mut receipt = Receipt {
+      inscription_id: crate::test::inscription_id(1),
+      inscription_number: 1,
+      old_satpoint: satpoint,
+      new_satpoint: satpoint,
+      op: OperationType::Mint,
+      from: script_key.clone(),
+      to: script_key.clone(),
+      result: Ok(Event::Mint(MintEvent {
+        tick: Tick::from_str("ORDI").unwrap(),
+        amount: 10,
+        msg: None,
+      })),
+    };
+    let balance = Balance {
+      tick: Tick::from_str("ORDI").unwrap(),
+      overall_balance: 10,
+      transferable_balance: 0,
+    };
+
+    let mut engine = sha256::Hash::engine();
+    engine.input(&[0; 32]);
+    engine.input(&7u32.to_le_bytes());
+    engine.input(
+      format!(
+        "receipt;{txid};{};1;{satpoint};{satpoint};{script_key};{script_key};mint,ordi,10\n",
+        receipt.inscription_id
+      )
+      .as_bytes(),
+    );
+    engine.input(format!("balance;{script_key};ordi;10;0\n").as_bytes());
+
+    let checksum = block_checksum(
+      None,
+      7,
+      &[(txid, vec![receipt.clone()])],
+      &[(script_key.clone(), balance.clone())],
+    );
+    assert_eq!(checksum, sha256::Hash::from_engine(engine));
+
+    receipt.result = Ok(Event::Mint(MintEvent {
+      tick: Tick::from_str("ordi").unwrap(),
+      amount: 10,
+      msg: Some("message".into()),
+    }));
+    assert_eq!(
+      block_checksum(None, 7, &[(txid, vec![receipt])], &[(script_key, balance)]),
+      checksum
+    );
+  }
+}
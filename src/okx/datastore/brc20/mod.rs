@@ -1,13 +1,22 @@
 pub(super) mod balance;
+pub(super) mod checksum;
 pub(super) mod errors;
 pub(super) mod events;
 pub mod redb;
+pub(super) mod snapshot;
 pub(super) mod tick;
 pub(super) mod token_info;
 pub(super) mod transferable_log;
 
 pub use self::{
-  balance::Balance, errors::BRC20Error, events::Receipt, events::*, tick::*, token_info::TokenInfo,
+  balance::Balance,
+  checksum::block_checksum,
+  errors::BRC20Error,
+  events::Receipt,
+  events::*,
+  snapshot::{Snapshot, SnapshotBalance, SnapshotTransferableAsset},
+  tick::*,
+  token_info::TokenInfo,
   transferable_log::TransferableLog,
 };
 use super::ScriptKey;
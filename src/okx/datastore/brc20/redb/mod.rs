@@ -1,6 +1,7 @@
 pub mod table;
 
 use super::{LowerTick, ScriptKey, Tick};
+use bitcoin::Txid;
 
 fn min_script_tick_id_key(script: &ScriptKey, tick: &Tick) -> String {
   script_tick_key(script, tick)
@@ -22,3 +23,61 @@ fn min_script_tick_key(script: &ScriptKey) -> String {
 fn max_script_tick_key(script: &ScriptKey) -> String {
   format!("{}_{}", script, LowerTick::max_hex())
 }
+
+fn script_inscription_number_key(script: &ScriptKey, inscription_number: i32) -> String {
+  // offset the number so cursed inscriptions sort before blessed ones.
+  format!(
+    "{}_{:08x}",
+    script,
+    i64::from(inscription_number) - i64::from(i32::MIN)
+  )
+}
+
+fn min_script_key(script: &ScriptKey) -> String {
+  format!("{}_", script)
+}
+
+fn max_script_key(script: &ScriptKey) -> String {
+  // because '`' is the next character after '_' in bytes order, it's greater than any key of the script.
+  format!("{}`", script)
+}
+
+fn tick_holder_key(tick: &Tick, overall_balance: u128, script: &ScriptKey) -> String {
+  // the balance is inverted so the holders of the tick are in descending balance order.
+  format!(
+    "{}_{:032x}_{}",
+    tick.to_lowercase().hex(),
+    u128::MAX - overall_balance,
+    script
+  )
+}
+
+fn min_tick_holder_key(tick: &Tick) -> String {
+  format!("{}_", tick.to_lowercase().hex())
+}
+
+fn max_tick_holder_key(tick: &Tick) -> String {
+  // because '`' is the next character after '_' in bytes order, it's greater than any holder key of the tick.
+  format!("{}`", tick.to_lowercase().hex())
+}
+
+fn script_tick_height_key(script: &ScriptKey, tick: &Tick, height: u32) -> String {
+  format!("{}_{:08x}", script_tick_key(script, tick), height)
+}
+
+fn min_script_tick_height_key(script: &ScriptKey, tick: &Tick) -> String {
+  format!("{}_", script_tick_key(script, tick))
+}
+
+fn script_height_txid_key(script: &ScriptKey, height: u32, txid: &Txid) -> String {
+  format!("{}_{:08x}_{}", script, height, txid)
+}
+
+fn min_script_height_txid_key(script: &ScriptKey) -> String {
+  format!("{}_", script)
+}
+
+fn max_script_height_txid_key(script: &ScriptKey) -> String {
+  // because '`' is the next character after '_' in bytes order, it's greater than any event key of the script.
+  format!("{}`", script)
+}
@@ -6,17 +6,25 @@ use crate::{
   okx::datastore::{
     brc20::{
       redb::{
-        max_script_tick_id_key, max_script_tick_key, min_script_tick_id_key, min_script_tick_key,
-        script_tick_key,
+        max_script_height_txid_key, max_script_key, max_script_tick_id_key, max_script_tick_key,
+        max_tick_holder_key, min_script_height_txid_key, min_script_key,
+        min_script_tick_height_key, min_script_tick_id_key, min_script_tick_key,
+        min_tick_holder_key, script_height_txid_key, script_inscription_number_key,
+        script_tick_height_key, script_tick_key, tick_holder_key,
       },
       Balance, Receipt, Tick, TokenInfo, TransferableLog,
     },
-    ScriptKey,
+    undo::Journal,
+    KeyPage, ScriptKey,
   },
   Result, SatPoint,
 };
-use bitcoin::{OutPoint, Txid};
+use bitcoin::{
+  hashes::{sha256, Hash},
+  OutPoint, Txid,
+};
 use redb::{MultimapTable, ReadableMultimapTable, ReadableTable, Table};
+use std::collections::HashSet;
 
 // BRC20_BALANCES
 pub fn get_balances<T>(table: &T, script_key: &ScriptKey) -> Result<Vec<Balance>>
@@ -26,13 +34,28 @@ where
   Ok(
     table
       .range(min_script_tick_key(script_key).as_str()..=max_script_tick_key(script_key).as_str())?
-      .flat_map(|result| {
-        result.map(|(_, data)| rmp_serde::from_slice::<Balance>(data.value()).unwrap())
-      })
+      .flat_map(|result| result.map(|(_, data)| decode_balance(data.value()).1))
       .collect(),
   )
 }
 
+// BRC20_BALANCES
+pub fn get_balances_page<T>(
+  table: &T,
+  script_key: &ScriptKey,
+  page: &KeyPage,
+) -> Result<Vec<(String, Balance)>>
+where
+  T: ReadableTable<&'static str, &'static [u8]>,
+{
+  page.read(
+    table,
+    &min_script_key(script_key),
+    Some(&max_script_key(script_key)),
+    |_, data| Ok(Some(decode_balance(data).1)),
+  )
+}
+
 // BRC20_BALANCES
 pub fn get_balance<T>(table: &T, script_key: &ScriptKey, tick: &Tick) -> Result<Option<Balance>>
 where
@@ -41,7 +64,116 @@ where
   Ok(
     table
       .get(script_tick_key(script_key, tick).as_str())?
-      .map(|v| rmp_serde::from_slice::<Balance>(v.value()).unwrap()),
+      .map(|v| decode_balance(v.value()).1),
+  )
+}
+
+// BRC20_BALANCES
+pub fn get_all_balances<T>(table: &T) -> Result<Vec<(ScriptKey, Balance)>>
+where
+  T: ReadableTable<&'static str, &'static [u8]>,
+{
+  Ok(
+    table
+      .range::<&str>(..)?
+      .flat_map(|result| result.map(|(_, data)| decode_balance(data.value())))
+      .collect(),
+  )
+}
+
+// BRC20_BALANCES
+/// Balances are saved with their script key, since the key of the entry only has the script hash of
+/// scripts without an address and not whether they are OP_RETURN.
+fn decode_balance(data: &[u8]) -> (ScriptKey, Balance) {
+  rmp_serde::from_slice::<(ScriptKey, Balance)>(data).unwrap()
+}
+
+// BRC20_SCRIPT_KEY_TO_EVENTS
+// BRC20_EVENTS
+/// A page of the transactions of the address in height order, with their receipts that involve the
+/// address and match `filter`. Transactions without such receipts are skipped.
+pub fn get_transactions_by_address_page<T, E>(
+  address_table: &T,
+  events_table: &E,
+  script_key: &ScriptKey,
+  page: &KeyPage,
+  filter: impl Fn(&Receipt) -> bool,
+) -> Result<Vec<(String, (Txid, Vec<Receipt>))>>
+where
+  T: ReadableTable<&'static str, &'static TxidValue>,
+  E: ReadableTable<&'static TxidValue, &'static [u8]>,
+{
+  page.read(
+    address_table,
+    &min_script_height_txid_key(script_key),
+    Some(&max_script_height_txid_key(script_key)),
+    |_, txid| {
+      let txid = Txid::load(*txid);
+      let receipts = get_transaction_receipts(events_table, &txid)?
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|receipt| receipt.from == *script_key || receipt.to == *script_key)
+        .filter(|receipt| filter(receipt))
+        .collect::<Vec<_>>();
+      Ok((!receipts.is_empty()).then_some((txid, receipts)))
+    },
+  )
+}
+
+// BRC20_BALANCE_HISTORY
+pub fn get_balance_at_height<T>(
+  table: &T,
+  script_key: &ScriptKey,
+  tick: &Tick,
+  height: u32,
+) -> Result<Option<Balance>>
+where
+  T: ReadableTable<&'static str, &'static [u8]>,
+{
+  Ok(
+    table
+      .range(
+        min_script_tick_height_key(script_key, tick).as_str()
+          ..=script_tick_height_key(script_key, tick, height).as_str(),
+      )?
+      .next_back()
+      .transpose()?
+      .map(|(_, v)| rmp_serde::from_slice::<Balance>(v.value()).unwrap()),
+  )
+}
+
+// BRC20_TICK_HOLDERS
+pub fn get_tick_holders_page<T>(
+  table: &T,
+  tick: &Tick,
+  page: &KeyPage,
+) -> Result<Vec<(String, (ScriptKey, u128))>>
+where
+  T: ReadableTable<&'static str, &'static [u8]>,
+{
+  page.read(
+    table,
+    &min_tick_holder_key(tick),
+    Some(&max_tick_holder_key(tick)),
+    |_, data| {
+      Ok(Some(
+        rmp_serde::from_slice::<(ScriptKey, u128)>(data).unwrap(),
+      ))
+    },
+  )
+}
+
+// BRC20_TICK_HOLDERS
+pub fn get_tick_holders_count<T>(table: &T, tick: &Tick) -> Result<u64>
+where
+  T: ReadableTable<&'static str, &'static [u8]>,
+{
+  Ok(
+    table
+      .range(min_tick_holder_key(tick).as_str()..max_tick_holder_key(tick).as_str())?
+      .count()
+      .try_into()
+      .unwrap(),
   )
 }
 
@@ -72,6 +204,35 @@ where
   )
 }
 
+// BRC20_TOKEN
+pub fn get_tokens_info_page<T>(
+  table: &T,
+  page: &KeyPage,
+  filter: impl Fn(&TokenInfo) -> bool,
+) -> Result<Vec<(String, TokenInfo)>>
+where
+  T: ReadableTable<&'static str, &'static [u8]>,
+{
+  page.read(table, "", None, |_, data| {
+    Ok(
+      Some(rmp_serde::from_slice::<TokenInfo>(data).unwrap())
+        .filter(|token_info| filter(token_info)),
+    )
+  })
+}
+
+// BRC20_BLOCK_CHECKSUMS
+pub fn get_block_checksum<T>(table: &T, height: u32) -> Result<Option<sha256::Hash>>
+where
+  T: ReadableTable<u32, &'static [u8; 32]>,
+{
+  Ok(
+    table
+      .get(height)?
+      .map(|checksum| sha256::Hash::from_byte_array(*checksum.value())),
+  )
+}
+
 // BRC20_EVENTS
 pub fn get_transaction_receipts<T>(table: &T, txid: &Txid) -> Result<Option<Vec<Receipt>>>
 where
@@ -85,32 +246,36 @@ where
 }
 
 // BRC20_SATPOINT_TO_TRANSFERABLE_ASSETS
-// BRC20_ADDRESS_TICKER_TO_TRANSFERABLE_ASSETS
-pub fn get_transferable_assets_by_account<T, S>(
-  address_table: &T,
+// BRC20_ADDRESS_INSCRIPTION_NUMBER_TO_TRANSFERABLE_ASSETS
+pub fn get_transferable_assets_by_account_page<T, S>(
+  number_table: &T,
   satpoint_table: &S,
   script: &ScriptKey,
-) -> Result<Vec<(SatPoint, TransferableLog)>>
+  page: &KeyPage,
+) -> Result<Vec<(String, (SatPoint, TransferableLog))>>
 where
-  T: ReadableMultimapTable<&'static str, &'static SatPointValue>,
+  T: ReadableTable<&'static str, &'static SatPointValue>,
   S: ReadableTable<&'static SatPointValue, &'static [u8]>,
 {
-  let mut transferable_assets = Vec::new();
-
-  for range in address_table
-    .range(min_script_tick_key(script).as_str()..max_script_tick_key(script).as_str())?
-  {
-    let (_, satpoints) = range?;
-    for satpoint_guard in satpoints {
-      let satpoint = SatPoint::load(*satpoint_guard?.value());
+  page
+    .read(
+      number_table,
+      &min_script_key(script),
+      Some(&max_script_key(script)),
+      |_, satpoint| Ok(Some(SatPoint::load(*satpoint))),
+    )?
+    .into_iter()
+    .map(|(key, satpoint)| {
       let entry = satpoint_table.get(&satpoint.store())?.unwrap();
-      transferable_assets.push((
-        satpoint,
-        rmp_serde::from_slice::<TransferableLog>(entry.value()).unwrap(),
-      ));
-    }
-  }
-  Ok(transferable_assets)
+      Ok((
+        key,
+        (
+          satpoint,
+          rmp_serde::from_slice::<TransferableLog>(entry.value()).unwrap(),
+        ),
+      ))
+    })
+    .collect()
 }
 
 // BRC20_SATPOINT_TO_TRANSFERABLE_ASSETS
@@ -158,6 +323,26 @@ where
   )
 }
 
+// BRC20_SATPOINT_TO_TRANSFERABLE_ASSETS
+pub fn get_all_transferable_assets<T>(table: &T) -> Result<Vec<(SatPoint, TransferableLog)>>
+where
+  T: ReadableTable<&'static SatPointValue, &'static [u8]>,
+{
+  Ok(
+    table
+      .range::<&SatPointValue>(..)?
+      .flat_map(|result| {
+        result.map(|(satpoint, data)| {
+          (
+            SatPoint::load(*satpoint.value()),
+            rmp_serde::from_slice::<TransferableLog>(data.value()).unwrap(),
+          )
+        })
+      })
+      .collect(),
+  )
+}
+
 // BRC20_SATPOINT_TO_TRANSFERABLE_ASSETS
 pub fn get_transferable_assets_by_outpoint<T>(
   table: &T,
@@ -191,25 +376,68 @@ where
 }
 
 // BRC20_BALANCES
+// BRC20_TICK_HOLDERS
 pub fn update_token_balance(
+  journal: &Journal,
   table: &mut Table<'_, '_, &'static str, &'static [u8]>,
+  holders_table: &mut Table<'_, '_, &'static str, &'static [u8]>,
   script_key: &ScriptKey,
   new_balance: Balance,
 ) -> Result<()> {
-  table.insert(
+  // an account holds the ticker as long as its overall balance is positive.
+  if let Some(old_balance) = get_balance(table, script_key, &new_balance.tick)? {
+    if old_balance.overall_balance > 0 {
+      journal.remove(
+        holders_table,
+        tick_holder_key(&old_balance.tick, old_balance.overall_balance, script_key).as_str(),
+      )?;
+    }
+  }
+  if new_balance.overall_balance > 0 {
+    journal.insert(
+      holders_table,
+      tick_holder_key(&new_balance.tick, new_balance.overall_balance, script_key).as_str(),
+      rmp_serde::to_vec(&(script_key, new_balance.overall_balance))
+        .unwrap()
+        .as_slice(),
+    )?;
+  }
+
+  journal.insert(
+    table,
     script_tick_key(script_key, &new_balance.tick).as_str(),
-    rmp_serde::to_vec(&new_balance).unwrap().as_slice(),
+    rmp_serde::to_vec(&(script_key, &new_balance))
+      .unwrap()
+      .as_slice(),
+  )?;
+  Ok(())
+}
+
+// BRC20_BALANCE_HISTORY
+pub fn save_balance_history(
+  journal: &Journal,
+  table: &mut Table<'_, '_, &'static str, &'static [u8]>,
+  script_key: &ScriptKey,
+  height: u32,
+  balance: &Balance,
+) -> Result<()> {
+  journal.insert(
+    table,
+    script_tick_height_key(script_key, &balance.tick, height).as_str(),
+    rmp_serde::to_vec(balance).unwrap().as_slice(),
   )?;
   Ok(())
 }
 
 // BRC20_TOKEN
 pub fn insert_token_info(
+  journal: &Journal,
   table: &mut Table<'_, '_, &'static str, &'static [u8]>,
   tick: &Tick,
   new_info: &TokenInfo,
 ) -> Result<()> {
-  table.insert(
+  journal.insert(
+    table,
     tick.to_lowercase().hex().as_str(),
     rmp_serde::to_vec(new_info).unwrap().as_slice(),
   )?;
@@ -218,6 +446,7 @@ pub fn insert_token_info(
 
 // BRC20_TOKEN
 pub fn update_mint_token_info(
+  journal: &Journal,
   table: &mut Table<'_, '_, &'static str, &'static [u8]>,
   tick: &Tick,
   minted_amt: u128,
@@ -229,7 +458,8 @@ pub fn update_mint_token_info(
   info.minted = minted_amt;
   info.latest_mint_number = minted_block_number;
 
-  table.insert(
+  journal.insert(
+    table,
     tick.to_lowercase().hex().as_str(),
     rmp_serde::to_vec(&info).unwrap().as_slice(),
   )?;
@@ -237,6 +467,7 @@ pub fn update_mint_token_info(
 }
 
 pub fn update_burned_token_info(
+  journal: &Journal,
   table: &mut Table<'_, '_, &'static str, &'static [u8]>,
   tick: &Tick,
   burned_amt: u128,
@@ -244,58 +475,426 @@ pub fn update_burned_token_info(
   let mut info =
     get_token_info(table, tick)?.unwrap_or_else(|| panic!("token {} not exist", tick.as_str()));
   info.burned_supply = burned_amt;
-  table.insert(
+  journal.insert(
+    table,
     tick.to_lowercase().hex().as_str(),
     rmp_serde::to_vec(&info).unwrap().as_slice(),
   )?;
   Ok(())
 }
 
+// BRC20_BLOCK_CHECKSUMS
+pub fn save_block_checksum(
+  journal: &Journal,
+  table: &mut Table<'_, '_, u32, &'static [u8; 32]>,
+  height: u32,
+  checksum: &sha256::Hash,
+) -> Result<()> {
+  journal.insert(table, height, checksum.as_byte_array())?;
+  Ok(())
+}
+
 // BRC20_EVENTS
+// BRC20_SCRIPT_KEY_TO_EVENTS
 pub fn save_transaction_receipts(
+  journal: &Journal,
   table: &mut Table<'_, '_, &'static TxidValue, &'static [u8]>,
+  address_table: &mut Table<'_, '_, &'static str, &'static TxidValue>,
+  height: u32,
   txid: &Txid,
   receipts: &[Receipt],
 ) -> Result<()> {
-  table.insert(
+  journal.insert(
+    table,
     &txid.store(),
     rmp_serde::to_vec(receipts).unwrap().as_slice(),
   )?;
+
+  let script_keys = receipts
+    .iter()
+    .flat_map(|receipt| [&receipt.from, &receipt.to])
+    .collect::<HashSet<_>>();
+  for script_key in script_keys {
+    journal.insert(
+      address_table,
+      script_height_txid_key(script_key, height, txid).as_str(),
+      &txid.store(),
+    )?;
+  }
   Ok(())
 }
 
 // BRC20_SATPOINT_TO_TRANSFERABLE_ASSETS
 // BRC20_ADDRESS_TICKER_TO_TRANSFERABLE_ASSETS
+// BRC20_ADDRESS_INSCRIPTION_NUMBER_TO_TRANSFERABLE_ASSETS
 pub fn insert_transferable_asset(
+  journal: &Journal,
   satpoint_table: &mut Table<'_, '_, &'static SatPointValue, &'static [u8]>,
   address_table: &mut MultimapTable<'_, '_, &'static str, &'static SatPointValue>,
+  number_table: &mut Table<'_, '_, &'static str, &'static SatPointValue>,
   satpoint: SatPoint,
   transferable_asset: &TransferableLog,
 ) -> Result<()> {
-  satpoint_table.insert(
+  journal.insert(
+    satpoint_table,
     &satpoint.store(),
     rmp_serde::to_vec(&transferable_asset).unwrap().as_slice(),
   )?;
-  address_table.insert(
+  journal.multimap_insert(
+    address_table,
     script_tick_key(&transferable_asset.owner, &transferable_asset.tick).as_str(),
     &satpoint.store(),
   )?;
+  journal.insert(
+    number_table,
+    script_inscription_number_key(
+      &transferable_asset.owner,
+      transferable_asset.inscription_number,
+    )
+    .as_str(),
+    &satpoint.store(),
+  )?;
   Ok(())
 }
 
 // BRC20_SATPOINT_TO_TRANSFERABLE_ASSETS
 // BRC20_ADDRESS_TICKER_TO_TRANSFERABLE_ASSETS
+// BRC20_ADDRESS_INSCRIPTION_NUMBER_TO_TRANSFERABLE_ASSETS
 pub fn remove_transferable_asset(
+  journal: &Journal,
   satpoint_table: &mut Table<'_, '_, &'static SatPointValue, &'static [u8]>,
   address_table: &mut MultimapTable<'_, '_, &'static str, &'static SatPointValue>,
+  number_table: &mut Table<'_, '_, &'static str, &'static SatPointValue>,
   satpoint: SatPoint,
 ) -> Result<()> {
-  if let Some(guard) = satpoint_table.remove(&satpoint.store())? {
+  if let Some(guard) = journal.remove(satpoint_table, &satpoint.store())? {
     let transferable_asset = rmp_serde::from_slice::<TransferableLog>(guard.value()).unwrap();
-    address_table.remove(
+    journal.multimap_remove(
+      address_table,
       script_tick_key(&transferable_asset.owner, &transferable_asset.tick).as_str(),
       &satpoint.store(),
     )?;
+    journal.remove(
+      number_table,
+      script_inscription_number_key(
+        &transferable_asset.owner,
+        transferable_asset.inscription_number,
+      )
+      .as_str(),
+    )?;
   }
   Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{
+    index::{
+      BRC20_BALANCES, BRC20_BALANCE_HISTORY, BRC20_EVENTS, BRC20_SCRIPT_KEY_TO_EVENTS,
+      BRC20_TICK_HOLDERS,
+    },
+    okx::datastore::brc20::{Event, MintEvent, OperationType},
+    test::{inscription_id, txid},
+  };
+  use bitcoin::Address;
+  use redb::Database;
+  use std::str::FromStr;
+  use tempfile::NamedTempFile;
+
+  fn script_key(address: &str) -> ScriptKey {
+    ScriptKey::from_address(Address::from_str(address).unwrap().assume_checked())
+  }
+
+  fn balance(tick: &Tick, overall_balance: u128) -> Balance {
+    Balance {
+      tick: tick.clone(),
+      overall_balance,
+      transferable_balance: 0,
+    }
+  }
+
+  #[test]
+  fn test_all_balances_keep_op_return_script_keys() {
+    let dbfile = NamedTempFile::new().unwrap();
+    let db = Database::create(dbfile.path()).unwrap();
+    let wtx = db.begin_write().unwrap();
+    let mut balances = wtx.open_table(BRC20_BALANCES).unwrap();
+    let mut holders = wtx.open_table(BRC20_TICK_HOLDERS).unwrap();
+
+    let ordi = Tick::from_str("ordi").unwrap();
+    let alice = script_key("bc1qhvd6suvqzjcu9pxjhrwhtrlj85ny3n2mqql5w4");
+    let op_return = ScriptKey::from_script(
+      &bitcoin::ScriptBuf::new_op_return(&[]),
+      crate::Chain::Mainnet,
+    );
+    assert!(matches!(
+      op_return,
+      ScriptKey::ScriptHash {
+        is_op_return: true,
+        ..
+      }
+    ));
+
+    for script_key in [&alice, &op_return] {
+      update_token_balance(
+        &Journal::default(),
+        &mut balances,
+        &mut holders,
+        script_key,
+        balance(&ordi, 10),
+      )
+      .unwrap();
+    }
+
+    let mut all_balances = get_all_balances(&balances).unwrap();
+    all_balances.sort_by_key(|(script_key, _)| script_key.to_string());
+    let mut expected = vec![
+      (alice.clone(), balance(&ordi, 10)),
+      (op_return.clone(), balance(&ordi, 10)),
+    ];
+    expected.sort_by_key(|(script_key, _)| script_key.to_string());
+    assert_eq!(all_balances, expected);
+    assert_eq!(
+      get_balance(&balances, &op_return, &ordi).unwrap(),
+      Some(balance(&ordi, 10))
+    );
+  }
+
+  #[test]
+  fn test_tick_holders() {
+    let dbfile = NamedTempFile::new().unwrap();
+    let db = Database::create(dbfile.path()).unwrap();
+    let wtx = db.begin_write().unwrap();
+    let mut balances = wtx.open_table(BRC20_BALANCES).unwrap();
+    let mut holders = wtx.open_table(BRC20_TICK_HOLDERS).unwrap();
+
+    let ordi = Tick::from_str("ordi").unwrap();
+    let sats = Tick::from_str("SATS").unwrap();
+    let alice = script_key("bc1qhvd6suvqzjcu9pxjhrwhtrlj85ny3n2mqql5w4");
+    let bob = script_key("132F25rTsvBdp9JzLLBHP5mvGY66i1xdiM");
+
+    update_token_balance(
+      &Journal::default(),
+      &mut balances,
+      &mut holders,
+      &alice,
+      balance(&ordi, 100),
+    )
+    .unwrap();
+    update_token_balance(
+      &Journal::default(),
+      &mut balances,
+      &mut holders,
+      &bob,
+      balance(&ordi, 20),
+    )
+    .unwrap();
+    update_token_balance(
+      &Journal::default(),
+      &mut balances,
+      &mut holders,
+      &bob,
+      balance(&sats, 1),
+    )
+    .unwrap();
+
+    fn tick_holders(
+      holders: &Table<'_, '_, &'static str, &'static [u8]>,
+      tick: &Tick,
+    ) -> Vec<(ScriptKey, u128)> {
+      get_tick_holders_page(holders, tick, &KeyPage::default())
+        .unwrap()
+        .into_iter()
+        .map(|(_, holder)| holder)
+        .collect()
+    }
+
+    assert_eq!(
+      tick_holders(&holders, &Tick::from_str("ORDI").unwrap()),
+      vec![(alice.clone(), 100), (bob.clone(), 20)]
+    );
+    assert_eq!(get_tick_holders_count(&holders, &ordi).unwrap(), 2);
+    assert_eq!(tick_holders(&holders, &sats), vec![(bob.clone(), 1)]);
+
+    update_token_balance(
+      &Journal::default(),
+      &mut balances,
+      &mut holders,
+      &bob,
+      balance(&ordi, 200),
+    )
+    .unwrap();
+    assert_eq!(
+      tick_holders(&holders, &ordi),
+      vec![(bob.clone(), 200), (alice.clone(), 100)]
+    );
+
+    update_token_balance(
+      &Journal::default(),
+      &mut balances,
+      &mut holders,
+      &bob,
+      balance(&ordi, 0),
+    )
+    .unwrap();
+    assert_eq!(tick_holders(&holders, &ordi), vec![(alice.clone(), 100)]);
+    assert_eq!(get_tick_holders_count(&holders, &ordi).unwrap(), 1);
+    assert_eq!(
+      get_balance(&balances, &bob, &ordi).unwrap(),
+      Some(balance(&ordi, 0))
+    );
+    assert_eq!(
+      get_all_balances(&balances).unwrap(),
+      vec![
+        (bob.clone(), balance(&ordi, 0)),
+        (bob.clone(), balance(&sats, 1)),
+        (alice.clone(), balance(&ordi, 100)),
+      ]
+    );
+  }
+
+  #[test]
+  fn test_balance_history() {
+    let dbfile = NamedTempFile::new().unwrap();
+    let db = Database::create(dbfile.path()).unwrap();
+    let wtx = db.begin_write().unwrap();
+    let mut table = wtx.open_table(BRC20_BALANCE_HISTORY).unwrap();
+
+    let ordi = Tick::from_str("ordi").unwrap();
+    let ordi5 = Tick::from_str("ordi5").unwrap();
+    let alice = script_key("bc1qhvd6suvqzjcu9pxjhrwhtrlj85ny3n2mqql5w4");
+
+    save_balance_history(
+      &Journal::default(),
+      &mut table,
+      &alice,
+      100,
+      &balance(&ordi, 10),
+    )
+    .unwrap();
+    save_balance_history(
+      &Journal::default(),
+      &mut table,
+      &alice,
+      256,
+      &balance(&ordi, 30),
+    )
+    .unwrap();
+    save_balance_history(
+      &Journal::default(),
+      &mut table,
+      &alice,
+      120,
+      &balance(&ordi5, 7),
+    )
+    .unwrap();
+
+    assert_eq!(
+      get_balance_at_height(&table, &alice, &ordi, 99).unwrap(),
+      None
+    );
+    assert_eq!(
+      get_balance_at_height(&table, &alice, &ordi, 100).unwrap(),
+      Some(balance(&ordi, 10))
+    );
+    assert_eq!(
+      get_balance_at_height(&table, &alice, &ordi, 255).unwrap(),
+      Some(balance(&ordi, 10))
+    );
+    assert_eq!(
+      get_balance_at_height(&table, &alice, &Tick::from_str("ORDI").unwrap(), 1000).unwrap(),
+      Some(balance(&ordi, 30))
+    );
+    assert_eq!(
+      get_balance_at_height(&table, &alice, &ordi5, 1000).unwrap(),
+      Some(balance(&ordi5, 7))
+    );
+  }
+
+  #[test]
+  fn test_transactions_by_address() {
+    let dbfile = NamedTempFile::new().unwrap();
+    let db = Database::create(dbfile.path()).unwrap();
+    let wtx = db.begin_write().unwrap();
+    let mut events = wtx.open_table(BRC20_EVENTS).unwrap();
+    let mut address_events = wtx.open_table(BRC20_SCRIPT_KEY_TO_EVENTS).unwrap();
+
+    let alice = script_key("bc1qhvd6suvqzjcu9pxjhrwhtrlj85ny3n2mqql5w4");
+    let bob = script_key("132F25rTsvBdp9JzLLBHP5mvGY66i1xdiM");
+
+    let receipt = |from: &ScriptKey, to: &ScriptKey| Receipt {
+      inscription_id: inscription_id(1),
+      inscription_number: 1,
+      old_satpoint: SatPoint::from_str(&format!("{}:0:0", txid(1))).unwrap(),
+      new_satpoint: SatPoint::from_str(&format!("{}:0:0", txid(2))).unwrap(),
+      op: OperationType::Mint,
+      from: from.clone(),
+      to: to.clone(),
+      result: Ok(Event::Mint(MintEvent {
+        tick: Tick::from_str("ordi").unwrap(),
+        amount: 1,
+        msg: None,
+      })),
+    };
+
+    save_transaction_receipts(
+      &Journal::default(),
+      &mut events,
+      &mut address_events,
+      200,
+      &txid(2),
+      &[receipt(&alice, &bob)],
+    )
+    .unwrap();
+    save_transaction_receipts(
+      &Journal::default(),
+      &mut events,
+      &mut address_events,
+      100,
+      &txid(1),
+      &[receipt(&alice, &alice)],
+    )
+    .unwrap();
+
+    let transactions = |script_key: &ScriptKey, page: KeyPage| {
+      page.split(
+        get_transactions_by_address_page(&address_events, &events, script_key, &page, |_| true)
+          .unwrap(),
+      )
+    };
+
+    assert_eq!(
+      transactions(&alice, KeyPage::default()),
+      (
+        vec![
+          (txid(1), vec![receipt(&alice, &alice)]),
+          (txid(2), vec![receipt(&alice, &bob)])
+        ],
+        None
+      )
+    );
+    assert_eq!(
+      transactions(
+        &alice,
+        KeyPage {
+          limit: 1,
+          reverse: true,
+          ..Default::default()
+        }
+      ),
+      (
+        vec![(txid(2), vec![receipt(&alice, &bob)])],
+        Some(script_height_txid_key(&alice, 200, &txid(2)))
+      )
+    );
+    assert_eq!(
+      transactions(&bob, KeyPage::default()),
+      (vec![(txid(2), vec![receipt(&alice, &bob)])], None)
+    );
+    assert_eq!(
+      get_transaction_receipts(&events, &txid(1)).unwrap(),
+      Some(vec![receipt(&alice, &alice)])
+    );
+  }
+}
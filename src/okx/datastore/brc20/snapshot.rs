@@ -0,0 +1,148 @@
+use super::*;
+use bitcoin::{
+  hashes::{sha256, Hash},
+  BlockHash,
+};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct SnapshotBalance {
+  pub script_key: ScriptKey,
+  pub balance: Balance,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct SnapshotTransferableAsset {
+  pub satpoint: SatPoint,
+  pub asset: TransferableLog,
+}
+
+/// The BRC20 state at the end of a block.
+///
+/// Entries are in the key order of their tables, so the same state always gives the same snapshot and state hash.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct Snapshot {
+  pub height: u32,
+  pub block_hash: BlockHash,
+  /// The checksum of the block, so replicas seeded from the snapshot keep chaining the same checksums.
+  #[serde(default)]
+  pub block_checksum: Option<sha256::Hash>,
+  pub tokens: Vec<TokenInfo>,
+  pub balances: Vec<SnapshotBalance>,
+  pub transferable_assets: Vec<SnapshotTransferableAsset>,
+  pub state_hash: sha256::Hash,
+}
+
+impl Snapshot {
+  pub fn new(
+    height: u32,
+    block_hash: BlockHash,
+    block_checksum: Option<sha256::Hash>,
+    tokens: Vec<TokenInfo>,
+    balances: Vec<SnapshotBalance>,
+    transferable_assets: Vec<SnapshotTransferableAsset>,
+  ) -> Self {
+    let state_hash = Self::state_hash(&tokens, &balances, &transferable_assets);
+    Self {
+      height,
+      block_hash,
+      block_checksum,
+      tokens,
+      balances,
+      transferable_assets,
+      state_hash,
+    }
+  }
+
+  /// Hashes the message pack encoding of the state.
+  pub fn state_hash(
+    tokens: &[TokenInfo],
+    balances: &[SnapshotBalance],
+    transferable_assets: &[SnapshotTransferableAsset],
+  ) -> sha256::Hash {
+    sha256::Hash::hash(&rmp_serde::to_vec(&(tokens, balances, transferable_assets)).unwrap())
+  }
+
+  pub fn is_valid(&self) -> bool {
+    Self::state_hash(&self.tokens, &self.balances, &self.transferable_assets) == self.state_hash
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use {super::*, crate::test::inscription_id, std::str::FromStr};
+
+  fn snapshot() -> Snapshot {
+    let tick = Tick::from_str("ordi").unwrap();
+    let script_key = ScriptKey::from_script(
+      &bitcoin::Address::from_str("bc1qhvd6suvqzjcu9pxjhrwhtrlj85ny3n2mqql5w4")
+        .unwrap()
+        .assume_checked()
+        .script_pubkey(),
+      crate::Chain::Mainnet,
+    );
+
+    Snapshot::new(
+      100,
+      BlockHash::all_zeros(),
+      None,
+      Vec::new(),
+      vec![
+        SnapshotBalance {
+          script_key: script_key.clone(),
+          balance: Balance {
+            tick: tick.clone(),
+            overall_balance: 30,
+            transferable_balance: 10,
+          },
+        },
+        SnapshotBalance {
+          script_key: ScriptKey::from_script(
+            &bitcoin::ScriptBuf::new_op_return(&[]),
+            crate::Chain::Mainnet,
+          ),
+          balance: Balance {
+            tick: tick.clone(),
+            overall_balance: 5,
+            transferable_balance: 0,
+          },
+        },
+      ],
+      vec![SnapshotTransferableAsset {
+        satpoint: SatPoint::from_str(
+          "1111111111111111111111111111111111111111111111111111111111111111:1:0",
+        )
+        .unwrap(),
+        asset: TransferableLog {
+          inscription_id: inscription_id(1),
+          inscription_number: 1,
+          amount: 10,
+          tick,
+          owner: script_key,
+        },
+      }],
+    )
+  }
+
+  #[test]
+  fn snapshot_state_hash_is_verified() {
+    let snapshot = snapshot();
+    assert!(snapshot.is_valid());
+
+    let decoded =
+      serde_json::from_str::<Snapshot>(&serde_json::to_string(&snapshot).unwrap()).unwrap();
+    assert_eq!(decoded, snapshot);
+    assert!(decoded.is_valid());
+    assert_eq!(
+      decoded.balances[1].script_key,
+      ScriptKey::ScriptHash {
+        script_hash: bitcoin::ScriptBuf::new_op_return(&[]).script_hash(),
+        is_op_return: true,
+      }
+    );
+
+    let mut tampered = snapshot;
+    tampered.balances[0].balance.overall_balance = 31;
+    assert!(!tampered.is_valid());
+  }
+}
@@ -1,5 +1,7 @@
 pub mod brc20;
 pub mod ord;
+pub mod page;
 mod script_key;
+pub mod undo;
 
-pub use self::script_key::ScriptKey;
+pub use self::{page::KeyPage, script_key::ScriptKey};
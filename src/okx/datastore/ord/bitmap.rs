@@ -1,4 +1,9 @@
-use {super::*, anyhow::anyhow, regex::Regex};
+use {
+  super::*,
+  anyhow::anyhow,
+  regex::Regex,
+  serde::{Deserialize, Serialize},
+};
 
 const BITMAP_KEY: &str = r"BITMAP";
 
@@ -25,11 +30,64 @@ impl District {
   pub fn to_collection_key(&self) -> String {
     format!("{}_{}", BITMAP_KEY, self.number)
   }
+
+  /// The prefix shared by the collection keys of all districts and parcels.
+  pub fn collection_key_prefix() -> String {
+    format!("{BITMAP_KEY}_")
+  }
+}
+
+/// A `<parcel>.<district>.bitmap` inscription, dividing a district into one parcel per transaction of its block.
+pub struct Parcel {
+  pub number: u32,
+  pub district: u32,
+}
+
+impl Parcel {
+  pub fn parse(bytes: &[u8]) -> Result<Self> {
+    let pattern = r"^(0|[1-9][0-9]*)\.(0|[1-9][0-9]*)\.bitmap$";
+    let content = std::str::from_utf8(bytes)?;
+    let re = Regex::new(pattern).unwrap();
+    if let Some(capture) = re.captures(content) {
+      return Ok(Self {
+        number: capture[1].parse()?,
+        district: capture[2].parse()?,
+      });
+    }
+    Err(anyhow!("No match found."))
+  }
+
+  pub fn to_collection_key(&self) -> String {
+    format!("{}_{}_{}", BITMAP_KEY, self.district, self.number)
+  }
+
+  /// The prefix shared by the collection keys of the parcels of a district.
+  pub fn collection_key_prefix(district: u32) -> String {
+    format!("{BITMAP_KEY}_{district}_")
+  }
+
+  /// Parses a key built by `to_collection_key`.
+  pub fn from_collection_key(key: &str) -> Option<Self> {
+    let (district, number) = key
+      .strip_prefix(&District::collection_key_prefix())?
+      .split_once('_')?;
+    Some(Self {
+      number: number.parse().ok()?,
+      district: district.parse().ok()?,
+    })
+  }
+}
+
+/// The block of a district, recorded when the block is indexed.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+pub struct DistrictBlock {
+  pub tx_count: u32,
+  pub size: u64,
 }
 
 #[cfg(test)]
 mod tests {
-  use super::District;
+  use super::{District, Parcel};
 
   #[test]
   fn validate_regex() {
@@ -49,4 +107,19 @@ mod tests {
     assert!(District::parse("01.bitmap".as_bytes()).is_err());
     assert!(District::parse((u64::MAX.to_string() + "1.bitmap").as_bytes()).is_err());
   }
+
+  #[test]
+  fn parse_parcel() {
+    let parcel = Parcel::parse("2.840000.bitmap".as_bytes()).unwrap();
+    assert_eq!(parcel.number, 2);
+    assert_eq!(parcel.district, 840000);
+    assert_eq!(parcel.to_collection_key(), "BITMAP_840000_2");
+
+    let parcel = Parcel::from_collection_key(&parcel.to_collection_key()).unwrap();
+    assert_eq!((parcel.number, parcel.district), (2, 840000));
+
+    assert!(Parcel::parse("840000.bitmap".as_bytes()).is_err());
+    assert!(Parcel::parse("01.840000.bitmap".as_bytes()).is_err());
+    assert!(Parcel::parse("1.2.3.bitmap".as_bytes()).is_err());
+  }
 }
@@ -1,4 +1,10 @@
-use {super::*, anyhow::anyhow, regex::Regex};
+use {
+  super::*,
+  anyhow::anyhow,
+  regex::Regex,
+  serde::{Deserialize, Serialize},
+  std::fmt::{self, Formatter},
+};
 
 const BTC_DOMAIN_KEY: &str = r"BTC_DOMAIN";
 
@@ -9,9 +15,100 @@ pub struct BtcDomain {
 }
 
 const DEFAULT_DOMAIN_LIST: [&str; 4] = ["btc", "unisat", "sats", "x"];
+
+/// A domain suffix and the height from which its registrations are indexed.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct DomainSuffix {
+  pub suffix: String,
+  #[serde(default)]
+  pub activation_height: u32,
+}
+
+/// The `domains` section of `ord.yaml`.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DomainConfig {
+  /// Suffixes indexed in addition to the default ones, or the activation heights of the default ones.
+  pub suffixes: Vec<DomainSuffix>,
+  /// The height from which `{"p":"sns","op":"reg","name":...}` registrations are indexed. They are ignored if absent.
+  pub sns_activation_height: Option<u32>,
+}
+
+impl DomainConfig {
+  pub fn check(&self) -> Result {
+    for DomainSuffix { suffix, .. } in &self.suffixes {
+      if !Self::is_suffix_valid(suffix) {
+        return Err(anyhow!(
+          "invalid domain suffix `{suffix}`, must only contain lowercase letters, digits and `-`"
+        ));
+      }
+    }
+    Ok(())
+  }
+
+  fn is_suffix_valid(suffix: &str) -> bool {
+    !suffix.is_empty()
+      && suffix
+        .chars()
+        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+  }
+
+  /// All suffixes with their activation heights, the default ones active from genesis unless configured.
+  fn all_suffixes(&self) -> Vec<DomainSuffix> {
+    let mut suffixes = DEFAULT_DOMAIN_LIST
+      .iter()
+      .filter(|default| !self.suffixes.iter().any(|s| s.suffix == **default))
+      .map(|default| DomainSuffix {
+        suffix: default.to_string(),
+        activation_height: 0,
+      })
+      .collect::<Vec<_>>();
+    suffixes.extend(self.suffixes.iter().cloned());
+    suffixes
+  }
+
+  /// The suffixes of the domains that may be registered at any height.
+  pub fn suffixes(&self) -> Vec<String> {
+    self
+      .all_suffixes()
+      .into_iter()
+      .map(|suffix| suffix.suffix)
+      .collect()
+  }
+
+  /// The suffixes of the domains that may be registered at the height.
+  pub fn active_suffixes(&self, height: u32) -> Vec<String> {
+    self
+      .all_suffixes()
+      .into_iter()
+      .filter(|suffix| height >= suffix.activation_height)
+      .map(|suffix| suffix.suffix)
+      .collect()
+  }
+
+  pub fn is_sns_active(&self, height: u32) -> bool {
+    self
+      .sns_activation_height
+      .is_some_and(|activation_height| height >= activation_height)
+  }
+}
+
+#[derive(Deserialize)]
+struct SnsRegistration {
+  p: String,
+  op: String,
+  name: String,
+}
+
 impl BtcDomain {
-  pub fn parse(bytes: &[u8]) -> Result<Self> {
-    let domains = DEFAULT_DOMAIN_LIST.join("|");
+  /// Parses a `name.suffix` domain with one of the suffixes.
+  pub fn parse(bytes: &[u8], suffixes: &[String]) -> Result<Self> {
+    let domains = suffixes
+      .iter()
+      .map(|suffix| regex::escape(suffix))
+      .collect::<Vec<_>>()
+      .join("|");
     let pattern = format!(r"^(?<name>.+)\.(?<domain>{domains})$");
     let content = std::str::from_utf8(bytes)?;
     let re = Regex::new(&pattern).unwrap();
@@ -19,16 +116,38 @@ impl BtcDomain {
       let name = &capture["name"];
       let domain = &capture["domain"];
       if Self::is_name_valid(name) {
-        return Ok(Self {
-          name: name.to_string(),
-          domain: domain.to_string(),
-          collection_kind: CollectionKind::try_from(domain).unwrap(),
-        });
+        if let Some(collection_kind) = CollectionKind::domain(domain, suffixes) {
+          return Ok(Self {
+            name: name.to_string(),
+            domain: domain.to_string(),
+            collection_kind,
+          });
+        }
       }
     }
     Err(anyhow!("No match found."))
   }
 
+  /// Parses a `{"p":"sns","op":"reg","name":"name.suffix"}` registration with one of the suffixes.
+  pub fn parse_sns(bytes: &[u8], suffixes: &[String]) -> Result<Self> {
+    let registration = serde_json::from_slice::<SnsRegistration>(bytes)?;
+    if registration.p != "sns" || registration.op != "reg" {
+      return Err(anyhow!("not a sns registration."));
+    }
+    Self::parse(registration.name.as_bytes(), suffixes)
+  }
+
+  /// Parses the domain registered by an inscription at the height.
+  pub fn resolve(bytes: &[u8], config: &DomainConfig, height: u32) -> Option<Self> {
+    let suffixes = config.active_suffixes(height);
+    Self::parse(bytes, &suffixes).ok().or_else(|| {
+      config
+        .is_sns_active(height)
+        .then(|| Self::parse_sns(bytes, &suffixes).ok())
+        .flatten()
+    })
+  }
+
   /// check the name is valid or not
   /// https://docs.btcname.id/docs/overview/chapter-4-thinking-about-.btc-domain-name/calibration-rules
   fn is_name_valid(name: &str) -> bool {
@@ -50,12 +169,19 @@ impl BtcDomain {
   }
 }
 
+impl Display for BtcDomain {
+  fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+    write!(f, "{}.{}", self.name, self.domain)
+  }
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
 
   #[test]
   fn validate_regex() {
+    let suffixes = DomainConfig::default().suffixes();
     let invalid_domains = [
       "abc.bitmap",
       "btc.com.btc",
@@ -72,7 +198,7 @@ mod tests {
       r#"{ "p":"sns", "op":"reg",    "name":"jack.btc"}"#,
     ];
     for domain in invalid_domains {
-      let btc_name = BtcDomain::parse(domain.as_bytes());
+      let btc_name = BtcDomain::parse(domain.as_bytes(), &suffixes);
       assert!(btc_name.is_err());
     }
 
@@ -88,7 +214,7 @@ mod tests {
       "\tjack.btc",
     ];
     for domain in valid_domains {
-      let btc_name = BtcDomain::parse(domain.as_bytes());
+      let btc_name = BtcDomain::parse(domain.as_bytes(), &suffixes);
       assert!(btc_name.is_ok());
       assert!(matches!(
         btc_name.unwrap().collection_kind,
@@ -98,20 +224,81 @@ mod tests {
 
     // test "unisat", "sats", "x"
     let valid_domain = "abcdef.unisat";
-    let unisat_name = BtcDomain::parse(valid_domain.as_bytes()).unwrap();
+    let unisat_name = BtcDomain::parse(valid_domain.as_bytes(), &suffixes).unwrap();
     assert_eq!(unisat_name.collection_kind, CollectionKind::UnisatName);
     let valid_domain = "abcdef.sats";
-    let sats_name = BtcDomain::parse(valid_domain.as_bytes()).unwrap();
+    let sats_name = BtcDomain::parse(valid_domain.as_bytes(), &suffixes).unwrap();
     assert_eq!(sats_name.collection_kind, CollectionKind::SatsName);
     let valid_domain = "abcdef.x";
-    let x_name = BtcDomain::parse(valid_domain.as_bytes()).unwrap();
+    let x_name = BtcDomain::parse(valid_domain.as_bytes(), &suffixes).unwrap();
     assert_eq!(x_name.collection_kind, CollectionKind::XName);
 
     for d in DEFAULT_DOMAIN_LIST {
       let s = format!("abc.{d}");
-      let btc_name = BtcDomain::parse(s.as_bytes()).unwrap();
+      let btc_name = BtcDomain::parse(s.as_bytes(), &suffixes).unwrap();
       assert!(DEFAULT_DOMAIN_LIST.contains(&btc_name.domain.as_str()));
       assert_eq!(btc_name.name, "abc");
     }
   }
+
+  #[test]
+  fn configured_suffixes_and_sns_registrations() {
+    let config = serde_yaml::from_str::<DomainConfig>(
+      "
+suffixes:
+- suffix: ord
+  activation_height: 10
+- suffix: btc
+  activation_height: 5
+sns_activation_height: 20
+",
+    )
+    .unwrap();
+    config.check().unwrap();
+
+    assert_eq!(config.suffixes(), vec!["unisat", "sats", "x", "ord", "btc"]);
+    assert_eq!(config.active_suffixes(4), vec!["unisat", "sats", "x"]);
+
+    assert!(BtcDomain::resolve(b"jack.ord", &config, 9).is_none());
+    let domain = BtcDomain::resolve(b"jack.ord", &config, 10).unwrap();
+    assert_eq!(domain.to_string(), "jack.ord");
+    assert_eq!(
+      domain.collection_kind,
+      CollectionKind::DomainName("ord".into())
+    );
+    assert_eq!(domain.collection_kind.to_string(), "ord_name");
+
+    assert!(CollectionKind::try_from("ord").is_err());
+    assert_eq!(
+      CollectionKind::domain("ord", &DomainConfig::default().suffixes()),
+      None
+    );
+    assert_eq!(
+      CollectionKind::domain("btc", &config.suffixes()),
+      Some(CollectionKind::BtcName)
+    );
+
+    let sns = br#"{ "p":"sns", "op":"reg",    "name":"Jack.btc"}"#;
+    assert!(BtcDomain::resolve(sns, &config, 19).is_none());
+    assert_eq!(
+      BtcDomain::resolve(sns, &config, 20).unwrap().to_string(),
+      "jack.btc"
+    );
+    assert!(
+      BtcDomain::resolve(br#"{"p":"sns","op":"mint","name":"jack.btc"}"#, &config, 20).is_none()
+    );
+    assert!(
+      BtcDomain::resolve(br#"{"p":"sns","op":"reg","name":"jack.com"}"#, &config, 20).is_none()
+    );
+
+    assert!(DomainConfig {
+      suffixes: vec![DomainSuffix {
+        suffix: "b_tc".into(),
+        activation_height: 0,
+      }],
+      sns_activation_height: None,
+    }
+    .check()
+    .is_err());
+  }
 }
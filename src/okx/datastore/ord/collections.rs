@@ -10,6 +10,9 @@ pub enum CollectionKind {
   UnisatName,
   SatsName,
   XName,
+  /// A domain with a suffix configured in `ord.yaml`.
+  DomainName(String),
+  BitMapParcel,
 }
 impl Display for CollectionKind {
   fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -23,11 +26,34 @@ impl Display for CollectionKind {
         CollectionKind::UnisatName => String::from("unisat_name"),
         CollectionKind::SatsName => String::from("sats_name"),
         CollectionKind::XName => String::from("x_name"),
+        CollectionKind::DomainName(suffix) => format!("{suffix}_name"),
+        CollectionKind::BitMapParcel => String::from("bitmap_parcel"),
       }
     )
   }
 }
 
+impl CollectionKind {
+  /// Whether the kind marks the inscription registering a domain.
+  pub fn is_domain(&self) -> bool {
+    matches!(
+      self,
+      CollectionKind::BtcName
+        | CollectionKind::UnisatName
+        | CollectionKind::SatsName
+        | CollectionKind::XName
+        | CollectionKind::DomainName(_)
+    )
+  }
+
+  /// The kind of the domains with the suffix, if it is one of the configured suffixes.
+  pub fn domain(suffix: &str, suffixes: &[String]) -> Option<Self> {
+    suffixes.iter().any(|s| s == suffix).then(|| {
+      Self::try_from(suffix).unwrap_or_else(|()| CollectionKind::DomainName(suffix.to_string()))
+    })
+  }
+}
+
 impl TryFrom<&str> for CollectionKind {
   type Error = ();
   fn try_from(value: &str) -> Result<Self, Self::Error> {
@@ -3,6 +3,7 @@ pub use self::operation::{Action, InscriptionOp};
 use {
   crate::{okx::datastore::ScriptKey, Chain, InscriptionId, Result, SatPoint},
   bitcoin::Txid,
+  bitmap::DistrictBlock,
   collections::CollectionKind,
   std::fmt::{Debug, Display},
 };
@@ -40,6 +41,8 @@ pub trait OrdReader {
     &self,
     collection_key: &str,
   ) -> Result<Option<InscriptionId>, Self::Error>;
+
+  fn get_district_block(&self, height: u32) -> Result<Option<DistrictBlock>, Self::Error>;
 }
 
 pub trait OrdReaderWriter: OrdReader {
@@ -60,4 +63,6 @@ pub trait OrdReaderWriter: OrdReader {
     inscription_id: &InscriptionId,
     kind: CollectionKind,
   ) -> Result<(), Self::Error>;
+
+  fn save_district_block(&mut self, height: u32, block: &DistrictBlock) -> Result<(), Self::Error>;
 }
@@ -1,8 +1,9 @@
 use crate::index::entry::Entry;
 use crate::index::{InscriptionEntryValue, InscriptionIdValue, OutPointValue, TxidValue};
 use crate::inscriptions::InscriptionId;
-use crate::okx::datastore::ord::collections::CollectionKind;
 use crate::okx::datastore::ord::InscriptionOp;
+use crate::okx::datastore::ord::{bitmap::DistrictBlock, collections::CollectionKind};
+use crate::okx::datastore::{undo::Journal, KeyPage};
 use bitcoin::consensus::Decodable;
 use bitcoin::{OutPoint, TxOut, Txid};
 use redb::{MultimapTable, ReadableMultimapTable, ReadableTable, Table};
@@ -35,6 +36,52 @@ where
   Ok(table.get(key)?.map(|v| InscriptionId::load(v.value())))
 }
 
+// COLLECTIONS_KEY_TO_INSCRIPTION_ID
+pub fn get_collection_inscription_ids_by_prefix<T>(
+  table: &T,
+  prefix: &str,
+) -> crate::Result<Vec<(String, InscriptionId)>>
+where
+  T: ReadableTable<&'static str, InscriptionIdValue>,
+{
+  Ok(
+    table
+      .range(prefix..)?
+      .flat_map(|result| {
+        result.map(|(key, value)| (key.value().to_string(), InscriptionId::load(value.value())))
+      })
+      .take_while(|(key, _)| key.starts_with(prefix))
+      .collect(),
+  )
+}
+
+// BITMAP_DISTRICT_BLOCKS
+pub fn get_district_block<T>(table: &T, height: u32) -> crate::Result<Option<DistrictBlock>>
+where
+  T: ReadableTable<u32, &'static [u8]>,
+{
+  Ok(
+    table
+      .get(height)?
+      .map(|v| rmp_serde::from_slice::<DistrictBlock>(v.value()).unwrap()),
+  )
+}
+
+// INSCRIPTION_ID_TO_SEQUENCE_NUMBER
+pub fn get_sequence_number_by_inscription_id<T>(
+  table: &T,
+  inscription_id: &InscriptionId,
+) -> crate::Result<Option<u32>>
+where
+  T: ReadableTable<InscriptionIdValue, u32>,
+{
+  Ok(
+    table
+      .get(&inscription_id.store())?
+      .map(|sequence_number| sequence_number.value()),
+  )
+}
+
 // SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY
 pub fn get_inscription_number_by_sequence_number<T>(
   table: &T,
@@ -75,41 +122,124 @@ where
 
 // ORD_TX_TO_OPERATIONS
 pub fn save_transaction_operations(
+  journal: &Journal,
   table: &mut Table<'_, '_, &'static TxidValue, &'static [u8]>,
   txid: &Txid,
   operations: &[InscriptionOp],
 ) -> crate::Result<()> {
-  table.insert(&txid.store(), rmp_serde::to_vec(operations)?.as_slice())?;
+  journal.insert(
+    table,
+    &txid.store(),
+    rmp_serde::to_vec(operations)?.as_slice(),
+  )?;
   Ok(())
 }
 
 // COLLECTIONS_KEY_TO_INSCRIPTION_ID
 pub fn set_inscription_by_collection_key(
+  journal: &Journal,
   table: &mut Table<'_, '_, &'static str, InscriptionIdValue>,
   key: &str,
   inscription_id: &InscriptionId,
 ) -> crate::Result<()> {
-  table.insert(key, inscription_id.store())?;
+  journal.insert(table, key, inscription_id.store())?;
   Ok(())
 }
 
 // COLLECTIONS_INSCRIPTION_ID_TO_KINDS
+/// Returns whether the inscription already belonged to the collection.
 pub fn add_inscription_attributes(
+  journal: &Journal,
   table: &mut MultimapTable<'_, '_, InscriptionIdValue, &'static [u8]>,
   inscription_id: &InscriptionId,
   kind: CollectionKind,
-) -> crate::Result<()> {
-  table.insert(
+) -> crate::Result<bool> {
+  Ok(journal.multimap_insert(
+    table,
     inscription_id.store(),
     rmp_serde::to_vec(&kind).unwrap().as_slice(),
-  )?;
+  )?)
+}
+
+// COLLECTION_KIND_TO_SEQUENCE_NUMBERS
+pub fn add_collection_member(
+  journal: &Journal,
+  table: &mut MultimapTable<'_, '_, &'static str, u32>,
+  kind: &str,
+  sequence_number: u32,
+) -> crate::Result<()> {
+  journal.multimap_insert(table, kind, sequence_number)?;
+  Ok(())
+}
+
+// COLLECTION_KIND_TO_SEQUENCE_NUMBERS
+/// A page of the sequence numbers of the members of the collection, keyed by the hex sequence number.
+pub fn get_collection_members_page<T>(
+  table: &T,
+  kind: &str,
+  page: &KeyPage,
+) -> crate::Result<Vec<(String, u32)>>
+where
+  T: ReadableMultimapTable<&'static str, u32>,
+{
+  page.read_sorted(
+    table.get(kind)?.map(|result| Ok(result?.value())),
+    |sequence_number| format!("{sequence_number:08x}"),
+    |sequence_number| Ok(Some(sequence_number)),
+  )
+}
+
+// COLLECTION_KIND_TO_COUNT
+pub fn increment_collection_count(
+  journal: &Journal,
+  table: &mut Table<'_, '_, &'static str, u64>,
+  kind: &str,
+) -> crate::Result<()> {
+  let count = table.get(kind)?.map(|count| count.value()).unwrap_or(0);
+  journal.insert(table, kind, count + 1)?;
+  Ok(())
+}
+
+// COLLECTION_KIND_TO_COUNT
+pub fn get_collection_count<T>(table: &T, kind: &str) -> crate::Result<Option<u64>>
+where
+  T: ReadableTable<&'static str, u64>,
+{
+  Ok(table.get(kind)?.map(|count| count.value()))
+}
+
+// COLLECTION_KIND_TO_COUNT
+pub fn get_collection_counts<T>(table: &T) -> crate::Result<Vec<(String, u64)>>
+where
+  T: ReadableTable<&'static str, u64>,
+{
+  table
+    .iter()?
+    .map(|result| {
+      let (kind, count) = result?;
+      Ok((kind.value().to_string(), count.value()))
+    })
+    .collect()
+}
+
+// BITMAP_DISTRICT_BLOCKS
+pub fn save_district_block(
+  journal: &Journal,
+  table: &mut Table<'_, '_, u32, &'static [u8]>,
+  height: u32,
+  block: &DistrictBlock,
+) -> crate::Result<()> {
+  journal.insert(table, height, rmp_serde::to_vec(block).unwrap().as_slice())?;
   Ok(())
 }
 
 #[cfg(test)]
 mod tests {
   use super::*;
-  use crate::index::{COLLECTIONS_INSCRIPTION_ID_TO_KINDS, ORD_TX_TO_OPERATIONS};
+  use crate::index::{
+    COLLECTIONS_INSCRIPTION_ID_TO_KINDS, COLLECTIONS_KEY_TO_INSCRIPTION_ID,
+    COLLECTION_KIND_TO_COUNT, COLLECTION_KIND_TO_SEQUENCE_NUMBERS, ORD_TX_TO_OPERATIONS,
+  };
   use crate::okx::datastore::ord::redb::table::{
     get_transaction_operations, save_transaction_operations,
   };
@@ -131,25 +261,121 @@ mod tests {
       InscriptionId::from_str("b61b0172d95e266c18aea0c624db987e971a5d6d4ebc2aaed85da4642d635735i0")
         .unwrap();
 
-    add_inscription_attributes(&mut table, &inscription_id, CollectionKind::BitMap).unwrap();
+    add_inscription_attributes(
+      &Journal::default(),
+      &mut table,
+      &inscription_id,
+      CollectionKind::BitMap,
+    )
+    .unwrap();
     assert_eq!(
       get_collections_of_inscription(&table, &inscription_id).unwrap(),
       Some(vec![CollectionKind::BitMap])
     );
 
-    add_inscription_attributes(&mut table, &inscription_id, CollectionKind::BRC20).unwrap();
+    add_inscription_attributes(
+      &Journal::default(),
+      &mut table,
+      &inscription_id,
+      CollectionKind::BRC20,
+    )
+    .unwrap();
     assert_eq!(
       get_collections_of_inscription(&table, &inscription_id).unwrap(),
       Some(vec![CollectionKind::BRC20, CollectionKind::BitMap])
     );
 
-    add_inscription_attributes(&mut table, &inscription_id, CollectionKind::BRC20).unwrap();
+    assert!(add_inscription_attributes(
+      &Journal::default(),
+      &mut table,
+      &inscription_id,
+      CollectionKind::BRC20
+    )
+    .unwrap());
     assert_eq!(
       get_collections_of_inscription(&table, &inscription_id).unwrap(),
       Some(vec![CollectionKind::BRC20, CollectionKind::BitMap])
     );
   }
 
+  #[test]
+  fn test_collection_members() {
+    let dbfile = NamedTempFile::new().unwrap();
+    let db = Database::create(dbfile.path()).unwrap();
+    let wtx = db.begin_write().unwrap();
+    let mut members = wtx
+      .open_multimap_table(COLLECTION_KIND_TO_SEQUENCE_NUMBERS)
+      .unwrap();
+    let mut counts = wtx.open_table(COLLECTION_KIND_TO_COUNT).unwrap();
+
+    for sequence_number in [7, 3, 5] {
+      add_collection_member(&Journal::default(), &mut members, "bitmap", sequence_number).unwrap();
+      increment_collection_count(&Journal::default(), &mut counts, "bitmap").unwrap();
+    }
+    add_collection_member(&Journal::default(), &mut members, "btc_name", 4).unwrap();
+    increment_collection_count(&Journal::default(), &mut counts, "btc_name").unwrap();
+
+    let members_page = |kind: &str, page: KeyPage| {
+      page.split(get_collection_members_page(&members, kind, &page).unwrap())
+    };
+
+    assert_eq!(
+      members_page("bitmap", KeyPage::default()),
+      (vec![3, 5, 7], None)
+    );
+    assert_eq!(
+      members_page(
+        "bitmap",
+        KeyPage {
+          after: Some("00000003".into()),
+          limit: 1,
+          reverse: false,
+        }
+      ),
+      (vec![5], Some("00000005".into()))
+    );
+    assert_eq!(
+      members_page("brc20", KeyPage::default()),
+      (Vec::<u32>::new(), None)
+    );
+
+    assert_eq!(get_collection_count(&counts, "bitmap").unwrap(), Some(3));
+    assert_eq!(get_collection_count(&counts, "brc20").unwrap(), None);
+    assert_eq!(
+      get_collection_counts(&counts).unwrap(),
+      vec![("bitmap".to_string(), 3), ("btc_name".to_string(), 1)]
+    );
+  }
+
+  #[test]
+  fn test_collection_inscription_ids_by_prefix() {
+    let dbfile = NamedTempFile::new().unwrap();
+    let db = Database::create(dbfile.path()).unwrap();
+    let wtx = db.begin_write().unwrap();
+    let mut table = wtx.open_table(COLLECTIONS_KEY_TO_INSCRIPTION_ID).unwrap();
+    let inscription_id =
+      InscriptionId::from_str("b61b0172d95e266c18aea0c624db987e971a5d6d4ebc2aaed85da4642d635735i0")
+        .unwrap();
+
+    for key in [
+      "BTC_DOMAIN_btc_a",
+      "BTC_DOMAIN_x_b",
+      "BTC_DOMAIO",
+      "DISTRICT_1",
+    ] {
+      set_inscription_by_collection_key(&Journal::default(), &mut table, key, &inscription_id)
+        .unwrap();
+    }
+
+    assert_eq!(
+      get_collection_inscription_ids_by_prefix(&table, "BTC_DOMAIN_").unwrap(),
+      vec![
+        ("BTC_DOMAIN_btc_a".to_string(), inscription_id),
+        ("BTC_DOMAIN_x_b".to_string(), inscription_id),
+      ]
+    );
+  }
+
   #[test]
   fn test_transaction_to_operations() {
     let dbfile = NamedTempFile::new().unwrap();
@@ -180,7 +406,8 @@ mod tests {
       }),
     };
 
-    save_transaction_operations(&mut table, &txid, &[operation.clone()]).unwrap();
+    save_transaction_operations(&Journal::default(), &mut table, &txid, &[operation.clone()])
+      .unwrap();
 
     // skip the inscription
     if let Action::New { inscription, .. } = &mut operation.action {
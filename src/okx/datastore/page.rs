@@ -0,0 +1,263 @@
+use {
+  crate::Result,
+  redb::{ReadableTable, RedbValue},
+  std::ops::Bound,
+};
+
+/// A page of entries in key order, starting after the key of the last entry of the previous page.
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeyPage {
+  /// The key of the last entry of the previous page.
+  pub after: Option<String>,
+  /// The maximum number of entries in the page.
+  pub limit: usize,
+  /// Whether the entries are in descending key order.
+  pub reverse: bool,
+}
+
+impl Default for KeyPage {
+  fn default() -> Self {
+    Self {
+      after: None,
+      limit: usize::MAX,
+      reverse: false,
+    }
+  }
+}
+
+impl KeyPage {
+  /// Reads the entries of the page from the keys of `table` in `min..max`, and the first entry of
+  /// the next page, if any. `decode` returns `None` for the entries to skip.
+  pub fn read<T, V, R>(
+    &self,
+    table: &T,
+    min: &str,
+    max: Option<&str>,
+    mut decode: impl FnMut(&str, V::SelfType<'_>) -> Result<Option<R>>,
+  ) -> Result<Vec<(String, R)>>
+  where
+    T: ReadableTable<&'static str, V>,
+    V: RedbValue + 'static,
+  {
+    let after = self.after.as_deref();
+
+    let lower = match after {
+      Some(after) if !self.reverse && after >= min => Bound::Excluded(after),
+      _ => Bound::Included(min),
+    };
+
+    let upper = match (after, max) {
+      (Some(after), Some(max)) if self.reverse && after < max => Bound::Excluded(after),
+      (Some(after), None) if self.reverse => Bound::Excluded(after),
+      (_, Some(max)) => Bound::Excluded(max),
+      (_, None) => Bound::Unbounded,
+    };
+
+    if let (Bound::Excluded(lower) | Bound::Included(lower), Bound::Excluded(upper)) =
+      (lower, upper)
+    {
+      if lower >= upper {
+        return Ok(Vec::new());
+      }
+    }
+
+    let range = table.range::<&str>((lower, upper))?;
+
+    let entries: Box<dyn Iterator<Item = _>> = if self.reverse {
+      Box::new(range.rev())
+    } else {
+      Box::new(range)
+    };
+
+    let mut page = Vec::new();
+    for entry in entries {
+      if page.len() > self.limit {
+        break;
+      }
+
+      let (key, value) = entry?;
+      if let Some(value) = decode(key.value(), value.value())? {
+        page.push((key.value().to_string(), value));
+      }
+    }
+
+    Ok(page)
+  }
+
+  /// Reads the entries of the page from `entries`, keyed by their position, and the first entry of
+  /// the next page, if any. `read` returns `None` for the entries to skip.
+  pub fn read_positions<T, R>(
+    &self,
+    entries: impl DoubleEndedIterator<Item = T> + ExactSizeIterator,
+    mut read: impl FnMut(T) -> Result<Option<R>>,
+  ) -> Result<Vec<(String, R)>> {
+    self.read_sorted(
+      entries.enumerate().map(Ok),
+      |(position, _)| format!("{position:08x}"),
+      |(_, entry)| read(entry),
+    )
+  }
+
+  /// Reads the entries of the page from `entries`, which are in ascending order of the keys returned
+  /// by `key`, and the first entry of the next page, if any. `read` returns `None` for the entries
+  /// to skip.
+  pub fn read_sorted<T, R>(
+    &self,
+    entries: impl DoubleEndedIterator<Item = Result<T>>,
+    key: impl Fn(&T) -> String,
+    mut read: impl FnMut(T) -> Result<Option<R>>,
+  ) -> Result<Vec<(String, R)>> {
+    let after = self.after.as_deref();
+
+    let entries: Box<dyn Iterator<Item = _>> = if self.reverse {
+      Box::new(entries.rev())
+    } else {
+      Box::new(entries)
+    };
+
+    let mut page = Vec::new();
+    for entry in entries {
+      if page.len() > self.limit {
+        break;
+      }
+
+      let entry = entry?;
+      let key = key(&entry);
+
+      match after {
+        Some(after) if !self.reverse && key.as_str() <= after => continue,
+        Some(after) if self.reverse && key.as_str() >= after => continue,
+        _ => {}
+      }
+
+      if let Some(value) = read(entry)? {
+        page.push((key, value));
+      }
+    }
+
+    Ok(page)
+  }
+
+  /// Splits the entries read for the page into its values and the key to continue after, if
+  /// another page follows.
+  pub fn split<R>(&self, mut entries: Vec<(String, R)>) -> (Vec<R>, Option<String>) {
+    let next = if entries.len() > self.limit {
+      entries.truncate(self.limit);
+      entries.last().map(|(key, _)| key.clone())
+    } else {
+      None
+    };
+
+    (entries.into_iter().map(|(_, value)| value).collect(), next)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use {super::*, redb::TableDefinition, tempfile::NamedTempFile};
+
+  const TABLE: TableDefinition<&str, u32> = TableDefinition::new("TABLE");
+
+  fn read_all(page: KeyPage, filter: impl Fn(u32) -> bool) -> Vec<Vec<u32>> {
+    let dbfile = NamedTempFile::new().unwrap();
+    let db = redb::Database::create(dbfile.path()).unwrap();
+    let wtx = db.begin_write().unwrap();
+    {
+      let mut table = wtx.open_table(TABLE).unwrap();
+      for (key, value) in [("a", 0), ("b_1", 1), ("b_2", 2), ("b_3", 3), ("c", 4)] {
+        table.insert(key, value).unwrap();
+      }
+    }
+    wtx.commit().unwrap();
+
+    let rtx = db.begin_read().unwrap();
+    let table = rtx.open_table(TABLE).unwrap();
+
+    let mut pages = Vec::new();
+    let mut page = page;
+    loop {
+      let entries = page
+        .read(&table, "b_", Some("b`"), |_, value| {
+          Ok(filter(value).then_some(value))
+        })
+        .unwrap();
+      let (values, next) = page.split(entries);
+      pages.push(values);
+      match next {
+        Some(next) => page.after = Some(next),
+        None => break,
+      }
+    }
+    pages
+  }
+
+  #[test]
+  fn pages_follow_key_order_within_range() {
+    assert_eq!(
+      read_all(
+        KeyPage {
+          limit: 2,
+          ..Default::default()
+        },
+        |_| true
+      ),
+      vec![vec![1, 2], vec![3]]
+    );
+    assert_eq!(read_all(KeyPage::default(), |_| true), vec![vec![1, 2, 3]]);
+  }
+
+  #[test]
+  fn pages_in_reverse_key_order() {
+    assert_eq!(
+      read_all(
+        KeyPage {
+          limit: 2,
+          reverse: true,
+          ..Default::default()
+        },
+        |_| true
+      ),
+      vec![vec![3, 2], vec![1]]
+    );
+  }
+
+  #[test]
+  fn skipped_entries_do_not_count_towards_the_limit() {
+    assert_eq!(
+      read_all(
+        KeyPage {
+          limit: 1,
+          ..Default::default()
+        },
+        |value| value != 2
+      ),
+      vec![vec![1], vec![3]]
+    );
+  }
+
+  #[test]
+  fn pages_by_position() {
+    let mut page = KeyPage {
+      limit: 2,
+      ..Default::default()
+    };
+
+    let read = |page: &KeyPage| {
+      page.split(
+        page
+          .read_positions([1, 2, 3, 4, 5].into_iter(), |value| {
+            Ok((value != 3).then_some(value))
+          })
+          .unwrap(),
+      )
+    };
+
+    assert_eq!(read(&page), (vec![1, 2], Some("00000001".into())));
+    page.after = Some("00000001".into());
+    assert_eq!(read(&page), (vec![4, 5], None));
+
+    page.reverse = true;
+    page.after = None;
+    assert_eq!(read(&page), (vec![5, 4], Some("00000003".into())));
+  }
+}
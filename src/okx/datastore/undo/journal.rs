@@ -0,0 +1,263 @@
+use {
+  redb::{
+    AccessGuard, MultimapTable, MultimapTableHandle, RedbKey, RedbValue, StorageError, Table,
+    TableDefinition, TableHandle, WriteTransaction,
+  },
+  serde::{Deserialize, Serialize},
+  std::{borrow::Borrow, cell::RefCell},
+};
+
+/// A write of an index table with what it replaced, enough to undo it.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub enum TableWrite {
+  /// A table entry was inserted or removed, `old` is the value it replaced.
+  Entry {
+    table: String,
+    key: Vec<u8>,
+    old: Option<Vec<u8>>,
+  },
+  /// A multimap value that was absent was inserted.
+  MultimapInserted {
+    table: String,
+    key: Vec<u8>,
+    value: Vec<u8>,
+  },
+  /// A multimap value that was present was removed.
+  MultimapRemoved {
+    table: String,
+    key: Vec<u8>,
+    value: Vec<u8>,
+  },
+}
+
+impl TableWrite {
+  pub fn table(&self) -> &str {
+    match self {
+      Self::Entry { table, .. }
+      | Self::MultimapInserted { table, .. }
+      | Self::MultimapRemoved { table, .. } => table,
+    }
+  }
+}
+
+/// The table writes of a block in the order they were made.
+///
+/// Writes go through the journal instead of the table, so that disconnecting the block can undo them in reverse.
+/// A disabled journal only makes the writes, for blocks too deep below the chain tip to be disconnected.
+#[derive(Debug, Default)]
+pub struct Journal {
+  writes: RefCell<Vec<TableWrite>>,
+  disabled: bool,
+}
+
+impl Journal {
+  pub fn disabled() -> Self {
+    Self {
+      writes: RefCell::default(),
+      disabled: true,
+    }
+  }
+
+  pub fn is_enabled(&self) -> bool {
+    !self.disabled
+  }
+
+  pub fn insert<'t, 'k, 'v, K: RedbKey + 'static, V: RedbValue + 'static>(
+    &self,
+    table: &'t mut Table<'_, '_, K, V>,
+    key: impl Borrow<K::SelfType<'k>>,
+    value: impl Borrow<V::SelfType<'v>>,
+  ) -> Result<Option<AccessGuard<'t, V>>, StorageError> {
+    if self.disabled {
+      return table.insert(key, value);
+    }
+    let name = table.name().to_string();
+    let key_bytes = K::as_bytes(key.borrow()).as_ref().to_vec();
+    let old = table.insert(key, value)?;
+    self.record_entry(name, key_bytes, old.as_ref());
+    Ok(old)
+  }
+
+  pub fn remove<'t, 'k, K: RedbKey + 'static, V: RedbValue + 'static>(
+    &self,
+    table: &'t mut Table<'_, '_, K, V>,
+    key: impl Borrow<K::SelfType<'k>>,
+  ) -> Result<Option<AccessGuard<'t, V>>, StorageError> {
+    if self.disabled {
+      return table.remove(key);
+    }
+    let name = table.name().to_string();
+    let key_bytes = K::as_bytes(key.borrow()).as_ref().to_vec();
+    let old = table.remove(key)?;
+    if old.is_some() {
+      self.record_entry(name, key_bytes, old.as_ref());
+    }
+    Ok(old)
+  }
+
+  pub fn pop_first<K: RedbKey + 'static, V: RedbValue + 'static>(
+    &self,
+    table: &mut Table<'_, '_, K, V>,
+  ) -> Result<(), StorageError> {
+    if self.disabled {
+      table.pop_first()?;
+      return Ok(());
+    }
+    let name = table.name().to_string();
+    if let Some((key, old)) = table.pop_first()? {
+      self.record_entry(
+        name,
+        K::as_bytes(&key.value()).as_ref().to_vec(),
+        Some(&old),
+      );
+    }
+    Ok(())
+  }
+
+  pub fn multimap_insert<'k, 'v, K: RedbKey + 'static, V: RedbKey + 'static>(
+    &self,
+    table: &mut MultimapTable<'_, '_, K, V>,
+    key: impl Borrow<K::SelfType<'k>>,
+    value: impl Borrow<V::SelfType<'v>>,
+  ) -> Result<bool, StorageError> {
+    if self.disabled {
+      return table.insert(key, value);
+    }
+    let write = TableWrite::MultimapInserted {
+      table: table.name().to_string(),
+      key: K::as_bytes(key.borrow()).as_ref().to_vec(),
+      value: V::as_bytes(value.borrow()).as_ref().to_vec(),
+    };
+    let existed = table.insert(key, value)?;
+    if !existed {
+      self.record(write);
+    }
+    Ok(existed)
+  }
+
+  pub fn multimap_remove<'a, K: RedbKey + 'static, V: RedbKey + 'static>(
+    &self,
+    table: &mut MultimapTable<'_, '_, K, V>,
+    key: impl Borrow<K::SelfType<'a>>,
+    value: impl Borrow<V::SelfType<'a>>,
+  ) -> Result<bool, StorageError> {
+    if self.disabled {
+      return table.remove(key, value);
+    }
+    let write = TableWrite::MultimapRemoved {
+      table: table.name().to_string(),
+      key: K::as_bytes(key.borrow()).as_ref().to_vec(),
+      value: V::as_bytes(value.borrow()).as_ref().to_vec(),
+    };
+    let existed = table.remove(key, value)?;
+    if existed {
+      self.record(write);
+    }
+    Ok(existed)
+  }
+
+  pub fn multimap_remove_all<'a, K: RedbKey + 'static, V: RedbKey + 'static>(
+    &self,
+    table: &mut MultimapTable<'_, '_, K, V>,
+    key: impl Borrow<K::SelfType<'a>>,
+  ) -> Result<(), StorageError> {
+    if self.disabled {
+      table.remove_all(key)?;
+      return Ok(());
+    }
+    let name = table.name().to_string();
+    let key_bytes = K::as_bytes(key.borrow()).as_ref().to_vec();
+    for value in table.remove_all(key)? {
+      self.record(TableWrite::MultimapRemoved {
+        table: name.clone(),
+        key: key_bytes.clone(),
+        value: V::as_bytes(&value?.value()).as_ref().to_vec(),
+      });
+    }
+    Ok(())
+  }
+
+  /// Records a write of an entry that is buffered in memory and flushed to the table later.
+  pub fn record_buffered<K: RedbKey + 'static, V: RedbValue + 'static>(
+    &self,
+    definition: TableDefinition<K, V>,
+    key: &[u8],
+    old: Option<&[u8]>,
+  ) {
+    self.record(TableWrite::Entry {
+      table: definition.name().to_string(),
+      key: key.to_vec(),
+      old: old.map(<[u8]>::to_vec),
+    });
+  }
+
+  pub fn take(&self) -> Vec<TableWrite> {
+    self.writes.take()
+  }
+
+  fn record_entry<V: RedbValue + 'static>(
+    &self,
+    table: String,
+    key: Vec<u8>,
+    old: Option<&AccessGuard<V>>,
+  ) {
+    self.record(TableWrite::Entry {
+      table,
+      key,
+      old: old.map(|old| V::as_bytes(&old.value()).as_ref().to_vec()),
+    });
+  }
+
+  fn record(&self, write: TableWrite) {
+    if !self.disabled {
+      self.writes.borrow_mut().push(write);
+    }
+  }
+}
+
+/// Undoes the writes of `definition` in reverse order.
+///
+/// Writes of different tables are independent, so each table is undone on its own.
+pub fn undo_table_writes<K: RedbKey + 'static, V: RedbValue + 'static>(
+  wtx: &WriteTransaction,
+  definition: TableDefinition<K, V>,
+  writes: &[TableWrite],
+) -> crate::Result {
+  let mut table = wtx.open_table(definition)?;
+  for write in writes
+    .iter()
+    .rev()
+    .filter(|write| write.table() == definition.name())
+  {
+    let TableWrite::Entry { key, old, .. } = write else {
+      anyhow::bail!("multimap write of table {}", definition.name());
+    };
+    match old {
+      Some(old) => table.insert(K::from_bytes(key), V::from_bytes(old))?,
+      None => table.remove(K::from_bytes(key))?,
+    };
+  }
+  Ok(())
+}
+
+/// Undoes the writes of the multimap table `definition` in reverse order.
+pub fn undo_multimap_table_writes<K: RedbKey + 'static, V: RedbKey + 'static>(
+  wtx: &WriteTransaction,
+  definition: redb::MultimapTableDefinition<K, V>,
+  writes: &[TableWrite],
+) -> crate::Result {
+  let mut table = wtx.open_multimap_table(definition)?;
+  let name = table.name().to_string();
+  for write in writes.iter().rev().filter(|write| write.table() == name) {
+    match write {
+      TableWrite::MultimapInserted { key, value, .. } => {
+        table.remove(K::from_bytes(key), V::from_bytes(value))?;
+      }
+      TableWrite::MultimapRemoved { key, value, .. } => {
+        table.insert(K::from_bytes(key), V::from_bytes(value))?;
+      }
+      TableWrite::Entry { .. } => anyhow::bail!("entry write of multimap table {name}"),
+    }
+  }
+  Ok(())
+}
@@ -0,0 +1,204 @@
+pub mod journal;
+pub mod redb;
+
+pub use self::journal::{Journal, TableWrite};
+
+use {
+  super::{
+    brc20::{Balance, Tick, TokenInfo, TransferableLog},
+    ScriptKey,
+  },
+  crate::{InscriptionId, SatPoint},
+  bitcoin::BlockHash,
+  serde::{Deserialize, Serialize},
+  std::collections::HashMap,
+};
+
+/// The value of a state key before and after a block.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub enum StateChange {
+  Balance {
+    script_key: ScriptKey,
+    tick: Tick,
+    old: Option<Balance>,
+    new: Option<Balance>,
+  },
+  Token {
+    tick: Tick,
+    old: Option<TokenInfo>,
+    new: Option<TokenInfo>,
+  },
+  TransferableAsset {
+    satpoint: SatPoint,
+    old: Option<TransferableLog>,
+    new: Option<TransferableLog>,
+  },
+  CollectionInscription {
+    key: String,
+    old: Option<InscriptionId>,
+    new: Option<InscriptionId>,
+  },
+}
+
+impl StateChange {
+  fn key(&self) -> String {
+    match self {
+      Self::Balance {
+        script_key, tick, ..
+      } => format!("balance_{}_{}", script_key, tick.to_lowercase().hex()),
+      Self::Token { tick, .. } => format!("token_{}", tick.to_lowercase().hex()),
+      Self::TransferableAsset { satpoint, .. } => format!("transferable_{satpoint}"),
+      Self::CollectionInscription { key, .. } => format!("collection_{key}"),
+    }
+  }
+
+  fn is_changed(&self) -> bool {
+    match self {
+      Self::Balance { old, new, .. } => old != new,
+      Self::Token { old, new, .. } => old != new,
+      Self::TransferableAsset { old, new, .. } => old != new,
+      Self::CollectionInscription { old, new, .. } => old != new,
+    }
+  }
+
+  /// Keeps the old value of `self` and takes the new value of a later change of the same key.
+  fn merge(&mut self, later: StateChange) {
+    match (self, later) {
+      (Self::Balance { new, .. }, Self::Balance { new: later, .. }) => *new = later,
+      (Self::Token { new, .. }, Self::Token { new: later, .. }) => *new = later,
+      (Self::TransferableAsset { new, .. }, Self::TransferableAsset { new: later, .. }) => {
+        *new = later
+      }
+      (Self::CollectionInscription { new, .. }, Self::CollectionInscription { new: later, .. }) => {
+        *new = later
+      }
+      _ => unreachable!("changes of the same key must be the same kind"),
+    }
+  }
+}
+
+/// Changes of the protocol state made by a block, in the order the keys were first changed.
+#[derive(Debug, Default)]
+pub struct UndoLog {
+  changes: Vec<StateChange>,
+  positions: HashMap<String, usize>,
+}
+
+impl UndoLog {
+  pub fn record(&mut self, change: StateChange) {
+    let key = change.key();
+    match self.positions.get(&key) {
+      Some(&position) => self.changes[position].merge(change),
+      None => {
+        self.positions.insert(key, self.changes.len());
+        self.changes.push(change);
+      }
+    }
+  }
+
+  /// The new balances of the changed balance keys, ordered by script and ticker.
+  pub fn changed_balances(&self) -> Vec<(ScriptKey, Balance)> {
+    let mut balances = self
+      .changes
+      .iter()
+      .filter(|change| change.is_changed())
+      .filter_map(|change| match change {
+        StateChange::Balance {
+          script_key,
+          new: Some(balance),
+          ..
+        } => Some((script_key.clone(), balance.clone())),
+        _ => None,
+      })
+      .collect::<Vec<_>>();
+    balances.sort_by_cached_key(|(script_key, balance)| {
+      (script_key.to_string(), balance.tick.to_lowercase().hex())
+    });
+    balances
+  }
+
+  /// Takes the recorded changes, leaving out the keys whose value ended up unchanged.
+  pub fn take_changes(&mut self) -> Vec<StateChange> {
+    self.positions.clear();
+    std::mem::take(&mut self.changes)
+      .into_iter()
+      .filter(StateChange::is_changed)
+      .collect()
+  }
+}
+
+/// The undo log of a block: the protocol state changes it made, and the table writes undone to disconnect it.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+pub struct BlockUndoLog {
+  pub changes: Vec<StateChange>,
+  pub writes: Vec<TableWrite>,
+}
+
+/// The changes of a block rolled back by a reorg.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct RolledBackBlock {
+  pub height: u32,
+  pub block_hash: BlockHash,
+  pub changes: Vec<StateChange>,
+}
+
+#[cfg(test)]
+mod tests {
+  use {super::*, std::str::FromStr};
+
+  fn balance(overall_balance: u128) -> Balance {
+    Balance {
+      tick: Tick::from_str("ordi").unwrap(),
+      overall_balance,
+      transferable_balance: 0,
+    }
+  }
+
+  fn balance_change(old: Option<u128>, new: Option<u128>) -> StateChange {
+    StateChange::Balance {
+      script_key: ScriptKey::from_script(
+        &bitcoin::Address::from_str("bc1qhvd6suvqzjcu9pxjhrwhtrlj85ny3n2mqql5w4")
+          .unwrap()
+          .assume_checked()
+          .script_pubkey(),
+        crate::Chain::Mainnet,
+      ),
+      tick: Tick::from_str("ORDI").unwrap(),
+      old: old.map(balance),
+      new: new.map(balance),
+    }
+  }
+
+  #[test]
+  fn undo_log_keeps_first_old_and_last_new_value() {
+    let mut undo_log = UndoLog::default();
+    undo_log.record(balance_change(None, Some(10)));
+    undo_log.record(StateChange::CollectionInscription {
+      key: "bitmap_0".into(),
+      old: None,
+      new: Some(crate::test::inscription_id(1)),
+    });
+    undo_log.record(balance_change(Some(10), Some(30)));
+
+    assert_eq!(
+      undo_log.take_changes(),
+      vec![
+        balance_change(None, Some(30)),
+        StateChange::CollectionInscription {
+          key: "bitmap_0".into(),
+          old: None,
+          new: Some(crate::test::inscription_id(1)),
+        }
+      ]
+    );
+    assert_eq!(undo_log.take_changes(), Vec::new());
+  }
+
+  #[test]
+  fn undo_log_drops_unchanged_values() {
+    let mut undo_log = UndoLog::default();
+    undo_log.record(balance_change(Some(10), Some(20)));
+    undo_log.record(balance_change(Some(20), Some(10)));
+    assert_eq!(undo_log.take_changes(), Vec::new());
+  }
+}
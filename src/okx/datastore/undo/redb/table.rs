@@ -0,0 +1,140 @@
+use crate::{
+  okx::datastore::undo::{BlockUndoLog, RolledBackBlock},
+  Result,
+};
+use redb::{ReadableTable, Table};
+use std::ops::RangeBounds;
+
+// UNDO_LOGS
+pub fn get_undo_log<T>(table: &T, height: u32) -> Result<Option<BlockUndoLog>>
+where
+  T: ReadableTable<u32, &'static [u8]>,
+{
+  Ok(
+    table
+      .get(height)?
+      .map(|v| rmp_serde::from_slice::<BlockUndoLog>(v.value()).unwrap()),
+  )
+}
+
+// UNDO_LOGS
+pub fn save_undo_log(
+  table: &mut Table<'_, '_, u32, &'static [u8]>,
+  height: u32,
+  undo_log: &BlockUndoLog,
+) -> Result<()> {
+  table.insert(height, rmp_serde::to_vec(undo_log).unwrap().as_slice())?;
+  Ok(())
+}
+
+// UNDO_LOGS
+pub fn remove_undo_logs(
+  table: &mut Table<'_, '_, u32, &'static [u8]>,
+  heights: impl RangeBounds<u32>,
+) -> Result<()> {
+  for result in table.drain::<u32>(heights)? {
+    result?;
+  }
+  Ok(())
+}
+
+// REORG_UNDO_LOGS
+pub fn get_rolled_back_blocks<T>(table: &T, height: u32) -> Result<Vec<Vec<RolledBackBlock>>>
+where
+  T: ReadableTable<(u32, u64), &'static [u8]>,
+{
+  table
+    .range((height, 0)..=(height, u64::MAX))?
+    .map(|result| {
+      result
+        .map(|(_, v)| rmp_serde::from_slice::<Vec<RolledBackBlock>>(v.value()).unwrap())
+        .map_err(|e| e.into())
+    })
+    .collect()
+}
+
+// REORG_UNDO_LOGS
+pub fn save_rolled_back_blocks(
+  table: &mut Table<'_, '_, (u32, u64), &'static [u8]>,
+  height: u32,
+  blocks: &[RolledBackBlock],
+) -> Result<()> {
+  // reorgs rolled back to the same height are kept in the order they happened.
+  let sequence = table
+    .range((height, 0)..=(height, u64::MAX))?
+    .next_back()
+    .transpose()?
+    .map_or(0, |(key, _)| key.value().1 + 1);
+
+  table.insert(
+    (height, sequence),
+    rmp_serde::to_vec(blocks).unwrap().as_slice(),
+  )?;
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::index::{REORG_UNDO_LOGS, UNDO_LOGS};
+  use bitcoin::BlockHash;
+  use redb::Database;
+  use std::str::FromStr;
+  use tempfile::NamedTempFile;
+
+  fn rolled_back_block(height: u32) -> RolledBackBlock {
+    RolledBackBlock {
+      height,
+      block_hash: BlockHash::from_str(&format!("{height:064x}")).unwrap(),
+      changes: Vec::new(),
+    }
+  }
+
+  #[test]
+  fn test_remove_undo_logs() {
+    let dbfile = NamedTempFile::new().unwrap();
+    let db = Database::create(dbfile.path()).unwrap();
+    let wtx = db.begin_write().unwrap();
+    let mut table = wtx.open_table(UNDO_LOGS).unwrap();
+
+    for height in 0..6 {
+      save_undo_log(&mut table, height, &BlockUndoLog::default()).unwrap();
+    }
+
+    remove_undo_logs(&mut table, ..2).unwrap();
+    remove_undo_logs(&mut table, 5..).unwrap();
+
+    assert_eq!(get_undo_log(&table, 1).unwrap(), None);
+    assert_eq!(
+      get_undo_log(&table, 2).unwrap(),
+      Some(BlockUndoLog::default())
+    );
+    assert_eq!(get_undo_log(&table, 5).unwrap(), None);
+    assert_eq!(table.len().unwrap(), 3);
+  }
+
+  #[test]
+  fn test_rolled_back_blocks_of_repeated_reorgs() {
+    let dbfile = NamedTempFile::new().unwrap();
+    let db = Database::create(dbfile.path()).unwrap();
+    let wtx = db.begin_write().unwrap();
+    let mut table = wtx.open_table(REORG_UNDO_LOGS).unwrap();
+
+    save_rolled_back_blocks(&mut table, 5, &[rolled_back_block(5)]).unwrap();
+    save_rolled_back_blocks(&mut table, 6, &[rolled_back_block(6)]).unwrap();
+    save_rolled_back_blocks(&mut table, 5, &[rolled_back_block(5), rolled_back_block(6)]).unwrap();
+
+    assert_eq!(
+      get_rolled_back_blocks(&table, 5).unwrap(),
+      vec![
+        vec![rolled_back_block(5)],
+        vec![rolled_back_block(5), rolled_back_block(6)]
+      ]
+    );
+    assert_eq!(
+      get_rolled_back_blocks(&table, 6).unwrap(),
+      vec![vec![rolled_back_block(6)]]
+    );
+    assert!(get_rolled_back_blocks(&table, 4).unwrap().is_empty());
+  }
+}
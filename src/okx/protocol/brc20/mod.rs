@@ -14,6 +14,8 @@ mod num;
 mod operation;
 mod params;
 mod policies;
+mod protocol;
+mod simulator;
 
 use self::error::Error;
 pub(crate) use self::{
@@ -21,6 +23,8 @@ pub(crate) use self::{
   msg_executor::{execute, ExecutionMessage},
   num::Num,
   operation::{deserialize_brc20_operation, Deploy, Mint, Operation, Transfer},
+  protocol::Brc20Protocol,
+  simulator::{Simulator, SimulatorState},
 };
 
 #[derive(Debug, Clone, PartialEq)]
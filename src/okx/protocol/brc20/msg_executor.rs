@@ -6,20 +6,21 @@ use crate::{
   okx::{
     datastore::{
       brc20::{
-        BRC20Error, Balance, Brc20Reader, Brc20ReaderWriter, DeployEvent, Event,
-        InscribeTransferEvent, MintEvent, Receipt, Tick, TokenInfo, TransferEvent, TransferableLog,
+        BRC20Error, Balance, Brc20ReaderWriter, DeployEvent, Event, InscribeTransferEvent,
+        MintEvent, Receipt, Tick, TokenInfo, TransferEvent, TransferableLog,
       },
       ord::OrdReader,
     },
     protocol::{
       brc20::{Message, Mint, Operation},
-      context::Context,
+      ChainContext,
     },
   },
   Chain, Result,
 };
 use anyhow::anyhow;
 use bigdecimal::num_bigint::Sign;
+use bitcoin::hashes::Hash;
 use std::str::FromStr;
 
 #[derive(Debug, Clone, PartialEq)]
@@ -35,7 +36,36 @@ pub struct ExecutionMessage {
 }
 
 impl ExecutionMessage {
-  pub fn from_message(context: &mut Context, msg: &Message, chain: Chain) -> Result<Self> {
+  /// A message for an operation that has not been inscribed yet, inscribed by and to the script
+  /// key. The inscription and its location are placeholders.
+  pub(crate) fn pending(op: Operation, script_key: ScriptKey) -> Self {
+    let satpoint = SatPoint {
+      outpoint: bitcoin::OutPoint {
+        txid: Txid::all_zeros(),
+        vout: 0,
+      },
+      offset: 0,
+    };
+
+    Self {
+      txid: satpoint.outpoint.txid,
+      inscription_id: InscriptionId {
+        txid: satpoint.outpoint.txid,
+        index: 0,
+      },
+      inscription_number: 0,
+      old_satpoint: satpoint,
+      new_satpoint: satpoint,
+      from: script_key.clone(),
+      to: Some(script_key),
+      op,
+    }
+  }
+
+  pub fn from_message<C>(context: &mut C, msg: &Message, chain: Chain) -> Result<Self>
+  where
+    C: OrdReader<Error = anyhow::Error>,
+  {
     Ok(Self {
       txid: msg.txid,
       inscription_id: msg.inscription_id,
@@ -55,11 +85,21 @@ impl ExecutionMessage {
   }
 }
 
-pub fn execute(context: &mut Context, msg: &ExecutionMessage) -> Result<Receipt> {
+/// Executes the message against any BRC20 state, the index tables or a simulated overlay.
+pub fn execute<C>(
+  context: &mut C,
+  chain_conf: ChainContext,
+  msg: &ExecutionMessage,
+) -> Result<Receipt>
+where
+  C: Brc20ReaderWriter<Error = anyhow::Error>,
+{
   log::debug!("BRC20 execute message: {:?}", msg);
   let event = match &msg.op {
-    Operation::Deploy(deploy) => process_deploy(context, msg, deploy.clone()),
-    Operation::Mint { mint, parent } => process_mint(context, msg, mint.clone(), *parent),
+    Operation::Deploy(deploy) => process_deploy(context, chain_conf, msg, deploy.clone()),
+    Operation::Mint { mint, parent } => {
+      process_mint(context, chain_conf, msg, mint.clone(), *parent)
+    }
     Operation::InscribeTransfer(transfer) => {
       process_inscribe_transfer(context, msg, transfer.clone())
     }
@@ -86,8 +126,9 @@ pub fn execute(context: &mut Context, msg: &ExecutionMessage) -> Result<Receipt>
   Ok(receipt)
 }
 
-fn process_deploy(
-  context: &mut Context,
+fn process_deploy<C: Brc20ReaderWriter<Error = anyhow::Error>>(
+  context: &mut C,
+  chain_conf: ChainContext,
   msg: &ExecutionMessage,
   deploy: Deploy,
 ) -> Result<Event, Error> {
@@ -101,8 +142,8 @@ fn process_deploy(
   // proposal for issuance self mint token.
   // https://l1f.discourse.group/t/brc-20-proposal-for-issuance-and-burn-enhancements-brc20-ip-1/621
   if tick.self_issuance_tick() {
-    if context.chain_conf.blockheight
-      < policies::HardForks::self_issuance_activation_height(context.chain_conf.chain)
+    if chain_conf.blockheight
+      < policies::HardForks::self_issuance_activation_height(chain_conf.chain)
     {
       return Err(Error::BRC20Error(BRC20Error::SelfIssuanceNotActivated));
     }
@@ -165,9 +206,9 @@ fn process_deploy(
     minted: 0u128,
     deploy_by: to_script_key,
     is_self_mint,
-    deployed_number: context.chain_conf.blockheight,
-    latest_mint_number: context.chain_conf.blockheight,
-    deployed_timestamp: context.chain_conf.blocktime,
+    deployed_number: chain_conf.blockheight,
+    latest_mint_number: chain_conf.blockheight,
+    deployed_timestamp: chain_conf.blocktime,
   };
   context
     .insert_token_info(&tick, &new_info)
@@ -182,8 +223,9 @@ fn process_deploy(
   }))
 }
 
-fn process_mint(
-  context: &mut Context,
+fn process_mint<C: Brc20ReaderWriter<Error = anyhow::Error>>(
+  context: &mut C,
+  chain_conf: ChainContext,
   msg: &ExecutionMessage,
   mint: Mint,
   parent: Option<InscriptionId>,
@@ -263,7 +305,7 @@ fn process_mint(
   // update token minted.
   let minted = minted.checked_add(&amt)?.checked_to_u128()?;
   context
-    .update_mint_token_info(&tick, minted, context.chain_conf.blockheight)
+    .update_mint_token_info(&tick, minted, chain_conf.blockheight)
     .map_err(Error::LedgerError)?;
 
   Ok(Event::Mint(MintEvent {
@@ -273,8 +315,8 @@ fn process_mint(
   }))
 }
 
-fn process_inscribe_transfer(
-  context: &mut Context,
+fn process_inscribe_transfer<C: Brc20ReaderWriter<Error = anyhow::Error>>(
+  context: &mut C,
   msg: &ExecutionMessage,
   transfer: Transfer,
 ) -> Result<Event, Error> {
@@ -345,7 +387,10 @@ fn process_inscribe_transfer(
   }))
 }
 
-fn process_transfer(context: &mut Context, msg: &ExecutionMessage) -> Result<Event, Error> {
+fn process_transfer<C: Brc20ReaderWriter<Error = anyhow::Error>>(
+  context: &mut C,
+  msg: &ExecutionMessage,
+) -> Result<Event, Error> {
   let transferable = context
     .get_transferable_assets_by_satpoint(&msg.old_satpoint)
     .map_err(Error::LedgerError)?
@@ -29,6 +29,26 @@ impl Operation {
       Operation::Transfer(_) => OperationType::Transfer,
     }
   }
+
+  /// The JSON inscribed to perform the operation. Inscribe-transfer and transfer operations share
+  /// the `transfer` JSON.
+  pub(crate) fn to_json(&self) -> String {
+    let raw_operation = match self.clone() {
+      Operation::Deploy(deploy) => RawOperation::Deploy(deploy),
+      Operation::Mint { mint, .. } => RawOperation::Mint(mint),
+      Operation::InscribeTransfer(transfer) | Operation::Transfer(transfer) => {
+        RawOperation::Transfer(transfer)
+      }
+    };
+
+    let mut json = serde_json::Map::new();
+    json.insert("p".into(), json!(PROTOCOL_LITERAL));
+    if let Value::Object(fields) = serde_json::to_value(raw_operation).unwrap() {
+      json.extend(fields);
+    }
+
+    Value::Object(json).to_string()
+  }
 }
 
 #[derive(Debug, PartialEq, Clone, Deserialize, Serialize)]
@@ -100,6 +120,52 @@ mod tests {
   use super::*;
   use crate::okx::datastore::ord::Action;
 
+  #[test]
+  fn operation_to_json_round_trip() {
+    let deploy = Operation::Deploy(Deploy {
+      tick: "ordi".to_string(),
+      max_supply: "21000000".to_string(),
+      mint_limit: Some("1000".to_string()),
+      decimals: None,
+      self_mint: None,
+    });
+
+    assert_eq!(
+      deploy.to_json(),
+      r#"{"p":"brc-20","op":"deploy","tick":"ordi","max":"21000000","lim":"1000"}"#
+    );
+
+    let inscribe_transfer = Operation::InscribeTransfer(Transfer {
+      tick: "ordi".to_string(),
+      amount: "4".to_string(),
+    });
+
+    assert_eq!(
+      inscribe_transfer.to_json(),
+      r#"{"p":"brc-20","op":"transfer","tick":"ordi","amt":"4"}"#
+    );
+
+    let inscription = Inscription::new(
+      Some("text/plain;charset=utf-8".as_bytes().to_vec()),
+      Some(inscribe_transfer.to_json().into_bytes()),
+    );
+
+    assert_eq!(
+      deserialize_brc20_operation(
+        &inscription,
+        &Action::New {
+          cursed: false,
+          unbound: false,
+          inscription: inscription.clone(),
+          vindicated: false,
+          parent: None,
+        },
+      )
+      .unwrap(),
+      inscribe_transfer
+    );
+  }
+
   #[test]
   fn test_deploy_deserialize() {
     let max_supply = "21000000".to_string();
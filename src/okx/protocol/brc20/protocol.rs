@@ -0,0 +1,145 @@
+use {
+  super::*,
+  crate::{
+    index::{entry::Entry, BlockData},
+    okx::{
+      datastore::{
+        brc20::{redb::table::get_transferable_assets_by_outpoint, Brc20ReaderWriter},
+        ord::{collections::CollectionKind, operation::InscriptionOp, OrdReaderWriter},
+      },
+      protocol::{context::Context, message::Message as ProtocolMessage, ChainContext, Protocol},
+    },
+  },
+  anyhow::anyhow,
+  axum::Router,
+  bitcoin::Transaction,
+  std::collections::{HashMap, HashSet},
+};
+
+pub(crate) struct Brc20Protocol {
+  first_height: Option<u32>,
+}
+
+impl Brc20Protocol {
+  pub(crate) fn new(first_height: Option<u32>) -> Self {
+    Self { first_height }
+  }
+}
+
+impl Protocol for Brc20Protocol {
+  fn name(&self) -> &'static str {
+    "brc20"
+  }
+
+  fn is_enabled(&self) -> bool {
+    self.first_height.is_some()
+  }
+
+  fn is_active(&self, chain_conf: &ChainContext) -> bool {
+    self
+      .first_height
+      .is_some_and(|first_height| chain_conf.blockheight >= first_height)
+  }
+
+  fn tables(&self) -> &'static [&'static str] {
+    &[
+      "BRC20_BALANCES",
+      "BRC20_BALANCE_HISTORY",
+      "BRC20_TOKEN",
+      "BRC20_TICK_HOLDERS",
+      "BRC20_EVENTS",
+      "BRC20_BLOCK_CHECKSUMS",
+      "BRC20_SCRIPT_KEY_TO_EVENTS",
+      "BRC20_SATPOINT_TO_TRANSFERABLE_ASSETS",
+      "BRC20_ADDRESS_TICKER_TO_TRANSFERABLE_ASSETS",
+      "BRC20_ADDRESS_INSCRIPTION_NUMBER_TO_TRANSFERABLE_ASSETS",
+      "COLLECTIONS_INSCRIPTION_ID_TO_KINDS",
+      "COLLECTION_KIND_TO_SEQUENCE_NUMBERS",
+      "COLLECTION_KIND_TO_COUNT",
+    ]
+  }
+
+  fn routes(&self) -> Router {
+    crate::subcommand::server::brc20::api_router()
+  }
+
+  fn resolve_message(
+    &self,
+    context: &Context,
+    _tx: &Transaction,
+    operation: &InscriptionOp,
+  ) -> Result<Option<ProtocolMessage>> {
+    let satpoint_to_transfer_assets = get_transferable_assets_by_outpoint(
+      context.BRC20_SATPOINT_TO_TRANSFERABLE_ASSETS,
+      operation.old_satpoint.outpoint,
+    )?
+    .into_iter()
+    .map(|(satpoint, asset)| (satpoint.store(), asset))
+    .collect::<HashMap<_, _>>();
+
+    let Some(msg) = Message::resolve(operation, satpoint_to_transfer_assets)? else {
+      return Ok(None);
+    };
+
+    log::debug!(
+      "BRC20 resolved the message from {:?}, msg {:?}",
+      operation,
+      msg
+    );
+    Ok(Some(ProtocolMessage::BRC20(msg)))
+  }
+
+  fn execute_messages(
+    &self,
+    context: &mut Context,
+    txid: &Txid,
+    messages: &[ProtocolMessage],
+  ) -> Result {
+    let chain_conf = context.chain_conf;
+    let mut receipts = vec![];
+    for message in messages {
+      match message {
+        ProtocolMessage::BRC20(brc20_msg) => {
+          let msg = ExecutionMessage::from_message(context, brc20_msg, chain_conf.chain)?;
+          receipts.push(execute(context, chain_conf, &msg)?);
+        }
+      }
+    }
+
+    context
+      .save_transaction_receipts(txid, &receipts)
+      .map_err(|e| anyhow!("failed to add transaction receipt to state! error: {e}"))?;
+
+    let brc20_inscriptions = receipts
+      .into_iter()
+      .map(|receipt| receipt.inscription_id)
+      .collect::<HashSet<_>>();
+
+    for inscription_id in brc20_inscriptions {
+      context
+        .add_inscription_attributes(&inscription_id, CollectionKind::BRC20)
+        .map_err(|e| anyhow!("failed to add inscription attributes to state! error: {e}"))?;
+    }
+    Ok(())
+  }
+
+  fn skip_transaction(&self, context: &mut Context, txid: &Txid) -> Result {
+    // every transaction with inscription operations has BRC20 receipts, empty while BRC20 isn't
+    // indexed.
+    context
+      .save_transaction_receipts(txid, &[])
+      .map_err(|e| anyhow!("failed to add transaction receipt to state! error: {e}"))
+  }
+
+  fn index_block(
+    &self,
+    context: &mut Context,
+    block: &BlockData,
+    _operations: &HashMap<Txid, Vec<InscriptionOp>>,
+  ) -> Result<u64> {
+    // only called on active blocks, which have a first height.
+    let first_height = self.first_height.unwrap();
+    context.save_brc20_block_checksum(first_height, block.txdata.iter().map(|(_, txid)| txid))?;
+    Ok(0)
+  }
+}
@@ -0,0 +1,330 @@
+use {
+  super::*,
+  crate::{
+    index::{entry::Entry, rtx::Rtx, BITMAP_DISTRICT_BLOCKS, COLLECTIONS_KEY_TO_INSCRIPTION_ID},
+    okx::{
+      datastore::{
+        brc20::{
+          Balance, Brc20Reader, Brc20ReaderWriter, Receipt, Tick, TokenInfo, TransferableLog,
+        },
+        ord::{
+          bitmap::DistrictBlock,
+          redb::table::{get_collection_inscription_id, get_district_block},
+          InscriptionOp, OrdReader,
+        },
+      },
+      protocol::ChainContext,
+    },
+    Chain,
+  },
+  anyhow::anyhow,
+  bitcoin::{OutPoint, TxOut},
+  std::collections::{BTreeMap, HashMap},
+};
+
+/// The changes made by the simulated transactions, kept between simulations on top of the same confirmed state.
+#[derive(Default)]
+pub(crate) struct SimulatorState {
+  balances: HashMap<String, Balance>,
+  token_infos: HashMap<String, TokenInfo>,
+  transferable_assets: BTreeMap<SatPoint, Option<TransferableLog>>,
+  inscription_numbers: HashMap<u32, i32>,
+  tx_outs: HashMap<OutPoint, TxOut>,
+  receipts: Vec<(Txid, Vec<Receipt>)>,
+}
+
+impl SimulatorState {
+  /// The receipts of the simulated transactions in execution order.
+  pub(crate) fn receipts(&self) -> &[(Txid, Vec<Receipt>)] {
+    &self.receipts
+  }
+}
+
+/// Executes the BRC20 messages of unconfirmed transactions on top of the confirmed state.
+///
+/// Reads fall through to the read transaction and every change is kept in memory, so the
+/// confirmed tables are never written.
+pub(crate) struct Simulator<'a, 'db> {
+  chain_conf: ChainContext,
+  rtx: &'a Rtx<'db>,
+  state: SimulatorState,
+}
+
+impl<'a, 'db> Simulator<'a, 'db> {
+  pub(crate) fn new(rtx: &'a Rtx<'db>, chain_conf: ChainContext) -> Self {
+    Self::resume(rtx, chain_conf, SimulatorState::default())
+  }
+
+  /// Continues a simulation on top of the confirmed state it was started on.
+  pub(crate) fn resume(rtx: &'a Rtx<'db>, chain_conf: ChainContext, state: SimulatorState) -> Self {
+    Self {
+      chain_conf,
+      rtx,
+      state,
+    }
+  }
+
+  /// Registers an inscription created by an unconfirmed transaction.
+  pub(crate) fn insert_inscription_number(&mut self, sequence_number: u32, number: i32) {
+    self
+      .state
+      .inscription_numbers
+      .insert(sequence_number, number);
+  }
+
+  /// Registers an output spent or created by an unconfirmed transaction.
+  pub(crate) fn insert_tx_out(&mut self, outpoint: OutPoint, tx_out: TxOut) {
+    self.state.tx_outs.insert(outpoint, tx_out);
+  }
+
+  /// The transferable assets on the output, including the ones inscribed or moved by earlier
+  /// unconfirmed transactions.
+  pub(crate) fn transferable_assets_on_output(
+    &self,
+    outpoint: OutPoint,
+  ) -> crate::Result<BTreeMap<SatPoint, TransferableLog>> {
+    let mut assets = self
+      .rtx
+      .brc20_transferable_assets_on_output_with_satpoints(outpoint)?
+      .into_iter()
+      .collect::<BTreeMap<_, _>>();
+
+    for (satpoint, asset) in &self.state.transferable_assets {
+      if satpoint.outpoint == outpoint {
+        match asset {
+          Some(asset) => assets.insert(*satpoint, asset.clone()),
+          None => assets.remove(satpoint),
+        };
+      }
+    }
+
+    Ok(assets)
+  }
+
+  /// Resolves and executes the BRC20 messages of the inscription operations of the transaction.
+  /// The operations must be in the order of the inputs they spend.
+  pub(crate) fn execute_transaction(
+    &mut self,
+    txid: Txid,
+    operations: &[InscriptionOp],
+  ) -> crate::Result {
+    let chain_conf = self.chain_conf;
+    let mut receipts = Vec::new();
+    for operation in operations {
+      let transfer_assets_cache = self
+        .transferable_assets_on_output(operation.old_satpoint.outpoint)?
+        .into_iter()
+        .map(|(satpoint, asset)| (satpoint.store(), asset))
+        .collect();
+
+      let Some(message) = Message::resolve(operation, transfer_assets_cache)? else {
+        continue;
+      };
+
+      let msg = ExecutionMessage::from_message(self, &message, chain_conf.chain)?;
+      receipts.push(execute(self, chain_conf, &msg)?);
+    }
+
+    if !receipts.is_empty() {
+      self.save_transaction_receipts(&txid, &receipts)?;
+    }
+
+    Ok(())
+  }
+
+  /// Executes an operation about to be inscribed to the script key without recording it, so
+  /// callers can tell whether it would succeed in the next block.
+  pub(crate) fn check_operation(
+    &mut self,
+    op: Operation,
+    script_key: ScriptKey,
+  ) -> crate::Result<Receipt> {
+    let chain_conf = self.chain_conf;
+    execute(self, chain_conf, &ExecutionMessage::pending(op, script_key))
+  }
+
+  pub(crate) fn into_state(self) -> SimulatorState {
+    self.state
+  }
+
+  fn balance_key(script_key: &ScriptKey, tick: &Tick) -> String {
+    format!("{}_{}", script_key, tick.to_lowercase().hex())
+  }
+
+  fn token_info_or_err(&self, tick: &Tick) -> crate::Result<TokenInfo> {
+    self
+      .get_token_info(tick)?
+      .ok_or_else(|| anyhow!("token {} not exist", tick.as_str()))
+  }
+}
+
+impl<'a, 'db> OrdReader for Simulator<'a, 'db> {
+  type Error = anyhow::Error;
+
+  fn get_inscription_number_by_sequence_number(
+    &self,
+    sequence_number: u32,
+  ) -> crate::Result<i32, Self::Error> {
+    if let Some(number) = self.state.inscription_numbers.get(&sequence_number) {
+      return Ok(*number);
+    }
+
+    self
+      .rtx
+      .sequence_number_to_inscription_entry(sequence_number)?
+      .map(|entry| entry.inscription_number)
+      .ok_or_else(|| {
+        anyhow!(
+          "failed to get inscription number! error: sequence number {} not found",
+          sequence_number
+        )
+      })
+  }
+
+  fn get_script_key_on_satpoint(
+    &mut self,
+    satpoint: &SatPoint,
+    chain: Chain,
+  ) -> crate::Result<ScriptKey, Self::Error> {
+    let tx_out = match self.state.tx_outs.get(&satpoint.outpoint) {
+      Some(tx_out) => Some(tx_out.clone()),
+      None => self.rtx.outpoint_to_entry(satpoint.outpoint)?,
+    };
+
+    tx_out
+      .map(|tx_out| ScriptKey::from_script(&tx_out.script_pubkey, chain))
+      .ok_or_else(|| {
+        anyhow!(
+          "failed to get tx out! error: outpoint {} not found",
+          satpoint.outpoint
+        )
+      })
+  }
+
+  fn get_collection_inscription_id(
+    &self,
+    collection_key: &str,
+  ) -> crate::Result<Option<InscriptionId>, Self::Error> {
+    get_collection_inscription_id(
+      &self.rtx.0.open_table(COLLECTIONS_KEY_TO_INSCRIPTION_ID)?,
+      collection_key,
+    )
+  }
+
+  fn get_district_block(&self, height: u32) -> crate::Result<Option<DistrictBlock>, Self::Error> {
+    get_district_block(&self.rtx.0.open_table(BITMAP_DISTRICT_BLOCKS)?, height)
+  }
+}
+
+impl<'a, 'db> Brc20Reader for Simulator<'a, 'db> {
+  type Error = anyhow::Error;
+
+  fn get_balance(
+    &self,
+    script_key: &ScriptKey,
+    tick: &Tick,
+  ) -> crate::Result<Option<Balance>, Self::Error> {
+    match self
+      .state
+      .balances
+      .get(&Self::balance_key(script_key, tick))
+    {
+      Some(balance) => Ok(Some(balance.clone())),
+      None => self
+        .rtx
+        .brc20_get_balance_by_address(tick, script_key.clone()),
+    }
+  }
+
+  fn get_token_info(&self, tick: &Tick) -> crate::Result<Option<TokenInfo>, Self::Error> {
+    match self.state.token_infos.get(&tick.to_lowercase().hex()) {
+      Some(token_info) => Ok(Some(token_info.clone())),
+      None => self.rtx.brc20_get_tick_info(tick),
+    }
+  }
+
+  fn get_transferable_assets_by_satpoint(
+    &self,
+    satpoint: &SatPoint,
+  ) -> crate::Result<Option<TransferableLog>, Self::Error> {
+    Ok(
+      self
+        .transferable_assets_on_output(satpoint.outpoint)?
+        .remove(satpoint),
+    )
+  }
+}
+
+impl<'a, 'db> Brc20ReaderWriter for Simulator<'a, 'db> {
+  fn update_token_balance(
+    &mut self,
+    script_key: &ScriptKey,
+    new_balance: Balance,
+  ) -> crate::Result<(), Self::Error> {
+    self.state.balances.insert(
+      Self::balance_key(script_key, &new_balance.tick),
+      new_balance,
+    );
+    Ok(())
+  }
+
+  fn insert_token_info(
+    &mut self,
+    tick: &Tick,
+    new_info: &TokenInfo,
+  ) -> crate::Result<(), Self::Error> {
+    self
+      .state
+      .token_infos
+      .insert(tick.to_lowercase().hex(), new_info.clone());
+    Ok(())
+  }
+
+  fn update_mint_token_info(
+    &mut self,
+    tick: &Tick,
+    minted_amt: u128,
+    minted_block_number: u32,
+  ) -> crate::Result<(), Self::Error> {
+    let mut info = self.token_info_or_err(tick)?;
+    info.minted = minted_amt;
+    info.latest_mint_number = minted_block_number;
+    self.insert_token_info(tick, &info)
+  }
+
+  fn update_burned_token_info(
+    &mut self,
+    tick: &Tick,
+    burned_amt: u128,
+  ) -> crate::Result<(), Self::Error> {
+    let mut info = self.token_info_or_err(tick)?;
+    info.burned_supply = burned_amt;
+    self.insert_token_info(tick, &info)
+  }
+
+  fn save_transaction_receipts(
+    &mut self,
+    txid: &Txid,
+    receipt: &[Receipt],
+  ) -> crate::Result<(), Self::Error> {
+    self.state.receipts.push((*txid, receipt.to_vec()));
+    Ok(())
+  }
+
+  fn insert_transferable_asset(
+    &mut self,
+    satpoint: SatPoint,
+    transferable_asset: &TransferableLog,
+  ) -> crate::Result<(), Self::Error> {
+    self
+      .state
+      .transferable_assets
+      .insert(satpoint, Some(transferable_asset.clone()));
+    Ok(())
+  }
+
+  fn remove_transferable_asset(&mut self, satpoint: SatPoint) -> crate::Result<(), Self::Error> {
+    self.state.transferable_assets.insert(satpoint, None);
+    Ok(())
+  }
+}
@@ -6,22 +6,28 @@ use crate::{
   okx::{
     datastore::{
       brc20::{
+        block_checksum,
         redb::table::{
-          get_balance, get_token_info, get_transferable_assets_by_satpoint, insert_token_info,
-          insert_transferable_asset, remove_transferable_asset, save_transaction_receipts,
-          update_burned_token_info, update_mint_token_info, update_token_balance,
+          get_balance, get_block_checksum, get_token_info, get_transaction_receipts,
+          get_transferable_assets_by_satpoint, insert_token_info, insert_transferable_asset,
+          remove_transferable_asset, save_balance_history, save_block_checksum,
+          save_transaction_receipts, update_burned_token_info, update_mint_token_info,
+          update_token_balance,
         },
         Balance, Brc20Reader, Brc20ReaderWriter, Receipt, Tick, TokenInfo, TransferableLog,
       },
       ord::{
+        bitmap::DistrictBlock,
         collections::CollectionKind,
         redb::table::{
-          add_inscription_attributes, get_collection_inscription_id,
-          get_inscription_number_by_sequence_number, get_txout_by_outpoint,
-          save_transaction_operations, set_inscription_by_collection_key,
+          add_collection_member, add_inscription_attributes, get_collection_inscription_id,
+          get_district_block, get_inscription_number_by_sequence_number,
+          get_sequence_number_by_inscription_id, get_txout_by_outpoint, increment_collection_count,
+          save_district_block, save_transaction_operations, set_inscription_by_collection_key,
         },
         InscriptionOp, OrdReader, OrdReaderWriter,
       },
+      undo::{Journal, StateChange, UndoLog},
       ScriptKey,
     },
     lru::SimpleLru,
@@ -46,18 +52,101 @@ pub struct Context<'a, 'db, 'txn> {
     &'a mut Table<'db, 'txn, &'static str, InscriptionIdValue>,
   pub(crate) COLLECTIONS_INSCRIPTION_ID_TO_KINDS:
     &'a mut MultimapTable<'db, 'txn, InscriptionIdValue, &'static [u8]>,
+  pub(crate) COLLECTION_KIND_TO_SEQUENCE_NUMBERS:
+    &'a mut MultimapTable<'db, 'txn, &'static str, u32>,
+  pub(crate) COLLECTION_KIND_TO_COUNT: &'a mut Table<'db, 'txn, &'static str, u64>,
+  pub(crate) INSCRIPTION_ID_TO_SEQUENCE_NUMBER: &'a mut Table<'db, 'txn, InscriptionIdValue, u32>,
   pub(crate) SEQUENCE_NUMBER_TO_INSCRIPTION_ENTRY:
     &'a mut Table<'db, 'txn, u32, InscriptionEntryValue>,
   pub(crate) OUTPOINT_TO_ENTRY: &'a mut Table<'db, 'txn, &'static OutPointValue, &'static [u8]>,
+  pub(crate) BITMAP_DISTRICT_BLOCKS: &'a mut Table<'db, 'txn, u32, &'static [u8]>,
 
   // BRC20 tables
   pub(crate) BRC20_BALANCES: &'a mut Table<'db, 'txn, &'static str, &'static [u8]>,
+  pub(crate) BRC20_BALANCE_HISTORY: &'a mut Table<'db, 'txn, &'static str, &'static [u8]>,
   pub(crate) BRC20_TOKEN: &'a mut Table<'db, 'txn, &'static str, &'static [u8]>,
+  pub(crate) BRC20_TICK_HOLDERS: &'a mut Table<'db, 'txn, &'static str, &'static [u8]>,
   pub(crate) BRC20_EVENTS: &'a mut Table<'db, 'txn, &'static TxidValue, &'static [u8]>,
+  pub(crate) BRC20_BLOCK_CHECKSUMS: &'a mut Table<'db, 'txn, u32, &'static [u8; 32]>,
+  pub(crate) BRC20_SCRIPT_KEY_TO_EVENTS: &'a mut Table<'db, 'txn, &'static str, &'static TxidValue>,
   pub(crate) BRC20_SATPOINT_TO_TRANSFERABLE_ASSETS:
     &'a mut Table<'db, 'txn, &'static SatPointValue, &'static [u8]>,
   pub(crate) BRC20_ADDRESS_TICKER_TO_TRANSFERABLE_ASSETS:
     &'a mut MultimapTable<'db, 'txn, &'static str, &'static SatPointValue>,
+  pub(crate) BRC20_ADDRESS_INSCRIPTION_NUMBER_TO_TRANSFERABLE_ASSETS:
+    &'a mut Table<'db, 'txn, &'static str, &'static SatPointValue>,
+
+  // undo
+  pub(crate) journal: &'a Journal,
+  pub(crate) undo_log: UndoLog,
+}
+
+impl<'a, 'db, 'txn> Context<'a, 'db, 'txn> {
+  /// Saves the BRC20 checksum of the block from its receipts and the balances changed so far.
+  ///
+  /// Blocks above `first_height` chain onto the checksum of the previous block, which must have been saved.
+  pub(crate) fn save_brc20_block_checksum<'t>(
+    &mut self,
+    first_height: u32,
+    txids: impl Iterator<Item = &'t Txid>,
+  ) -> crate::Result {
+    let height = self.chain_conf.blockheight;
+
+    let previous = if height > first_height {
+      Some(
+        get_block_checksum(self.BRC20_BLOCK_CHECKSUMS, height - 1)?
+          .ok_or_else(|| anyhow!("BRC20 checksum of block {} is missing", height - 1))?,
+      )
+    } else {
+      None
+    };
+
+    let mut receipts = Vec::new();
+    for txid in txids {
+      if let Some(tx_receipts) = get_transaction_receipts(self.BRC20_EVENTS, txid)? {
+        receipts.push((*txid, tx_receipts));
+      }
+    }
+
+    save_block_checksum(
+      self.journal,
+      self.BRC20_BLOCK_CHECKSUMS,
+      height,
+      &block_checksum(
+        previous,
+        height,
+        &receipts,
+        &self.undo_log.changed_balances(),
+      ),
+    )
+  }
+
+  fn record_token_change(
+    &mut self,
+    tick: &Tick,
+    old: Option<TokenInfo>,
+  ) -> crate::Result<(), anyhow::Error> {
+    let new = get_token_info(self.BRC20_TOKEN, tick)?;
+    self.undo_log.record(StateChange::Token {
+      tick: tick.clone(),
+      old,
+      new,
+    });
+    Ok(())
+  }
+
+  fn record_transferable_asset_change(
+    &mut self,
+    satpoint: SatPoint,
+    old: Option<TransferableLog>,
+  ) -> crate::Result<(), anyhow::Error> {
+    let new =
+      get_transferable_assets_by_satpoint(self.BRC20_SATPOINT_TO_TRANSFERABLE_ASSETS, &satpoint)?;
+    self
+      .undo_log
+      .record(StateChange::TransferableAsset { satpoint, old, new });
+    Ok(())
+  }
 }
 
 impl<'a, 'db, 'txn> OrdReader for Context<'a, 'db, 'txn> {
@@ -118,6 +207,10 @@ impl<'a, 'db, 'txn> OrdReader for Context<'a, 'db, 'txn> {
   ) -> crate::Result<Option<InscriptionId>, Self::Error> {
     get_collection_inscription_id(self.COLLECTIONS_KEY_TO_INSCRIPTION_ID, collection_key)
   }
+
+  fn get_district_block(&self, height: u32) -> crate::Result<Option<DistrictBlock>, Self::Error> {
+    get_district_block(self.BITMAP_DISTRICT_BLOCKS, height)
+  }
 }
 
 impl<'a, 'db, 'txn> OrdReaderWriter for Context<'a, 'db, 'txn> {
@@ -126,7 +219,7 @@ impl<'a, 'db, 'txn> OrdReaderWriter for Context<'a, 'db, 'txn> {
     txid: &Txid,
     operations: &[InscriptionOp],
   ) -> crate::Result<(), Self::Error> {
-    save_transaction_operations(self.ORD_TX_TO_OPERATIONS, txid, operations)
+    save_transaction_operations(self.journal, self.ORD_TX_TO_OPERATIONS, txid, operations)
   }
 
   fn set_inscription_by_collection_key(
@@ -134,7 +227,19 @@ impl<'a, 'db, 'txn> OrdReaderWriter for Context<'a, 'db, 'txn> {
     key: &str,
     inscription_id: &InscriptionId,
   ) -> crate::Result<(), Self::Error> {
-    set_inscription_by_collection_key(self.COLLECTIONS_KEY_TO_INSCRIPTION_ID, key, inscription_id)
+    let old = get_collection_inscription_id(self.COLLECTIONS_KEY_TO_INSCRIPTION_ID, key)?;
+    set_inscription_by_collection_key(
+      self.journal,
+      self.COLLECTIONS_KEY_TO_INSCRIPTION_ID,
+      key,
+      inscription_id,
+    )?;
+    self.undo_log.record(StateChange::CollectionInscription {
+      key: key.to_string(),
+      old,
+      new: Some(*inscription_id),
+    });
+    Ok(())
   }
 
   fn add_inscription_attributes(
@@ -142,11 +247,39 @@ impl<'a, 'db, 'txn> OrdReaderWriter for Context<'a, 'db, 'txn> {
     inscription_id: &InscriptionId,
     kind: CollectionKind,
   ) -> crate::Result<(), Self::Error> {
-    add_inscription_attributes(
+    let kind_name = kind.to_string();
+    if add_inscription_attributes(
+      self.journal,
       self.COLLECTIONS_INSCRIPTION_ID_TO_KINDS,
       inscription_id,
       kind,
-    )
+    )? {
+      return Ok(());
+    }
+
+    let sequence_number = get_sequence_number_by_inscription_id(
+      self.INSCRIPTION_ID_TO_SEQUENCE_NUMBER,
+      inscription_id,
+    )?
+    .ok_or(anyhow!(
+      "failed to get sequence number! error: inscription id {} not found",
+      inscription_id
+    ))?;
+    add_collection_member(
+      self.journal,
+      self.COLLECTION_KIND_TO_SEQUENCE_NUMBERS,
+      &kind_name,
+      sequence_number,
+    )?;
+    increment_collection_count(self.journal, self.COLLECTION_KIND_TO_COUNT, &kind_name)
+  }
+
+  fn save_district_block(
+    &mut self,
+    height: u32,
+    block: &DistrictBlock,
+  ) -> crate::Result<(), Self::Error> {
+    save_district_block(self.journal, self.BITMAP_DISTRICT_BLOCKS, height, block)
   }
 }
 
@@ -225,7 +358,27 @@ impl<'a, 'db, 'txn> Brc20ReaderWriter for Context<'a, 'db, 'txn> {
     script_key: &ScriptKey,
     new_balance: Balance,
   ) -> crate::Result<(), Self::Error> {
-    update_token_balance(self.BRC20_BALANCES, script_key, new_balance)
+    save_balance_history(
+      self.journal,
+      self.BRC20_BALANCE_HISTORY,
+      script_key,
+      self.chain_conf.blockheight,
+      &new_balance,
+    )?;
+    let old = get_balance(self.BRC20_BALANCES, script_key, &new_balance.tick)?;
+    self.undo_log.record(StateChange::Balance {
+      script_key: script_key.clone(),
+      tick: new_balance.tick.clone(),
+      old,
+      new: Some(new_balance.clone()),
+    });
+    update_token_balance(
+      self.journal,
+      self.BRC20_BALANCES,
+      self.BRC20_TICK_HOLDERS,
+      script_key,
+      new_balance,
+    )
   }
 
   fn insert_token_info(
@@ -233,7 +386,9 @@ impl<'a, 'db, 'txn> Brc20ReaderWriter for Context<'a, 'db, 'txn> {
     tick: &Tick,
     new_info: &TokenInfo,
   ) -> crate::Result<(), Self::Error> {
-    insert_token_info(self.BRC20_TOKEN, tick, new_info)
+    let old = get_token_info(self.BRC20_TOKEN, tick)?;
+    insert_token_info(self.journal, self.BRC20_TOKEN, tick, new_info)?;
+    self.record_token_change(tick, old)
   }
 
   fn update_mint_token_info(
@@ -242,7 +397,15 @@ impl<'a, 'db, 'txn> Brc20ReaderWriter for Context<'a, 'db, 'txn> {
     minted_amt: u128,
     minted_block_number: u32,
   ) -> crate::Result<(), Self::Error> {
-    update_mint_token_info(self.BRC20_TOKEN, tick, minted_amt, minted_block_number)
+    let old = get_token_info(self.BRC20_TOKEN, tick)?;
+    update_mint_token_info(
+      self.journal,
+      self.BRC20_TOKEN,
+      tick,
+      minted_amt,
+      minted_block_number,
+    )?;
+    self.record_token_change(tick, old)
   }
 
   fn update_burned_token_info(
@@ -250,7 +413,9 @@ impl<'a, 'db, 'txn> Brc20ReaderWriter for Context<'a, 'db, 'txn> {
     tick: &Tick,
     burned_amt: u128,
   ) -> crate::Result<(), Self::Error> {
-    update_burned_token_info(self.BRC20_TOKEN, tick, burned_amt)
+    let old = get_token_info(self.BRC20_TOKEN, tick)?;
+    update_burned_token_info(self.journal, self.BRC20_TOKEN, tick, burned_amt)?;
+    self.record_token_change(tick, old)
   }
 
   fn save_transaction_receipts(
@@ -258,7 +423,14 @@ impl<'a, 'db, 'txn> Brc20ReaderWriter for Context<'a, 'db, 'txn> {
     txid: &Txid,
     receipt: &[Receipt],
   ) -> crate::Result<(), Self::Error> {
-    save_transaction_receipts(self.BRC20_EVENTS, txid, receipt)
+    save_transaction_receipts(
+      self.journal,
+      self.BRC20_EVENTS,
+      self.BRC20_SCRIPT_KEY_TO_EVENTS,
+      self.chain_conf.blockheight,
+      txid,
+      receipt,
+    )
   }
 
   fn insert_transferable_asset(
@@ -266,19 +438,29 @@ impl<'a, 'db, 'txn> Brc20ReaderWriter for Context<'a, 'db, 'txn> {
     satpoint: SatPoint,
     transferable_asset: &TransferableLog,
   ) -> crate::Result<(), Self::Error> {
+    let old =
+      get_transferable_assets_by_satpoint(self.BRC20_SATPOINT_TO_TRANSFERABLE_ASSETS, &satpoint)?;
     insert_transferable_asset(
+      self.journal,
       self.BRC20_SATPOINT_TO_TRANSFERABLE_ASSETS,
       self.BRC20_ADDRESS_TICKER_TO_TRANSFERABLE_ASSETS,
+      self.BRC20_ADDRESS_INSCRIPTION_NUMBER_TO_TRANSFERABLE_ASSETS,
       satpoint,
       transferable_asset,
-    )
+    )?;
+    self.record_transferable_asset_change(satpoint, old)
   }
 
   fn remove_transferable_asset(&mut self, satpoint: SatPoint) -> crate::Result<(), Self::Error> {
+    let old =
+      get_transferable_assets_by_satpoint(self.BRC20_SATPOINT_TO_TRANSFERABLE_ASSETS, &satpoint)?;
     remove_transferable_asset(
+      self.journal,
       self.BRC20_SATPOINT_TO_TRANSFERABLE_ASSETS,
       self.BRC20_ADDRESS_TICKER_TO_TRANSFERABLE_ASSETS,
+      self.BRC20_ADDRESS_INSCRIPTION_NUMBER_TO_TRANSFERABLE_ASSETS,
       satpoint,
-    )
+    )?;
+    self.record_transferable_asset_change(satpoint, old)
   }
 }
@@ -4,13 +4,19 @@ pub(crate) mod execute_manager;
 pub(crate) mod message;
 pub(crate) mod ord;
 pub(crate) mod protocol_manager;
+pub(crate) mod registry;
 pub(crate) mod resolve_manager;
 
 pub use self::protocol_manager::ProtocolManager;
 
 use {
-  self::{execute_manager::CallManager, message::Message, resolve_manager::MsgResolveManager},
-  crate::{Chain, Options},
+  self::{
+    execute_manager::CallManager,
+    message::Message,
+    registry::{Protocol, ProtocolRegistry},
+    resolve_manager::MsgResolveManager,
+  },
+  crate::{okx::datastore::ord::btc_name::DomainConfig, Chain, Options, Result},
 };
 
 #[derive(Debug, Copy, Clone, PartialEq)]
@@ -26,11 +32,15 @@ pub struct ProtocolConfig {
   enable_ord_receipts: bool,
   enable_index_bitmap: bool,
   enable_index_domain: bool,
+  domain_config: DomainConfig,
 }
 
 impl ProtocolConfig {
-  pub(crate) fn new_with_options(options: &Options) -> Self {
-    Self {
+  pub(crate) fn new_with_options(options: &Options) -> Result<Self> {
+    let domain_config = options.load_config()?.domains;
+    domain_config.check()?;
+
+    Ok(Self {
       first_inscription_height: options.first_inscription_height(),
       first_brc20_height: if options.enable_index_brc20 {
         Some(options.first_brc20_height())
@@ -40,6 +50,11 @@ impl ProtocolConfig {
       enable_ord_receipts: options.enable_save_ord_receipts,
       enable_index_bitmap: options.enable_index_bitmap,
       enable_index_domain: options.enable_index_domain,
-    }
+      domain_config,
+    })
+  }
+
+  pub(crate) fn domain_config(&self) -> &DomainConfig {
+    &self.domain_config
   }
 }
@@ -1,19 +1,78 @@
 use crate::okx::datastore::ord::{OrdReader, OrdReaderWriter};
-use crate::okx::protocol::context::Context;
+use crate::okx::protocol::{context::Context, ChainContext, Protocol};
 use {
   crate::{
+    index::BlockData,
     okx::datastore::ord::{
-      bitmap::District,
+      bitmap::{District, DistrictBlock, Parcel},
       collections::CollectionKind,
       operation::{Action, InscriptionOp},
     },
     Inscription, InscriptionId, Result,
   },
   anyhow::anyhow,
+  axum::Router,
   bitcoin::Txid,
   std::collections::HashMap,
 };
 
+/// Indexes the first valid inscription of each bitmap district and parcel into the collections.
+pub(crate) struct BitmapProtocol {
+  enabled: bool,
+}
+
+impl BitmapProtocol {
+  pub(crate) fn new(enabled: bool) -> Self {
+    Self { enabled }
+  }
+}
+
+impl Protocol for BitmapProtocol {
+  fn name(&self) -> &'static str {
+    "bitmap"
+  }
+
+  fn is_enabled(&self) -> bool {
+    self.enabled
+  }
+
+  fn is_active(&self, _chain_conf: &ChainContext) -> bool {
+    self.enabled
+  }
+
+  fn tables(&self) -> &'static [&'static str] {
+    &[
+      "COLLECTIONS_KEY_TO_INSCRIPTION_ID",
+      "COLLECTIONS_INSCRIPTION_ID_TO_KINDS",
+      "COLLECTION_KIND_TO_SEQUENCE_NUMBERS",
+      "COLLECTION_KIND_TO_COUNT",
+      "BITMAP_DISTRICT_BLOCKS",
+    ]
+  }
+
+  fn routes(&self) -> Router {
+    crate::subcommand::server::ord::bitmap_api_router()
+  }
+
+  fn index_block(
+    &self,
+    context: &mut Context,
+    block: &BlockData,
+    operations: &HashMap<Txid, Vec<InscriptionOp>>,
+  ) -> Result<u64> {
+    // record the block first, so parcels of the district of this very block can be validated.
+    context.save_district_block(
+      context.chain_conf.blockheight,
+      &DistrictBlock {
+        tx_count: block.tx_count,
+        size: block.size,
+      },
+    )?;
+
+    index_bitmap(context, operations)
+  }
+}
+
 pub fn index_bitmap(
   context: &mut Context,
   operations: &HashMap<Txid, Vec<InscriptionOp>>,
@@ -35,14 +94,26 @@ pub fn index_bitmap(
 
   for op in positive_inscriptions.into_iter() {
     match op.action {
-      Action::New { inscription, .. } => {
+      Action::New {
+        inscription,
+        parent,
+        ..
+      } => {
         if let Some((inscription_id, district)) =
-          index_district(context, inscription, op.inscription_id)?
+          index_district(context, &inscription, op.inscription_id)?
         {
           let key = district.to_collection_key();
           context.set_inscription_by_collection_key(&key, &inscription_id)?;
           context.add_inscription_attributes(&inscription_id, CollectionKind::BitMap)?;
 
+          count += 1;
+        } else if let Some((inscription_id, parcel)) =
+          index_parcel(context, &inscription, parent, op.inscription_id)?
+        {
+          let key = parcel.to_collection_key();
+          context.set_inscription_by_collection_key(&key, &inscription_id)?;
+          context.add_inscription_attributes(&inscription_id, CollectionKind::BitMapParcel)?;
+
           count += 1;
         }
       }
@@ -54,7 +125,7 @@ pub fn index_bitmap(
 
 fn index_district(
   context: &mut Context,
-  inscription: Inscription,
+  inscription: &Inscription,
   inscription_id: InscriptionId,
 ) -> Result<Option<(InscriptionId, District)>> {
   if let Some(content) = inscription.body() {
@@ -89,3 +160,59 @@ fn index_district(
   }
   Ok(None)
 }
+
+/// Parcels are children of their district inscription, with one parcel per transaction of the district block.
+fn index_parcel(
+  context: &mut Context,
+  inscription: &Inscription,
+  parent: Option<InscriptionId>,
+  inscription_id: InscriptionId,
+) -> Result<Option<(InscriptionId, Parcel)>> {
+  let Some(content) = inscription.body() else {
+    return Ok(None);
+  };
+  let Ok(parcel) = Parcel::parse(content) else {
+    return Ok(None);
+  };
+
+  let district_key = District {
+    number: parcel.district,
+  }
+  .to_collection_key();
+  let district_inscription_id = context
+    .get_collection_inscription_id(&district_key)
+    .map_err(|e| anyhow!("failed to get collection inscription! key: {district_key} error: {e}"))?;
+  if district_inscription_id.is_none() || district_inscription_id != parent {
+    return Ok(None);
+  }
+
+  let Some(district_block) = context.get_district_block(parcel.district)? else {
+    return Ok(None);
+  };
+  if parcel.number >= district_block.tx_count {
+    return Ok(None);
+  }
+
+  let collection_key = parcel.to_collection_key();
+  if context
+    .get_collection_inscription_id(&collection_key)
+    .map_err(|e| anyhow!("failed to get collection inscription! key: {collection_key} error: {e}"))?
+    .is_some()
+  {
+    log::info!(
+      "duplicate parcel! number: {}.{} inscription_id {}",
+      parcel.number,
+      parcel.district,
+      inscription_id,
+    );
+    return Ok(None);
+  }
+
+  log::info!(
+    "found valid parcel! number: {}.{} inscription_id {}",
+    parcel.number,
+    parcel.district,
+    inscription_id,
+  );
+  Ok(Some((inscription_id, parcel)))
+}
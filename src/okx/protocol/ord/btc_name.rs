@@ -1,20 +1,71 @@
 use crate::okx::datastore::ord::{OrdReader, OrdReaderWriter};
-use crate::okx::protocol::context::Context;
+use crate::okx::protocol::{context::Context, ChainContext, Protocol};
 use {
   crate::{
+    index::BlockData,
     okx::datastore::ord::{
-      btc_name::BtcDomain,
+      btc_name::{BtcDomain, DomainConfig},
       operation::{Action, InscriptionOp},
     },
     Inscription, InscriptionId, Result,
   },
   anyhow::anyhow,
+  axum::Router,
   bitcoin::Txid,
   std::collections::HashMap,
 };
 
+/// Indexes the first valid inscription of each btc domain into the collections.
+pub(crate) struct BtcDomainProtocol {
+  enabled: bool,
+  config: DomainConfig,
+}
+
+impl BtcDomainProtocol {
+  pub(crate) fn new(enabled: bool, config: DomainConfig) -> Self {
+    Self { enabled, config }
+  }
+}
+
+impl Protocol for BtcDomainProtocol {
+  fn name(&self) -> &'static str {
+    "btc_domain"
+  }
+
+  fn is_enabled(&self) -> bool {
+    self.enabled
+  }
+
+  fn is_active(&self, _chain_conf: &ChainContext) -> bool {
+    self.enabled
+  }
+
+  fn tables(&self) -> &'static [&'static str] {
+    &[
+      "COLLECTIONS_KEY_TO_INSCRIPTION_ID",
+      "COLLECTIONS_INSCRIPTION_ID_TO_KINDS",
+      "COLLECTION_KIND_TO_SEQUENCE_NUMBERS",
+      "COLLECTION_KIND_TO_COUNT",
+    ]
+  }
+
+  fn routes(&self) -> Router {
+    crate::subcommand::server::ord::btc_domain_api_router()
+  }
+
+  fn index_block(
+    &self,
+    context: &mut Context,
+    _block: &BlockData,
+    operations: &HashMap<Txid, Vec<InscriptionOp>>,
+  ) -> Result<u64> {
+    index_btc_domain(context, &self.config, operations)
+  }
+}
+
 pub fn index_btc_domain(
   context: &mut Context,
+  config: &DomainConfig,
   operations: &HashMap<Txid, Vec<InscriptionOp>>,
 ) -> Result<u64> {
   let mut count = 0;
@@ -36,7 +87,7 @@ pub fn index_btc_domain(
     match op.action {
       Action::New { inscription, .. } => {
         if let Some((inscription_id, btc_domain)) =
-          do_index_btc_domain(context, inscription, op.inscription_id)?
+          do_index_btc_domain(context, config, inscription, op.inscription_id)?
         {
           let key = btc_domain.to_collection_key();
           context.set_inscription_by_collection_key(&key, &inscription_id)?;
@@ -52,11 +103,12 @@ pub fn index_btc_domain(
 
 fn do_index_btc_domain(
   context: &mut Context,
+  config: &DomainConfig,
   inscription: Inscription,
   inscription_id: InscriptionId,
 ) -> Result<Option<(InscriptionId, BtcDomain)>> {
   if let Some(content) = inscription.body() {
-    if let Ok(btc_name) = BtcDomain::parse(content) {
+    if let Some(btc_name) = BtcDomain::resolve(content, config, context.chain_conf.blockheight) {
       let collection_key = btc_name.to_collection_key();
 
       if context
@@ -2,28 +2,22 @@ use crate::okx::datastore::ord::OrdReaderWriter;
 use crate::okx::protocol::context::Context;
 use {
   super::*,
-  crate::{
-    index::BlockData,
-    okx::{datastore::ord::operation::InscriptionOp, protocol::ord as ord_proto},
-    Instant, Result,
-  },
+  crate::{index::BlockData, okx::datastore::ord::operation::InscriptionOp, Instant, Result},
   bitcoin::Txid,
   std::collections::HashMap,
 };
 
 pub struct ProtocolManager {
   config: ProtocolConfig,
-  call_man: CallManager,
-  resolve_man: MsgResolveManager,
+  registry: ProtocolRegistry,
 }
 
 impl ProtocolManager {
   // Need three datastore, and they're all in the same write transaction.
   pub fn new(config: ProtocolConfig) -> Self {
     Self {
-      config: config.clone(),
-      call_man: CallManager::new(),
-      resolve_man: MsgResolveManager::new(config),
+      registry: ProtocolRegistry::new(&config),
+      config,
     }
   }
 
@@ -34,6 +28,8 @@ impl ProtocolManager {
     operations: HashMap<Txid, Vec<InscriptionOp>>,
   ) -> Result {
     let start = Instant::now();
+    let resolve_man = MsgResolveManager::new(&self.registry);
+    let call_man = CallManager::new(&self.registry);
     let mut inscriptions_size = 0;
     let mut messages_size = 0;
     let mut cost1 = 0u128;
@@ -64,36 +60,31 @@ impl ProtocolManager {
 
         let start = Instant::now();
         // Resolve and execute messages.
-        let messages = self
-          .resolve_man
-          .resolve_message(context, tx, tx_operations)?;
+        let messages = resolve_man.resolve_message(context, tx, tx_operations)?;
         cost2 += start.elapsed().as_micros();
 
         let start = Instant::now();
-        self.call_man.execute_message(context, txid, &messages)?;
+        call_man.execute_message(context, txid, &messages)?;
         cost3 += start.elapsed().as_micros();
         messages_size += messages.len();
       }
     }
 
-    let bitmap_start = Instant::now();
-    let mut bitmap_count = 0;
-    let mut btc_domain_count = 0;
-    if self.config.enable_index_bitmap {
-      bitmap_count = ord_proto::bitmap::index_bitmap(context, &operations)?;
+    let block_start = Instant::now();
+    let mut block_counts = Vec::new();
+    let chain_conf = context.chain_conf;
+    for protocol in self.registry.active(&chain_conf) {
+      let count = protocol.index_block(context, block, &operations)?;
+      block_counts.push(format!("{} {count}", protocol.name()));
     }
-    if self.config.enable_index_domain {
-      btc_domain_count = ord_proto::btc_name::index_btc_domain(context, &operations)?;
-    }
-    let cost4 = bitmap_start.elapsed().as_millis();
+    let cost4 = block_start.elapsed().as_millis();
 
     log::info!(
-      "Protocol Manager indexed block {} with ord inscriptions {}, messages {}, bitmap {}, btc domain {}, in {} ms, {}/{}/{}/{}",
+      "Protocol Manager indexed block {} with ord inscriptions {}, messages {}, block entries [{}], in {} ms, {}/{}/{}/{}",
       context.chain_conf.blockheight,
       inscriptions_size,
       messages_size,
-      bitmap_count,
-      btc_domain_count,
+      block_counts.join(", "),
       start.elapsed().as_millis(),
       cost1/1000,
       cost2/1000,
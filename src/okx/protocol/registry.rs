@@ -0,0 +1,165 @@
+use {
+  super::*,
+  crate::{
+    index::BlockData,
+    okx::{
+      datastore::ord::operation::InscriptionOp,
+      protocol::{brc20::Brc20Protocol, context::Context, ord as ord_proto},
+    },
+    Result,
+  },
+  axum::Router,
+  bitcoin::{Transaction, Txid},
+  std::collections::HashMap,
+};
+
+/// An inscription metaprotocol indexed alongside ord.
+///
+/// Protocols resolve messages from the inscription operations of each transaction and execute them
+/// against the tables they own. Hooks that need the whole block run after all of its transactions.
+///
+/// The messages are variants of [`Message`] and the tables are reached through the fields of
+/// [`Context`], which the updater opens from its write transaction so every write is journaled. A
+/// new protocol adds its variant and tables there, the updater and the API server pick it up from
+/// the registry.
+pub(crate) trait Protocol {
+  fn name(&self) -> &'static str;
+
+  /// Whether the configuration enables indexing the protocol. The routes of disabled protocols
+  /// are still served.
+  fn is_enabled(&self) -> bool;
+
+  /// Whether the protocol indexes the block being processed.
+  fn is_active(&self, chain_conf: &ChainContext) -> bool;
+
+  /// The names of the tables written by the protocol.
+  fn tables(&self) -> &'static [&'static str];
+
+  /// The JSON API routes of the protocol, served under `/api/v1` without the server state.
+  fn routes(&self) -> Router {
+    Router::new()
+  }
+
+  /// Resolves the message of an inscription operation of the transaction, if it belongs to the protocol.
+  fn resolve_message(
+    &self,
+    _context: &Context,
+    _tx: &Transaction,
+    _operation: &InscriptionOp,
+  ) -> Result<Option<Message>> {
+    Ok(None)
+  }
+
+  /// Executes the resolved messages of a transaction with inscription operations.
+  fn execute_messages(
+    &self,
+    _context: &mut Context,
+    _txid: &Txid,
+    _messages: &[Message],
+  ) -> Result {
+    Ok(())
+  }
+
+  /// Called instead of `execute_messages` for a transaction with inscription operations in a block
+  /// the protocol doesn't index.
+  fn skip_transaction(&self, _context: &mut Context, _txid: &Txid) -> Result {
+    Ok(())
+  }
+
+  /// Indexes the block after its transactions were executed, returning the number of indexed entries.
+  fn index_block(
+    &self,
+    _context: &mut Context,
+    _block: &BlockData,
+    _operations: &HashMap<Txid, Vec<InscriptionOp>>,
+  ) -> Result<u64> {
+    Ok(0)
+  }
+}
+
+/// The known protocols, configured by `ProtocolConfig`, in the order they index each transaction.
+pub(crate) struct ProtocolRegistry {
+  protocols: Vec<Box<dyn Protocol>>,
+}
+
+impl ProtocolRegistry {
+  pub(crate) fn new(config: &ProtocolConfig) -> Self {
+    Self {
+      protocols: vec![
+        Box::new(Brc20Protocol::new(config.first_brc20_height)),
+        Box::new(ord_proto::bitmap::BitmapProtocol::new(
+          config.enable_index_bitmap,
+        )),
+        Box::new(ord_proto::btc_name::BtcDomainProtocol::new(
+          config.enable_index_domain,
+          config.domain_config.clone(),
+        )),
+      ],
+    }
+  }
+
+  pub(crate) fn protocols(&self) -> impl Iterator<Item = &dyn Protocol> {
+    self.protocols.iter().map(|protocol| protocol.as_ref())
+  }
+
+  pub(crate) fn enabled(&self) -> impl Iterator<Item = &dyn Protocol> {
+    self.protocols().filter(|protocol| protocol.is_enabled())
+  }
+
+  /// The JSON API routes of the known protocols.
+  pub(crate) fn routes(&self) -> Router {
+    self.protocols().fold(Router::new(), |router, protocol| {
+      router.merge(protocol.routes())
+    })
+  }
+
+  pub(crate) fn active<'a>(
+    &'a self,
+    chain_conf: &'a ChainContext,
+  ) -> impl Iterator<Item = &'a dyn Protocol> {
+    self
+      .protocols()
+      .filter(move |protocol| protocol.is_active(chain_conf))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn registry_follows_protocol_config() {
+    let config = ProtocolConfig {
+      first_inscription_height: 0,
+      first_brc20_height: Some(10),
+      enable_ord_receipts: true,
+      enable_index_bitmap: false,
+      enable_index_domain: true,
+      domain_config: Default::default(),
+    };
+
+    let registry = ProtocolRegistry::new(&config);
+    assert_eq!(
+      registry
+        .enabled()
+        .map(|protocol| protocol.name())
+        .collect::<Vec<_>>(),
+      vec!["brc20", "btc_domain"]
+    );
+    assert_eq!(registry.protocols().count(), 3);
+
+    let chain_conf = |blockheight| ChainContext {
+      chain: Chain::Regtest,
+      blockheight,
+      blocktime: 0,
+    };
+    assert_eq!(
+      registry
+        .active(&chain_conf(9))
+        .map(|protocol| protocol.name())
+        .collect::<Vec<_>>(),
+      vec!["btc_domain"]
+    );
+    assert_eq!(registry.active(&chain_conf(10)).count(), 2);
+  }
+}
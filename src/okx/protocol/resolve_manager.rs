@@ -1,27 +1,22 @@
 use {
   super::*,
   crate::{
-    index::entry::{Entry, SatPointValue},
     okx::{
-      datastore::{
-        brc20::{redb::table::get_transferable_assets_by_outpoint, TransferableLog},
-        ord::operation::InscriptionOp,
-      },
+      datastore::ord::operation::InscriptionOp,
       protocol::{context::Context, Message},
     },
     Result,
   },
   bitcoin::Transaction,
-  std::collections::HashMap,
 };
 
-pub struct MsgResolveManager {
-  config: ProtocolConfig,
+pub struct MsgResolveManager<'a> {
+  registry: &'a ProtocolRegistry,
 }
 
-impl MsgResolveManager {
-  pub fn new(config: ProtocolConfig) -> Self {
-    Self { config }
+impl<'a> MsgResolveManager<'a> {
+  pub fn new(registry: &'a ProtocolRegistry) -> Self {
+    Self { registry }
   }
 
   pub fn resolve_message(
@@ -48,30 +43,11 @@ impl MsgResolveManager {
         }
         let operation = operation_iter.next().unwrap();
 
-        // Parse BRC20 message through inscription operation.
-        if self
-          .config
-          .first_brc20_height
-          .map(|height| context.chain_conf.blockheight >= height)
-          .unwrap_or(false)
-        {
-          let satpoint_to_transfer_assets: HashMap<SatPointValue, TransferableLog> =
-            get_transferable_assets_by_outpoint(
-              context.BRC20_SATPOINT_TO_TRANSFERABLE_ASSETS,
-              input.previous_output,
-            )?
-            .into_iter()
-            .map(|(satpoint, asset)| (satpoint.store(), asset))
-            .collect();
-
-          if let Some(msg) = brc20::Message::resolve(operation, satpoint_to_transfer_assets)? {
-            log::debug!(
-              "BRC20 resolved the message from {:?}, msg {:?}",
-              operation,
-              msg
-            );
-            messages.push(Message::BRC20(msg));
-            continue;
+        // An operation carries at most one message, resolved by the first protocol claiming it.
+        for protocol in self.registry.active(&context.chain_conf) {
+          if let Some(msg) = protocol.resolve_message(context, tx, operation)? {
+            messages.push(msg);
+            break;
           }
         }
       }
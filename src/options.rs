@@ -51,6 +51,11 @@ pub struct Options {
   pub(crate) first_inscription_height: Option<u32>,
   #[arg(long, help = "Limit index to <HEIGHT_LIMIT> blocks.")]
   pub(crate) height_limit: Option<u32>,
+  #[arg(
+    long,
+    help = "Keep the undo logs to recover from reorgs up to <MAX_REORG_DEPTH> blocks deep. Default 10."
+  )]
+  pub(crate) max_reorg_depth: Option<u32>,
   #[arg(long, help = "Use index at <INDEX>.")]
   pub(crate) index: Option<PathBuf>,
   #[arg(
@@ -58,6 +63,11 @@ pub struct Options {
     help = "Track location of runes. RUNES ARE IN AN UNFINISHED PRE-ALPHA STATE AND SUBJECT TO CHANGE AT ANY TIME."
   )]
   pub(crate) index_runes: bool,
+  #[arg(
+    long,
+    help = "Track the unspent outputs and the outputs holding inscriptions of every address."
+  )]
+  pub(crate) index_addresses: bool,
   #[arg(long, help = "Track location of all satoshis.")]
   pub(crate) index_sats: bool,
   #[arg(long, help = "Store transactions in index.")]
@@ -91,6 +101,12 @@ pub struct Options {
     help = "Don't look for BRC20 messages below <FIRST_BRC20_HEIGHT>."
   )]
   pub(crate) first_brc20_height: Option<u32>,
+  #[arg(
+    long,
+    requires = "enable_index_brc20",
+    help = "Simulate the BRC20 messages of mempool transactions and serve them as pending operations."
+  )]
+  pub(crate) enable_brc20_mempool: bool,
   #[clap(long, default_value = "200", help = "DB commit interval.")]
   pub(crate) commit_height_interval: u64,
   #[clap(
@@ -166,6 +182,10 @@ impl Options {
     }
   }
 
+  pub(crate) fn max_reorg_depth(&self) -> u32 {
+    self.max_reorg_depth.unwrap_or(10)
+  }
+
   pub(crate) fn index_runes(&self) -> bool {
     self.index_runes && self.chain() != Chain::Mainnet
   }
@@ -864,6 +884,16 @@ mod tests {
     assert_eq!(arguments.options.db_cache_size, Some(16000000000));
   }
 
+  #[test]
+  fn max_reorg_depth_defaults_to_10() {
+    assert_eq!(Options::default().max_reorg_depth(), 10);
+
+    let options = Arguments::try_parse_from(["ord", "--max-reorg-depth", "25", "index", "update"])
+      .unwrap()
+      .options;
+    assert_eq!(options.max_reorg_depth(), 25);
+  }
+
   #[test]
   fn index_runes_only_returns_true_if_index_runes_flag_is_passed_and_not_on_mainnnet() {
     assert!(Arguments::try_parse_from([
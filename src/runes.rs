@@ -1,6 +1,12 @@
 use super::*;
 
-pub use {edict::Edict, rune::Rune, rune_id::RuneId, runestone::Runestone};
+pub use {
+  edict::Edict,
+  event::{BurnReason, RuneError, RuneEvent},
+  rune::Rune,
+  rune_id::RuneId,
+  runestone::Runestone,
+};
 
 pub(crate) use {etching::Etching, pile::Pile, spaced_rune::SpacedRune};
 
@@ -11,6 +17,7 @@ const RESERVED: u128 = 6402364363415443603228541259936211926;
 
 mod edict;
 mod etching;
+mod event;
 mod pile;
 mod rune;
 mod rune_id;
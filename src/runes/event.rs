@@ -0,0 +1,110 @@
+use super::*;
+
+/// What a transaction did to runes, saved per transaction in the order the rune updater applied it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum RuneEvent {
+  /// A new rune was etched, with the supply allocated by the etching transaction.
+  Etched {
+    id: RuneId,
+    rune: SpacedRune,
+    supply: u128,
+  },
+  /// An edict claimed runes of an open mint.
+  Minted { id: RuneId, amount: u128 },
+  /// An output received runes, by edict or as the destination of unallocated runes.
+  Transferred {
+    id: RuneId,
+    output: u32,
+    amount: u128,
+  },
+  /// Runes were burned, for the given reason.
+  Burned {
+    id: RuneId,
+    amount: u128,
+    reason: BurnReason,
+  },
+  /// The runestone or its etching was ignored.
+  Invalid(RuneError),
+}
+
+/// Why runes were burned.
+#[derive(
+  Debug,
+  Clone,
+  Copy,
+  PartialEq,
+  Eq,
+  PartialOrd,
+  Ord,
+  Hash,
+  Serialize,
+  Deserialize,
+  strum_macros::Display,
+)]
+#[strum(serialize_all = "camelCase")]
+pub enum BurnReason {
+  /// The transaction had no non-OP_RETURN output to receive the unallocated runes.
+  Unallocated,
+  /// The runes were allocated to an OP_RETURN output.
+  OpReturn,
+  /// The runestone had unrecognized fields, so the runes of the inputs were burned.
+  InvalidRunestone,
+}
+
+#[derive(Debug, Clone, PartialEq, thiserror::Error, Serialize, Deserialize)]
+pub enum RuneError {
+  #[error("runestone is malformed")]
+  MalformedRunestone,
+  #[error("runestone has unrecognized fields, the runes of the inputs are burned")]
+  UnrecognizedFields,
+  #[error("rune {0} is less than the minimum rune at this height")]
+  RuneBelowMinimum(Rune),
+  #[error("rune {0} is reserved")]
+  ReservedRune(Rune),
+  #[error("rune {0} is already etched")]
+  RuneAlreadyEtched(Rune),
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn rune_events_round_trip() {
+    let events = vec![
+      RuneEvent::Etched {
+        id: RuneId {
+          height: 2,
+          index: 1,
+        },
+        rune: SpacedRune {
+          rune: Rune(99246114928149462),
+          spacers: 1,
+        },
+        supply: u128::MAX,
+      },
+      RuneEvent::Transferred {
+        id: RuneId {
+          height: 2,
+          index: 1,
+        },
+        output: 0,
+        amount: u128::MAX,
+      },
+      RuneEvent::Burned {
+        id: RuneId {
+          height: 2,
+          index: 1,
+        },
+        amount: 1,
+        reason: BurnReason::OpReturn,
+      },
+      RuneEvent::Invalid(RuneError::RuneAlreadyEtched(Rune(99246114928149462))),
+    ];
+
+    assert_eq!(
+      rmp_serde::from_slice::<Vec<RuneEvent>>(&rmp_serde::to_vec(&events).unwrap()).unwrap(),
+      events
+    );
+  }
+}
@@ -72,7 +72,7 @@ impl Runestone {
     Self::decipher(transaction).ok().flatten()
   }
 
-  fn decipher(transaction: &Transaction) -> Result<Option<Self>, script::Error> {
+  pub(crate) fn decipher(transaction: &Transaction) -> Result<Option<Self>, script::Error> {
     let Some(payload) = Runestone::payload(transaction)? else {
       return Ok(None);
     };
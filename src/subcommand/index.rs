@@ -1,15 +1,24 @@
 use super::*;
 
 mod export;
+pub mod export_brc20;
+pub mod import_brc20;
 pub mod info;
+pub mod rollback;
 mod update;
 
 #[derive(Debug, Parser)]
 pub(crate) enum IndexSubcommand {
   #[command(about = "Write inscription numbers and ids to a tab-separated file")]
   Export(export::Export),
+  #[command(about = "Write a snapshot of the BRC20 state at a block height to a file")]
+  ExportBrc20(export_brc20::ExportBrc20),
+  #[command(about = "Seed the BRC20 state of an index from a snapshot")]
+  ImportBrc20(import_brc20::ImportBrc20),
   #[command(about = "Print index statistics")]
   Info(info::Info),
+  #[command(about = "Roll the index back to a block height")]
+  Rollback(rollback::Rollback),
   #[command(about = "Update the index", alias = "run")]
   Update,
 }
@@ -18,7 +27,10 @@ impl IndexSubcommand {
   pub(crate) fn run(self, options: Options) -> SubcommandResult {
     match self {
       Self::Export(export) => export.run(options),
+      Self::ExportBrc20(export) => export.run(options),
+      Self::ImportBrc20(import) => import.run(options),
       Self::Info(info) => info.run(options),
+      Self::Rollback(rollback) => rollback.run(options),
       Self::Update => update::run(options),
     }
   }
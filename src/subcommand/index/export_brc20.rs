@@ -0,0 +1,63 @@
+use {
+  super::*,
+  crate::okx::datastore::brc20,
+  std::io::{BufWriter, Write},
+};
+
+#[derive(Debug, Parser)]
+pub(crate) struct ExportBrc20 {
+  #[arg(long, help = "Export the BRC20 state at the end of block <HEIGHT>.")]
+  height: u32,
+  #[arg(long, help = "Write snapshot to <OUTPUT>.")]
+  output: PathBuf,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Output {
+  pub height: u32,
+  pub block_hash: BlockHash,
+  pub state_hash: String,
+  pub tokens: usize,
+  pub balances: usize,
+  pub transferable_assets: usize,
+}
+
+impl From<&brc20::Snapshot> for Output {
+  fn from(snapshot: &brc20::Snapshot) -> Self {
+    Self {
+      height: snapshot.height,
+      block_hash: snapshot.block_hash,
+      state_hash: snapshot.state_hash.to_string(),
+      tokens: snapshot.tokens.len(),
+      balances: snapshot.balances.len(),
+      transferable_assets: snapshot.transferable_assets.len(),
+    }
+  }
+}
+
+impl ExportBrc20 {
+  pub(crate) fn run(self, mut options: Options) -> SubcommandResult {
+    options.height_limit = Some(self.height + 1);
+
+    let index = Index::open(&options)?;
+
+    index.update()?;
+
+    let block_count = index.block_count()?;
+    if block_count != self.height + 1 {
+      bail!(
+        "index is at height {}, roll it back with `ord index rollback --height {}` to export the state at that height",
+        i64::from(block_count) - 1,
+        self.height
+      );
+    }
+
+    let snapshot = index.export_brc20_snapshot()?;
+
+    let mut writer = BufWriter::new(File::create(&self.output)?);
+    serde_json::to_writer(&mut writer, &snapshot)?;
+    writer.flush()?;
+
+    Ok(Box::new(Output::from(&snapshot)))
+  }
+}
@@ -0,0 +1,30 @@
+use {super::*, crate::okx::datastore::brc20, export_brc20::Output, std::io::BufReader};
+
+#[derive(Debug, Parser)]
+pub(crate) struct ImportBrc20 {
+  #[arg(long, help = "Read snapshot from <INPUT>.")]
+  input: PathBuf,
+}
+
+impl ImportBrc20 {
+  pub(crate) fn run(self, mut options: Options) -> SubcommandResult {
+    let snapshot: brc20::Snapshot =
+      serde_json::from_reader(BufReader::new(File::open(&self.input)?))?;
+
+    if !snapshot.is_valid() {
+      bail!("snapshot state hash {} does not match", snapshot.state_hash);
+    }
+
+    // index the blocks up to the snapshot without replaying BRC20, the BRC20 state is taken from
+    // the snapshot.
+    options.enable_index_brc20 = false;
+    options.height_limit = Some(snapshot.height + 1);
+
+    let index = Index::open(&options)?;
+
+    index.update()?;
+    index.import_brc20_snapshot(&snapshot)?;
+
+    Ok(Box::new(Output::from(&snapshot)))
+  }
+}
@@ -0,0 +1,36 @@
+use super::*;
+
+#[derive(Debug, Parser)]
+pub(crate) struct Rollback {
+  #[arg(
+    long,
+    help = "Roll the index back to <HEIGHT>, the last block that stays indexed."
+  )]
+  height: u32,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Output {
+  pub height: u32,
+}
+
+impl Rollback {
+  pub(crate) fn run(self, options: Options) -> SubcommandResult {
+    let index = Index::open(&options)?;
+
+    let block_count = index.block_count()?;
+    if block_count <= self.height + 1 {
+      bail!(
+        "index is at height {}, which is not above {}",
+        block_count.saturating_sub(1),
+        self.height
+      );
+    }
+
+    index.rollback(self.height)?;
+
+    Ok(Box::new(Output {
+      height: index.block_count()?.saturating_sub(1),
+    }))
+  }
+}
@@ -104,6 +104,8 @@ impl Preview {
           options: options.clone(),
           subcommand: Subcommand::Wallet(super::wallet::Wallet {
             name: "ord".into(),
+            descriptor: None,
+            descriptor_range: 1000,
             subcommand: super::wallet::Subcommand::Inscribe(super::wallet::inscribe::Inscribe {
               batch: None,
               cbor_metadata: None,
@@ -118,6 +120,7 @@ impl Preview {
               no_backup: true,
               no_limit: false,
               parent: None,
+              psbt: false,
               postage: Some(TARGET_POSTAGE),
               reinscribe: false,
               satpoint: None,
@@ -137,6 +140,8 @@ impl Preview {
           options: options.clone(),
           subcommand: Subcommand::Wallet(super::wallet::Wallet {
             name: "ord".into(),
+            descriptor: None,
+            descriptor_range: 1000,
             subcommand: super::wallet::Subcommand::Inscribe(super::wallet::inscribe::Inscribe {
               batch: Some(batch),
               cbor_metadata: None,
@@ -151,6 +156,7 @@ impl Preview {
               no_backup: true,
               no_limit: false,
               parent: None,
+              psbt: false,
               postage: Some(TARGET_POSTAGE),
               reinscribe: false,
               satpoint: None,
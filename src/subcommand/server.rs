@@ -7,15 +7,16 @@ use {
   },
   super::*,
   crate::{
+    okx::protocol::registry::ProtocolRegistry,
     server_config::ServerConfig,
     templates::{
-      BlockHtml, BlockJson, BlocksHtml, ChildrenHtml, ChildrenJson, ClockSvg, CollectionsHtml,
-      HomeHtml, InputHtml, InscriptionHtml, InscriptionJson, InscriptionsBlockHtml,
-      InscriptionsHtml, InscriptionsJson, OutputHtml, OutputJson, PageContent, PageHtml,
-      PreviewAudioHtml, PreviewCodeHtml, PreviewFontHtml, PreviewImageHtml, PreviewMarkdownHtml,
-      PreviewModelHtml, PreviewPdfHtml, PreviewTextHtml, PreviewUnknownHtml, PreviewVideoHtml,
-      RangeHtml, RareTxt, RuneHtml, RuneJson, RunesHtml, RunesJson, SatHtml, SatInscriptionJson,
-      SatInscriptionsJson, SatJson, TransactionHtml,
+      BlockHtml, BlockJson, BlocksHtml, ChildInscriptionJson, ChildInscriptionsJson, ChildrenHtml,
+      ChildrenJson, ClockSvg, CollectionsHtml, HomeHtml, InputHtml, InscriptionHtml,
+      InscriptionJson, InscriptionsBlockHtml, InscriptionsHtml, InscriptionsJson, OutputHtml,
+      OutputJson, PageContent, PageHtml, PreviewAudioHtml, PreviewCodeHtml, PreviewFontHtml,
+      PreviewImageHtml, PreviewMarkdownHtml, PreviewModelHtml, PreviewPdfHtml, PreviewTextHtml,
+      PreviewUnknownHtml, PreviewVideoHtml, RangeHtml, RareTxt, RuneHtml, RuneJson, RunesHtml,
+      RunesJson, SatHtml, SatInscriptionJson, SatInscriptionsJson, SatJson, TransactionHtml,
     },
   },
   axum::{
@@ -39,7 +40,10 @@ use {
   std::{cmp::Ordering, io::Read, str, sync::Arc},
   tokio_stream::StreamExt,
   tower_http::{
-    compression::CompressionLayer,
+    compression::{
+      predicate::{DefaultPredicate, NotForContentType, Predicate},
+      CompressionLayer,
+    },
     cors::{Any, CorsLayer},
     set_header::SetResponseHeaderLayer,
   },
@@ -49,12 +53,15 @@ use {
 mod accept_encoding;
 mod accept_json;
 mod api;
-mod brc20;
+pub(crate) mod brc20;
 mod error;
+mod feed;
 mod info;
-mod ord;
+pub(crate) mod ord;
 mod response;
+mod runes;
 mod sat;
+mod state;
 mod types;
 mod utils;
 
@@ -200,6 +207,20 @@ impl Server {
       });
       INDEXER.lock().unwrap().replace(index_thread);
 
+      if index.is_brc20_mempool_enabled() {
+        let index_clone = index.clone();
+
+        thread::spawn(move || loop {
+          if SHUTTING_DOWN.load(atomic::Ordering::Relaxed) {
+            break;
+          }
+          if let Err(error) = index_clone.update_brc20_mempool() {
+            log::warn!("Updating BRC20 mempool: {error}");
+          }
+          thread::sleep(Duration::from_millis(5000));
+        });
+      }
+
       #[derive(OpenApi)]
       #[openapi(
       paths(
@@ -207,8 +228,12 @@ impl Server {
       brc20::brc20_all_balance,
       brc20::brc20_tick_info,
       brc20::brc20_all_tick_info,
+      brc20::brc20_tick_holders,
       brc20::brc20_tx_events,
       brc20::brc20_block_events,
+      brc20::brc20_block_checksum,
+      brc20::brc20_address_events,
+      brc20::brc20_mempool_address_events,
       brc20::brc20_transferable,
       brc20::brc20_all_transferable,
 
@@ -217,13 +242,36 @@ impl Server {
       ord::ord_outpoint,
       ord::ord_txid_inscriptions,
       ord::ord_block_inscriptions,
+      ord::ord_domain,
+      ord::ord_address_domains,
+      ord::ord_collections,
+      ord::ord_collection_inscriptions,
+      ord::ord_inscription_children,
+      ord::ord_inscription_ancestors,
+      ord::ord_bitmap_district,
+      ord::ord_bitmap_district_parcels,
+      ord::ord_address_bitmaps,
+      ord::ord_address_inscriptions,
+
+      runes::runes_rune,
+      runes::runes_holders,
+      runes::runes_supply,
+      runes::runes_address_balances,
+      runes::runes_tx_events,
+      runes::runes_block_events,
 
       info::node_info,
+      feed::event_feed,
+
+      state::state_block_changes,
+      state::state_reorg_undone,
       ),
       components(schemas(
       // BRC20 schemas
       brc20::ApiTickInfo,
       brc20::ApiTickInfos,
+      brc20::ApiTickHolder,
+      brc20::ApiTickHolders,
       brc20::ApiBalance,
       brc20::ApiBalances,
       brc20::ApiTxEvent,
@@ -233,17 +281,24 @@ impl Server {
       brc20::ApiTransferEvent,
       brc20::ApiErrorEvent,
       brc20::ApiTxEvents,
+      brc20::ApiAddressEvents,
+      brc20::ApiMempoolEvents,
       brc20::ApiBlockEvents,
+      brc20::ApiBlockChecksum,
       brc20::ApiTransferableAsset,
       brc20::ApiTransferableAssets,
 
       // BRC20 responses schemas
       response::ApiBRC20Tick,
       response::ApiBRC20AllTick,
+      response::ApiBRC20TickHolders,
       response::ApiBRC20Balance,
       response::ApiBRC20AllBalance,
       response::ApiBRC20TxEvents,
+      response::ApiBRC20AddressEvents,
+      response::ApiBRC20MempoolEvents,
       response::ApiBRC20BlockEvents,
+      response::ApiBRC20BlockChecksum,
       response::ApiBRC20Transferable,
 
       // Ord schemas
@@ -256,12 +311,69 @@ impl Server {
       ord::ApiTxInscription,
       ord::ApiTxInscriptions,
       ord::ApiBlockInscriptions,
+      ord::ApiDomain,
+      ord::ApiDomains,
+      ord::ApiCollection,
+      ord::ApiCollections,
+      ord::ApiCollectionInscription,
+      ord::ApiCollectionInscriptions,
+      ord::ApiChildInscription,
+      ord::ApiInscriptionChildren,
+      ord::ApiAncestor,
+      ord::ApiInscriptionAncestors,
+      ord::ApiDistrictBlock,
+      ord::ApiDistrict,
+      ord::ApiDistricts,
+      ord::ApiParcel,
+      ord::ApiParcels,
+      ord::ApiAddressInscription,
+      ord::ApiAddressInscriptions,
 
       // Ord responses schemas
       response::ApiOrdInscription,
       response::ApiOrdTxInscriptions,
       response::ApiOrdBlockInscriptions,
       response::ApiOrdOutPointResult,
+      response::ApiOrdDomain,
+      response::ApiOrdDomains,
+      response::ApiOrdCollections,
+      response::ApiOrdCollectionInscriptions,
+      response::ApiOrdInscriptionChildren,
+      response::ApiOrdInscriptionAncestors,
+      response::ApiOrdDistrict,
+      response::ApiOrdDistricts,
+      response::ApiOrdParcels,
+      response::ApiOrdAddressInscriptions,
+
+      // Runes schemas
+      runes::ApiRune,
+      runes::ApiRuneHolder,
+      runes::ApiRuneHolders,
+      runes::ApiRuneSupplyPoint,
+      runes::ApiRuneSupply,
+      runes::ApiRuneBalance,
+      runes::ApiRuneBalances,
+      runes::ApiRuneEvent,
+      runes::ApiRuneTxEvents,
+      runes::ApiRuneBlockEvents,
+
+      // Runes responses schemas
+      response::ApiRunesRune,
+      response::ApiRunesHolders,
+      response::ApiRunesSupply,
+      response::ApiRunesBalances,
+      response::ApiRunesTxEvents,
+      response::ApiRunesBlockEvents,
+
+      // State schemas
+      state::ApiStateChange,
+      state::ApiBlockStateChanges,
+      state::ApiReorgUndone,
+      state::ApiUndoneReorg,
+
+      // State responses schemas
+      response::ApiStateBlockChanges,
+      response::ApiStateReorgUndone,
 
       // Node Info schemas
       info::NodeInfo,
@@ -291,54 +403,39 @@ impl Server {
           get(|| async { ApiDoc::openapi().to_pretty_json().unwrap() }),
         )
         .route("/node/info", get(info::node_info))
-        .route("/ord/id/:id/inscription", get(ord::ord_inscription_id))
-        .route(
-          "/ord/number/:number/inscription",
-          get(ord::ord_inscription_number),
-        )
-        .route("/ord/outpoint/:outpoint/info", get(ord::ord_outpoint))
-        .route(
-          "/ord/tx/:txid/inscriptions",
-          get(ord::ord_txid_inscriptions),
-        )
-        .route(
-          "/ord/block/:blockhash/inscriptions",
-          get(ord::ord_block_inscriptions),
-        )
-        .route(
-          "/ord/debug/bitmap/district/:number",
-          get(ord::ord_debug_bitmap_district),
-        )
+        .route("/feed", get(feed::event_feed))
         .route(
-          "/ord/debug/btc/domain/:btc_name",
-          get(ord::ord_debug_btc_domain),
+          "/state/block/:height/changes",
+          get(state::state_block_changes),
         )
-        .route("/brc20/tick/:tick", get(brc20::brc20_tick_info))
-        .route("/brc20/tick", get(brc20::brc20_all_tick_info))
         .route(
-          "/brc20/tick/:tick/address/:address/balance",
-          get(brc20::brc20_balance),
+          "/state/reorg/:height/undone",
+          get(state::state_reorg_undone),
         )
+        .route("/ord/collections", get(ord::ord_collections))
         .route(
-          "/brc20/address/:address/balance",
-          get(brc20::brc20_all_balance),
+          "/ord/collection/:kind",
+          get(ord::ord_collection_inscriptions),
         )
+        .route("/ord/id/:id/inscription", get(ord::ord_inscription_id))
+        .route("/ord/id/:id/children", get(ord::ord_inscription_children))
+        .route("/ord/id/:id/ancestors", get(ord::ord_inscription_ancestors))
         .route(
-          "/brc20/tick/:tick/address/:address/transferable",
-          get(brc20::brc20_transferable),
+          "/ord/address/:address/inscriptions",
+          get(ord::ord_address_inscriptions),
         )
         .route(
-          "/brc20/address/:address/transferable",
-          get(brc20::brc20_all_transferable),
+          "/ord/number/:number/inscription",
+          get(ord::ord_inscription_number),
         )
+        .route("/ord/outpoint/:outpoint/info", get(ord::ord_outpoint))
         .route(
-          "/brc20/outpoint/:outpoint/transferable",
-          get(brc20::brc20_outpoint),
+          "/ord/tx/:txid/inscriptions",
+          get(ord::ord_txid_inscriptions),
         )
-        .route("/brc20/tx/:txid/events", get(brc20::brc20_tx_events))
         .route(
-          "/brc20/block/:block_hash/events",
-          get(brc20::brc20_block_events),
+          "/ord/block/:blockhash/inscriptions",
+          get(ord::ord_block_inscriptions),
         )
         .route(
           "/sat/outpoint/:outpoint/info",
@@ -347,6 +444,20 @@ impl Server {
         .route(
           "/sat/outpoint/:outpoint/rarity",
           get(sat::sat_range_with_rarity_by_outpoint),
+        )
+        // the protocol routes don't use the server state.
+        .merge(
+          ProtocolRegistry::new(index.protocol_config())
+            .routes()
+            .with_state(()),
+        )
+        .merge(
+          if index.has_rune_index() {
+            runes::api_router()
+          } else {
+            Router::new()
+          }
+          .with_state(()),
         );
 
       let api_router = Router::new().nest("/v1", api_v1_router);
@@ -401,6 +512,14 @@ impl Server {
           "/r/children/:inscription_id/:page",
           get(Self::children_recursive_paginated),
         )
+        .route(
+          "/r/children/:inscription_id/inscriptions",
+          get(Self::child_inscriptions_recursive),
+        )
+        .route(
+          "/r/children/:inscription_id/inscriptions/:page",
+          get(Self::child_inscriptions_recursive_paginated),
+        )
         .route("/r/metadata/:inscription_id", get(Self::metadata))
         .route("/r/sat/:sat_number", get(Self::sat_inscriptions))
         .route(
@@ -438,7 +557,10 @@ impl Server {
             .allow_methods([http::Method::GET])
             .allow_origin(Any),
         )
-        .layer(CompressionLayer::new())
+        // compressed server-sent events would be buffered instead of streamed.
+        .layer(CompressionLayer::new().compress_when(
+          DefaultPredicate::new().and(NotForContentType::const_new("text/event-stream")),
+        ))
         .with_state(server_config);
 
       match (self.http_port(), self.https_port()) {
@@ -1539,6 +1661,59 @@ impl Server {
     Ok(Json(ChildrenJson { ids, more, page }).into_response())
   }
 
+  async fn child_inscriptions_recursive(
+    Extension(index): Extension<Arc<Index>>,
+    Path(inscription_id): Path<InscriptionId>,
+  ) -> ServerResult<Response> {
+    Self::child_inscriptions_recursive_paginated(Extension(index), Path((inscription_id, 0))).await
+  }
+
+  async fn child_inscriptions_recursive_paginated(
+    Extension(index): Extension<Arc<Index>>,
+    Path((parent, page)): Path<(InscriptionId, usize)>,
+  ) -> ServerResult<Response> {
+    let parent_sequence_number = index
+      .get_inscription_entry(parent)?
+      .ok_or_not_found(|| format!("inscription {parent}"))?
+      .sequence_number;
+
+    let (ids, more) =
+      index.get_children_by_sequence_number_paginated(parent_sequence_number, 100, page)?;
+
+    let children = ids
+      .into_iter()
+      .map(|id| {
+        let entry = index
+          .get_inscription_entry(id)?
+          .ok_or_not_found(|| format!("inscription {id}"))?;
+
+        let satpoint = index
+          .get_inscription_satpoint_by_id(id)?
+          .ok_or_not_found(|| format!("inscription {id}"))?;
+
+        let content_type = index
+          .get_inscription_by_id(id)?
+          .and_then(|inscription| inscription.content_type().map(str::to_string));
+
+        Ok(ChildInscriptionJson {
+          id,
+          number: entry.inscription_number,
+          satpoint,
+          content_type,
+        })
+      })
+      .collect::<ServerResult<Vec<_>>>()?;
+
+    Ok(
+      Json(ChildInscriptionsJson {
+        children,
+        more,
+        page,
+      })
+      .into_response(),
+    )
+  }
+
   async fn inscriptions(
     Extension(server_config): Extension<Arc<ServerConfig>>,
     Extension(index): Extension<Arc<Index>>,
@@ -1692,7 +1867,7 @@ impl Server {
 mod tests {
   use {
     super::*,
-    crate::runes::{Edict, Etching, Rune, Runestone},
+    crate::runes::{Edict, Etching, Rune, RuneId, Runestone, CLAIM_BIT},
     reqwest::Url,
     serde::de::DeserializeOwned,
     std::net::TcpListener,
@@ -2442,17 +2617,13 @@ mod tests {
   }
 
   #[test]
-  fn runes_are_spaced() {
+  fn runes_api_balances_and_holders() {
     let server = TestServer::new_with_regtest_with_index_runes();
 
     server.mine_blocks(1);
 
-    let rune = Rune(RUNE);
-
-    server.assert_response_regex(format!("/rune/{rune}"), StatusCode::NOT_FOUND, ".*");
-
     let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
-      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+      inputs: &[(1, 0, 0, Witness::new())],
       op_return: Some(
         Runestone {
           edicts: vec![Edict {
@@ -2461,8 +2632,8 @@ mod tests {
             output: 0,
           }],
           etching: Some(Etching {
-            rune: Some(rune),
-            symbol: Some('%'),
+            rune: Some(Rune(RUNE)),
+            symbol: Some('$'),
             spacers: 1,
             ..Default::default()
           }),
@@ -2475,82 +2646,114 @@ mod tests {
 
     server.mine_blocks(1);
 
-    let id = RuneId {
-      height: 2,
-      index: 1,
-    };
-
     assert_eq!(
-      server.index.runes().unwrap(),
-      [(
-        id,
-        RuneEntry {
-          etching: txid,
-          rune,
-          supply: u128::MAX,
-          symbol: Some('%'),
-          timestamp: 2,
-          spacers: 1,
-          ..Default::default()
-        }
-      )]
+      server
+        .get_json::<ApiResponse<runes::ApiRune>>("/api/v1/runes/A•AAAAAAAAAAAA")
+        .data,
+      runes::ApiRune {
+        rune: "A•AAAAAAAAAAAA".into(),
+        id: "2/1".into(),
+        number: 0,
+        etching: txid.to_string(),
+        divisibility: 0,
+        symbol: Some("$".into()),
+        supply: u128::MAX.to_string(),
+        burned: "0".into(),
+        mints: 0,
+        limit: None,
+        end: None,
+        deadline: None,
+        timestamp: 2,
+      }
     );
 
+    server.assert_response_regex("/api/v1/runes/AAAAAAAAAAAAB", StatusCode::NOT_FOUND, ".*");
+
+    // the test outputs pay to the empty script, which has no address
+    let script_key = ScriptBuf::new().script_hash();
+
     assert_eq!(
-      server.index.get_rune_balances().unwrap(),
-      [(OutPoint { txid, vout: 0 }, vec![(id, u128::MAX)])]
+      server
+        .get_json::<ApiResponse<runes::ApiRuneHolders>>("/api/v1/runes/AAAAAAAAAAAAA/holders")
+        .data,
+      runes::ApiRuneHolders {
+        rune: "A•AAAAAAAAAAAA".into(),
+        total: 1,
+        holders: vec![runes::ApiRuneHolder {
+          holder: types::ScriptPubkey::NonStandard(script_key.to_string()),
+          balance: u128::MAX.to_string(),
+        }],
+        next_cursor: None,
+      }
     );
 
-    server.assert_response_regex(
-      format!("/rune/{rune}"),
-      StatusCode::OK,
-      r".*<title>Rune A•AAAAAAAAAAAA</title>.*<h1>A•AAAAAAAAAAAA</h1>.*",
+    assert_eq!(
+      server
+        .get_json::<ApiResponse<runes::ApiRuneBalances>>(format!(
+          "/api/v1/address/{script_key}/runes"
+        ))
+        .data
+        .balances,
+      vec![runes::ApiRuneBalance {
+        rune: "A•AAAAAAAAAAAA".into(),
+        id: "2/1".into(),
+        divisibility: 0,
+        symbol: Some("$".into()),
+        balance: u128::MAX.to_string(),
+        outputs: 1,
+      }]
     );
 
-    server.assert_response_regex(
-      format!("/inscription/{txid}i0"),
-      StatusCode::OK,
-      ".*<dt>rune</dt>.*<dd><a href=/rune/A•AAAAAAAAAAAA>A•AAAAAAAAAAAA</a></dd>.*",
-    );
+    server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 1, 0, Witness::new())],
+      op_return: Some(
+        Runestone {
+          burn: true,
+          ..Default::default()
+        }
+        .encipher(),
+      ),
+      ..Default::default()
+    });
 
-    server.assert_response_regex(
-      "/runes",
-      StatusCode::OK,
-      ".*<li><a href=/rune/A•AAAAAAAAAAAA>A•AAAAAAAAAAAA</a></li>.*",
-    );
+    server.mine_blocks(1);
 
-    server.assert_response_regex(
-      format!("/tx/{txid}"),
-      StatusCode::OK,
-      ".*
-  <dt>etching</dt>
-  <dd><a href=/rune/A•AAAAAAAAAAAA>A•AAAAAAAAAAAA</a></dd>
-.*",
+    assert_eq!(
+      server
+        .get_json::<ApiResponse<runes::ApiRuneHolders>>("/api/v1/runes/AAAAAAAAAAAAA/holders")
+        .data
+        .total,
+      0
     );
 
-    server.assert_response_regex(
-      format!("/output/{txid}:0"),
-      StatusCode::OK,
-      ".*<tr>
-        <td><a href=/rune/A•AAAAAAAAAAAA>A•AAAAAAAAAAAA</a></td>
-        <td>340282366920938463463374607431768211455\u{00A0}%</td>
-      </tr>.*",
+    assert!(server
+      .get_json::<ApiResponse<runes::ApiRuneBalances>>(format!(
+        "/api/v1/address/{script_key}/runes"
+      ))
+      .data
+      .balances
+      .is_empty());
+  }
+
+  #[test]
+  fn runes_api_holders_without_inscription_index() {
+    let server = TestServer::new_server(
+      test_bitcoincore_rpc::builder()
+        .network(bitcoin::Network::Regtest)
+        .build(),
+      None,
+      &[
+        "--chain",
+        "regtest",
+        "--index-runes",
+        "--no-index-inscriptions",
+      ],
+      &["--enable-json-api"],
     );
-  }
-
-  #[test]
-  fn transactions_link_to_etching() {
-    let server = TestServer::new_with_regtest_with_index_runes();
 
     server.mine_blocks(1);
 
-    server.assert_response_regex(
-      "/runes",
-      StatusCode::OK,
-      ".*<title>Runes</title>.*<h1>Runes</h1>\n<ul>\n</ul>.*",
-    );
-
-    let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+    server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
       inputs: &[(1, 0, 0, Witness::new())],
       op_return: Some(
         Runestone {
@@ -2572,64 +2775,118 @@ mod tests {
 
     server.mine_blocks(1);
 
-    let id = RuneId {
-      height: 2,
-      index: 1,
-    };
+    // the spent rune output is neither cached nor indexed, so its script is fetched from bitcoind.
+    server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 1, 0, Witness::new())],
+      outputs: 2,
+      ..Default::default()
+    });
 
-    assert_eq!(
-      server.index.runes().unwrap(),
-      [(
-        id,
-        RuneEntry {
-          etching: txid,
-          rune: Rune(RUNE),
-          supply: u128::MAX,
-          timestamp: 2,
-          ..Default::default()
-        }
-      )]
-    );
+    server.mine_blocks(1);
+
+    let script_key = ScriptBuf::new().script_hash();
 
     assert_eq!(
-      server.index.get_rune_balances().unwrap(),
-      [(OutPoint { txid, vout: 0 }, vec![(id, u128::MAX)])]
+      server
+        .get_json::<ApiResponse<runes::ApiRuneHolders>>("/api/v1/runes/AAAAAAAAAAAAA/holders")
+        .data,
+      runes::ApiRuneHolders {
+        rune: "AAAAAAAAAAAAA".into(),
+        total: 1,
+        holders: vec![runes::ApiRuneHolder {
+          holder: types::ScriptPubkey::NonStandard(script_key.to_string()),
+          balance: u128::MAX.to_string(),
+        }],
+        next_cursor: None,
+      }
     );
 
-    server.assert_response_regex(
-      format!("/tx/{txid}"),
-      StatusCode::OK,
-      ".*
-  <dt>etching</dt>
-  <dd><a href=/rune/AAAAAAAAAAAAA>AAAAAAAAAAAAA</a></dd>
-.*",
+    assert_eq!(
+      server
+        .get_json::<ApiResponse<runes::ApiRuneBalances>>(format!(
+          "/api/v1/address/{script_key}/runes"
+        ))
+        .data
+        .balances[0]
+        .outputs,
+      1
     );
   }
 
   #[test]
-  fn runes_are_displayed_on_output_page() {
+  fn runes_api_events() {
     let server = TestServer::new_with_regtest_with_index_runes();
 
     server.mine_blocks(1);
 
-    let rune = Rune(RUNE);
-
-    server.assert_response_regex(format!("/rune/{rune}"), StatusCode::NOT_FOUND, ".*");
+    let etching = Runestone {
+      edicts: vec![Edict {
+        id: 0,
+        amount: u128::MAX,
+        output: 0,
+      }],
+      etching: Some(Etching {
+        rune: Some(Rune(RUNE)),
+        ..Default::default()
+      }),
+      ..Default::default()
+    }
+    .encipher();
 
     let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
-      inputs: &[(1, 0, 0, Default::default())],
+      inputs: &[(1, 0, 0, Witness::new())],
+      op_return: Some(etching.clone()),
+      ..Default::default()
+    });
+
+    let block = server.mine_blocks(1).remove(0);
+
+    let events = runes::ApiRuneTxEvents {
+      txid: txid.to_string(),
+      events: vec![
+        runes::ApiRuneEvent::Etched {
+          id: "2/1".into(),
+          rune: "AAAAAAAAAAAAA".into(),
+          supply: u128::MAX.to_string(),
+        },
+        runes::ApiRuneEvent::Transferred {
+          id: "2/1".into(),
+          rune: "AAAAAAAAAAAAA".into(),
+          output: 0,
+          amount: u128::MAX.to_string(),
+        },
+      ],
+    };
+
+    assert_eq!(
+      server
+        .get_json::<ApiResponse<runes::ApiRuneTxEvents>>(format!("/api/v1/runes/tx/{txid}/events"))
+        .data,
+      events
+    );
+
+    assert_eq!(
+      server
+        .get_json::<ApiResponse<runes::ApiRuneBlockEvents>>(format!(
+          "/api/v1/runes/block/{}/events",
+          block.block_hash()
+        ))
+        .data
+        .block,
+      vec![events]
+    );
+
+    let duplicate_txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 0, 0, Witness::new())],
+      op_return: Some(etching.clone()),
+      ..Default::default()
+    });
+
+    let burn_txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 1, 0, Witness::new())],
       op_return: Some(
         Runestone {
-          edicts: vec![Edict {
-            id: 0,
-            amount: u128::MAX,
-            output: 0,
-          }],
-          etching: Some(Etching {
-            divisibility: 1,
-            rune: Some(rune),
-            ..Default::default()
-          }),
+          burn: true,
           ..Default::default()
         }
         .encipher(),
@@ -2639,21 +2896,395 @@ mod tests {
 
     server.mine_blocks(1);
 
-    let id = RuneId {
-      height: 2,
-      index: 1,
-    };
+    assert_eq!(
+      server
+        .get_json::<ApiResponse<runes::ApiRuneTxEvents>>(format!(
+          "/api/v1/runes/tx/{duplicate_txid}/events"
+        ))
+        .data
+        .events,
+      vec![runes::ApiRuneEvent::Invalid {
+        msg: "rune AAAAAAAAAAAAA is already etched".into(),
+      }]
+    );
 
     assert_eq!(
-      server.index.runes().unwrap(),
-      [(
-        id,
-        RuneEntry {
-          divisibility: 1,
-          etching: txid,
-          rune,
-          supply: u128::MAX,
-          timestamp: 2,
+      server
+        .get_json::<ApiResponse<runes::ApiRuneTxEvents>>(format!(
+          "/api/v1/runes/tx/{burn_txid}/events"
+        ))
+        .data
+        .events,
+      vec![
+        runes::ApiRuneEvent::Invalid {
+          msg: "runestone has unrecognized fields, the runes of the inputs are burned".into(),
+        },
+        runes::ApiRuneEvent::Burned {
+          id: "2/1".into(),
+          rune: "AAAAAAAAAAAAA".into(),
+          amount: u128::MAX.to_string(),
+          reason: "invalidRunestone".into(),
+        },
+      ]
+    );
+
+    server.assert_response_regex(
+      format!("/api/v1/runes/tx/{}/events", block.txdata[0].txid()),
+      StatusCode::NOT_FOUND,
+      ".*",
+    );
+  }
+
+  #[test]
+  fn runes_api_supply() {
+    let server = TestServer::new_with_regtest_with_index_runes();
+
+    server.mine_blocks(1);
+
+    server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, Witness::new())],
+      op_return: Some(
+        Runestone {
+          edicts: vec![
+            Edict {
+              id: 0,
+              amount: 700,
+              output: 0,
+            },
+            Edict {
+              id: 0,
+              amount: 300,
+              output: 1,
+            },
+          ],
+          etching: Some(Etching {
+            rune: Some(Rune(RUNE)),
+            limit: Some(1000),
+            ..Default::default()
+          }),
+          ..Default::default()
+        }
+        .encipher(),
+      ),
+      ..Default::default()
+    });
+
+    server.mine_blocks(1);
+
+    let id = RuneId {
+      height: 2,
+      index: 1,
+    };
+
+    server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 0, 0, Witness::new())],
+      op_return: Some(
+        Runestone {
+          edicts: vec![Edict {
+            id: u128::from(id) | CLAIM_BIT,
+            amount: 1000,
+            output: 0,
+          }],
+          ..Default::default()
+        }
+        .encipher(),
+      ),
+      ..Default::default()
+    });
+
+    server.mine_blocks(1);
+
+    server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 1, 0, Witness::new())],
+      op_return: Some(
+        Runestone {
+          burn: true,
+          ..Default::default()
+        }
+        .encipher(),
+      ),
+      ..Default::default()
+    });
+
+    server.mine_blocks(1);
+
+    server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(3, 1, 0, Witness::new())],
+      op_return: Some(Runestone::default().encipher()),
+      outputs: 0,
+      ..Default::default()
+    });
+
+    server.mine_blocks(1);
+
+    let point = |height, supply: u128, burned: u128, mints, minted: u128, burns: [u128; 3]| {
+      runes::ApiRuneSupplyPoint {
+        height,
+        supply: supply.to_string(),
+        burned: burned.to_string(),
+        circulating: (supply - burned).to_string(),
+        mints,
+        minted: minted.to_string(),
+        burned_unallocated: burns[0].to_string(),
+        burned_op_return: burns[1].to_string(),
+        burned_invalid_runestone: burns[2].to_string(),
+      }
+    };
+
+    let points = vec![
+      point(2, 1000, 300, 0, 0, [0, 300, 0]),
+      point(3, 2000, 300, 1, 1000, [0, 0, 0]),
+      point(4, 2000, 1000, 1, 0, [0, 0, 700]),
+      point(5, 2000, 2000, 1, 0, [1000, 0, 0]),
+    ];
+
+    assert_eq!(
+      server
+        .get_json::<ApiResponse<runes::ApiRuneSupply>>("/api/v1/runes/AAAAAAAAAAAAA/supply")
+        .data,
+      runes::ApiRuneSupply {
+        rune: "AAAAAAAAAAAAA".into(),
+        id: "2/1".into(),
+        points: points.clone(),
+      }
+    );
+
+    assert_eq!(
+      server
+        .get_json::<ApiResponse<runes::ApiRuneSupply>>(
+          "/api/v1/runes/AAAAAAAAAAAAA/supply?from=3&to=4"
+        )
+        .data
+        .points,
+      points[1..3]
+    );
+
+    server.assert_response_regex(
+      "/api/v1/runes/AAAAAAAAAAAAA/supply?from=4&to=3",
+      StatusCode::BAD_REQUEST,
+      ".*",
+    );
+
+    server.assert_response_regex(
+      "/api/v1/runes/AAAAAAAAAAAAB/supply",
+      StatusCode::NOT_FOUND,
+      ".*",
+    );
+  }
+
+  #[test]
+  fn runes_are_spaced() {
+    let server = TestServer::new_with_regtest_with_index_runes();
+
+    server.mine_blocks(1);
+
+    let rune = Rune(RUNE);
+
+    server.assert_response_regex(format!("/rune/{rune}"), StatusCode::NOT_FOUND, ".*");
+
+    let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+      op_return: Some(
+        Runestone {
+          edicts: vec![Edict {
+            id: 0,
+            amount: u128::MAX,
+            output: 0,
+          }],
+          etching: Some(Etching {
+            rune: Some(rune),
+            symbol: Some('%'),
+            spacers: 1,
+            ..Default::default()
+          }),
+          ..Default::default()
+        }
+        .encipher(),
+      ),
+      ..Default::default()
+    });
+
+    server.mine_blocks(1);
+
+    let id = RuneId {
+      height: 2,
+      index: 1,
+    };
+
+    assert_eq!(
+      server.index.runes().unwrap(),
+      [(
+        id,
+        RuneEntry {
+          etching: txid,
+          rune,
+          supply: u128::MAX,
+          symbol: Some('%'),
+          timestamp: 2,
+          spacers: 1,
+          ..Default::default()
+        }
+      )]
+    );
+
+    assert_eq!(
+      server.index.get_rune_balances().unwrap(),
+      [(OutPoint { txid, vout: 0 }, vec![(id, u128::MAX)])]
+    );
+
+    server.assert_response_regex(
+      format!("/rune/{rune}"),
+      StatusCode::OK,
+      r".*<title>Rune A•AAAAAAAAAAAA</title>.*<h1>A•AAAAAAAAAAAA</h1>.*",
+    );
+
+    server.assert_response_regex(
+      format!("/inscription/{txid}i0"),
+      StatusCode::OK,
+      ".*<dt>rune</dt>.*<dd><a href=/rune/A•AAAAAAAAAAAA>A•AAAAAAAAAAAA</a></dd>.*",
+    );
+
+    server.assert_response_regex(
+      "/runes",
+      StatusCode::OK,
+      ".*<li><a href=/rune/A•AAAAAAAAAAAA>A•AAAAAAAAAAAA</a></li>.*",
+    );
+
+    server.assert_response_regex(
+      format!("/tx/{txid}"),
+      StatusCode::OK,
+      ".*
+  <dt>etching</dt>
+  <dd><a href=/rune/A•AAAAAAAAAAAA>A•AAAAAAAAAAAA</a></dd>
+.*",
+    );
+
+    server.assert_response_regex(
+      format!("/output/{txid}:0"),
+      StatusCode::OK,
+      ".*<tr>
+        <td><a href=/rune/A•AAAAAAAAAAAA>A•AAAAAAAAAAAA</a></td>
+        <td>340282366920938463463374607431768211455\u{00A0}%</td>
+      </tr>.*",
+    );
+  }
+
+  #[test]
+  fn transactions_link_to_etching() {
+    let server = TestServer::new_with_regtest_with_index_runes();
+
+    server.mine_blocks(1);
+
+    server.assert_response_regex(
+      "/runes",
+      StatusCode::OK,
+      ".*<title>Runes</title>.*<h1>Runes</h1>\n<ul>\n</ul>.*",
+    );
+
+    let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, Witness::new())],
+      op_return: Some(
+        Runestone {
+          edicts: vec![Edict {
+            id: 0,
+            amount: u128::MAX,
+            output: 0,
+          }],
+          etching: Some(Etching {
+            rune: Some(Rune(RUNE)),
+            ..Default::default()
+          }),
+          ..Default::default()
+        }
+        .encipher(),
+      ),
+      ..Default::default()
+    });
+
+    server.mine_blocks(1);
+
+    let id = RuneId {
+      height: 2,
+      index: 1,
+    };
+
+    assert_eq!(
+      server.index.runes().unwrap(),
+      [(
+        id,
+        RuneEntry {
+          etching: txid,
+          rune: Rune(RUNE),
+          supply: u128::MAX,
+          timestamp: 2,
+          ..Default::default()
+        }
+      )]
+    );
+
+    assert_eq!(
+      server.index.get_rune_balances().unwrap(),
+      [(OutPoint { txid, vout: 0 }, vec![(id, u128::MAX)])]
+    );
+
+    server.assert_response_regex(
+      format!("/tx/{txid}"),
+      StatusCode::OK,
+      ".*
+  <dt>etching</dt>
+  <dd><a href=/rune/AAAAAAAAAAAAA>AAAAAAAAAAAAA</a></dd>
+.*",
+    );
+  }
+
+  #[test]
+  fn runes_are_displayed_on_output_page() {
+    let server = TestServer::new_with_regtest_with_index_runes();
+
+    server.mine_blocks(1);
+
+    let rune = Rune(RUNE);
+
+    server.assert_response_regex(format!("/rune/{rune}"), StatusCode::NOT_FOUND, ".*");
+
+    let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, Default::default())],
+      op_return: Some(
+        Runestone {
+          edicts: vec![Edict {
+            id: 0,
+            amount: u128::MAX,
+            output: 0,
+          }],
+          etching: Some(Etching {
+            divisibility: 1,
+            rune: Some(rune),
+            ..Default::default()
+          }),
+          ..Default::default()
+        }
+        .encipher(),
+      ),
+      ..Default::default()
+    });
+
+    server.mine_blocks(1);
+
+    let id = RuneId {
+      height: 2,
+      index: 1,
+    };
+
+    assert_eq!(
+      server.index.runes().unwrap(),
+      [(
+        id,
+        RuneEntry {
+          divisibility: 1,
+          etching: txid,
+          rune,
+          supply: u128::MAX,
+          timestamp: 2,
           ..Default::default()
         }
       )]
@@ -3639,12 +4270,145 @@ mod tests {
     .unwrap()
     .unwrap();
 
-    assert_eq!(headers["content-type"], "application/octet-stream");
-    assert!(body.is_empty());
+    assert_eq!(headers["content-type"], "application/octet-stream");
+    assert!(body.is_empty());
+  }
+
+  #[test]
+  fn text_preview() {
+    let server = TestServer::new_with_regtest();
+    server.mine_blocks(1);
+
+    let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(
+        1,
+        0,
+        0,
+        inscription("text/plain;charset=utf-8", "hello").to_witness(),
+      )],
+      ..Default::default()
+    });
+
+    let inscription_id = InscriptionId { txid, index: 0 };
+
+    server.mine_blocks(1);
+
+    server.assert_response_csp(
+      format!("/preview/{}", inscription_id),
+      StatusCode::OK,
+      "default-src 'self'",
+      format!(".*<html lang=en data-inscription={}>.*", inscription_id),
+    );
+  }
+
+  #[test]
+  fn audio_preview() {
+    let server = TestServer::new_with_regtest();
+    server.mine_blocks(1);
+
+    let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("audio/flac", "hello").to_witness())],
+      ..Default::default()
+    });
+    let inscription_id = InscriptionId { txid, index: 0 };
+
+    server.mine_blocks(1);
+
+    server.assert_response_regex(
+      format!("/preview/{inscription_id}"),
+      StatusCode::OK,
+      format!(r".*<audio .*>\s*<source src=/content/{inscription_id}>.*"),
+    );
+  }
+
+  #[test]
+  fn font_preview() {
+    let server = TestServer::new_with_regtest();
+    server.mine_blocks(1);
+
+    let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("font/ttf", "hello").to_witness())],
+      ..Default::default()
+    });
+    let inscription_id = InscriptionId { txid, index: 0 };
+
+    server.mine_blocks(1);
+
+    server.assert_response_regex(
+      format!("/preview/{inscription_id}"),
+      StatusCode::OK,
+      format!(r".*src: url\(/content/{inscription_id}\).*"),
+    );
+  }
+
+  #[test]
+  fn pdf_preview() {
+    let server = TestServer::new_with_regtest();
+    server.mine_blocks(1);
+
+    let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(
+        1,
+        0,
+        0,
+        inscription("application/pdf", "hello").to_witness(),
+      )],
+      ..Default::default()
+    });
+    let inscription_id = InscriptionId { txid, index: 0 };
+
+    server.mine_blocks(1);
+
+    server.assert_response_regex(
+      format!("/preview/{inscription_id}"),
+      StatusCode::OK,
+      format!(r".*<canvas data-inscription={inscription_id}></canvas>.*"),
+    );
+  }
+
+  #[test]
+  fn markdown_preview() {
+    let server = TestServer::new_with_regtest();
+    server.mine_blocks(1);
+
+    let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/markdown", "hello").to_witness())],
+      ..Default::default()
+    });
+    let inscription_id = InscriptionId { txid, index: 0 };
+
+    server.mine_blocks(1);
+
+    server.assert_response_regex(
+      format!("/preview/{inscription_id}"),
+      StatusCode::OK,
+      format!(r".*<html lang=en data-inscription={inscription_id}>.*"),
+    );
+  }
+
+  #[test]
+  fn image_preview() {
+    let server = TestServer::new_with_regtest();
+    server.mine_blocks(1);
+
+    let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("image/png", "hello").to_witness())],
+      ..Default::default()
+    });
+    let inscription_id = InscriptionId { txid, index: 0 };
+
+    server.mine_blocks(1);
+
+    server.assert_response_csp(
+      format!("/preview/{inscription_id}"),
+      StatusCode::OK,
+      "default-src 'self' 'unsafe-inline'",
+      format!(r".*background-image: url\(/content/{inscription_id}\);.*"),
+    );
   }
 
   #[test]
-  fn text_preview() {
+  fn iframe_preview() {
     let server = TestServer::new_with_regtest();
     server.mine_blocks(1);
 
@@ -3653,50 +4417,48 @@ mod tests {
         1,
         0,
         0,
-        inscription("text/plain;charset=utf-8", "hello").to_witness(),
+        inscription("text/html;charset=utf-8", "hello").to_witness(),
       )],
       ..Default::default()
     });
 
-    let inscription_id = InscriptionId { txid, index: 0 };
-
     server.mine_blocks(1);
 
     server.assert_response_csp(
-      format!("/preview/{}", inscription_id),
+      format!("/preview/{}", InscriptionId { txid, index: 0 }),
       StatusCode::OK,
-      "default-src 'self'",
-      format!(".*<html lang=en data-inscription={}>.*", inscription_id),
+      "default-src 'self' 'unsafe-eval' 'unsafe-inline' data: blob:",
+      "hello",
     );
   }
 
   #[test]
-  fn audio_preview() {
+  fn unknown_preview() {
     let server = TestServer::new_with_regtest();
     server.mine_blocks(1);
 
     let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
-      inputs: &[(1, 0, 0, inscription("audio/flac", "hello").to_witness())],
+      inputs: &[(1, 0, 0, inscription("text/foo", "hello").to_witness())],
       ..Default::default()
     });
-    let inscription_id = InscriptionId { txid, index: 0 };
 
     server.mine_blocks(1);
 
-    server.assert_response_regex(
-      format!("/preview/{inscription_id}"),
+    server.assert_response_csp(
+      format!("/preview/{}", InscriptionId { txid, index: 0 }),
       StatusCode::OK,
-      format!(r".*<audio .*>\s*<source src=/content/{inscription_id}>.*"),
+      "default-src 'self'",
+      fs::read_to_string("templates/preview-unknown.html").unwrap(),
     );
   }
 
   #[test]
-  fn font_preview() {
+  fn video_preview() {
     let server = TestServer::new_with_regtest();
     server.mine_blocks(1);
 
     let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
-      inputs: &[(1, 0, 0, inscription("font/ttf", "hello").to_witness())],
+      inputs: &[(1, 0, 0, inscription("video/webm", "hello").to_witness())],
       ..Default::default()
     });
     let inscription_id = InscriptionId { txid, index: 0 };
@@ -3706,207 +4468,844 @@ mod tests {
     server.assert_response_regex(
       format!("/preview/{inscription_id}"),
       StatusCode::OK,
-      format!(r".*src: url\(/content/{inscription_id}\).*"),
+      format!(r".*<video .*>\s*<source src=/content/{inscription_id}>.*"),
     );
   }
 
   #[test]
-  fn pdf_preview() {
-    let server = TestServer::new_with_regtest();
+  fn inscription_page_title() {
+    let server = TestServer::new_with_regtest_with_index_sats();
     server.mine_blocks(1);
 
     let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
-      inputs: &[(
-        1,
-        0,
-        0,
-        inscription("application/pdf", "hello").to_witness(),
-      )],
+      inputs: &[(1, 0, 0, inscription("text/foo", "hello").to_witness())],
       ..Default::default()
     });
-    let inscription_id = InscriptionId { txid, index: 0 };
 
     server.mine_blocks(1);
 
     server.assert_response_regex(
-      format!("/preview/{inscription_id}"),
+      format!("/inscription/{}", InscriptionId { txid, index: 0 }),
       StatusCode::OK,
-      format!(r".*<canvas data-inscription={inscription_id}></canvas>.*"),
+      ".*<title>Inscription 0</title>.*",
     );
   }
 
   #[test]
-  fn markdown_preview() {
-    let server = TestServer::new_with_regtest();
+  fn inscription_page_has_sat_when_sats_are_tracked() {
+    let server = TestServer::new_with_regtest_with_index_sats();
     server.mine_blocks(1);
 
     let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
-      inputs: &[(1, 0, 0, inscription("text/markdown", "hello").to_witness())],
+      inputs: &[(1, 0, 0, inscription("text/foo", "hello").to_witness())],
       ..Default::default()
     });
-    let inscription_id = InscriptionId { txid, index: 0 };
 
     server.mine_blocks(1);
 
     server.assert_response_regex(
-      format!("/preview/{inscription_id}"),
+      format!("/inscription/{}", InscriptionId { txid, index: 0 }),
       StatusCode::OK,
-      format!(r".*<html lang=en data-inscription={inscription_id}>.*"),
+      r".*<dt>sat</dt>\s*<dd><a href=/sat/5000000000>5000000000</a></dd>\s*<dt>preview</dt>.*",
     );
   }
 
   #[test]
-  fn image_preview() {
+  fn inscription_page_does_not_have_sat_when_sats_are_not_tracked() {
     let server = TestServer::new_with_regtest();
     server.mine_blocks(1);
 
     let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
-      inputs: &[(1, 0, 0, inscription("image/png", "hello").to_witness())],
+      inputs: &[(1, 0, 0, inscription("text/foo", "hello").to_witness())],
       ..Default::default()
     });
-    let inscription_id = InscriptionId { txid, index: 0 };
 
     server.mine_blocks(1);
 
-    server.assert_response_csp(
-      format!("/preview/{inscription_id}"),
+    server.assert_response_regex(
+      format!("/inscription/{}", InscriptionId { txid, index: 0 }),
       StatusCode::OK,
-      "default-src 'self' 'unsafe-inline'",
-      format!(r".*background-image: url\(/content/{inscription_id}\);.*"),
+      r".*<dt>output value</dt>\s*<dd>5000000000</dd>\s*<dt>preview</dt>.*",
     );
   }
 
   #[test]
-  fn iframe_preview() {
-    let server = TestServer::new_with_regtest();
+  fn strict_transport_security_header_is_set() {
+    assert_eq!(
+      TestServer::new()
+        .get("/status")
+        .headers()
+        .get(header::STRICT_TRANSPORT_SECURITY)
+        .unwrap(),
+      "max-age=31536000; includeSubDomains; preload",
+    );
+  }
+
+  #[test]
+  fn brc20_block_checksum() {
+    let server = TestServer::new_server(
+      test_bitcoincore_rpc::builder()
+        .network(bitcoin::Network::Regtest)
+        .build(),
+      None,
+      &["--chain", "regtest", "--enable-index-brc20"],
+      &[],
+    );
+
     server.mine_blocks(1);
 
+    let first = server
+      .get_json::<ApiResponse<brc20::ApiBlockChecksum>>("/api/v1/brc20/block/0/checksum")
+      .data;
+    let second = server
+      .get_json::<ApiResponse<brc20::ApiBlockChecksum>>("/api/v1/brc20/block/1/checksum")
+      .data;
+
+    assert_eq!(second.height, 1);
+    assert_eq!(
+      second.blockhash,
+      server
+        .index
+        .block_hash(Some(1))
+        .unwrap()
+        .unwrap()
+        .to_string()
+    );
+    assert_eq!(second.checksum.len(), 64);
+    assert_ne!(first.checksum, second.checksum);
+
+    server.assert_response_regex(
+      "/api/v1/brc20/block/2/checksum",
+      StatusCode::BAD_REQUEST,
+      ".*height 2 has not been indexed yet.*",
+    );
+  }
+
+  #[test]
+  fn event_feed_resumes_after_last_event_id() {
+    let server = TestServer::new_with_regtest_with_json_api();
+
+    server.mine_blocks(2);
+
+    let block_hash = |height| server.index.block_hash(Some(height)).unwrap().unwrap();
+
+    // the lines of the events up to the first event with an id.
+    let feed = |last_event_id: String| {
+      let response = reqwest::blocking::Client::builder()
+        .timeout(Duration::from_secs(10))
+        .build()
+        .unwrap()
+        .get(server.join_url("/api/v1/feed"))
+        .header("Last-Event-ID", last_event_id)
+        .send()
+        .unwrap();
+      assert_eq!(response.status(), StatusCode::OK);
+
+      let mut lines = Vec::new();
+      for line in io::BufRead::lines(io::BufReader::new(response)) {
+        let line = line.unwrap();
+        if line.is_empty() || line.starts_with(':') {
+          continue;
+        }
+        let id = line.starts_with("id:");
+        lines.push(line);
+        if id {
+          break;
+        }
+      }
+      lines
+    };
+
+    let lines = feed(format!("1:{}", block_hash(1)));
+    assert_eq!(lines[0], "event:blockConnected");
+    assert_eq!(
+      lines[1],
+      format!("data:{{\"height\":2,\"blockhash\":\"{}\"}}", block_hash(2))
+    );
+    assert_eq!(lines[2], format!("id:2:{}", block_hash(2)));
+
+    // the block of the id is no longer indexed, so the feed sends the recent blocks again.
+    let lines = feed(format!("1:{}", block_hash(0)));
+    assert_eq!(lines[0], "event:resync");
+    assert_eq!(lines[1], "data:{\"height\":0}");
+    assert_eq!(lines[2], "event:blockConnected");
+    assert_eq!(lines.last().unwrap(), &format!("id:0:{}", block_hash(0)));
+
+    assert_eq!(
+      reqwest::blocking::Client::new()
+        .get(server.join_url("/api/v1/feed"))
+        .header("Last-Event-ID", "2")
+        .send()
+        .unwrap()
+        .status(),
+      StatusCode::BAD_REQUEST
+    );
+  }
+
+  #[test]
+  fn brc20_routes_are_served_and_receipts_saved_without_brc20_index() {
+    let server = TestServer::new_with_regtest();
+
+    server.mine_blocks(1);
     let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+      ..Default::default()
+    });
+    server.mine_blocks(1);
+
+    let events = server
+      .get_json::<ApiResponse<brc20::ApiTxEvents>>(&format!("/api/v1/brc20/tx/{txid}/events"))
+      .data
+      .events;
+    assert!(events.is_empty());
+  }
+
+  #[test]
+  fn brc20_mempool_address_events() {
+    let server = TestServer::new_server(
+      test_bitcoincore_rpc::builder()
+        .network(bitcoin::Network::Regtest)
+        .build(),
+      None,
+      &[
+        "--chain",
+        "regtest",
+        "--enable-index-brc20",
+        "--enable-brc20-mempool",
+      ],
+      &[],
+    );
+
+    server.mine_blocks(1);
+    server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
       inputs: &[(
         1,
         0,
         0,
-        inscription("text/html;charset=utf-8", "hello").to_witness(),
+        inscription(
+          "text/plain;charset=utf-8",
+          r#"{"p":"brc-20","op":"deploy","tick":"ordi","max":"1000","lim":"10"}"#,
+        )
+        .to_witness(),
+      )],
+      ..Default::default()
+    });
+    server.mine_blocks(1);
+    let mint = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(
+        2,
+        0,
+        0,
+        inscription(
+          "text/plain;charset=utf-8",
+          r#"{"p":"brc-20","op":"mint","tick":"ordi","amt":"10"}"#,
+        )
+        .to_witness(),
+      )],
+      ..Default::default()
+    });
+    server.mine_blocks(1);
+
+    let owner = match server
+      .get_json::<ApiResponse<brc20::ApiTxEvents>>(&format!("/api/v1/brc20/tx/{mint}/events"))
+      .data
+      .events
+      .remove(0)
+    {
+      brc20::ApiTxEvent::Mint(event) => event.to,
+      event => panic!("unexpected event {event:?}"),
+    };
+    let owner = match owner {
+      types::ScriptPubkey::Address(address) => address,
+      types::ScriptPubkey::NonStandard(script_hash) => script_hash,
+    };
+
+    let inscribe_transfer = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(
+        3,
+        0,
+        0,
+        inscription(
+          "text/plain;charset=utf-8",
+          r#"{"p":"brc-20","op":"transfer","tick":"ordi","amt":"4"}"#,
+        )
+        .to_witness(),
       )],
       ..Default::default()
     });
+    server.index.update_brc20_mempool().unwrap();
+
+    let pending = server
+      .get_json::<ApiResponse<brc20::ApiMempoolEvents>>(&format!(
+        "/api/v1/brc20/mempool/address/{owner}"
+      ))
+      .data;
+
+    assert_eq!(pending.height, 4);
+    assert_eq!(pending.events.len(), 1);
+    assert_eq!(pending.events[0].txid, inscribe_transfer.to_string());
+    // inscribe-transfer events have the shape of mint events, so they deserialize as one
+    match &pending.events[0].events[..] {
+      [brc20::ApiTxEvent::Mint(event)] => {
+        assert_eq!(event.event, "inscribeTransfer");
+        assert_eq!(event.tick, "ordi");
+        assert_eq!(event.amount, "4000000000000000000");
+        assert!(event.valid);
+      }
+      events => panic!("unexpected events {events:?}"),
+    }
+
+    // transactions already simulated keep their receipts
+    server.index.update_brc20_mempool().unwrap();
+    assert_eq!(
+      server
+        .get_json::<ApiResponse<brc20::ApiMempoolEvents>>(&format!(
+          "/api/v1/brc20/mempool/address/{owner}"
+        ))
+        .data
+        .events
+        .len(),
+      1
+    );
+
+    // the simulation never reaches the confirmed tables
+    assert!(server
+      .get_json::<ApiResponse<brc20::ApiTransferableAssets>>(&format!(
+        "/api/v1/brc20/address/{owner}/transferable"
+      ))
+      .data
+      .inscriptions
+      .is_empty());
 
     server.mine_blocks(1);
+    server.index.update().unwrap();
+    server.index.update_brc20_mempool().unwrap();
 
-    server.assert_response_csp(
-      format!("/preview/{}", InscriptionId { txid, index: 0 }),
-      StatusCode::OK,
-      "default-src 'self' 'unsafe-eval' 'unsafe-inline' data: blob:",
-      "hello",
+    assert!(server
+      .get_json::<ApiResponse<brc20::ApiMempoolEvents>>(&format!(
+        "/api/v1/brc20/mempool/address/{owner}"
+      ))
+      .data
+      .events
+      .is_empty());
+  }
+
+  #[test]
+  fn brc20_all_transferable_pages_in_inscription_number_order() {
+    let server = TestServer::new_server(
+      test_bitcoincore_rpc::builder()
+        .network(bitcoin::Network::Regtest)
+        .build(),
+      None,
+      &["--chain", "regtest", "--enable-index-brc20"],
+      &[],
+    );
+
+    server.mine_blocks(1);
+
+    let inscribe = |height: usize, content: &str| {
+      let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+        inputs: &[(
+          height,
+          0,
+          0,
+          inscription("text/plain;charset=utf-8", content).to_witness(),
+        )],
+        ..Default::default()
+      });
+      server.mine_blocks(1);
+      txid
+    };
+
+    inscribe(
+      1,
+      r#"{"p":"brc-20","op":"deploy","tick":"ordi","max":"1000","lim":"10"}"#,
+    );
+    let mint = inscribe(2, r#"{"p":"brc-20","op":"mint","tick":"ordi","amt":"10"}"#);
+    let transfers = (3..6)
+      .map(|height| {
+        InscriptionId {
+          txid: inscribe(
+            height,
+            r#"{"p":"brc-20","op":"transfer","tick":"ordi","amt":"1"}"#,
+          ),
+          index: 0,
+        }
+        .to_string()
+      })
+      .collect::<Vec<String>>();
+
+    let owner = match server
+      .get_json::<ApiResponse<brc20::ApiTxEvents>>(&format!("/api/v1/brc20/tx/{mint}/events"))
+      .data
+      .events
+      .remove(0)
+    {
+      brc20::ApiTxEvent::Mint(event) => event.to,
+      event => panic!("unexpected event {event:?}"),
+    };
+    let owner = match owner {
+      types::ScriptPubkey::Address(address) => address,
+      types::ScriptPubkey::NonStandard(script_hash) => script_hash,
+    };
+
+    let first = server
+      .get_json::<ApiResponse<brc20::ApiTransferableAssets>>(&format!(
+        "/api/v1/brc20/address/{owner}/transferable?limit=2"
+      ))
+      .data;
+
+    assert_eq!(
+      first
+        .inscriptions
+        .iter()
+        .map(|asset| asset.inscription_id.clone())
+        .collect::<Vec<String>>(),
+      transfers[..2]
+    );
+    assert!(first.inscriptions[0].inscription_number < first.inscriptions[1].inscription_number);
+
+    let second = server
+      .get_json::<ApiResponse<brc20::ApiTransferableAssets>>(&format!(
+        "/api/v1/brc20/address/{owner}/transferable?limit=2&cursor={}",
+        first.next_cursor.unwrap()
+      ))
+      .data;
+
+    assert_eq!(second.inscriptions.len(), 1);
+    assert_eq!(second.inscriptions[0].inscription_id, transfers[2]);
+    assert_eq!(second.next_cursor, None);
+
+    let descending = server
+      .get_json::<ApiResponse<brc20::ApiTransferableAssets>>(&format!(
+        "/api/v1/brc20/address/{owner}/transferable?order=desc&limit=1"
+      ))
+      .data;
+
+    assert_eq!(descending.inscriptions[0].inscription_id, transfers[2]);
+    assert!(descending.next_cursor.is_some());
+  }
+
+  #[test]
+  fn brc20_mempool_address_events_requires_watcher() {
+    let server = TestServer::new_server(
+      test_bitcoincore_rpc::builder()
+        .network(bitcoin::Network::Regtest)
+        .build(),
+      None,
+      &["--chain", "regtest", "--enable-index-brc20"],
+      &[],
+    );
+
+    server.assert_response_regex(
+      "/api/v1/brc20/mempool/address/bcrt1qs758ursh4q9z627kt3pp5yysm78ddny6txaqgw",
+      StatusCode::BAD_REQUEST,
+      ".*BRC20 mempool is not enabled.*",
     );
   }
 
   #[test]
-  fn unknown_preview() {
-    let server = TestServer::new_with_regtest();
+  fn ord_domain_resolution() {
+    let server = TestServer::new_server(
+      test_bitcoincore_rpc::builder()
+        .network(bitcoin::Network::Regtest)
+        .build(),
+      None,
+      &[
+        "--chain",
+        "regtest",
+        "--enable-index-domain",
+        "--index-addresses",
+      ],
+      &[],
+    );
+
     server.mine_blocks(1);
 
     let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
-      inputs: &[(1, 0, 0, inscription("text/foo", "hello").to_witness())],
+      inputs: &[(1, 0, 0, inscription("text/plain", "Jack.btc").to_witness())],
+      ..Default::default()
+    });
+
+    server.mine_blocks(1);
+
+    let domain = server
+      .get_json::<ApiResponse<ord::ApiDomain>>("/api/v1/ord/domain/jack.btc")
+      .data;
+
+    assert_eq!(domain.name, "jack.btc");
+    assert_eq!(domain.collection, "btc_name");
+    assert_eq!(
+      domain.inscription_id,
+      InscriptionId { txid, index: 0 }.to_string()
+    );
+    assert_eq!(domain.location, format!("{txid}:0:0"));
+
+    let owner = match domain.owner.clone().unwrap() {
+      types::ScriptPubkey::Address(address) => address,
+      types::ScriptPubkey::NonStandard(script_hash) => script_hash,
+    };
+
+    server.mine_blocks(1);
+
+    let second = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[
+        (2, 0, 0, inscription("text/plain", "jill.btc").to_witness()),
+        (3, 0, 0, inscription("text/plain", "hello").to_witness()),
+      ],
       ..Default::default()
     });
 
     server.mine_blocks(1);
 
-    server.assert_response_csp(
-      format!("/preview/{}", InscriptionId { txid, index: 0 }),
-      StatusCode::OK,
-      "default-src 'self'",
-      fs::read_to_string("templates/preview-unknown.html").unwrap(),
+    let first_page = server
+      .get_json::<ApiResponse<ord::ApiDomains>>(&format!(
+        "/api/v1/ord/address/{owner}/domains?limit=1"
+      ))
+      .data;
+    let second_page = server
+      .get_json::<ApiResponse<ord::ApiDomains>>(&format!(
+        "/api/v1/ord/address/{owner}/domains?limit=1&cursor={}",
+        first_page.next_cursor.clone().unwrap()
+      ))
+      .data;
+    assert!(second_page.next_cursor.is_none());
+
+    let mut domains = first_page
+      .domains
+      .into_iter()
+      .chain(second_page.domains)
+      .map(|domain| (domain.name, domain.inscription_id))
+      .collect::<Vec<_>>();
+    domains.sort();
+    assert_eq!(
+      domains,
+      vec![
+        ("jack.btc".into(), domain.inscription_id.clone()),
+        (
+          "jill.btc".into(),
+          InscriptionId {
+            txid: second,
+            index: 0
+          }
+          .to_string()
+        ),
+      ]
+    );
+
+    server.assert_response_regex(
+      "/api/v1/ord/domain/joe.btc",
+      StatusCode::NOT_FOUND,
+      ".*domain joe.btc not found.*",
+    );
+    server.assert_response_regex(
+      "/api/v1/ord/domain/jack.com",
+      StatusCode::BAD_REQUEST,
+      ".*invalid domain jack.com.*",
     );
   }
 
   #[test]
-  fn video_preview() {
-    let server = TestServer::new_with_regtest();
-    server.mine_blocks(1);
+  fn ord_bitmap_district_and_parcels() {
+    let server = TestServer::new_server(
+      test_bitcoincore_rpc::builder()
+        .network(bitcoin::Network::Regtest)
+        .build(),
+      None,
+      &[
+        "--chain",
+        "regtest",
+        "--enable-index-bitmap",
+        "--first-inscription-height",
+        "4",
+      ],
+      &[],
+    );
 
-    let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
-      inputs: &[(1, 0, 0, inscription("video/webm", "hello").to_witness())],
+    server.mine_blocks(2);
+
+    server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, Default::default())],
+      ..Default::default()
+    });
+
+    // only the header of block 3 is fetched, below the first inscription height.
+    let district_block = server.mine_blocks(1)[0].clone();
+
+    let district_txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 0, 0, inscription("text/plain", "3.bitmap").to_witness())],
       ..Default::default()
     });
-    let inscription_id = InscriptionId { txid, index: 0 };
 
     server.mine_blocks(1);
 
+    let district_id = InscriptionId {
+      txid: district_txid,
+      index: 0,
+    };
+
+    let parcel = |content: &str| {
+      Inscription {
+        content_type: Some("text/plain".into()),
+        body: Some(content.into()),
+        parent: Some(district_id.value()),
+        ..Default::default()
+      }
+      .to_witness()
+    };
+
+    let mut parcel_ids = Vec::new();
+    for (height, content) in [(4, "0.3.bitmap"), (5, "1.3.bitmap"), (6, "2.3.bitmap")] {
+      parcel_ids.push(
+        InscriptionId {
+          txid: server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+            inputs: &[
+              (height, 0, 0, parcel(content)),
+              (height, 1, 0, Default::default()),
+            ],
+            ..Default::default()
+          }),
+          index: 0,
+        }
+        .to_string(),
+      );
+      server.mine_blocks(1);
+    }
+
+    let district = server
+      .get_json::<ApiResponse<ord::ApiDistrict>>("/api/v1/ord/bitmap/district/3")
+      .data;
+
+    assert_eq!(district.number, 3);
+    assert_eq!(district.inscription_id, district_id.to_string());
+    let block = district.block.clone().unwrap();
+    assert_eq!(block.tx_count, 2);
+    assert_eq!(block.size, u64::try_from(district_block.size()).unwrap());
+    assert_eq!(block.timestamp, district_block.header.time);
+    assert_eq!(block.blockhash, district_block.block_hash().to_string());
+
+    // block 3 has two transactions, so `2.3.bitmap` is not a parcel.
+    let parcels = server
+      .get_json::<ApiResponse<ord::ApiParcels>>("/api/v1/ord/bitmap/district/3/parcels?limit=1")
+      .data;
+    assert_eq!(parcels.parcels.len(), 1);
+    assert_eq!(parcels.parcels[0].name, "0.3.bitmap");
+    assert_eq!(parcels.parcels[0].inscription_id, parcel_ids[0]);
+
+    let parcels = server
+      .get_json::<ApiResponse<ord::ApiParcels>>(&format!(
+        "/api/v1/ord/bitmap/district/3/parcels?limit=1&cursor={}",
+        parcels.next_cursor.unwrap()
+      ))
+      .data;
+    assert_eq!(parcels.parcels.len(), 1);
+    assert_eq!(parcels.parcels[0].name, "1.3.bitmap");
+    assert_eq!(parcels.parcels[0].inscription_id, parcel_ids[1]);
+    assert_eq!(parcels.next_cursor, None);
+
     server.assert_response_regex(
-      format!("/preview/{inscription_id}"),
-      StatusCode::OK,
-      format!(r".*<video .*>\s*<source src=/content/{inscription_id}>.*"),
+      "/api/v1/ord/bitmap/district/2",
+      StatusCode::NOT_FOUND,
+      ".*district 2 not found.*",
     );
   }
 
   #[test]
-  fn inscription_page_title() {
-    let server = TestServer::new_with_regtest_with_index_sats();
+  fn ord_address_bitmaps() {
+    let server = TestServer::new_server(
+      test_bitcoincore_rpc::builder()
+        .network(bitcoin::Network::Regtest)
+        .build(),
+      None,
+      &[
+        "--chain",
+        "regtest",
+        "--enable-index-bitmap",
+        "--index-addresses",
+      ],
+      &[],
+    );
+
     server.mine_blocks(1);
 
-    let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
-      inputs: &[(1, 0, 0, inscription("text/foo", "hello").to_witness())],
+    server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "1.bitmap").to_witness())],
       ..Default::default()
     });
 
     server.mine_blocks(1);
 
-    server.assert_response_regex(
-      format!("/inscription/{}", InscriptionId { txid, index: 0 }),
-      StatusCode::OK,
-      ".*<title>Inscription 0</title>.*",
-    );
+    let district = server
+      .get_json::<ApiResponse<ord::ApiDistrict>>("/api/v1/ord/bitmap/district/1")
+      .data;
+
+    let owner = match district.owner.clone().unwrap() {
+      types::ScriptPubkey::Address(address) => address,
+      types::ScriptPubkey::NonStandard(script_hash) => script_hash,
+    };
+
+    let districts = server
+      .get_json::<ApiResponse<ord::ApiDistricts>>(&format!("/api/v1/ord/address/{owner}/bitmaps"))
+      .data;
+    assert_eq!(districts.districts, vec![district]);
+    assert_eq!(districts.next_cursor, None);
   }
 
   #[test]
-  fn inscription_page_has_sat_when_sats_are_tracked() {
-    let server = TestServer::new_with_regtest_with_index_sats();
-    server.mine_blocks(1);
+  fn ord_collection_listing() {
+    let server = TestServer::new_server(
+      test_bitcoincore_rpc::builder()
+        .network(bitcoin::Network::Regtest)
+        .build(),
+      None,
+      &["--chain", "regtest", "--enable-index-bitmap"],
+      &[],
+    );
 
-    let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
-      inputs: &[(1, 0, 0, inscription("text/foo", "hello").to_witness())],
-      ..Default::default()
-    });
+    server.mine_blocks(2);
 
-    server.mine_blocks(1);
+    let txids = ["0.bitmap", "1.bitmap", "0.bitmap"]
+      .into_iter()
+      .enumerate()
+      .map(|(i, content)| {
+        let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+          inputs: &[(i + 1, 0, 0, inscription("text/plain", content).to_witness())],
+          ..Default::default()
+        });
+        server.mine_blocks(1);
+        txid
+      })
+      .collect::<Vec<_>>();
+
+    assert_eq!(
+      server
+        .get_json::<ApiResponse<ord::ApiCollections>>("/api/v1/ord/collections")
+        .data
+        .collections,
+      vec![ord::ApiCollection {
+        kind: "bitmap".into(),
+        count: 2,
+      }]
+    );
+
+    let collection = server
+      .get_json::<ApiResponse<ord::ApiCollectionInscriptions>>(
+        "/api/v1/ord/collection/bitmap?order=desc&limit=1",
+      )
+      .data;
+    assert_eq!(collection.total, 2);
+    assert!(collection.next_cursor.is_some());
+    assert_eq!(
+      collection.inscriptions,
+      vec![ord::ApiCollectionInscription {
+        inscription_id: InscriptionId {
+          txid: txids[1],
+          index: 0
+        }
+        .to_string(),
+        inscription_number: 1,
+      }]
+    );
+
+    assert_eq!(
+      server
+        .get_json::<ApiResponse<ord::ApiInscription>>(&format!(
+          "/api/v1/ord/id/{}/inscription",
+          InscriptionId {
+            txid: txids[0],
+            index: 0
+          }
+        ))
+        .data
+        .collections,
+      vec!["bitmap"]
+    );
 
     server.assert_response_regex(
-      format!("/inscription/{}", InscriptionId { txid, index: 0 }),
-      StatusCode::OK,
-      r".*<dt>sat</dt>\s*<dd><a href=/sat/5000000000>5000000000</a></dd>\s*<dt>preview</dt>.*",
+      "/api/v1/ord/collection/btc_name",
+      StatusCode::NOT_FOUND,
+      ".*collection btc_name not found.*",
     );
   }
 
   #[test]
-  fn inscription_page_does_not_have_sat_when_sats_are_not_tracked() {
-    let server = TestServer::new_with_regtest();
+  fn ord_domain_resolution_with_configured_suffixes() {
+    let server = TestServer::new_server(
+      test_bitcoincore_rpc::builder()
+        .network(bitcoin::Network::Regtest)
+        .build(),
+      Some(
+        "
+hidden: []
+domains:
+  suffixes:
+  - suffix: ord
+    activation_height: 5
+  sns_activation_height: 4
+"
+        .into(),
+      ),
+      &["--chain", "regtest", "--enable-index-domain"],
+      &[],
+    );
+
+    server.mine_blocks(3);
+
+    let inscribe = |input: usize, content: &str| {
+      server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+        inputs: &[(input, 0, 0, inscription("text/plain", content).to_witness())],
+        ..Default::default()
+      })
+    };
+
+    // the suffix is not active at height 4.
+    inscribe(1, "jack.ord");
+    inscribe(
+      2,
+      r#"{"p":"sns","op":"reg","name":"jill.ord","desc":"ignored"}"#,
+    );
     server.mine_blocks(1);
 
-    let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
-      inputs: &[(1, 0, 0, inscription("text/foo", "hello").to_witness())],
-      ..Default::default()
-    });
+    server.assert_response_regex(
+      "/api/v1/ord/domain/jack.ord",
+      StatusCode::NOT_FOUND,
+      ".*domain jack.ord not found.*",
+    );
 
+    let jack = inscribe(3, "jack.ord");
     server.mine_blocks(1);
 
+    let domain = server
+      .get_json::<ApiResponse<ord::ApiDomain>>("/api/v1/ord/domain/jack.ord")
+      .data;
+    assert_eq!(domain.collection, "ord_name");
+    assert_eq!(
+      domain.inscription_id,
+      InscriptionId {
+        txid: jack,
+        index: 0
+      }
+      .to_string()
+    );
+
     server.assert_response_regex(
-      format!("/inscription/{}", InscriptionId { txid, index: 0 }),
-      StatusCode::OK,
-      r".*<dt>output value</dt>\s*<dd>5000000000</dd>\s*<dt>preview</dt>.*",
+      "/api/v1/ord/domain/jill.ord",
+      StatusCode::NOT_FOUND,
+      ".*domain jill.ord not found.*",
     );
-  }
 
-  #[test]
-  fn strict_transport_security_header_is_set() {
+    let sns = inscribe(4, r#"{"p":"sns","op":"reg","name":"jill.btc"}"#);
+    server.mine_blocks(1);
     assert_eq!(
-      TestServer::new()
-        .get("/status")
-        .headers()
-        .get(header::STRICT_TRANSPORT_SECURITY)
-        .unwrap(),
-      "max-age=31536000; includeSubDomains; preload",
+      server
+        .get_json::<ApiResponse<ord::ApiDomain>>("/api/v1/ord/domain/jill.btc")
+        .data
+        .inscription_id,
+      InscriptionId {
+        txid: sns,
+        index: 0
+      }
+      .to_string()
     );
   }
 
@@ -5120,6 +6519,233 @@ next
     assert_eq!(children_json.page, 1);
   }
 
+  #[test]
+  fn inscription_provenance_endpoints() {
+    let server = TestServer::new_with_regtest_with_json_api();
+    server.mine_blocks(1);
+
+    let parent_txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "parent").to_witness())],
+      ..Default::default()
+    });
+
+    server.mine_blocks(1);
+
+    let parent = InscriptionId {
+      txid: parent_txid,
+      index: 0,
+    };
+
+    let child = |parent: InscriptionId, content: &str| {
+      Inscription {
+        content_type: Some("text/plain".into()),
+        body: Some(content.into()),
+        parent: Some(parent.value()),
+        ..Default::default()
+      }
+      .to_witness()
+    };
+
+    let child_txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[
+        (2, 0, 0, child(parent, "child")),
+        (2, 1, 0, Default::default()),
+      ],
+      ..Default::default()
+    });
+
+    server.mine_blocks(1);
+
+    let child_id = InscriptionId {
+      txid: child_txid,
+      index: 0,
+    };
+
+    let grandchild_txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[
+        (3, 0, 0, child(child_id, "grandchild")),
+        (3, 1, 0, Default::default()),
+      ],
+      ..Default::default()
+    });
+
+    server.mine_blocks(1);
+
+    let grandchild_id = InscriptionId {
+      txid: grandchild_txid,
+      index: 0,
+    };
+
+    let children = server
+      .get_json::<ApiResponse<ord::ApiInscriptionChildren>>(format!(
+        "/api/v1/ord/id/{parent}/children"
+      ))
+      .data;
+    assert_eq!(children.total, 1);
+    assert_eq!(
+      children.children,
+      vec![ord::ApiChildInscription {
+        inscription_id: child_id.to_string(),
+        inscription_number: 1,
+        location: format!("{grandchild_txid}:0:5000000000"),
+        child_count: 1,
+      }]
+    );
+
+    let ancestors = server
+      .get_json::<ApiResponse<ord::ApiInscriptionAncestors>>(format!(
+        "/api/v1/ord/id/{grandchild_id}/ancestors"
+      ))
+      .data;
+    assert_eq!(ancestors.total, 2);
+    assert_eq!(
+      ancestors.ancestors,
+      vec![
+        ord::ApiAncestor {
+          inscription_id: child_id.to_string(),
+          inscription_number: 1,
+          depth: 1,
+        },
+        ord::ApiAncestor {
+          inscription_id: parent.to_string(),
+          inscription_number: 0,
+          depth: 2,
+        },
+      ]
+    );
+
+    let first_page = server
+      .get_json::<ApiResponse<ord::ApiInscriptionAncestors>>(format!(
+        "/api/v1/ord/id/{grandchild_id}/ancestors?limit=1"
+      ))
+      .data;
+    assert_eq!(first_page.ancestors.len(), 1);
+
+    assert_eq!(
+      server
+        .get_json::<ApiResponse<ord::ApiInscriptionAncestors>>(format!(
+          "/api/v1/ord/id/{grandchild_id}/ancestors?limit=1&cursor={}",
+          first_page.next_cursor.unwrap()
+        ))
+        .data
+        .ancestors,
+      vec![ord::ApiAncestor {
+        inscription_id: parent.to_string(),
+        inscription_number: 0,
+        depth: 2,
+      }]
+    );
+
+    assert_eq!(
+      server.get_json::<ChildInscriptionsJson>(format!("/r/children/{child_id}/inscriptions")),
+      ChildInscriptionsJson {
+        children: vec![ChildInscriptionJson {
+          id: grandchild_id,
+          number: 2,
+          satpoint: SatPoint {
+            outpoint: OutPoint {
+              txid: grandchild_txid,
+              vout: 0
+            },
+            offset: 0,
+          },
+          content_type: Some("text/plain".into()),
+        }],
+        more: false,
+        page: 0,
+      }
+    );
+
+    assert_eq!(
+      server
+        .get_json::<ChildInscriptionsJson>(format!("/r/children/{child_id}/inscriptions/1"))
+        .children,
+      Vec::new()
+    );
+
+    server.assert_response_regex(
+      format!("/api/v1/ord/id/{}/ancestors", inscription_id(1)),
+      StatusCode::NOT_FOUND,
+      ".*unknown inscription id.*",
+    );
+  }
+
+  #[test]
+  fn ord_address_inscriptions() {
+    let server = TestServer::new_server(
+      test_bitcoincore_rpc::builder()
+        .network(bitcoin::network::constants::Network::Regtest)
+        .build(),
+      None,
+      &["--chain", "regtest", "--index-addresses"],
+      &[],
+    );
+    server.mine_blocks(1);
+
+    let txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(1, 0, 0, inscription("text/plain", "hello").to_witness())],
+      ..Default::default()
+    });
+
+    server.mine_blocks(1);
+
+    let inscription_id = InscriptionId { txid, index: 0 };
+
+    // the test outputs pay to the empty script, which has no address
+    let script_key = ScriptBuf::new().script_hash();
+
+    let inscriptions = server
+      .get_json::<ApiResponse<ord::ApiAddressInscriptions>>(format!(
+        "/api/v1/ord/address/{script_key}/inscriptions"
+      ))
+      .data;
+    assert_eq!(
+      inscriptions,
+      ord::ApiAddressInscriptions {
+        inscriptions: vec![ord::ApiAddressInscription {
+          inscription_id: inscription_id.to_string(),
+          inscription_number: 0,
+          location: format!("{txid}:0:0"),
+        }],
+        next_cursor: None,
+      }
+    );
+
+    let transfer_txid = server.bitcoin_rpc_server.broadcast_tx(TransactionTemplate {
+      inputs: &[(2, 1, 0, Default::default())],
+      ..Default::default()
+    });
+
+    server.mine_blocks(1);
+
+    let inscriptions = server
+      .get_json::<ApiResponse<ord::ApiAddressInscriptions>>(format!(
+        "/api/v1/ord/address/{script_key}/inscriptions"
+      ))
+      .data;
+    assert_eq!(inscriptions.inscriptions.len(), 1);
+    assert_eq!(
+      inscriptions.inscriptions[0].location,
+      format!("{transfer_txid}:0:0")
+    );
+    assert_eq!(inscriptions.next_cursor, None);
+  }
+
+  #[test]
+  fn ord_address_inscriptions_requires_address_index() {
+    let server = TestServer::new_with_regtest_with_json_api();
+
+    assert_eq!(
+      server
+        .get(format!(
+          "/api/v1/ord/address/{}/inscriptions",
+          ScriptBuf::new().script_hash()
+        ))
+        .status(),
+      StatusCode::BAD_REQUEST
+    );
+  }
+
   #[test]
   fn inscriptions_in_block_page() {
     let server = TestServer::new_with_regtest_with_index_sats();
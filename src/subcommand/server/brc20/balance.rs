@@ -1,4 +1,9 @@
-use {super::*, crate::okx::datastore::brc20::Tick, axum::Json, utoipa::ToSchema};
+use {
+  super::*,
+  crate::okx::datastore::brc20::{Balance, Tick},
+  axum::Json,
+  utoipa::{IntoParams, ToSchema},
+};
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
@@ -17,15 +22,34 @@ pub struct ApiBalance {
   pub overall_balance: String,
 }
 
+impl From<Balance> for ApiBalance {
+  fn from(balance: Balance) -> Self {
+    Self {
+      tick: balance.tick.to_string(),
+      available_balance: (balance.overall_balance - balance.transferable_balance).to_string(),
+      transferable_balance: balance.transferable_balance.to_string(),
+      overall_balance: balance.overall_balance.to_string(),
+    }
+  }
+}
+
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct BalanceQuery {
+  /// Query the balance at the end of the block height instead of the latest one.
+  pub height: Option<u32>,
+}
+
 /// Get the ticker balance of the address.
 ///
-/// Retrieve the asset balance of the 'ticker' for the address.
+/// Retrieve the asset balance of the 'ticker' for the address, optionally at a past block height.
 #[utoipa::path(
     get,
     path = "/api/v1/brc20/tick/{ticker}/address/{address}/balance",
     params(
         ("ticker" = String, Path, description = "Token ticker", min_length = 4, max_length = 4),
-        ("address" = String, Path, description = "Address")
+        ("address" = String, Path, description = "Address"),
+        BalanceQuery
   ),
     responses(
       (status = 200, description = "Obtain account balance by query ticker.", body = BRC20Balance),
@@ -37,8 +61,9 @@ pub struct ApiBalance {
 pub(crate) async fn brc20_balance(
   Extension(index): Extension<Arc<Index>>,
   Path((tick, address)): Path<(String, String)>,
+  Query(query): Query<BalanceQuery>,
 ) -> ApiResult<ApiBalance> {
-  log::debug!("rpc: get brc20_balance: {} {}", tick, address);
+  log::debug!("rpc: get brc20_balance: {} {} {:?}", tick, address, query);
 
   let rtx = index.begin_read()?;
   let chain = index.get_chain();
@@ -47,19 +72,23 @@ pub(crate) async fn brc20_balance(
   let script_key = utils::parse_and_validate_script_key_with_chain(&address, chain)
     .map_err(ApiError::bad_request)?;
 
-  let balance = Index::get_brc20_balance_by_tick_and_address(ticker, script_key, &rtx)?
-    .ok_or(BRC20ApiError::UnknownTicker(tick.clone()))?;
-
-  let available_balance = balance.overall_balance - balance.transferable_balance;
+  let balance = match query.height {
+    Some(height) => {
+      if rtx
+        .block_height()?
+        .map_or(true, |indexed| height > indexed.n())
+      {
+        return Err(BRC20ApiError::HeightNotIndexed(height).into());
+      }
+      Index::get_brc20_balance_by_tick_and_address_at_height(ticker, script_key, height, &rtx)?
+    }
+    None => Index::get_brc20_balance_by_tick_and_address(ticker, script_key, &rtx)?,
+  }
+  .ok_or(BRC20ApiError::UnknownTicker(tick.clone()))?;
 
   log::debug!("rpc: get brc20_balance: {} {} {:?}", tick, address, balance);
 
-  Ok(Json(ApiResponse::ok(ApiBalance {
-    tick: balance.tick.to_string(),
-    available_balance: available_balance.to_string(),
-    transferable_balance: balance.transferable_balance.to_string(),
-    overall_balance: balance.overall_balance.to_string(),
-  })))
+  Ok(Json(ApiResponse::ok(balance.into())))
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
@@ -68,16 +97,20 @@ pub(crate) async fn brc20_balance(
 pub struct ApiBalances {
   #[schema(value_type = Vec<brc20::Balance>)]
   pub balance: Vec<ApiBalance>,
+  /// The cursor of the next page, absent on the last page.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub next_cursor: Option<String>,
 }
 
 /// Get all ticker balances of the address.
 ///
-/// Retrieve all BRC20 protocol asset balances associated with a address.
+/// Retrieve all BRC20 protocol asset balances associated with a address in ticker order.
 #[utoipa::path(
     get,
     path = "/api/v1/brc20/address/{address}/balance",
     params(
-        ("address" = String, Path, description = "Address")
+        ("address" = String, Path, description = "Address"),
+        CursorPagination
   ),
     responses(
       (status = 200, description = "Obtain account balances by query address.", body = BRC20AllBalance),
@@ -89,8 +122,9 @@ pub struct ApiBalances {
 pub(crate) async fn brc20_all_balance(
   Extension(index): Extension<Arc<Index>>,
   Path(account): Path<String>,
+  Query(pagination): Query<CursorPagination>,
 ) -> ApiResult<ApiBalances> {
-  log::debug!("rpc: get brc20_all_balance: {}", account);
+  log::debug!("rpc: get brc20_all_balance: {} {:?}", account, pagination);
 
   let rtx = index.begin_read()?;
   let chain = index.get_chain();
@@ -98,7 +132,11 @@ pub(crate) async fn brc20_all_balance(
   let script_key = utils::parse_and_validate_script_key_with_chain(&account, chain)
     .map_err(ApiError::bad_request)?;
 
-  let all_balance = rtx.brc20_get_all_balance_by_address(script_key)?;
+  let page = pagination.key_page()?;
+  let (all_balance, next_cursor) = CursorPagination::page(
+    &page,
+    rtx.brc20_get_balances_page_by_address(&script_key, &page)?,
+  );
   log::debug!("rpc: get brc20_all_balance: {} {:?}", account, all_balance);
 
   Ok(Json(ApiResponse::ok(ApiBalances {
@@ -111,5 +149,6 @@ pub(crate) async fn brc20_all_balance(
         overall_balance: bal.overall_balance.to_string(),
       })
       .collect(),
+    next_cursor,
   })))
 }
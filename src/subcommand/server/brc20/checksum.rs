@@ -0,0 +1,56 @@
+use {super::*, axum::Json, utoipa::ToSchema};
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[schema(as = brc20::BlockChecksum)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiBlockChecksum {
+  /// The height of the block.
+  #[schema(format = "uint32")]
+  pub height: u32,
+  /// The block hash.
+  pub blockhash: String,
+  /// The hex encoded sha256 checksum chaining the BRC20 receipts and balance changes of every block up to this one.
+  pub checksum: String,
+}
+
+/// Get the BRC20 checksum of the block.
+///
+/// Retrieve the rolling BRC20 state checksum at the block height. Replicas that indexed the same BRC20 state
+/// have the same checksum.
+#[utoipa::path(
+    get,
+    path = "/api/v1/brc20/block/{height}/checksum",
+    params(
+        ("height" = u32, Path, description = "Block height")
+  ),
+    responses(
+      (status = 200, description = "Obtain the BRC20 checksum of the block.", body = BRC20BlockChecksum),
+      (status = 400, description = "Bad query.", body = ApiError, example = json!(&ApiError::bad_request("bad request"))),
+      (status = 404, description = "Not found.", body = ApiError, example = json!(&ApiError::not_found("not found"))),
+      (status = 500, description = "Internal server error.", body = ApiError, example = json!(&ApiError::internal("internal error"))),
+    )
+  )]
+pub(crate) async fn brc20_block_checksum(
+  Extension(index): Extension<Arc<Index>>,
+  Path(height): Path<u32>,
+) -> ApiResult<ApiBlockChecksum> {
+  log::debug!("rpc: get brc20_block_checksum: {}", height);
+
+  let rtx = index.begin_read()?;
+
+  let block_hash = rtx
+    .block_hash(Some(height))?
+    .ok_or(BRC20ApiError::HeightNotIndexed(height))?;
+
+  let checksum = rtx
+    .brc20_get_block_checksum(height)?
+    .ok_or(BRC20ApiError::BlockChecksumNotFound(height))?;
+
+  log::debug!("rpc: get brc20_block_checksum: {} {}", height, checksum);
+
+  Ok(Json(ApiResponse::ok(ApiBlockChecksum {
+    height,
+    blockhash: block_hash.to_string(),
+    checksum: checksum.to_string(),
+  })))
+}
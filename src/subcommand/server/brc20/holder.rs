@@ -0,0 +1,80 @@
+use {super::*, crate::okx::datastore::brc20::Tick, axum::Json, utoipa::ToSchema};
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[schema(as = brc20::TickHolder)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiTickHolder {
+  /// The holder which is an address or script pubkey hash.
+  pub holder: ScriptPubkey,
+  /// Overall balance of the holder.
+  #[schema(format = "uint64")]
+  pub overall_balance: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[schema(as = brc20::TickHolders)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiTickHolders {
+  /// Name of the ticker.
+  pub tick: String,
+  /// The number of holders of the ticker.
+  #[schema(format = "uint64")]
+  pub total: u64,
+  #[schema(value_type = Vec<brc20::TickHolder>)]
+  pub holders: Vec<ApiTickHolder>,
+  /// The cursor of the next page, absent on the last page.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub next_cursor: Option<String>,
+}
+
+/// Get the holders of the ticker.
+///
+/// Retrieve the holders of the ticker sorted by overall balance in descending order.
+#[utoipa::path(
+    get,
+    path = "/api/v1/brc20/tick/{ticker}/holders",
+    params(
+        ("ticker" = String, Path, description = "Token ticker", min_length = 4, max_length = 5),
+        CursorPagination
+  ),
+    responses(
+      (status = 200, description = "Obtain the holders of the ticker.", body = BRC20TickHolders),
+      (status = 400, description = "Bad query.", body = ApiError, example = json!(&ApiError::bad_request("bad request"))),
+      (status = 404, description = "Not found.", body = ApiError, example = json!(&ApiError::not_found("not found"))),
+      (status = 500, description = "Internal server error.", body = ApiError, example = json!(&ApiError::internal("internal error"))),
+    )
+  )]
+pub(crate) async fn brc20_tick_holders(
+  Extension(index): Extension<Arc<Index>>,
+  Path(tick): Path<String>,
+  Query(pagination): Query<CursorPagination>,
+) -> ApiResult<ApiTickHolders> {
+  log::debug!("rpc: get brc20_tick_holders: {} {:?}", tick, pagination);
+
+  let rtx = index.begin_read()?;
+
+  let ticker = Tick::from_str(&tick).map_err(|_| BRC20ApiError::InvalidTicker(tick.clone()))?;
+  let tick_info = rtx
+    .brc20_get_tick_info(&ticker)?
+    .ok_or(BRC20ApiError::UnknownTicker(tick.clone()))?;
+
+  let total = rtx.brc20_get_tick_holders_count(&ticker)?;
+  let page = pagination.key_page()?;
+  let (holders, next_cursor) =
+    CursorPagination::page(&page, rtx.brc20_get_tick_holders_page(&ticker, &page)?);
+
+  log::debug!("rpc: get brc20_tick_holders: {} total {}", tick, total);
+
+  Ok(Json(ApiResponse::ok(ApiTickHolders {
+    tick: tick_info.tick.to_string(),
+    total,
+    holders: holders
+      .into_iter()
+      .map(|(script_key, overall_balance)| ApiTickHolder {
+        holder: script_key.into(),
+        overall_balance: overall_balance.to_string(),
+      })
+      .collect(),
+    next_cursor,
+  })))
+}
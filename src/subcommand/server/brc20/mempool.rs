@@ -0,0 +1,71 @@
+use {super::*, crate::okx::datastore::brc20::OperationType, axum::Json, utoipa::ToSchema};
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[schema(as = brc20::MempoolEvents)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiMempoolEvents {
+  /// The height of the block the pending events would be confirmed in.
+  #[schema(format = "uint32")]
+  pub height: u32,
+  /// The pending events grouped by unconfirmed transaction, in execution order.
+  #[schema(value_type = Vec<brc20::TxEvents>)]
+  pub events: Vec<ApiTxEvents>,
+}
+
+// /brc20/mempool/address/:address
+/// Get the pending events of the address.
+///
+/// Retrieve the inscribe-transfer and transfer events of unconfirmed transactions sent or received by the address,
+/// with the result they would have if the transactions were confirmed in the next block.
+#[utoipa::path(
+  get,
+  path = "/api/v1/brc20/mempool/address/{address}",
+  params(
+      ("address" = String, Path, description = "Address")
+),
+  responses(
+    (status = 200, description = "Obtain the pending events of the address.", body = BRC20MempoolEvents),
+    (status = 400, description = "Bad query.", body = ApiError, example = json!(&ApiError::bad_request("bad request"))),
+    (status = 404, description = "Not found.", body = ApiError, example = json!(&ApiError::not_found("not found"))),
+    (status = 500, description = "Internal server error.", body = ApiError, example = json!(&ApiError::internal("internal error"))),
+  )
+)]
+pub(crate) async fn brc20_mempool_address_events(
+  Extension(index): Extension<Arc<Index>>,
+  Path(address): Path<String>,
+) -> ApiResult<ApiMempoolEvents> {
+  log::debug!("rpc: get brc20_mempool_address_events: {}", address);
+
+  let script_key = utils::parse_and_validate_script_key_with_chain(&address, index.get_chain())
+    .map_err(ApiError::bad_request)?;
+
+  let receipts = index
+    .brc20_mempool_receipts(&script_key)
+    .ok_or(BRC20ApiError::MempoolNotEnabled)?;
+
+  let mut events: Vec<ApiTxEvents> = Vec::new();
+  for (txid, receipt) in receipts.into_iter().filter(|(_, receipt)| {
+    receipt.op == OperationType::InscribeTransfer || receipt.op == OperationType::Transfer
+  }) {
+    match events.last_mut() {
+      Some(tx_events) if tx_events.txid == txid.to_string() => {
+        tx_events.events.push(receipt.into())
+      }
+      _ => events.push(ApiTxEvents {
+        txid: txid.to_string(),
+        events: vec![receipt.into()],
+      }),
+    }
+  }
+
+  log::debug!(
+    "rpc: get brc20_mempool_address_events: {} {:?}",
+    address,
+    events
+  );
+
+  Ok(Json(ApiResponse::ok(ApiMempoolEvents {
+    height: index.block_count()?,
+    events,
+  })))
+}
@@ -1,11 +1,51 @@
 use super::{types::ScriptPubkey, *};
 mod balance;
+mod checksum;
+mod holder;
+mod mempool;
 mod outpoint;
+mod pagination;
 mod receipt;
 mod ticker;
 mod transferable;
 
-pub(super) use {balance::*, outpoint::*, receipt::*, ticker::*, transferable::*};
+pub(super) use {
+  balance::*, checksum::*, holder::*, mempool::*, outpoint::*, pagination::*, receipt::*,
+  ticker::*, transferable::*,
+};
+
+/// The routes of the BRC20 API.
+pub(crate) fn api_router() -> Router {
+  Router::new()
+    .route("/brc20/tick/:tick", get(brc20_tick_info))
+    .route("/brc20/tick", get(brc20_all_tick_info))
+    .route("/brc20/tick/:tick/holders", get(brc20_tick_holders))
+    .route(
+      "/brc20/tick/:tick/address/:address/balance",
+      get(brc20_balance),
+    )
+    .route("/brc20/address/:address/balance", get(brc20_all_balance))
+    .route(
+      "/brc20/tick/:tick/address/:address/transferable",
+      get(brc20_transferable),
+    )
+    .route(
+      "/brc20/address/:address/transferable",
+      get(brc20_all_transferable),
+    )
+    .route(
+      "/brc20/outpoint/:outpoint/transferable",
+      get(brc20_outpoint),
+    )
+    .route("/brc20/tx/:txid/events", get(brc20_tx_events))
+    .route("/brc20/block/:block_hash/events", get(brc20_block_events))
+    .route("/brc20/block/:height/checksum", get(brc20_block_checksum))
+    .route("/brc20/address/:address/events", get(brc20_address_events))
+    .route(
+      "/brc20/mempool/address/:address",
+      get(brc20_mempool_address_events),
+    )
+}
 
 #[derive(Debug, thiserror::Error)]
 pub(super) enum BRC20ApiError {
@@ -16,6 +56,17 @@ pub(super) enum BRC20ApiError {
   /// Thrown when a transaction receipt was requested but not matching transaction receipt exists
   #[error("transaction receipt {0} not found")]
   TransactionReceiptNotFound(Txid),
+  #[error("height {0} has not been indexed yet")]
+  HeightNotIndexed(u32),
+  #[error("BRC20 checksum of block {0} not found")]
+  BlockChecksumNotFound(u32),
+  #[error("invalid cursor {0}")]
+  InvalidCursor(String),
+  #[error("invalid limit {0}, must be greater than 0")]
+  InvalidLimit(usize),
+  /// Thrown when pending operations were requested but the mempool is not watched
+  #[error("BRC20 mempool is not enabled")]
+  MempoolNotEnabled,
   /// Thrown when an internal error occurs
   #[error("internal error: {0}")]
   Internal(String),
@@ -27,6 +78,11 @@ impl From<BRC20ApiError> for ApiError {
       BRC20ApiError::InvalidTicker(_) => Self::bad_request(error.to_string()),
       BRC20ApiError::UnknownTicker(_) => Self::not_found(error.to_string()),
       BRC20ApiError::TransactionReceiptNotFound(_) => Self::not_found(error.to_string()),
+      BRC20ApiError::HeightNotIndexed(_) => Self::bad_request(error.to_string()),
+      BRC20ApiError::BlockChecksumNotFound(_) => Self::not_found(error.to_string()),
+      BRC20ApiError::InvalidCursor(_) => Self::bad_request(error.to_string()),
+      BRC20ApiError::InvalidLimit(_) => Self::bad_request(error.to_string()),
+      BRC20ApiError::MempoolNotEnabled => Self::bad_request(error.to_string()),
       BRC20ApiError::Internal(_) => Self::internal(error.to_string()),
     }
   }
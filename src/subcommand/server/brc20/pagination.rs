@@ -0,0 +1,142 @@
+use {
+  super::*,
+  crate::okx::datastore::KeyPage,
+  utoipa::{IntoParams, ToSchema},
+};
+
+const DEFAULT_PAGE_LIMIT: usize = 100;
+const MAX_PAGE_LIMIT: usize = 1000;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum Order {
+  /// Ascending key order.
+  #[default]
+  Asc,
+  /// Descending key order.
+  Desc,
+}
+
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct CursorPagination {
+  /// Maximum number of entries per page, 100 by default and at most 1000.
+  pub limit: Option<usize>,
+  /// The `nextCursor` returned by the previous page.
+  pub cursor: Option<String>,
+  /// Order of the entries, `asc` by default.
+  #[param(inline)]
+  pub order: Option<Order>,
+}
+
+impl CursorPagination {
+  /// The page of entries in key order selected by the query.
+  ///
+  /// The cursor is the hex encoded key of the last entry of the previous page, so pages stay stable
+  /// while new entries are indexed.
+  pub(crate) fn key_page(&self) -> Result<KeyPage, BRC20ApiError> {
+    let limit = match self.limit {
+      Some(0) => return Err(BRC20ApiError::InvalidLimit(0)),
+      Some(limit) => limit.min(MAX_PAGE_LIMIT),
+      None => DEFAULT_PAGE_LIMIT,
+    };
+
+    let after = self
+      .cursor
+      .as_ref()
+      .map(|cursor| {
+        hex::decode(cursor)
+          .ok()
+          .and_then(|key| String::from_utf8(key).ok())
+          .ok_or_else(|| BRC20ApiError::InvalidCursor(cursor.clone()))
+      })
+      .transpose()?;
+
+    Ok(KeyPage {
+      after,
+      limit,
+      reverse: self.order.unwrap_or_default() == Order::Desc,
+    })
+  }
+
+  /// Splits the entries read for `page` into the page and the cursor of the next page.
+  pub(crate) fn page<T>(page: &KeyPage, entries: Vec<(String, T)>) -> (Vec<T>, Option<String>) {
+    let (entries, next) = page.split(entries);
+    (entries, next.map(|key| hex::encode(key.as_bytes())))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn key_page_applies_default_and_maximum_limit() {
+    assert_eq!(
+      CursorPagination::default().key_page().unwrap().limit,
+      DEFAULT_PAGE_LIMIT
+    );
+    assert_eq!(
+      CursorPagination {
+        limit: Some(5000),
+        ..Default::default()
+      }
+      .key_page()
+      .unwrap()
+      .limit,
+      MAX_PAGE_LIMIT
+    );
+  }
+
+  #[test]
+  fn key_page_decodes_cursor_and_order() {
+    assert_eq!(
+      CursorPagination {
+        limit: Some(3),
+        cursor: Some(hex::encode("b_2")),
+        order: Some(Order::Desc),
+      }
+      .key_page()
+      .unwrap(),
+      KeyPage {
+        after: Some("b_2".into()),
+        limit: 3,
+        reverse: true,
+      }
+    );
+  }
+
+  #[test]
+  fn next_cursor_is_hex_encoded_key() {
+    let page = KeyPage {
+      limit: 1,
+      ..Default::default()
+    };
+
+    assert_eq!(
+      CursorPagination::page(&page, vec![("a".to_string(), 1), ("b".to_string(), 2)]),
+      (vec![1], Some(hex::encode("a")))
+    );
+    assert_eq!(
+      CursorPagination::page(&page, vec![("a".to_string(), 1)]),
+      (vec![1], None)
+    );
+  }
+
+  #[test]
+  fn key_page_rejects_invalid_params() {
+    assert!(CursorPagination {
+      limit: Some(0),
+      ..Default::default()
+    }
+    .key_page()
+    .is_err());
+
+    assert!(CursorPagination {
+      cursor: Some("zz".into()),
+      ..Default::default()
+    }
+    .key_page()
+    .is_err());
+  }
+}
@@ -1,6 +1,9 @@
 use {
-  self::okx::datastore::brc20::OperationType, super::*,
-  crate::okx::datastore::brc20 as brc20_store, axum::Json, utoipa::ToSchema,
+  self::okx::datastore::brc20::OperationType,
+  super::*,
+  crate::okx::datastore::brc20 as brc20_store,
+  axum::Json,
+  utoipa::{IntoParams, ToSchema},
 };
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
@@ -338,16 +341,20 @@ pub(crate) async fn brc20_tx_events(
 pub struct ApiBlockEvents {
   #[schema(value_type = Vec<brc20::TxEvents>)]
   pub block: Vec<ApiTxEvents>,
+  /// The cursor of the next page, absent on the last page.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub next_cursor: Option<String>,
 }
 
 /// Get block events by blockhash.
 ///
-/// Retrieve all BRC20 events associated with a block.
+/// Retrieve all BRC20 events associated with a block in transaction order.
 #[utoipa::path(
     get,
     path = "/api/v1/brc20/block/{blockhash}/events",
     params(
-        ("blockhash" = String, Path, description = "block hash")
+        ("blockhash" = String, Path, description = "block hash"),
+        CursorPagination
   ),
     responses(
       (status = 200, description = "Obtain block events by block hash", body = BRC20BlockEvents),
@@ -359,15 +366,25 @@ pub struct ApiBlockEvents {
 pub(crate) async fn brc20_block_events(
   Extension(index): Extension<Arc<Index>>,
   Path(blockhash): Path<String>,
+  Query(pagination): Query<CursorPagination>,
 ) -> ApiResult<ApiBlockEvents> {
-  log::debug!("rpc: get brc20_block_events: {}", blockhash);
+  log::debug!(
+    "rpc: get brc20_block_events: {} {:?}",
+    blockhash,
+    pagination
+  );
 
   let blockhash = bitcoin::BlockHash::from_str(&blockhash).map_err(ApiError::bad_request)?;
 
   let rtx = index.begin_read()?;
   let client = index.bitcoin_rpc_client()?;
 
-  let block_events = Index::get_brc20_block_receipts(blockhash, &rtx, &client)?;
+  // the transactions keep the block order, so their position is a stable key.
+  let page = pagination.key_page()?;
+  let (block_events, next_cursor) = CursorPagination::page(
+    &page,
+    Index::get_brc20_block_receipts(blockhash, &rtx, &client, &page)?,
+  );
 
   log::debug!(
     "rpc: get brc20_block_events: {} {:?}",
@@ -382,7 +399,125 @@ pub(crate) async fn brc20_block_events(
         txid: txid.to_string(),
         events: events.into_iter().map(|e| e.into()).collect(),
       })
-      .filter(|e| !e.events.is_empty())
       .collect(),
+    next_cursor,
+  })))
+}
+
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct AddressEventsFilter {
+  /// Only return events of the ticker.
+  pub tick: Option<String>,
+  /// Only return events of the operation type, one of `deploy`, `mint`, `inscribeTransfer` and `transfer`.
+  pub op: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[schema(as = brc20::AddressEvents)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiAddressEvents {
+  #[schema(value_type = Vec<brc20::TxEvents>)]
+  pub events: Vec<ApiTxEvents>,
+  /// The cursor of the next page, absent on the last page.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub next_cursor: Option<String>,
+}
+
+/// Get the events of the address.
+///
+/// Retrieve all BRC20 events sent or received by the address, in block order.
+#[utoipa::path(
+    get,
+    path = "/api/v1/brc20/address/{address}/events",
+    params(
+        ("address" = String, Path, description = "Address"),
+        AddressEventsFilter,
+        CursorPagination
+  ),
+    responses(
+      (status = 200, description = "Obtain account events by query address.", body = BRC20AddressEvents),
+      (status = 400, description = "Bad query.", body = ApiError, example = json!(&ApiError::bad_request("bad request"))),
+      (status = 404, description = "Not found.", body = ApiError, example = json!(&ApiError::not_found("not found"))),
+      (status = 500, description = "Internal server error.", body = ApiError, example = json!(&ApiError::internal("internal error"))),
+    )
+  )]
+pub(crate) async fn brc20_address_events(
+  Extension(index): Extension<Arc<Index>>,
+  Path(address): Path<String>,
+  Query(filter): Query<AddressEventsFilter>,
+  Query(pagination): Query<CursorPagination>,
+) -> ApiResult<ApiAddressEvents> {
+  log::debug!(
+    "rpc: get brc20_address_events: {} {:?} {:?}",
+    address,
+    filter,
+    pagination
+  );
+
+  let rtx = index.begin_read()?;
+  let chain = index.get_chain();
+
+  let script_key = utils::parse_and_validate_script_key_with_chain(&address, chain)
+    .map_err(ApiError::bad_request)?;
+
+  let tick = filter
+    .tick
+    .as_ref()
+    .map(|tick| {
+      brc20_store::Tick::from_str(tick).map_err(|_| BRC20ApiError::InvalidTicker(tick.clone()))
+    })
+    .transpose()?;
+
+  if let Some(op) = &filter.op {
+    if ![
+      OperationType::Deploy,
+      OperationType::Mint,
+      OperationType::InscribeTransfer,
+      OperationType::Transfer,
+    ]
+    .iter()
+    .any(|op_type| op_type.to_string() == *op)
+    {
+      return Err(ApiError::bad_request(format!(
+        "invalid operation type {op}"
+      )));
+    }
+  }
+
+  let page = pagination.key_page()?;
+  let address_events =
+    rtx.brc20_get_transactions_page_by_address(&script_key, &page, |receipt| {
+      filter
+        .op
+        .as_ref()
+        .map_or(true, |op| receipt.op.to_string() == *op)
+        && tick.as_ref().map_or(true, |tick| {
+          let receipt_tick = match &receipt.result {
+            Ok(brc20_store::Event::Deploy(event)) => &event.tick,
+            Ok(brc20_store::Event::Mint(event)) => &event.tick,
+            Ok(brc20_store::Event::InscribeTransfer(event)) => &event.tick,
+            Ok(brc20_store::Event::Transfer(event)) => &event.tick,
+            Err(_) => return false,
+          };
+          receipt_tick.to_lowercase() == tick.to_lowercase()
+        })
+    })?;
+
+  let (address_events, next_cursor) = CursorPagination::page(&page, address_events);
+
+  let events = address_events
+    .into_iter()
+    .map(|(txid, receipts)| ApiTxEvents {
+      txid: txid.to_string(),
+      events: receipts.into_iter().map(|receipt| receipt.into()).collect(),
+    })
+    .collect::<Vec<_>>();
+
+  log::debug!("rpc: get brc20_address_events: {} {:?}", address, events);
+
+  Ok(Json(ApiResponse::ok(ApiAddressEvents {
+    events,
+    next_cursor,
   })))
 }
@@ -2,7 +2,7 @@ use {
   super::*,
   crate::okx::datastore::brc20::{Tick, TokenInfo},
   axum::Json,
-  utoipa::ToSchema,
+  utoipa::{IntoParams, ToSchema},
 };
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
@@ -55,10 +55,15 @@ pub struct ApiTickInfo {
   /// The timestamp of the block that the ticker deployed.
   #[schema(format = "uint32")]
   pub deploy_blocktime: u32,
+  /// The number of addresses holding a positive balance of the ticker.<br>
+  /// Absent in state changes, as the number of holders is only indexed for the latest block.
+  #[schema(format = "uint64")]
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub holders: Option<u64>,
 }
 
-impl From<TokenInfo> for ApiTickInfo {
-  fn from(tick_info: TokenInfo) -> Self {
+impl ApiTickInfo {
+  pub(crate) fn new(tick_info: TokenInfo, holders: Option<u64>) -> Self {
     Self {
       tick: tick_info.tick.to_string(),
       inscription_id: tick_info.inscription_id.to_string(),
@@ -73,6 +78,7 @@ impl From<TokenInfo> for ApiTickInfo {
       txid: tick_info.inscription_id.txid.to_string(),
       deploy_height: tick_info.deployed_number,
       deploy_blocktime: tick_info.deployed_timestamp,
+      holders,
     }
   }
 }
@@ -106,9 +112,14 @@ pub(crate) async fn brc20_tick_info(
     .brc20_get_tick_info(&ticker)?
     .ok_or(BRC20ApiError::UnknownTicker(tick.clone()))?;
 
+  let holders = rtx.brc20_get_tick_holders_count(&ticker)?;
+
   log::debug!("rpc: get brc20_tick_info: {:?} {:?}", tick, tick_info);
 
-  Ok(Json(ApiResponse::ok(tick_info.into())))
+  Ok(Json(ApiResponse::ok(ApiTickInfo::new(
+    tick_info,
+    Some(holders),
+  ))))
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
@@ -117,14 +128,46 @@ pub(crate) async fn brc20_tick_info(
 pub struct ApiTickInfos {
   #[schema(value_type = Vec<brc20::TickInfo>)]
   pub tokens: Vec<ApiTickInfo>,
+  /// The cursor of the next page, absent on the last page.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub next_cursor: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct TickFilter {
+  /// Only return tickers whose self mint flag matches.
+  pub self_mint: Option<bool>,
+  /// Only return tickers deployed after the height.
+  pub deployed_after_height: Option<u32>,
+  /// Only return tickers whose minted amount has or has not reached the supply.
+  pub fully_minted: Option<bool>,
+}
+
+impl TickFilter {
+  fn matches(&self, tick_info: &TokenInfo) -> bool {
+    self
+      .self_mint
+      .map_or(true, |self_mint| tick_info.is_self_mint == self_mint)
+      && self
+        .deployed_after_height
+        .map_or(true, |height| tick_info.deployed_number > height)
+      && self.fully_minted.map_or(true, |fully_minted| {
+        (tick_info.minted >= tick_info.supply) == fully_minted
+      })
+  }
 }
 
 /// Get all tickers info.
 ///
-/// Retrieve detailed information about all tickers.
+/// Retrieve detailed information about all tickers in ticker order.
 #[utoipa::path(
     get,
     path = "/api/v1/brc20/tick",
+    params(
+        CursorPagination,
+        TickFilter
+  ),
     responses(
       (status = 200, description = "Obtain matching all BRC20 tickers.", body = BRC20AllTick),
       (status = 400, description = "Bad query.", body = ApiError, example = json!(&ApiError::bad_request("bad request"))),
@@ -134,14 +177,32 @@ pub struct ApiTickInfos {
   )]
 pub(crate) async fn brc20_all_tick_info(
   Extension(index): Extension<Arc<Index>>,
+  Query(pagination): Query<CursorPagination>,
+  Query(filter): Query<TickFilter>,
 ) -> ApiResult<ApiTickInfos> {
-  log::debug!("rpc: get brc20_all_tick_info");
+  log::debug!(
+    "rpc: get brc20_all_tick_info: {:?} {:?}",
+    pagination,
+    filter
+  );
+
+  let page = pagination.key_page()?;
 
   let rtx = index.begin_read()?;
-  let all_tick_info = rtx.brc20_get_all_tick_info()?;
+  let (all_tick_info, next_cursor) = CursorPagination::page(
+    &page,
+    rtx.brc20_get_tick_info_page(&page, |tick_info| filter.matches(tick_info))?,
+  );
   log::debug!("rpc: get brc20_all_tick_info: {:?}", all_tick_info);
 
+  let mut tokens = Vec::new();
+  for tick_info in all_tick_info {
+    let holders = rtx.brc20_get_tick_holders_count(&tick_info.tick)?;
+    tokens.push(ApiTickInfo::new(tick_info, Some(holders)));
+  }
+
   Ok(Json(ApiResponse::ok(ApiTickInfos {
-    tokens: all_tick_info.into_iter().map(|t| t.into()).collect(),
+    tokens,
+    next_cursor,
   })))
 }
@@ -1,4 +1,9 @@
-use {super::*, crate::okx::datastore::brc20::Tick, axum::Json, utoipa::ToSchema};
+use {
+  super::*,
+  crate::okx::datastore::brc20::{Tick, TransferableLog},
+  axum::Json,
+  utoipa::ToSchema,
+};
 
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
 #[schema(as = brc20::TransferableAsset)]
@@ -19,6 +24,19 @@ pub struct ApiTransferableAsset {
   pub location: SatPoint,
 }
 
+impl From<(SatPoint, TransferableLog)> for ApiTransferableAsset {
+  fn from((satpoint, transferable_asset): (SatPoint, TransferableLog)) -> Self {
+    Self {
+      inscription_id: transferable_asset.inscription_id.to_string(),
+      inscription_number: transferable_asset.inscription_number,
+      amount: transferable_asset.amount.to_string(),
+      tick: transferable_asset.tick.as_str().to_string(),
+      owner: transferable_asset.owner.to_string(),
+      location: satpoint,
+    }
+  }
+}
+
 /// Get the transferable inscriptions of the address.
 ///
 /// Retrieve the transferable inscriptions with the ticker from the given address.
@@ -58,22 +76,16 @@ pub(crate) async fn brc20_transferable(
     brc20_transferable_assets
   );
 
-  let mut api_transferable_assets = Vec::new();
-  for (satpoint, transferable_asset) in brc20_transferable_assets {
-    api_transferable_assets.push(ApiTransferableAsset {
-      inscription_id: transferable_asset.inscription_id.to_string(),
-      inscription_number: transferable_asset.inscription_number,
-      amount: transferable_asset.amount.to_string(),
-      tick: transferable_asset.tick.as_str().to_string(),
-      owner: transferable_asset.owner.to_string(),
-      location: satpoint,
-    });
-  }
+  let mut api_transferable_assets: Vec<ApiTransferableAsset> = brc20_transferable_assets
+    .into_iter()
+    .map(|asset| asset.into())
+    .collect();
 
-  api_transferable_assets.sort_by(|a, b| a.inscription_number.cmp(&b.inscription_number));
+  api_transferable_assets.sort_by_key(|asset| asset.inscription_number);
 
   Ok(Json(ApiResponse::ok(ApiTransferableAssets {
     inscriptions: api_transferable_assets,
+    next_cursor: None,
   })))
 }
 
@@ -83,16 +95,20 @@ pub(crate) async fn brc20_transferable(
 pub struct ApiTransferableAssets {
   #[schema(value_type = Vec<brc20::brc20::TransferableAsset>)]
   pub inscriptions: Vec<brc20::ApiTransferableAsset>,
+  /// The cursor of the next page, absent on the last page.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub next_cursor: Option<String>,
 }
 
 /// Get the balance of ticker of the address.
 ///
-/// Retrieve the balance of the ticker from the given address.
+/// Retrieve the transferable inscriptions of all tickers from the given address, ordered by inscription number.
 #[utoipa::path(
   get,
   path = "/api/v1/brc20/address/{address}/transferable",
   params(
-      ("address" = String, Path, description = "Address")
+      ("address" = String, Path, description = "Address"),
+      CursorPagination
 ),
   responses(
     (status = 200, description = "Obtain account all transferable inscriptions.", body = BRC20Transferable),
@@ -104,8 +120,9 @@ pub struct ApiTransferableAssets {
 pub(crate) async fn brc20_all_transferable(
   Extension(index): Extension<Arc<Index>>,
   Path(account): Path<String>,
+  Query(pagination): Query<CursorPagination>,
 ) -> ApiResult<ApiTransferableAssets> {
-  log::debug!("rpc: get brc20_all_transferable: {account}");
+  log::debug!("rpc: get brc20_all_transferable: {account} {pagination:?}");
 
   let rtx = index.begin_read()?;
   let chain = index.get_chain();
@@ -113,27 +130,23 @@ pub(crate) async fn brc20_all_transferable(
   let script_key = utils::parse_and_validate_script_key_with_chain(&account, chain)
     .map_err(ApiError::bad_request)?;
 
-  let brc20_transferable_assets = rtx.brc20_get_all_transferable_by_address(script_key)?;
+  let page = pagination.key_page()?;
+  let (brc20_transferable_assets, next_cursor) = CursorPagination::page(
+    &page,
+    rtx.brc20_get_transferable_page_by_address(&script_key, &page)?,
+  );
   log::debug!(
     "rpc: get brc20_all_transferable: {account} {:?}",
     brc20_transferable_assets
   );
 
-  let mut api_transferable_assets = Vec::new();
-  for (satpoint, transferable_asset) in brc20_transferable_assets {
-    api_transferable_assets.push(ApiTransferableAsset {
-      inscription_id: transferable_asset.inscription_id.to_string(),
-      inscription_number: transferable_asset.inscription_number,
-      amount: transferable_asset.amount.to_string(),
-      tick: transferable_asset.tick.as_str().to_string(),
-      owner: transferable_asset.owner.to_string(),
-      location: satpoint,
-    });
-  }
-
-  api_transferable_assets.sort_by(|a, b| a.inscription_number.cmp(&b.inscription_number));
+  let api_transferable_assets = brc20_transferable_assets
+    .into_iter()
+    .map(|asset| asset.into())
+    .collect();
 
   Ok(Json(ApiResponse::ok(ApiTransferableAssets {
     inscriptions: api_transferable_assets,
+    next_cursor,
   })))
 }
@@ -0,0 +1,307 @@
+use {
+  super::*,
+  crate::{
+    index::{event::BlockEvent, rtx::Rtx},
+    okx::datastore::KeyPage,
+  },
+  axum::response::sse::{Event, KeepAlive, Sse},
+  futures::{stream, Stream, StreamExt as _},
+  std::{collections::BTreeMap, convert::Infallible},
+  tokio::sync::broadcast::{self, error::RecvError},
+  utoipa::IntoParams,
+};
+
+/// The maximum number of blocks replayed before the feed yields its events to the subscriber.
+const FEED_BATCH_BLOCKS: u32 = 10;
+
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct FeedQuery {
+  /// Replay the indexed blocks from the height before following new blocks.
+  pub from_height: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct FeedBlock {
+  height: u32,
+  blockhash: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct FeedBRC20Events {
+  height: u32,
+  #[serde(flatten)]
+  events: brc20::ApiTxEvents,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct FeedInscriptionOps {
+  height: u32,
+  #[serde(flatten)]
+  inscriptions: ord::ApiTxInscriptions,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct FeedResync {
+  height: u32,
+}
+
+/// Subscribe to the indexed events.
+///
+/// Stream server-sent events for every block committed to the index. A `blockConnected` event is followed by the
+/// `brc20Events` and `inscriptionOps` events of the block, and a `blockDisconnected` event is sent for every block
+/// rolled back by a reorg, before the blocks replacing it.
+///
+/// The last event of each block has the id `<height>:<blockhash>`, which resumes the feed after that block when sent
+/// back in the `Last-Event-ID` header. A `resync` event is sent when the feed can no longer tell which of the blocks
+/// it sent were rolled back, because the subscriber fell behind or resumed from a block that is no longer indexed.
+/// The subscriber should then discard the blocks from the height of the event, which the feed sends again.
+#[utoipa::path(
+    get,
+    path = "/api/v1/feed",
+    params(
+        FeedQuery,
+        ("Last-Event-ID" = Option<String>, Header, description = "The id of the last event received, `<height>:<blockhash>`")
+  ),
+    responses(
+      (status = 200, description = "Stream of indexed events.", body = String, content_type = "text/event-stream"),
+      (status = 400, description = "Bad query.", body = ApiError, example = json!(&ApiError::bad_request("bad request"))),
+    )
+  )]
+pub(crate) async fn event_feed(
+  Extension(index): Extension<Arc<Index>>,
+  Query(query): Query<FeedQuery>,
+  headers: HeaderMap,
+) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, ApiError> {
+  log::debug!("rpc: get event_feed: {:?}", query);
+
+  let last_event_id = headers
+    .get("last-event-id")
+    .map(|id| {
+      id.to_str()
+        .ok()
+        .and_then(|id| id.split_once(':'))
+        .and_then(|(height, block_hash)| Some((height.parse().ok()?, block_hash.parse().ok()?)))
+        .ok_or_else(|| ApiError::bad_request(format!("invalid Last-Event-ID {id:?}")))
+    })
+    .transpose()?;
+
+  // subscribe before reading the index, so no block committed in between is missed.
+  let block_events = index.subscribe_block_events();
+
+  let mut sent = BTreeMap::new();
+  let next_height = match last_event_id {
+    Some((height, block_hash)) => {
+      sent.insert(height, block_hash);
+      height + 1
+    }
+    None => match query.from_height {
+      Some(height) => height,
+      None => index.block_count()?,
+    },
+  };
+
+  let feed = Feed {
+    index,
+    block_events,
+    next_height,
+    sent,
+    caught_up: false,
+    lagged: false,
+  };
+
+  let events = stream::unfold(Some(feed), |feed| async move {
+    let mut feed = feed?;
+
+    if feed.caught_up {
+      match feed.block_events.recv().await {
+        Ok(BlockEvent::Connected { .. } | BlockEvent::Disconnected { .. }) => {}
+        Err(RecvError::Lagged(_)) => feed.lagged = true,
+        Err(RecvError::Closed) => return None,
+      }
+    }
+
+    match tokio::task::spawn_blocking(move || {
+      let events = feed.sync();
+      (feed, events)
+    })
+    .await
+    {
+      Ok((feed, Ok(events))) => Some((events, Some(feed))),
+      Ok((_, Err(err))) => {
+        log::warn!("event feed stopped: {err}");
+        None
+      }
+      Err(err) => {
+        log::warn!("event feed stopped: {err}");
+        None
+      }
+    }
+  })
+  .flat_map(|events| stream::iter(events.into_iter().map(Ok)));
+
+  Ok(Sse::new(events).keep_alive(KeepAlive::default()))
+}
+
+struct Feed {
+  index: Arc<Index>,
+  block_events: broadcast::Receiver<BlockEvent>,
+  next_height: u32,
+  /// The hashes of the most recent blocks sent to the subscriber, by height.
+  sent: BTreeMap<u32, BlockHash>,
+  caught_up: bool,
+  lagged: bool,
+}
+
+impl Feed {
+  /// The events bringing the subscriber up to date with the index.
+  ///
+  /// The blocks sent to the subscriber are checked against the index first, so the blocks rolled back by a reorg are
+  /// always disconnected before the blocks replacing them are sent, however the notifications were received.
+  fn sync(&mut self) -> Result<Vec<Event>> {
+    let index = self.index.clone();
+    let rtx = index.begin_read()?;
+    let mut events = Vec::new();
+
+    if std::mem::take(&mut self.lagged) {
+      events.push(self.resync(&rtx)?);
+    }
+
+    while let Some((&height, &block_hash)) = self.sent.last_key_value() {
+      if rtx.block_hash(Some(height))? == Some(block_hash) {
+        break;
+      }
+
+      self.sent.remove(&height);
+      self.next_height = height;
+
+      if self.sent.is_empty() {
+        // the rolled back blocks before this one are unknown.
+        events.push(self.resync(&rtx)?);
+        break;
+      }
+
+      let mut event = Event::default()
+        .event("blockDisconnected")
+        .json_data(FeedBlock {
+          height,
+          blockhash: block_hash.to_string(),
+        })?;
+      if let Some((&parent_height, parent_hash)) = self.sent.last_key_value() {
+        event = event.id(format!("{parent_height}:{parent_hash}"));
+      }
+      events.push(event);
+    }
+
+    let mut replayed = 0;
+    self.caught_up = true;
+    while let Some(block_hash) = rtx.block_hash(Some(self.next_height))? {
+      if replayed == FEED_BATCH_BLOCKS {
+        self.caught_up = false;
+        break;
+      }
+
+      let height = self.next_height;
+      let mut block_events = block_connected_events(&index, &rtx, height, block_hash)?;
+      let last = block_events.pop().unwrap();
+      block_events.push(last.id(format!("{height}:{block_hash}")));
+      events.extend(block_events);
+
+      self.sent.insert(height, block_hash);
+      self.next_height += 1;
+      replayed += 1;
+    }
+
+    let oldest = self.next_height.saturating_sub(index.max_reorg_depth() + 1);
+    self.sent = self.sent.split_off(&oldest);
+
+    Ok(events)
+  }
+
+  /// Sends the blocks within the maximum reorg depth again, as the feed can't tell which of them were rolled back.
+  fn resync(&mut self, rtx: &Rtx) -> Result<Event> {
+    let height = self
+      .next_height
+      .saturating_sub(self.index.max_reorg_depth());
+
+    self.sent.clear();
+    self.next_height = height;
+
+    let mut event = Event::default()
+      .event("resync")
+      .json_data(FeedResync { height })?;
+    if let Some(parent_height) = height.checked_sub(1) {
+      if let Some(parent_hash) = rtx.block_hash(Some(parent_height))? {
+        self.sent.insert(parent_height, parent_hash);
+        event = event.id(format!("{parent_height}:{parent_hash}"));
+      }
+    }
+
+    Ok(event)
+  }
+}
+
+fn block_connected_events(
+  index: &Index,
+  rtx: &Rtx,
+  height: u32,
+  block_hash: BlockHash,
+) -> Result<Vec<Event>> {
+  let client = index.client();
+
+  let mut events = vec![Event::default()
+    .event("blockConnected")
+    .json_data(FeedBlock {
+      height,
+      blockhash: block_hash.to_string(),
+    })?];
+
+  for (_, (txid, receipts)) in
+    Index::get_brc20_block_receipts(block_hash, rtx, client, &KeyPage::default())?
+  {
+    events.push(
+      Event::default()
+        .event("brc20Events")
+        .json_data(FeedBRC20Events {
+          height,
+          events: brc20::ApiTxEvents {
+            txid: txid.to_string(),
+            events: receipts.into_iter().map(|e| e.into()).collect(),
+          },
+        })?,
+    );
+  }
+
+  for (txid, operations) in Index::get_ord_block_inscription_operations(block_hash, rtx, client)? {
+    if operations.is_empty() {
+      continue;
+    }
+    let mut inscriptions = Vec::new();
+    for operation in operations {
+      inscriptions.push(ord::ApiTxInscription::parse_from_operation(
+        operation,
+        rtx,
+        client,
+        index.get_chain(),
+        index.has_transactions_index(),
+      )?);
+    }
+    events.push(
+      Event::default()
+        .event("inscriptionOps")
+        .json_data(FeedInscriptionOps {
+          height,
+          inscriptions: ord::ApiTxInscriptions {
+            inscriptions,
+            txid: txid.to_string(),
+          },
+        })?,
+    );
+  }
+
+  Ok(events)
+}
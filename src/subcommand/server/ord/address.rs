@@ -0,0 +1,101 @@
+use {super::*, axum::Json, utoipa::ToSchema};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[schema(as = ord::ApiAddressInscription)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiAddressInscription {
+  /// The inscription id.
+  pub inscription_id: String,
+  /// The inscription number.
+  pub inscription_number: i32,
+  /// The current location of the inscription.
+  pub location: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[schema(as = ord::ApiAddressInscriptions)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiAddressInscriptions {
+  /// The page of inscriptions in outpoint and offset order.
+  #[schema(value_type = Vec<ord::ApiAddressInscription>)]
+  pub inscriptions: Vec<ApiAddressInscription>,
+  /// The cursor of the next page, absent on the last page.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub next_cursor: Option<String>,
+}
+
+// /ord/address/:address/inscriptions
+/// Get the inscriptions owned by the address.
+///
+/// Retrieve the inscriptions on the outputs of the address. Requires the server to index with `--index-addresses`.
+#[utoipa::path(
+  get,
+  path = "/api/v1/ord/address/{address}/inscriptions",
+  params(
+      ("address" = String, Path, description = "Address"),
+      CursorPagination
+),
+  responses(
+    (status = 200, description = "Obtain the inscriptions owned by the address.", body = ApiOrdAddressInscriptions),
+    (status = 400, description = "Bad query.", body = ApiError, example = json!(&ApiError::bad_request("bad request"))),
+    (status = 404, description = "Not found.", body = ApiError, example = json!(&ApiError::not_found("not found"))),
+    (status = 500, description = "Internal server error.", body = ApiError, example = json!(&ApiError::internal("internal error"))),
+  )
+)]
+pub(crate) async fn ord_address_inscriptions(
+  Extension(index): Extension<Arc<Index>>,
+  Path(address): Path<String>,
+  Query(pagination): Query<CursorPagination>,
+) -> ApiResult<ApiAddressInscriptions> {
+  log::debug!(
+    "rpc: get ord_address_inscriptions: {} {:?}",
+    address,
+    pagination
+  );
+
+  if !index.has_address_index() {
+    return Err(ApiError::bad_request(
+      "address index is not enabled, run the server with --index-addresses.",
+    ));
+  }
+
+  let script_key = utils::parse_and_validate_script_key_with_chain(&address, index.get_chain())
+    .map_err(ApiError::bad_request)?;
+
+  let rtx = index.begin_read()?;
+
+  let page = pagination.key_page()?;
+  let (satpoints, next_cursor) = CursorPagination::page(
+    &page,
+    rtx.script_key_inscriptions_page(
+      &script_key.to_string(),
+      &page,
+      |satpoint, inscription_id| Ok(Some((satpoint, inscription_id))),
+    )?,
+  );
+
+  let inscriptions = satpoints
+    .into_iter()
+    .map(|(satpoint, inscription_id)| {
+      let entry = rtx
+        .get_inscription_entry(inscription_id)?
+        .ok_or(OrdApiError::UnknownInscriptionId(inscription_id))?;
+      Ok(ApiAddressInscription {
+        inscription_id: inscription_id.to_string(),
+        inscription_number: entry.inscription_number,
+        location: satpoint.to_string(),
+      })
+    })
+    .collect::<Result<Vec<_>, ApiError>>()?;
+
+  log::debug!(
+    "rpc: get ord_address_inscriptions: {} {:?}",
+    address,
+    inscriptions
+  );
+
+  Ok(Json(ApiResponse::ok(ApiAddressInscriptions {
+    inscriptions,
+    next_cursor,
+  })))
+}
@@ -0,0 +1,313 @@
+use {
+  super::*,
+  crate::okx::datastore::ord::{bitmap::District, collections::CollectionKind},
+  axum::Json,
+  utoipa::ToSchema,
+};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[schema(as = ord::ApiDistrictBlock)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiDistrictBlock {
+  /// The hash of the block mined at the district height.
+  pub blockhash: String,
+  /// The block timestamp.
+  #[schema(format = "uint32")]
+  pub timestamp: u32,
+  /// The number of transactions in the block, which is the number of parcels of the district.
+  #[schema(format = "uint32")]
+  pub tx_count: u32,
+  /// The serialized size of the block in bytes.
+  #[schema(format = "uint64")]
+  pub size: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[schema(as = ord::ApiDistrict)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiDistrict {
+  /// The district number, which is the height of its block.
+  #[schema(format = "uint32")]
+  pub number: u32,
+  /// The id of the first inscription registering the district.
+  pub inscription_id: String,
+  /// The number of the inscription.
+  pub inscription_number: i32,
+  /// The current location of the inscription.
+  pub location: String,
+  /// The current owner of the inscription, absent while the inscription is unbound.
+  pub owner: Option<ScriptPubkey>,
+  /// The block of the district, absent if the block was indexed before bitmap indexing started.
+  #[schema(value_type = Option<ord::ApiDistrictBlock>)]
+  pub block: Option<ApiDistrictBlock>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[schema(as = ord::ApiDistricts)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiDistricts {
+  /// The page of districts in outpoint and offset order of their inscriptions.
+  #[schema(value_type = Vec<ord::ApiDistrict>)]
+  pub districts: Vec<ApiDistrict>,
+  /// The cursor of the next page, absent on the last page.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub next_cursor: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[schema(as = ord::ApiParcel)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiParcel {
+  /// The parcel name, e.g. `0.100.bitmap`.
+  pub name: String,
+  /// The parcel number, which is the index of a transaction in the district block.
+  #[schema(format = "uint32")]
+  pub number: u32,
+  /// The id of the first inscription registering the parcel.
+  pub inscription_id: String,
+  /// The number of the inscription.
+  pub inscription_number: i32,
+  /// The current location of the inscription.
+  pub location: String,
+  /// The current owner of the inscription, absent while the inscription is unbound.
+  pub owner: Option<ScriptPubkey>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[schema(as = ord::ApiParcels)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiParcels {
+  /// The district number.
+  #[schema(format = "uint32")]
+  pub district: u32,
+  /// The page of registered parcels of the district in ascending number order.
+  #[schema(value_type = Vec<ord::ApiParcel>)]
+  pub parcels: Vec<ApiParcel>,
+  /// The cursor of the next page, absent on the last page.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub next_cursor: Option<String>,
+}
+
+// /ord/bitmap/district/:number
+/// Get the bitmap district.
+///
+/// Retrieve the inscription registering the `<number>.bitmap` district with its current owner and the data of its block.
+#[utoipa::path(
+  get,
+  path = "/api/v1/ord/bitmap/district/{number}",
+  params(
+      ("number" = u32, Path, description = "District number")
+),
+  responses(
+    (status = 200, description = "Obtain the district.", body = ApiOrdDistrict),
+    (status = 400, description = "Bad query.", body = ApiError, example = json!(&ApiError::bad_request("bad request"))),
+    (status = 404, description = "Not found.", body = ApiError, example = json!(&ApiError::not_found("not found"))),
+    (status = 500, description = "Internal server error.", body = ApiError, example = json!(&ApiError::internal("internal error"))),
+  )
+)]
+pub(crate) async fn ord_bitmap_district(
+  Extension(index): Extension<Arc<Index>>,
+  Path(number): Path<u32>,
+) -> ApiResult<ApiDistrict> {
+  log::debug!("rpc: get ord_bitmap_district: {number}");
+
+  let rtx = index.begin_read()?;
+  let inscription_id = rtx
+    .ord_district_to_inscription_id(number)?
+    .ok_or_api_not_found(format!("district {number} not found."))?;
+
+  let ownership = resolve_ownership(&index, &rtx, &index.bitcoin_rpc_client()?, inscription_id)?;
+  let district = resolve_district(&rtx, number, inscription_id, ownership)?;
+
+  log::debug!("rpc: get ord_bitmap_district: {number} {:?}", district);
+
+  Ok(Json(ApiResponse::ok(district)))
+}
+
+// /ord/bitmap/district/:number/parcels
+/// Get the parcels of the bitmap district.
+///
+/// Retrieve the `<parcel>.<number>.bitmap` inscriptions registered as children of the district inscription.
+#[utoipa::path(
+  get,
+  path = "/api/v1/ord/bitmap/district/{number}/parcels",
+  params(
+      ("number" = u32, Path, description = "District number"),
+      CursorPagination
+),
+  responses(
+    (status = 200, description = "Obtain the parcels of the district.", body = ApiOrdParcels),
+    (status = 400, description = "Bad query.", body = ApiError, example = json!(&ApiError::bad_request("bad request"))),
+    (status = 404, description = "Not found.", body = ApiError, example = json!(&ApiError::not_found("not found"))),
+    (status = 500, description = "Internal server error.", body = ApiError, example = json!(&ApiError::internal("internal error"))),
+  )
+)]
+pub(crate) async fn ord_bitmap_district_parcels(
+  Extension(index): Extension<Arc<Index>>,
+  Path(number): Path<u32>,
+  Query(pagination): Query<CursorPagination>,
+) -> ApiResult<ApiParcels> {
+  log::debug!(
+    "rpc: get ord_bitmap_district_parcels: {number} {:?}",
+    pagination
+  );
+
+  let rtx = index.begin_read()?;
+  rtx
+    .ord_district_to_inscription_id(number)?
+    .ok_or_api_not_found(format!("district {number} not found."))?;
+
+  // a district has at most one parcel per transaction of its block, the owners are only resolved for the page.
+  let page = pagination.key_page()?;
+  let (parcels, next_cursor) = CursorPagination::page(
+    &page,
+    page.read_sorted(
+      rtx.bitmap_parcels(number)?.into_iter().map(Ok),
+      |(parcel, _)| format!("{parcel:08x}"),
+      |parcel| Ok(Some(parcel)),
+    )?,
+  );
+
+  let client = index.bitcoin_rpc_client()?;
+  let parcels = parcels
+    .into_iter()
+    .map(|(parcel, inscription_id)| {
+      let ownership = resolve_ownership(&index, &rtx, &client, inscription_id)?;
+      Ok(ApiParcel {
+        name: format!("{parcel}.{number}.bitmap"),
+        number: parcel,
+        inscription_id: inscription_id.to_string(),
+        inscription_number: ownership.inscription_number,
+        location: ownership.location.to_string(),
+        owner: ownership.owner,
+      })
+    })
+    .collect::<Result<Vec<_>, ApiError>>()?;
+
+  log::debug!(
+    "rpc: get ord_bitmap_district_parcels: {number} {:?}",
+    parcels
+  );
+
+  Ok(Json(ApiResponse::ok(ApiParcels {
+    district: number,
+    parcels,
+    next_cursor,
+  })))
+}
+
+// /ord/address/:address/bitmaps
+/// Get the bitmap districts owned by the address.
+///
+/// Retrieve the districts whose registering inscriptions are on the outputs of the address. Requires the server to
+/// index with `--index-addresses`.
+#[utoipa::path(
+  get,
+  path = "/api/v1/ord/address/{address}/bitmaps",
+  params(
+      ("address" = String, Path, description = "Address"),
+      CursorPagination
+),
+  responses(
+    (status = 200, description = "Obtain the districts owned by the address.", body = ApiOrdDistricts),
+    (status = 400, description = "Bad query.", body = ApiError, example = json!(&ApiError::bad_request("bad request"))),
+    (status = 404, description = "Not found.", body = ApiError, example = json!(&ApiError::not_found("not found"))),
+    (status = 500, description = "Internal server error.", body = ApiError, example = json!(&ApiError::internal("internal error"))),
+  )
+)]
+pub(crate) async fn ord_address_bitmaps(
+  Extension(index): Extension<Arc<Index>>,
+  Path(address): Path<String>,
+  Query(pagination): Query<CursorPagination>,
+) -> ApiResult<ApiDistricts> {
+  log::debug!("rpc: get ord_address_bitmaps: {} {:?}", address, pagination);
+
+  if !index.has_address_index() {
+    return Err(ApiError::bad_request(
+      "address index is not enabled, run the server with --index-addresses.",
+    ));
+  }
+
+  let script_key = utils::parse_and_validate_script_key_with_chain(&address, index.get_chain())
+    .map_err(ApiError::bad_request)?;
+
+  let rtx = index.begin_read()?;
+
+  let page = pagination.key_page()?;
+  let (districts, next_cursor) = CursorPagination::page(
+    &page,
+    rtx.script_key_inscriptions_page(
+      &script_key.to_string(),
+      &page,
+      |satpoint, inscription_id| {
+        // only the inscriptions marked as registering a district are parsed.
+        if !rtx
+          .ord_inscription_id_to_collections(inscription_id)?
+          .unwrap_or_default()
+          .contains(&CollectionKind::BitMap)
+        {
+          return Ok(None);
+        }
+
+        let entry = rtx
+          .get_inscription_entry(inscription_id)?
+          .ok_or_else(|| anyhow!("inscription {inscription_id} not found"))?;
+        let Some(district) = index
+          .get_inscription_by_id(inscription_id)?
+          .and_then(|inscription| District::parse(inscription.body()?).ok())
+        else {
+          return Ok(None);
+        };
+
+        Ok(Some((
+          district.number,
+          inscription_id,
+          Ownership {
+            inscription_number: entry.inscription_number,
+            location: satpoint,
+            owner: Some(script_key.clone().into()),
+          },
+        )))
+      },
+    )?,
+  );
+
+  let districts = districts
+    .into_iter()
+    .map(|(number, inscription_id, ownership)| {
+      resolve_district(&rtx, number, inscription_id, ownership)
+    })
+    .collect::<Result<Vec<_>, ApiError>>()?;
+
+  log::debug!("rpc: get ord_address_bitmaps: {address} {:?}", districts);
+
+  Ok(Json(ApiResponse::ok(ApiDistricts {
+    districts,
+    next_cursor,
+  })))
+}
+
+fn resolve_district(
+  rtx: &Rtx,
+  number: u32,
+  inscription_id: InscriptionId,
+  ownership: Ownership,
+) -> Result<ApiDistrict, ApiError> {
+  let block = rtx
+    .bitmap_district_block(number)?
+    .map(|(header, district_block)| ApiDistrictBlock {
+      blockhash: header.block_hash().to_string(),
+      timestamp: header.time,
+      tx_count: district_block.tx_count,
+      size: district_block.size,
+    });
+
+  Ok(ApiDistrict {
+    number,
+    inscription_id: inscription_id.to_string(),
+    inscription_number: ownership.inscription_number,
+    location: ownership.location.to_string(),
+    owner: ownership.owner,
+    block,
+  })
+}
@@ -0,0 +1,136 @@
+use {super::*, axum::Json, utoipa::ToSchema};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[schema(as = ord::ApiCollection)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiCollection {
+  /// The collection kind, e.g. `bitmap` or `btc_name`.
+  pub kind: String,
+  /// The number of inscriptions in the collection.
+  #[schema(format = "uint64")]
+  pub count: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[schema(as = ord::ApiCollections)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiCollections {
+  #[schema(value_type = Vec<ord::ApiCollection>)]
+  pub collections: Vec<ApiCollection>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[schema(as = ord::ApiCollectionInscription)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiCollectionInscription {
+  /// The inscription id.
+  pub inscription_id: String,
+  /// The inscription number.
+  pub inscription_number: i32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[schema(as = ord::ApiCollectionInscriptions)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiCollectionInscriptions {
+  /// The collection kind.
+  pub kind: String,
+  /// The number of inscriptions in the collection.
+  #[schema(format = "uint64")]
+  pub total: u64,
+  /// The page of inscriptions in inscription order.
+  #[schema(value_type = Vec<ord::ApiCollectionInscription>)]
+  pub inscriptions: Vec<ApiCollectionInscription>,
+  /// The cursor of the next page, absent on the last page.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub next_cursor: Option<String>,
+}
+
+// /ord/collections
+/// Get the collections.
+///
+/// Retrieve every collection kind with inscriptions and the number of its inscriptions.
+#[utoipa::path(
+  get,
+  path = "/api/v1/ord/collections",
+  responses(
+    (status = 200, description = "Obtain the collections.", body = ApiOrdCollections),
+    (status = 500, description = "Internal server error.", body = ApiError, example = json!(&ApiError::internal("internal error"))),
+  )
+)]
+pub(crate) async fn ord_collections(
+  Extension(index): Extension<Arc<Index>>,
+) -> ApiResult<ApiCollections> {
+  log::debug!("rpc: get ord_collections");
+
+  let collections = index
+    .begin_read()?
+    .ord_collection_counts()?
+    .into_iter()
+    .map(|(kind, count)| ApiCollection { kind, count })
+    .collect::<Vec<_>>();
+
+  log::debug!("rpc: get ord_collections: {:?}", collections);
+
+  Ok(Json(ApiResponse::ok(ApiCollections { collections })))
+}
+
+// /ord/collection/:kind
+/// Get the inscriptions of the collection.
+///
+/// Retrieve the inscriptions of the collection kind in inscription order.
+#[utoipa::path(
+  get,
+  path = "/api/v1/ord/collection/{kind}",
+  params(
+      ("kind" = String, Path, description = "Collection kind, e.g. bitmap or btc_name"),
+      CursorPagination
+),
+  responses(
+    (status = 200, description = "Obtain the inscriptions of the collection.", body = ApiOrdCollectionInscriptions),
+    (status = 400, description = "Bad query.", body = ApiError, example = json!(&ApiError::bad_request("bad request"))),
+    (status = 404, description = "Not found.", body = ApiError, example = json!(&ApiError::not_found("not found"))),
+    (status = 500, description = "Internal server error.", body = ApiError, example = json!(&ApiError::internal("internal error"))),
+  )
+)]
+pub(crate) async fn ord_collection_inscriptions(
+  Extension(index): Extension<Arc<Index>>,
+  Path(kind): Path<String>,
+  Query(pagination): Query<CursorPagination>,
+) -> ApiResult<ApiCollectionInscriptions> {
+  log::debug!(
+    "rpc: get ord_collection_inscriptions: {} {:?}",
+    kind,
+    pagination
+  );
+
+  let rtx = index.begin_read()?;
+  let total = rtx
+    .ord_collection_count(&kind)?
+    .ok_or_api_not_found(format!("collection {kind} not found."))?;
+
+  let page = pagination.key_page()?;
+  let (entries, next_cursor) =
+    CursorPagination::page(&page, rtx.ord_collection_inscriptions_page(&kind, &page)?);
+
+  let inscriptions = entries
+    .into_iter()
+    .map(|entry| ApiCollectionInscription {
+      inscription_id: entry.id.to_string(),
+      inscription_number: entry.inscription_number,
+    })
+    .collect::<Vec<_>>();
+
+  log::debug!(
+    "rpc: get ord_collection_inscriptions: {} total {}",
+    kind,
+    total
+  );
+
+  Ok(Json(ApiResponse::ok(ApiCollectionInscriptions {
+    kind,
+    total,
+    inscriptions,
+    next_cursor,
+  })))
+}
@@ -0,0 +1,190 @@
+use {
+  super::{error::ApiError, types::ScriptPubkey, *},
+  crate::{
+    index::rtx::Rtx,
+    okx::datastore::ord::{btc_name::BtcDomain, collections::CollectionKind},
+  },
+  axum::Json,
+  utoipa::ToSchema,
+};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[schema(as = ord::ApiDomain)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiDomain {
+  /// The domain name, e.g. `jack.btc`.
+  pub name: String,
+  /// The domain collection, e.g. `btc_name`.
+  pub collection: String,
+  /// The id of the first inscription registering the domain.
+  pub inscription_id: String,
+  /// The number of the inscription.
+  pub inscription_number: i32,
+  /// The current location of the inscription.
+  pub location: String,
+  /// The current owner of the inscription, absent while the inscription is unbound.
+  pub owner: Option<ScriptPubkey>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[schema(as = ord::ApiDomains)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiDomains {
+  /// The page of domains in outpoint and offset order of their inscriptions.
+  #[schema(value_type = Vec<ord::ApiDomain>)]
+  pub domains: Vec<ApiDomain>,
+  /// The cursor of the next page, absent on the last page.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub next_cursor: Option<String>,
+}
+
+// /ord/domain/:name
+/// Resolve the domain name.
+///
+/// Retrieve the inscription registering the `.btc`, `.unisat`, `.sats`, `.x` or configured domain with its current location and owner.
+#[utoipa::path(
+  get,
+  path = "/api/v1/ord/domain/{name}",
+  params(
+      ("name" = String, Path, description = "Domain name, e.g. jack.btc")
+),
+  responses(
+    (status = 200, description = "Obtain the domain.", body = ApiOrdDomain),
+    (status = 400, description = "Bad query.", body = ApiError, example = json!(&ApiError::bad_request("bad request"))),
+    (status = 404, description = "Not found.", body = ApiError, example = json!(&ApiError::not_found("not found"))),
+    (status = 500, description = "Internal server error.", body = ApiError, example = json!(&ApiError::internal("internal error"))),
+  )
+)]
+pub(crate) async fn ord_domain(
+  Extension(index): Extension<Arc<Index>>,
+  Path(name): Path<String>,
+) -> ApiResult<ApiDomain> {
+  log::debug!("rpc: get ord_domain: {name}");
+
+  let domain = BtcDomain::parse(
+    name.as_bytes(),
+    &index.protocol_config().domain_config().suffixes(),
+  )
+  .map_err(|_| ApiError::bad_request(format!("invalid domain {name}")))?;
+
+  let rtx = index.begin_read()?;
+  let inscription_id = rtx
+    .btc_name_to_inscription_id(&domain)?
+    .ok_or_api_not_found(format!("domain {domain} not found."))?;
+
+  let domain = resolve_domain(
+    &index,
+    &rtx,
+    &index.bitcoin_rpc_client()?,
+    domain,
+    inscription_id,
+  )?;
+
+  log::debug!("rpc: get ord_domain: {name} {:?}", domain);
+
+  Ok(Json(ApiResponse::ok(domain)))
+}
+
+// /ord/address/:address/domains
+/// Get the domains owned by the address.
+///
+/// Retrieve the domains whose registering inscriptions are on the outputs of the address. Requires the server to index
+/// with `--index-addresses`.
+#[utoipa::path(
+  get,
+  path = "/api/v1/ord/address/{address}/domains",
+  params(
+      ("address" = String, Path, description = "Address"),
+      CursorPagination
+),
+  responses(
+    (status = 200, description = "Obtain the domains owned by the address.", body = ApiOrdDomains),
+    (status = 400, description = "Bad query.", body = ApiError, example = json!(&ApiError::bad_request("bad request"))),
+    (status = 404, description = "Not found.", body = ApiError, example = json!(&ApiError::not_found("not found"))),
+    (status = 500, description = "Internal server error.", body = ApiError, example = json!(&ApiError::internal("internal error"))),
+  )
+)]
+pub(crate) async fn ord_address_domains(
+  Extension(index): Extension<Arc<Index>>,
+  Path(address): Path<String>,
+  Query(pagination): Query<CursorPagination>,
+) -> ApiResult<ApiDomains> {
+  log::debug!("rpc: get ord_address_domains: {} {:?}", address, pagination);
+
+  if !index.has_address_index() {
+    return Err(ApiError::bad_request(
+      "address index is not enabled, run the server with --index-addresses.",
+    ));
+  }
+
+  let script_key = utils::parse_and_validate_script_key_with_chain(&address, index.get_chain())
+    .map_err(ApiError::bad_request)?;
+
+  let rtx = index.begin_read()?;
+  let config = index.protocol_config().domain_config();
+
+  let page = pagination.key_page()?;
+  let (domains, next_cursor) = CursorPagination::page(
+    &page,
+    rtx.script_key_inscriptions_page(
+      &script_key.to_string(),
+      &page,
+      |satpoint, inscription_id| {
+        // only the inscriptions marked as registering a domain are parsed.
+        if !rtx
+          .ord_inscription_id_to_collections(inscription_id)?
+          .unwrap_or_default()
+          .iter()
+          .any(CollectionKind::is_domain)
+        {
+          return Ok(None);
+        }
+
+        let entry = rtx
+          .get_inscription_entry(inscription_id)?
+          .ok_or_else(|| anyhow!("inscription {inscription_id} not found"))?;
+        let Some(domain) = index
+          .get_inscription_by_id(inscription_id)?
+          .and_then(|inscription| BtcDomain::resolve(inscription.body()?, config, entry.height))
+        else {
+          return Ok(None);
+        };
+
+        Ok(Some(ApiDomain {
+          name: domain.to_string(),
+          collection: domain.collection_kind.to_string(),
+          inscription_id: inscription_id.to_string(),
+          inscription_number: entry.inscription_number,
+          location: satpoint.to_string(),
+          owner: Some(script_key.clone().into()),
+        }))
+      },
+    )?,
+  );
+
+  log::debug!("rpc: get ord_address_domains: {address} {:?}", domains);
+
+  Ok(Json(ApiResponse::ok(ApiDomains {
+    domains,
+    next_cursor,
+  })))
+}
+
+fn resolve_domain(
+  index: &Index,
+  rtx: &Rtx,
+  client: &Client,
+  domain: BtcDomain,
+  inscription_id: InscriptionId,
+) -> Result<ApiDomain, ApiError> {
+  let ownership = resolve_ownership(index, rtx, client, inscription_id)?;
+
+  Ok(ApiDomain {
+    name: domain.to_string(),
+    collection: domain.collection_kind.to_string(),
+    inscription_id: inscription_id.to_string(),
+    inscription_number: ownership.inscription_number,
+    location: ownership.location.to_string(),
+    owner: ownership.owner,
+  })
+}
@@ -1,6 +1,9 @@
 use {
   super::{error::ApiError, types::ScriptPubkey, *},
-  crate::{index::rtx::Rtx, okx::datastore::ScriptKey},
+  crate::{
+    index::rtx::Rtx,
+    okx::datastore::{ord::btc_name::BtcDomain, ScriptKey},
+  },
   axum::Json,
   utoipa::ToSchema,
 };
@@ -255,9 +258,14 @@ pub(crate) async fn ord_debug_btc_domain(
 ) -> ApiResult<InscriptionId> {
   log::info!("rpc: get ord_debug_btc_domain:{btc_domain}");
 
+  let domain = BtcDomain::parse(
+    btc_domain.as_bytes(),
+    &index.protocol_config().domain_config().suffixes(),
+  )?;
+
   let rtx = index.begin_read()?;
   let inscription_id = rtx
-    .btc_name_to_inscription_id(&btc_domain)?
+    .btc_name_to_inscription_id(&domain)?
     .ok_or_api_not_found(format!("btc domain {btc_domain} not found."))?;
 
   log::info!(
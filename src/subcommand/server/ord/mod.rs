@@ -1,10 +1,85 @@
-use super::*;
+use {
+  super::{brc20::CursorPagination, error::ApiError, types::ScriptPubkey, *},
+  crate::{index::rtx::Rtx, okx::datastore::ScriptKey},
+};
 
+mod address;
+mod bitmap;
+mod collection;
+mod domain;
 mod inscription;
 mod outpoint;
+mod provenance;
 mod transaction;
 
-pub(super) use {inscription::*, outpoint::*, transaction::*};
+pub(super) use {
+  address::*, bitmap::*, collection::*, domain::*, inscription::*, outpoint::*, provenance::*,
+  transaction::*,
+};
+
+/// The routes of the bitmap collection API.
+pub(crate) fn bitmap_api_router() -> Router {
+  Router::new()
+    .route("/ord/bitmap/district/:number", get(ord_bitmap_district))
+    .route(
+      "/ord/bitmap/district/:number/parcels",
+      get(ord_bitmap_district_parcels),
+    )
+    .route("/ord/address/:address/bitmaps", get(ord_address_bitmaps))
+    .route(
+      "/ord/debug/bitmap/district/:number",
+      get(ord_debug_bitmap_district),
+    )
+}
+
+/// The routes of the btc domain collection API.
+pub(crate) fn btc_domain_api_router() -> Router {
+  Router::new()
+    .route("/ord/domain/:name", get(ord_domain))
+    .route("/ord/address/:address/domains", get(ord_address_domains))
+    .route("/ord/debug/btc/domain/:btc_name", get(ord_debug_btc_domain))
+}
+
+/// The current location and owner of a collection inscription.
+struct Ownership {
+  inscription_number: i32,
+  location: SatPoint,
+  /// Absent while the inscription is unbound.
+  owner: Option<ScriptPubkey>,
+}
+
+fn resolve_ownership(
+  index: &Index,
+  rtx: &Rtx,
+  client: &Client,
+  inscription_id: InscriptionId,
+) -> Result<Ownership, ApiError> {
+  let inscription_entry = rtx
+    .get_inscription_entry(inscription_id)?
+    .ok_or(OrdApiError::UnknownInscriptionId(inscription_id))?;
+
+  let location = Index::get_inscription_satpoint_by_id_with_rtx(inscription_id, rtx)?
+    .ok_or(OrdApiError::SatPointNotFound(inscription_id))?;
+
+  let owner = if location.outpoint == unbound_outpoint() {
+    None
+  } else {
+    Index::fetch_vout(
+      rtx,
+      client,
+      location.outpoint,
+      index.get_chain(),
+      index.has_transactions_index(),
+    )?
+    .map(|vout| ScriptKey::from_script(&vout.script_pubkey, index.get_chain()).into())
+  };
+
+  Ok(Ownership {
+    inscription_number: inscription_entry.inscription_number,
+    location,
+    owner,
+  })
+}
 
 #[derive(Debug, thiserror::Error)]
 pub enum OrdApiError {
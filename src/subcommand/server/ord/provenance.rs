@@ -0,0 +1,201 @@
+use {super::*, axum::Json, utoipa::ToSchema};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[schema(as = ord::ApiChildInscription)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiChildInscription {
+  /// The inscription id.
+  pub inscription_id: String,
+  /// The inscription number.
+  pub inscription_number: i32,
+  /// The current location of the inscription.
+  pub location: String,
+  /// The number of children of the inscription, so clients can walk to the grandchildren.
+  #[schema(format = "uint64")]
+  pub child_count: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[schema(as = ord::ApiInscriptionChildren)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiInscriptionChildren {
+  /// The parent inscription id.
+  pub inscription_id: String,
+  /// The number of children of the parent.
+  #[schema(format = "uint64")]
+  pub total: u64,
+  /// The page of children in inscription order.
+  #[schema(value_type = Vec<ord::ApiChildInscription>)]
+  pub children: Vec<ApiChildInscription>,
+  /// The cursor of the next page, absent on the last page.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub next_cursor: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[schema(as = ord::ApiAncestor)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiAncestor {
+  /// The inscription id.
+  pub inscription_id: String,
+  /// The inscription number.
+  pub inscription_number: i32,
+  /// The number of generations between the ancestor and the inscription, 1 for the parent.
+  #[schema(format = "uint32")]
+  pub depth: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[schema(as = ord::ApiInscriptionAncestors)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiInscriptionAncestors {
+  /// The inscription id.
+  pub inscription_id: String,
+  /// The number of ancestors of the inscription.
+  #[schema(format = "uint64")]
+  pub total: u64,
+  /// The page of ancestors from the parent up to the root of the tree.
+  #[schema(value_type = Vec<ord::ApiAncestor>)]
+  pub ancestors: Vec<ApiAncestor>,
+  /// The cursor of the next page, absent on the last page.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub next_cursor: Option<String>,
+}
+
+// /ord/id/:id/children
+/// Get the children of the inscription.
+///
+/// Retrieve the child inscriptions in inscription order, with the number of their own children.
+#[utoipa::path(
+  get,
+  path = "/api/v1/ord/id/{id}/children",
+  params(
+      ("id" = String, Path, description = "inscription ID"),
+      CursorPagination
+),
+  responses(
+    (status = 200, description = "Obtain the children of the inscription.", body = ApiOrdInscriptionChildren),
+    (status = 400, description = "Bad query.", body = ApiError, example = json!(&ApiError::bad_request("bad request"))),
+    (status = 404, description = "Not found.", body = ApiError, example = json!(&ApiError::not_found("not found"))),
+    (status = 500, description = "Internal server error.", body = ApiError, example = json!(&ApiError::internal("internal error"))),
+  )
+)]
+pub(crate) async fn ord_inscription_children(
+  Extension(index): Extension<Arc<Index>>,
+  Path(id): Path<String>,
+  Query(pagination): Query<CursorPagination>,
+) -> ApiResult<ApiInscriptionChildren> {
+  log::debug!("rpc: get ord_inscription_children: {} {:?}", id, pagination);
+
+  let id = InscriptionId::from_str(&id).map_err(ApiError::bad_request)?;
+
+  let rtx = index.begin_read()?;
+  let entry = rtx
+    .get_inscription_entry(id)?
+    .ok_or(OrdApiError::UnknownInscriptionId(id))?;
+
+  let page = pagination.key_page()?;
+  let total = rtx.inscription_child_count(entry.sequence_number)?;
+  let (children, next_cursor) = CursorPagination::page(
+    &page,
+    rtx.inscription_children_page(entry.sequence_number, &page)?,
+  );
+
+  let children = children
+    .into_iter()
+    .map(|child| {
+      let location = Index::get_inscription_satpoint_by_id_with_rtx(child.id, &rtx)?
+        .ok_or(OrdApiError::SatPointNotFound(child.id))?;
+      Ok(ApiChildInscription {
+        inscription_id: child.id.to_string(),
+        inscription_number: child.inscription_number,
+        location: location.to_string(),
+        child_count: rtx.inscription_child_count(child.sequence_number)?,
+      })
+    })
+    .collect::<Result<Vec<_>, ApiError>>()?;
+
+  log::debug!("rpc: get ord_inscription_children: {} total {}", id, total);
+
+  Ok(Json(ApiResponse::ok(ApiInscriptionChildren {
+    inscription_id: id.to_string(),
+    total,
+    children,
+    next_cursor,
+  })))
+}
+
+// /ord/id/:id/ancestors
+/// Get the ancestors of the inscription.
+///
+/// Retrieve the parent of the inscription, the parent of the parent and so on up to the root of the tree.
+#[utoipa::path(
+  get,
+  path = "/api/v1/ord/id/{id}/ancestors",
+  params(
+      ("id" = String, Path, description = "inscription ID"),
+      CursorPagination
+),
+  responses(
+    (status = 200, description = "Obtain the ancestors of the inscription.", body = ApiOrdInscriptionAncestors),
+    (status = 400, description = "Bad query.", body = ApiError, example = json!(&ApiError::bad_request("bad request"))),
+    (status = 404, description = "Not found.", body = ApiError, example = json!(&ApiError::not_found("not found"))),
+    (status = 500, description = "Internal server error.", body = ApiError, example = json!(&ApiError::internal("internal error"))),
+  )
+)]
+pub(crate) async fn ord_inscription_ancestors(
+  Extension(index): Extension<Arc<Index>>,
+  Path(id): Path<String>,
+  Query(pagination): Query<CursorPagination>,
+) -> ApiResult<ApiInscriptionAncestors> {
+  log::debug!(
+    "rpc: get ord_inscription_ancestors: {} {:?}",
+    id,
+    pagination
+  );
+
+  let id = InscriptionId::from_str(&id).map_err(ApiError::bad_request)?;
+
+  let rtx = index.begin_read()?;
+  let mut parent = rtx
+    .get_inscription_entry(id)?
+    .ok_or(OrdApiError::UnknownInscriptionId(id))?
+    .parent;
+
+  // a parent is always inscribed before its children, so the walk ends at the root.
+  let mut ancestors = Vec::new();
+  while let Some(sequence_number) = parent {
+    let entry = rtx
+      .sequence_number_to_inscription_entry(sequence_number)?
+      .ok_or_else(|| {
+        ApiError::internal(format!("inscription entry {sequence_number} not found"))
+      })?;
+    parent = entry.parent;
+    ancestors.push(entry);
+  }
+
+  let page = pagination.key_page()?;
+  let total = u64::try_from(ancestors.len()).unwrap();
+  let ancestors = ancestors.into_iter().zip(1..).collect::<Vec<_>>();
+
+  // the depth of an ancestor never changes, so its position is a stable key.
+  let (ancestors, next_cursor) = CursorPagination::page(
+    &page,
+    page.read_positions(ancestors.into_iter(), |(ancestor, depth)| {
+      Ok(Some(ApiAncestor {
+        inscription_id: ancestor.id.to_string(),
+        inscription_number: ancestor.inscription_number,
+        depth,
+      }))
+    })?,
+  );
+
+  log::debug!("rpc: get ord_inscription_ancestors: {} total {}", id, total);
+
+  Ok(Json(ApiResponse::ok(ApiInscriptionAncestors {
+    inscription_id: id.to_string(),
+    total,
+    ancestors,
+    next_cursor,
+  })))
+}
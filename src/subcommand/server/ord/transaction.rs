@@ -54,7 +54,7 @@ pub struct ApiTxInscription {
 }
 
 impl ApiTxInscription {
-  pub(super) fn parse_from_operation(
+  pub(crate) fn parse_from_operation(
     operation: InscriptionOp,
     rtx: &Rtx,
     client: &Client,
@@ -6,10 +6,14 @@ use {
 #[aliases(
   ApiBRC20Tick = ApiResponse<brc20::ApiTickInfo>,
   ApiBRC20AllTick = ApiResponse<brc20::ApiTickInfos>,
+  ApiBRC20TickHolders = ApiResponse<brc20::ApiTickHolders>,
   ApiBRC20Balance = ApiResponse<brc20::ApiBalance>,
   ApiBRC20AllBalance = ApiResponse<brc20::ApiBalances>,
   ApiBRC20TxEvents = ApiResponse<brc20::ApiTxEvents>,
+  ApiBRC20AddressEvents = ApiResponse<brc20::ApiAddressEvents>,
+  ApiBRC20MempoolEvents = ApiResponse<brc20::ApiMempoolEvents>,
   ApiBRC20BlockEvents = ApiResponse<brc20::ApiBlockEvents>,
+  ApiBRC20BlockChecksum = ApiResponse<brc20::ApiBlockChecksum>,
   ApiBRC20Transferable = ApiResponse<brc20::ApiTransferableAssets>,
 
   ApiOrdInscription = ApiResponse<ord::ApiInscription>,
@@ -17,6 +21,26 @@ use {
   ApiOrdOutPointResult = ApiResponse<ord::ApiOutPointResult>,
   ApiOrdTxInscriptions = ApiResponse<ord::ApiTxInscriptions>,
   ApiOrdBlockInscriptions = ApiResponse<ord::ApiBlockInscriptions>,
+  ApiOrdDomain = ApiResponse<ord::ApiDomain>,
+  ApiOrdDomains = ApiResponse<ord::ApiDomains>,
+  ApiOrdCollections = ApiResponse<ord::ApiCollections>,
+  ApiOrdCollectionInscriptions = ApiResponse<ord::ApiCollectionInscriptions>,
+  ApiOrdInscriptionChildren = ApiResponse<ord::ApiInscriptionChildren>,
+  ApiOrdInscriptionAncestors = ApiResponse<ord::ApiInscriptionAncestors>,
+  ApiOrdDistrict = ApiResponse<ord::ApiDistrict>,
+  ApiOrdDistricts = ApiResponse<ord::ApiDistricts>,
+  ApiOrdParcels = ApiResponse<ord::ApiParcels>,
+  ApiOrdAddressInscriptions = ApiResponse<ord::ApiAddressInscriptions>,
+
+  ApiRunesRune = ApiResponse<runes::ApiRune>,
+  ApiRunesHolders = ApiResponse<runes::ApiRuneHolders>,
+  ApiRunesSupply = ApiResponse<runes::ApiRuneSupply>,
+  ApiRunesBalances = ApiResponse<runes::ApiRuneBalances>,
+  ApiRunesTxEvents = ApiResponse<runes::ApiRuneTxEvents>,
+  ApiRunesBlockEvents = ApiResponse<runes::ApiRuneBlockEvents>,
+
+  ApiStateBlockChanges = ApiResponse<state::ApiBlockStateChanges>,
+  ApiStateReorgUndone = ApiResponse<state::ApiReorgUndone>,
 
   Node = ApiResponse<NodeInfo>
 )]
@@ -0,0 +1,611 @@
+use {
+  super::{brc20::CursorPagination, types::ScriptPubkey, *},
+  crate::{
+    index::rtx::Rtx,
+    runes::{RuneEvent, SpacedRune},
+  },
+  axum::Json,
+  utoipa::{IntoParams, ToSchema},
+};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[schema(as = runes::ApiRune)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiRune {
+  /// The rune name with its spacers, e.g. `UNCOMMON•GOODS`.
+  pub rune: String,
+  /// The rune id, the etching block height and transaction index joined by `/`.
+  pub id: String,
+  /// The etching order of the rune.
+  #[schema(format = "uint64")]
+  pub number: u64,
+  /// The id of the etching transaction.
+  pub etching: String,
+  pub divisibility: u8,
+  pub symbol: Option<String>,
+  /// The amount of the rune in circulation, in base units.
+  #[schema(format = "uint128")]
+  pub supply: String,
+  /// The amount of the rune that has been burned, in base units.
+  #[schema(format = "uint128")]
+  pub burned: String,
+  /// The number of mints of the rune.
+  #[schema(format = "uint64")]
+  pub mints: u64,
+  /// The amount of each mint, absent if the rune is not mintable.
+  #[schema(format = "uint128")]
+  pub limit: Option<String>,
+  /// The height at which minting ends.
+  #[schema(format = "uint32")]
+  pub end: Option<u32>,
+  /// The timestamp at which minting ends.
+  #[schema(format = "uint32")]
+  pub deadline: Option<u32>,
+  /// The timestamp of the etching block.
+  #[schema(format = "uint32")]
+  pub timestamp: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[schema(as = runes::ApiRuneHolder)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiRuneHolder {
+  /// The holder which is an address or script pubkey hash.
+  pub holder: ScriptPubkey,
+  /// The balance of the holder, in base units.
+  #[schema(format = "uint128")]
+  pub balance: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[schema(as = runes::ApiRuneHolders)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiRuneHolders {
+  /// The rune name with its spacers.
+  pub rune: String,
+  /// The number of holders of the rune.
+  #[schema(format = "uint64")]
+  pub total: u64,
+  #[schema(value_type = Vec<runes::ApiRuneHolder>)]
+  pub holders: Vec<ApiRuneHolder>,
+  /// The cursor of the next page, absent on the last page.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub next_cursor: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[schema(as = runes::ApiRuneBalance)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiRuneBalance {
+  /// The rune name with its spacers.
+  pub rune: String,
+  /// The rune id.
+  pub id: String,
+  pub divisibility: u8,
+  pub symbol: Option<String>,
+  /// The balance of the address, in base units.
+  #[schema(format = "uint128")]
+  pub balance: String,
+  /// The number of outputs of the address holding the rune.
+  #[schema(format = "uint64")]
+  pub outputs: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[schema(as = runes::ApiRuneBalances)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiRuneBalances {
+  #[schema(value_type = Vec<runes::ApiRuneBalance>)]
+  pub balances: Vec<ApiRuneBalance>,
+}
+
+/// A rune event of a transaction.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[schema(as = runes::ApiRuneEvent)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum ApiRuneEvent {
+  /// A new rune was etched, with the supply allocated by the etching transaction.
+  #[serde(rename_all = "camelCase")]
+  Etched {
+    id: String,
+    rune: String,
+    supply: String,
+  },
+  /// An edict claimed runes of an open mint.
+  #[serde(rename_all = "camelCase")]
+  Minted {
+    id: String,
+    rune: String,
+    amount: String,
+  },
+  /// An output received runes.
+  #[serde(rename_all = "camelCase")]
+  Transferred {
+    id: String,
+    rune: String,
+    output: u32,
+    amount: String,
+  },
+  /// Runes were burned.
+  #[serde(rename_all = "camelCase")]
+  Burned {
+    id: String,
+    rune: String,
+    amount: String,
+    /// Why the runes were burned: `unallocated`, `opReturn` or `invalidRunestone`.
+    reason: String,
+  },
+  /// The runestone or its etching was ignored.
+  #[serde(rename_all = "camelCase")]
+  Invalid { msg: String },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[schema(as = runes::ApiRuneTxEvents)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiRuneTxEvents {
+  pub txid: String,
+  #[schema(value_type = Vec<runes::ApiRuneEvent>)]
+  pub events: Vec<ApiRuneEvent>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[schema(as = runes::ApiRuneBlockEvents)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiRuneBlockEvents {
+  #[schema(value_type = Vec<runes::ApiRuneTxEvents>)]
+  pub block: Vec<ApiRuneTxEvents>,
+  /// The cursor of the next page, absent on the last page.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub next_cursor: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[schema(as = runes::ApiRuneSupplyPoint)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiRuneSupplyPoint {
+  /// The height of the block that etched, minted or burned the rune.
+  #[schema(format = "uint32")]
+  pub height: u32,
+  /// The supply of the rune at the end of the block, in base units.
+  #[schema(format = "uint128")]
+  pub supply: String,
+  /// The amount of the rune burned up to the end of the block, in base units.
+  #[schema(format = "uint128")]
+  pub burned: String,
+  /// The supply less the burned amount, in base units.
+  #[schema(format = "uint128")]
+  pub circulating: String,
+  /// The number of mints up to the end of the block.
+  #[schema(format = "uint64")]
+  pub mints: u64,
+  /// The amount minted in the block, in base units.
+  #[schema(format = "uint128")]
+  pub minted: String,
+  /// The amount burned in the block for lack of a non-OP_RETURN output to receive it.
+  #[schema(format = "uint128")]
+  pub burned_unallocated: String,
+  /// The amount burned in the block by allocating it to OP_RETURN outputs.
+  #[schema(format = "uint128")]
+  pub burned_op_return: String,
+  /// The amount burned in the block by runestones with unrecognized fields.
+  #[schema(format = "uint128")]
+  pub burned_invalid_runestone: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[schema(as = runes::ApiRuneSupply)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiRuneSupply {
+  /// The rune name with its spacers.
+  pub rune: String,
+  /// The rune id.
+  pub id: String,
+  #[schema(value_type = Vec<runes::ApiRuneSupplyPoint>)]
+  pub points: Vec<ApiRuneSupplyPoint>,
+}
+
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct HeightRange {
+  /// The first height of the range, the etching height if absent.
+  pub from: Option<u32>,
+  /// The last height of the range, inclusive, the latest height if absent.
+  pub to: Option<u32>,
+}
+
+/// The routes of the runes API, served when the index tracks runes.
+pub(crate) fn api_router() -> Router {
+  Router::new()
+    .route("/runes/:rune", get(runes_rune))
+    .route("/runes/:rune/holders", get(runes_holders))
+    .route("/runes/:rune/supply", get(runes_supply))
+    .route("/runes/tx/:txid/events", get(runes_tx_events))
+    .route("/runes/block/:blockhash/events", get(runes_block_events))
+    .route("/address/:address/runes", get(runes_address_balances))
+}
+
+// /runes/:rune
+/// Get the rune.
+///
+/// Retrieve the etching parameters and the supply of the rune.
+#[utoipa::path(
+  get,
+  path = "/api/v1/runes/{rune}",
+  params(
+      ("rune" = String, Path, description = "Rune name, with or without spacers")
+),
+  responses(
+    (status = 200, description = "Obtain the rune.", body = ApiRunesRune),
+    (status = 400, description = "Bad query.", body = ApiError, example = json!(&ApiError::bad_request("bad request"))),
+    (status = 404, description = "Not found.", body = ApiError, example = json!(&ApiError::not_found("not found"))),
+    (status = 500, description = "Internal server error.", body = ApiError, example = json!(&ApiError::internal("internal error"))),
+  )
+)]
+pub(crate) async fn runes_rune(
+  Extension(index): Extension<Arc<Index>>,
+  Path(rune): Path<String>,
+) -> ApiResult<ApiRune> {
+  log::debug!("rpc: get runes_rune: {rune}");
+
+  let spaced_rune = parse_rune(&rune)?;
+
+  let (id, entry) = index
+    .begin_read()?
+    .rune(spaced_rune.rune)?
+    .ok_or_api_not_found(format!("rune {spaced_rune} not found."))?;
+
+  let api_rune = ApiRune {
+    rune: entry.spaced_rune().to_string(),
+    id: id.to_string(),
+    number: entry.number,
+    etching: entry.etching.to_string(),
+    divisibility: entry.divisibility,
+    symbol: entry.symbol.map(String::from),
+    supply: entry.supply.to_string(),
+    burned: entry.burned.to_string(),
+    mints: entry.mints,
+    limit: entry.limit.map(|limit| limit.to_string()),
+    end: entry.end,
+    deadline: entry.deadline,
+    timestamp: entry.timestamp,
+  };
+
+  log::debug!("rpc: get runes_rune: {rune} {:?}", api_rune);
+
+  Ok(Json(ApiResponse::ok(api_rune)))
+}
+
+// /runes/:rune/holders
+/// Get the holders of the rune.
+///
+/// Retrieve the holders of the rune sorted by balance in descending order.
+#[utoipa::path(
+  get,
+  path = "/api/v1/runes/{rune}/holders",
+  params(
+      ("rune" = String, Path, description = "Rune name, with or without spacers"),
+      CursorPagination
+),
+  responses(
+    (status = 200, description = "Obtain the holders of the rune.", body = ApiRunesHolders),
+    (status = 400, description = "Bad query.", body = ApiError, example = json!(&ApiError::bad_request("bad request"))),
+    (status = 404, description = "Not found.", body = ApiError, example = json!(&ApiError::not_found("not found"))),
+    (status = 500, description = "Internal server error.", body = ApiError, example = json!(&ApiError::internal("internal error"))),
+  )
+)]
+pub(crate) async fn runes_holders(
+  Extension(index): Extension<Arc<Index>>,
+  Path(rune): Path<String>,
+  Query(pagination): Query<CursorPagination>,
+) -> ApiResult<ApiRuneHolders> {
+  log::debug!("rpc: get runes_holders: {} {:?}", rune, pagination);
+
+  let spaced_rune = parse_rune(&rune)?;
+
+  let rtx = index.begin_read()?;
+  let (id, entry) = rtx
+    .rune(spaced_rune.rune)?
+    .ok_or_api_not_found(format!("rune {spaced_rune} not found."))?;
+
+  let total = rtx.rune_holders_count(id)?;
+  let page = pagination.key_page()?;
+  let (holders, next_cursor) = CursorPagination::page(&page, rtx.rune_holders_page(id, &page)?);
+  let holders = holders
+    .into_iter()
+    .map(|(script_key, balance)| {
+      Ok(ApiRuneHolder {
+        holder: utils::parse_and_validate_script_key_with_chain(&script_key, index.get_chain())?
+          .into(),
+        balance: balance.to_string(),
+      })
+    })
+    .collect::<Result<Vec<_>>>()?;
+
+  log::debug!("rpc: get runes_holders: {} total {}", rune, total);
+
+  Ok(Json(ApiResponse::ok(ApiRuneHolders {
+    rune: entry.spaced_rune().to_string(),
+    total,
+    holders,
+    next_cursor,
+  })))
+}
+
+// /runes/:rune/supply
+/// Get the supply history of the rune.
+///
+/// Retrieve the supply of the rune at each height in the range at which it was etched, minted or burned,
+/// with the amounts minted and burned at that height and the reasons of the burns.
+#[utoipa::path(
+  get,
+  path = "/api/v1/runes/{rune}/supply",
+  params(
+      ("rune" = String, Path, description = "Rune name, with or without spacers"),
+      HeightRange
+),
+  responses(
+    (status = 200, description = "Obtain the supply history of the rune.", body = ApiRunesSupply),
+    (status = 400, description = "Bad query.", body = ApiError, example = json!(&ApiError::bad_request("bad request"))),
+    (status = 404, description = "Not found.", body = ApiError, example = json!(&ApiError::not_found("not found"))),
+    (status = 500, description = "Internal server error.", body = ApiError, example = json!(&ApiError::internal("internal error"))),
+  )
+)]
+pub(crate) async fn runes_supply(
+  Extension(index): Extension<Arc<Index>>,
+  Path(rune): Path<String>,
+  Query(range): Query<HeightRange>,
+) -> ApiResult<ApiRuneSupply> {
+  log::debug!("rpc: get runes_supply: {} {:?}", rune, range);
+
+  let spaced_rune = parse_rune(&rune)?;
+
+  let from = range.from.unwrap_or_default();
+  let to = range.to.unwrap_or(u32::MAX);
+  if from > to {
+    return Err(ApiError::bad_request(format!(
+      "invalid height range {from} to {to}"
+    )));
+  }
+
+  let rtx = index.begin_read()?;
+  let (id, entry) = rtx
+    .rune(spaced_rune.rune)?
+    .ok_or_api_not_found(format!("rune {spaced_rune} not found."))?;
+
+  let points = rtx
+    .rune_supply_history(id, from, to)?
+    .into_iter()
+    .map(|(height, supply)| ApiRuneSupplyPoint {
+      height,
+      supply: supply.supply.to_string(),
+      burned: supply.burned.to_string(),
+      circulating: supply.circulating().to_string(),
+      mints: supply.mints,
+      minted: supply.minted.to_string(),
+      burned_unallocated: supply.burned_unallocated.to_string(),
+      burned_op_return: supply.burned_op_return.to_string(),
+      burned_invalid_runestone: supply.burned_invalid_runestone.to_string(),
+    })
+    .collect::<Vec<_>>();
+
+  log::debug!("rpc: get runes_supply: {} points {}", rune, points.len());
+
+  Ok(Json(ApiResponse::ok(ApiRuneSupply {
+    rune: entry.spaced_rune().to_string(),
+    id: id.to_string(),
+    points,
+  })))
+}
+
+// /address/:address/runes
+/// Get the rune balances of the address.
+///
+/// Retrieve the balance of every rune held by the outputs of the address, in rune id order.
+#[utoipa::path(
+  get,
+  path = "/api/v1/address/{address}/runes",
+  params(
+      ("address" = String, Path, description = "Address")
+),
+  responses(
+    (status = 200, description = "Obtain the rune balances of the address.", body = ApiRunesBalances),
+    (status = 400, description = "Bad query.", body = ApiError, example = json!(&ApiError::bad_request("bad request"))),
+    (status = 404, description = "Not found.", body = ApiError, example = json!(&ApiError::not_found("not found"))),
+    (status = 500, description = "Internal server error.", body = ApiError, example = json!(&ApiError::internal("internal error"))),
+  )
+)]
+pub(crate) async fn runes_address_balances(
+  Extension(index): Extension<Arc<Index>>,
+  Path(address): Path<String>,
+) -> ApiResult<ApiRuneBalances> {
+  log::debug!("rpc: get runes_address_balances: {address}");
+
+  let script_key = utils::parse_and_validate_script_key_with_chain(&address, index.get_chain())
+    .map_err(ApiError::bad_request)?;
+
+  let rtx = index.begin_read()?;
+
+  let mut balances: BTreeMap<RuneId, (u128, u64)> = BTreeMap::new();
+  for outpoint in rtx.script_key_to_rune_outpoints(&script_key.to_string())? {
+    for (id, balance) in rtx.rune_balances_on_output(outpoint)? {
+      let (total, outputs) = balances.entry(id).or_default();
+      *total += balance;
+      *outputs += 1;
+    }
+  }
+
+  let balances = balances
+    .into_iter()
+    .map(|(id, (balance, outputs))| {
+      let entry = rtx
+        .rune_id_to_rune_entry(id)?
+        .ok_or_else(|| ApiError::internal(format!("rune {id} not found")))?;
+      Ok(ApiRuneBalance {
+        rune: entry.spaced_rune().to_string(),
+        id: id.to_string(),
+        divisibility: entry.divisibility,
+        symbol: entry.symbol.map(String::from),
+        balance: balance.to_string(),
+        outputs,
+      })
+    })
+    .collect::<Result<Vec<_>, ApiError>>()?;
+
+  log::debug!("rpc: get runes_address_balances: {address} {:?}", balances);
+
+  Ok(Json(ApiResponse::ok(ApiRuneBalances { balances })))
+}
+
+// /runes/tx/:txid/events
+/// Get the rune events of the transaction.
+///
+/// Retrieve the etching, mints, transfers, burns and ignored runestones of the transaction.
+#[utoipa::path(
+  get,
+  path = "/api/v1/runes/tx/{txid}/events",
+  params(
+      ("txid" = String, Path, description = "transaction ID")
+),
+  responses(
+    (status = 200, description = "Obtain the rune events of the transaction.", body = ApiRunesTxEvents),
+    (status = 400, description = "Bad query.", body = ApiError, example = json!(&ApiError::bad_request("bad request"))),
+    (status = 404, description = "Not found.", body = ApiError, example = json!(&ApiError::not_found("not found"))),
+    (status = 500, description = "Internal server error.", body = ApiError, example = json!(&ApiError::internal("internal error"))),
+  )
+)]
+pub(crate) async fn runes_tx_events(
+  Extension(index): Extension<Arc<Index>>,
+  Path(txid): Path<String>,
+) -> ApiResult<ApiRuneTxEvents> {
+  log::debug!("rpc: get runes_tx_events: {txid}");
+
+  let txid = Txid::from_str(&txid).map_err(ApiError::bad_request)?;
+
+  let rtx = index.begin_read()?;
+  let events = rtx
+    .rune_transaction_events(txid)?
+    .ok_or_api_not_found(format!("rune events of transaction {txid} not found."))?;
+
+  let tx_events = api_tx_events(&rtx, txid, events)?;
+
+  log::debug!("rpc: get runes_tx_events: {txid} {:?}", tx_events);
+
+  Ok(Json(ApiResponse::ok(tx_events)))
+}
+
+// /runes/block/:blockhash/events
+/// Get the rune events of the block.
+///
+/// Retrieve the rune events of the transactions of the block in transaction order.
+#[utoipa::path(
+  get,
+  path = "/api/v1/runes/block/{blockhash}/events",
+  params(
+      ("blockhash" = String, Path, description = "block hash"),
+      CursorPagination
+),
+  responses(
+    (status = 200, description = "Obtain the rune events of the block.", body = ApiRunesBlockEvents),
+    (status = 400, description = "Bad query.", body = ApiError, example = json!(&ApiError::bad_request("bad request"))),
+    (status = 404, description = "Not found.", body = ApiError, example = json!(&ApiError::not_found("not found"))),
+    (status = 500, description = "Internal server error.", body = ApiError, example = json!(&ApiError::internal("internal error"))),
+  )
+)]
+pub(crate) async fn runes_block_events(
+  Extension(index): Extension<Arc<Index>>,
+  Path(blockhash): Path<String>,
+  Query(pagination): Query<CursorPagination>,
+) -> ApiResult<ApiRuneBlockEvents> {
+  log::debug!(
+    "rpc: get runes_block_events: {} {:?}",
+    blockhash,
+    pagination
+  );
+
+  let blockhash = bitcoin::BlockHash::from_str(&blockhash).map_err(ApiError::bad_request)?;
+
+  let rtx = index.begin_read()?;
+  let client = index.bitcoin_rpc_client()?;
+
+  // the transactions keep the block order, so their position is a stable key.
+  let page = pagination.key_page()?;
+  let (block_events, next_cursor) = CursorPagination::page(
+    &page,
+    Index::get_rune_block_events(blockhash, &rtx, &client, &page)?,
+  );
+
+  let block = block_events
+    .into_iter()
+    .map(|(txid, events)| api_tx_events(&rtx, txid, events))
+    .collect::<Result<Vec<_>, ApiError>>()?;
+
+  log::debug!("rpc: get runes_block_events: {} {:?}", blockhash, block);
+
+  Ok(Json(ApiResponse::ok(ApiRuneBlockEvents {
+    block,
+    next_cursor,
+  })))
+}
+
+fn api_tx_events(
+  rtx: &Rtx,
+  txid: Txid,
+  events: Vec<RuneEvent>,
+) -> Result<ApiRuneTxEvents, ApiError> {
+  let rune = |id: RuneId| -> Result<String, ApiError> {
+    Ok(
+      rtx
+        .rune_id_to_rune_entry(id)?
+        .ok_or_else(|| ApiError::internal(format!("rune {id} not found")))?
+        .spaced_rune()
+        .to_string(),
+    )
+  };
+
+  let events = events
+    .into_iter()
+    .map(|event| {
+      Ok(match event {
+        RuneEvent::Etched {
+          id,
+          rune: spaced_rune,
+          supply,
+        } => ApiRuneEvent::Etched {
+          id: id.to_string(),
+          rune: spaced_rune.to_string(),
+          supply: supply.to_string(),
+        },
+        RuneEvent::Minted { id, amount } => ApiRuneEvent::Minted {
+          id: id.to_string(),
+          rune: rune(id)?,
+          amount: amount.to_string(),
+        },
+        RuneEvent::Transferred { id, output, amount } => ApiRuneEvent::Transferred {
+          id: id.to_string(),
+          rune: rune(id)?,
+          output,
+          amount: amount.to_string(),
+        },
+        RuneEvent::Burned { id, amount, reason } => ApiRuneEvent::Burned {
+          id: id.to_string(),
+          rune: rune(id)?,
+          amount: amount.to_string(),
+          reason: reason.to_string(),
+        },
+        RuneEvent::Invalid(error) => ApiRuneEvent::Invalid {
+          msg: error.to_string(),
+        },
+      })
+    })
+    .collect::<Result<Vec<_>, ApiError>>()?;
+
+  Ok(ApiRuneTxEvents {
+    txid: txid.to_string(),
+    events,
+  })
+}
+
+fn parse_rune(rune: &str) -> Result<SpacedRune, ApiError> {
+  SpacedRune::from_str(rune).map_err(|_| ApiError::bad_request(format!("invalid rune {rune}")))
+}
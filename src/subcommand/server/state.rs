@@ -0,0 +1,216 @@
+use {
+  super::{types::ScriptPubkey, *},
+  crate::okx::datastore::undo::{RolledBackBlock, StateChange},
+  axum::Json,
+  utoipa::ToSchema,
+};
+
+/// A protocol state entry changed by a block, with its value before and after the block.
+///
+/// An absent old value means the entry was created by the block, an absent new value means it was removed.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum ApiStateChange {
+  #[serde(rename_all = "camelCase")]
+  Brc20Balance {
+    address: ScriptPubkey,
+    tick: String,
+    #[schema(value_type = Option<brc20::Balance>)]
+    old: Option<brc20::ApiBalance>,
+    #[schema(value_type = Option<brc20::Balance>)]
+    new: Option<brc20::ApiBalance>,
+  },
+  #[serde(rename_all = "camelCase")]
+  Brc20Token {
+    tick: String,
+    #[schema(value_type = Option<brc20::TickInfo>)]
+    old: Option<brc20::ApiTickInfo>,
+    #[schema(value_type = Option<brc20::TickInfo>)]
+    new: Option<brc20::ApiTickInfo>,
+  },
+  #[serde(rename_all = "camelCase")]
+  Brc20Transferable {
+    satpoint: SatPoint,
+    #[schema(value_type = Option<brc20::TransferableAsset>)]
+    old: Option<brc20::ApiTransferableAsset>,
+    #[schema(value_type = Option<brc20::TransferableAsset>)]
+    new: Option<brc20::ApiTransferableAsset>,
+  },
+  #[serde(rename_all = "camelCase")]
+  Collection {
+    key: String,
+    old: Option<String>,
+    new: Option<String>,
+  },
+}
+
+impl From<StateChange> for ApiStateChange {
+  fn from(change: StateChange) -> Self {
+    match change {
+      StateChange::Balance {
+        script_key,
+        tick,
+        old,
+        new,
+      } => Self::Brc20Balance {
+        address: script_key.into(),
+        tick: tick.to_string(),
+        old: old.map(|balance| balance.into()),
+        new: new.map(|balance| balance.into()),
+      },
+      StateChange::Token { tick, old, new } => Self::Brc20Token {
+        tick: tick.to_string(),
+        old: old.map(|token_info| brc20::ApiTickInfo::new(token_info, None)),
+        new: new.map(|token_info| brc20::ApiTickInfo::new(token_info, None)),
+      },
+      StateChange::TransferableAsset { satpoint, old, new } => Self::Brc20Transferable {
+        satpoint,
+        old: old.map(|transferable| (satpoint, transferable).into()),
+        new: new.map(|transferable| (satpoint, transferable).into()),
+      },
+      StateChange::CollectionInscription { key, old, new } => Self::Collection {
+        key,
+        old: old.map(|inscription_id| inscription_id.to_string()),
+        new: new.map(|inscription_id| inscription_id.to_string()),
+      },
+    }
+  }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiBlockStateChanges {
+  /// The height of the block.
+  #[schema(format = "uint32")]
+  pub height: u32,
+  /// The block hash.
+  pub blockhash: String,
+  /// The state changes made by the block, in the order the entries were first changed.
+  pub changes: Vec<ApiStateChange>,
+}
+
+impl From<RolledBackBlock> for ApiBlockStateChanges {
+  fn from(block: RolledBackBlock) -> Self {
+    Self {
+      height: block.height,
+      blockhash: block.block_hash.to_string(),
+      changes: block
+        .changes
+        .into_iter()
+        .map(|change| change.into())
+        .collect(),
+    }
+  }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiUndoneReorg {
+  /// The rolled back blocks in ascending height order. Their changes were undone in reverse.
+  pub blocks: Vec<ApiBlockStateChanges>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiReorgUndone {
+  /// The height the index was rolled back to.
+  #[schema(format = "uint32")]
+  pub height: u32,
+  /// The reorgs that rolled the index back to the height, in the order they happened.
+  pub reorgs: Vec<ApiUndoneReorg>,
+}
+
+/// Get the state changes of the block.
+///
+/// Retrieve the BRC20 balances, tickers, transferable inscriptions and collection entries changed by the block.
+/// Only blocks that a reorg can still disconnect keep their state changes.
+#[utoipa::path(
+    get,
+    path = "/api/v1/state/block/{height}/changes",
+    params(
+        ("height" = u32, Path, description = "Block height")
+  ),
+    responses(
+      (status = 200, description = "Obtain the state changes of the block.", body = ApiStateBlockChanges),
+      (status = 400, description = "Bad query.", body = ApiError, example = json!(&ApiError::bad_request("bad request"))),
+      (status = 404, description = "Not found.", body = ApiError, example = json!(&ApiError::not_found("not found"))),
+      (status = 500, description = "Internal server error.", body = ApiError, example = json!(&ApiError::internal("internal error"))),
+    )
+  )]
+pub(crate) async fn state_block_changes(
+  Extension(index): Extension<Arc<Index>>,
+  Path(height): Path<u32>,
+) -> ApiResult<ApiBlockStateChanges> {
+  log::debug!("rpc: get state_block_changes: {}", height);
+
+  let rtx = index.begin_read()?;
+
+  let block_hash = rtx
+    .block_hash(Some(height))?
+    .ok_or_else(|| ApiError::not_found(format!("block {height} not found")))?;
+
+  // undo logs are pruned once their block is deeper than a reorg can reach.
+  if height < rtx.block_count()?.saturating_sub(index.max_reorg_depth()) {
+    return Err(ApiError::not_found(format!(
+      "state changes of block {height} are pruned"
+    )));
+  }
+
+  // blocks without state changes have no undo log.
+  let changes = rtx.undo_log(height)?.unwrap_or_default();
+
+  log::debug!("rpc: get state_block_changes: {} {:?}", height, changes);
+
+  Ok(Json(ApiResponse::ok(
+    RolledBackBlock {
+      height,
+      block_hash,
+      changes,
+    }
+    .into(),
+  )))
+}
+
+/// Get the state changes undone by a reorg.
+///
+/// Retrieve the blocks rolled back by each reorg that restored the index to the height, with the state changes that were undone.
+#[utoipa::path(
+    get,
+    path = "/api/v1/state/reorg/{height}/undone",
+    params(
+        ("height" = u32, Path, description = "The height the index was rolled back to")
+  ),
+    responses(
+      (status = 200, description = "Obtain the state changes undone by the reorg.", body = ApiStateReorgUndone),
+      (status = 400, description = "Bad query.", body = ApiError, example = json!(&ApiError::bad_request("bad request"))),
+      (status = 404, description = "Not found.", body = ApiError, example = json!(&ApiError::not_found("not found"))),
+      (status = 500, description = "Internal server error.", body = ApiError, example = json!(&ApiError::internal("internal error"))),
+    )
+  )]
+pub(crate) async fn state_reorg_undone(
+  Extension(index): Extension<Arc<Index>>,
+  Path(height): Path<u32>,
+) -> ApiResult<ApiReorgUndone> {
+  log::debug!("rpc: get state_reorg_undone: {}", height);
+
+  let rtx = index.begin_read()?;
+
+  let reorgs = rtx.rolled_back_blocks(height)?;
+  if reorgs.is_empty() {
+    return Err(ApiError::not_found(format!(
+      "no reorg rolled back to height {height}"
+    )));
+  }
+
+  log::debug!("rpc: get state_reorg_undone: {} {:?}", height, reorgs);
+
+  Ok(Json(ApiResponse::ok(ApiReorgUndone {
+    height,
+    reorgs: reorgs
+      .into_iter()
+      .map(|blocks| ApiUndoneReorg {
+        blocks: blocks.into_iter().map(|block| block.into()).collect(),
+      })
+      .collect(),
+  })))
+}
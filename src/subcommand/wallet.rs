@@ -1,25 +1,31 @@
 use {
   super::*,
+  base64::Engine,
   bitcoin::secp256k1::{
     rand::{self, RngCore},
     All, Secp256k1,
   },
   bitcoin::{
     bip32::{ChildNumber, DerivationPath, ExtendedPrivKey, Fingerprint},
+    psbt::Psbt,
     Network,
   },
   bitcoincore_rpc::bitcoincore_rpc_json::{ImportDescriptors, Timestamp},
   fee_rate::FeeRate,
   miniscript::descriptor::{Descriptor, DescriptorSecretKey, DescriptorXKey, Wildcard},
   transaction_builder::TransactionBuilder,
+  watch_only::WatchOnly,
 };
 
 pub mod balance;
+pub mod brc20;
+pub mod broadcast_psbt;
 pub mod cardinals;
 pub mod create;
 pub mod etch;
 pub mod inscribe;
 pub mod inscriptions;
+pub mod offer;
 pub mod outputs;
 pub mod receive;
 mod restore;
@@ -27,11 +33,23 @@ pub mod sats;
 pub mod send;
 pub mod transaction_builder;
 pub mod transactions;
+mod watch_only;
 
 #[derive(Debug, Parser)]
 pub(crate) struct Wallet {
   #[arg(long, default_value = "ord", help = "Use wallet named <WALLET>.")]
   pub(crate) name: String,
+  #[arg(
+    long,
+    help = "Inspect the watch-only wallet described by public output descriptor <DESCRIPTOR>, or by the descriptors in file <DESCRIPTOR>, one per line, using the index's address tracking instead of a Bitcoin Core wallet. Only supported by read-only commands."
+  )]
+  pub(crate) descriptor: Option<String>,
+  #[arg(
+    long,
+    default_value = "1000",
+    help = "Derive the first <DESCRIPTOR_RANGE> addresses of ranged descriptors."
+  )]
+  pub(crate) descriptor_range: u32,
   #[command(subcommand)]
   pub(crate) subcommand: Subcommand,
 }
@@ -40,6 +58,10 @@ pub(crate) struct Wallet {
 pub(crate) enum Subcommand {
   #[command(about = "Get wallet balance")]
   Balance,
+  #[command(subcommand, about = "Deploy, mint and transfer BRC20 tickers")]
+  Brc20(brc20::Brc20),
+  #[command(about = "Finalize and broadcast externally signed PSBTs")]
+  BroadcastPsbt(broadcast_psbt::BroadcastPsbt),
   #[command(about = "Create new wallet")]
   Create(create::Create),
   #[command(about = "Create rune")]
@@ -48,6 +70,8 @@ pub(crate) enum Subcommand {
   Inscribe(inscribe::Inscribe),
   #[command(about = "List wallet inscriptions")]
   Inscriptions,
+  #[command(subcommand, about = "Create and accept offers to sell inscriptions")]
+  Offer(offer::Offer),
   #[command(about = "Generate receive address")]
   Receive,
   #[command(about = "Restore wallet")]
@@ -64,21 +88,80 @@ pub(crate) enum Subcommand {
   Cardinals,
 }
 
+impl Subcommand {
+  fn is_read_only(&self) -> bool {
+    matches!(
+      self,
+      Self::Balance | Self::Cardinals | Self::Inscriptions | Self::Outputs | Self::Sats(_)
+    )
+  }
+}
+
 impl Wallet {
   pub(crate) fn run(self, options: Options) -> SubcommandResult {
+    let source = match &self.descriptor {
+      Some(descriptor) => {
+        if !self.subcommand.is_read_only() {
+          bail!("watch-only wallets only support the `balance`, `cardinals`, `inscriptions`, `outputs` and `sats` commands");
+        }
+
+        WalletSource::WatchOnly(WatchOnly::load(
+          descriptor,
+          self.descriptor_range,
+          options.chain(),
+        )?)
+      }
+      None => WalletSource::Core(self.name.clone()),
+    };
+
     match self.subcommand {
-      Subcommand::Balance => balance::run(self.name, options),
+      Subcommand::Balance => balance::run(source, options),
+      Subcommand::Brc20(brc20) => brc20.run(self.name, options),
+      Subcommand::BroadcastPsbt(broadcast_psbt) => broadcast_psbt.run(options),
       Subcommand::Create(create) => create.run(self.name, options),
       Subcommand::Etch(etch) => etch.run(self.name, options),
       Subcommand::Inscribe(inscribe) => inscribe.run(self.name, options),
-      Subcommand::Inscriptions => inscriptions::run(self.name, options),
+      Subcommand::Inscriptions => inscriptions::run(source, options),
+      Subcommand::Offer(offer) => offer.run(self.name, options),
       Subcommand::Receive => receive::run(self.name, options),
       Subcommand::Restore(restore) => restore.run(self.name, options),
-      Subcommand::Sats(sats) => sats.run(self.name, options),
+      Subcommand::Sats(sats) => sats.run(source, options),
       Subcommand::Send(send) => send.run(self.name, options),
       Subcommand::Transactions(transactions) => transactions.run(self.name, options),
-      Subcommand::Outputs => outputs::run(self.name, options),
-      Subcommand::Cardinals => cardinals::run(self.name, options),
+      Subcommand::Outputs => outputs::run(source, options),
+      Subcommand::Cardinals => cardinals::run(source, options),
+    }
+  }
+}
+
+/// Where read-only commands find the unspent outputs of the wallet.
+pub(crate) enum WalletSource {
+  Core(String),
+  WatchOnly(WatchOnly),
+}
+
+impl WalletSource {
+  pub(crate) fn get_unspent_outputs(
+    &self,
+    options: &Options,
+    index: &Index,
+  ) -> Result<BTreeMap<OutPoint, Amount>> {
+    match self {
+      Self::Core(wallet) => get_unspent_outputs(
+        &bitcoin_rpc_client_for_wallet_command(wallet.clone(), options)?,
+        index,
+      ),
+      Self::WatchOnly(watch_only) => watch_only.get_unspent_outputs(index),
+    }
+  }
+
+  pub(crate) fn get_addresses(&self, options: &Options) -> Result<Vec<Address>> {
+    match self {
+      Self::Core(wallet) => get_addresses(
+        &bitcoin_rpc_client_for_wallet_command(wallet.clone(), options)?,
+        options.chain(),
+      ),
+      Self::WatchOnly(watch_only) => Ok(watch_only.addresses().to_vec()),
     }
   }
 }
@@ -119,10 +202,10 @@ pub(crate) fn get_unspent_outputs(
 }
 
 pub(crate) fn get_unspent_output_ranges(
-  client: &Client,
+  unspent_outputs: BTreeMap<OutPoint, Amount>,
   index: &Index,
 ) -> Result<Vec<(OutPoint, Vec<(u64, u64)>)>> {
-  get_unspent_outputs(client, index)?
+  unspent_outputs
     .into_keys()
     .map(|outpoint| match index.list(outpoint)? {
       Some(List::Unspent(sat_ranges)) => Ok((outpoint, sat_ranges)),
@@ -132,6 +215,41 @@ pub(crate) fn get_unspent_output_ranges(
     .collect()
 }
 
+/// The addresses the wallet received to, including the ones it no longer holds outputs on, and the
+/// addresses of its unspent outputs, which covers change addresses.
+pub(crate) fn get_addresses(client: &Client, chain: Chain) -> Result<Vec<Address>> {
+  let mut addresses = client
+    .list_received_by_address(None, Some(0), Some(true), None)?
+    .into_iter()
+    .map(|received| received.address.require_network(chain.network()))
+    .collect::<Result<Vec<Address>, _>>()?;
+
+  let mut script_pubkeys = client
+    .list_unspent(None, None, None, None, None)?
+    .into_iter()
+    .map(|utxo| utxo.script_pub_key)
+    .collect::<Vec<ScriptBuf>>();
+
+  for outpoint in get_locked_outputs(client)? {
+    script_pubkeys.push(
+      client.get_raw_transaction(&outpoint.txid, None)?.output
+        [TryInto::<usize>::try_into(outpoint.vout).unwrap()]
+      .script_pubkey
+      .clone(),
+    );
+  }
+
+  for script_pubkey in script_pubkeys {
+    if let Ok(address) = Address::from_script(&script_pubkey, chain.network()) {
+      if !addresses.contains(&address) {
+        addresses.push(address);
+      }
+    }
+  }
+
+  Ok(addresses)
+}
+
 pub(crate) fn get_locked_outputs(client: &Client) -> Result<BTreeSet<OutPoint>> {
   #[derive(Deserialize)]
   pub(crate) struct JsonOutPoint {
@@ -148,6 +266,92 @@ pub(crate) fn get_locked_outputs(client: &Client) -> Result<BTreeSet<OutPoint>>
   )
 }
 
+/// Creates a PSBT spending the inputs of the unsigned transaction, with the outputs they spend
+/// taken from the transactions that have not been broadcast yet, or else from Bitcoin Core. The
+/// wallet then adds the key origins of its inputs and outputs without signing, so that external
+/// signers can tell which keys to sign with.
+pub(crate) fn create_psbt(
+  client: &Client,
+  unsigned_transaction: &Transaction,
+  unbroadcast_transactions: &[&Transaction],
+) -> Result<Psbt> {
+  let mut psbt = Psbt::from_unsigned_tx(unsigned_transaction.clone())?;
+
+  for (input, tx_in) in psbt.inputs.iter_mut().zip(&unsigned_transaction.input) {
+    let previous_output = tx_in.previous_output;
+
+    let transaction = match unbroadcast_transactions
+      .iter()
+      .find(|transaction| transaction.txid() == previous_output.txid)
+    {
+      Some(transaction) => (*transaction).clone(),
+      None => client.get_raw_transaction(&previous_output.txid, None)?,
+    };
+
+    input.witness_utxo = Some(
+      transaction
+        .output
+        .into_iter()
+        .nth(previous_output.vout.try_into().unwrap())
+        .ok_or_else(|| anyhow!("output {previous_output} not found"))?,
+    );
+  }
+
+  decode_psbt(
+    &client
+      .wallet_process_psbt(&encode_psbt(&psbt), Some(false), None, Some(true))?
+      .psbt,
+  )
+}
+
+/// Locks the inputs of a transaction emitted as a PSBT, except the ones spending transactions that
+/// have not been broadcast yet, so that other commands don't spend them before it is broadcast.
+pub(crate) fn lock_inputs(
+  client: &Client,
+  unsigned_transaction: &Transaction,
+  unbroadcast_transactions: &[&Transaction],
+) -> Result {
+  let inputs = unsigned_transaction
+    .input
+    .iter()
+    .map(|tx_in| tx_in.previous_output)
+    .filter(|previous_output| {
+      !unbroadcast_transactions
+        .iter()
+        .any(|transaction| transaction.txid() == previous_output.txid)
+    })
+    .collect::<Vec<OutPoint>>();
+
+  if !client.lock_unspent(&inputs)? {
+    bail!("failed to lock UTXOs");
+  }
+
+  Ok(())
+}
+
+pub(crate) fn encode_psbt(psbt: &Psbt) -> String {
+  base64::engine::general_purpose::STANDARD.encode(psbt.serialize())
+}
+
+pub(crate) fn decode_psbt(psbt: &str) -> Result<Psbt> {
+  Ok(Psbt::deserialize(
+    &base64::engine::general_purpose::STANDARD
+      .decode(psbt.trim())
+      .context("PSBT is not valid base64")?,
+  )?)
+}
+
+/// Reads a PSBT given as base64, or as the path of a file containing base64.
+pub(crate) fn load_psbt(psbt: &str) -> Result<Psbt> {
+  if Path::new(psbt).is_file() {
+    decode_psbt(
+      &fs::read_to_string(psbt).with_context(|| format!("failed to read PSBT from `{psbt}`"))?,
+    )
+  } else {
+    decode_psbt(psbt)
+  }
+}
+
 pub(crate) fn get_change_address(client: &Client, chain: Chain) -> Result<Address> {
   Ok(
     client
@@ -1,7 +1,9 @@
-use {super::*, std::collections::BTreeSet};
+use {super::*, crate::okx::datastore::ScriptKey, std::collections::BTreeSet};
 
 #[derive(Serialize, Deserialize, Debug, PartialEq)]
 pub struct Output {
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub brc20: Option<BTreeMap<String, Brc20Balance>>,
   pub cardinal: u64,
   pub ordinal: u64,
   #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -11,13 +13,18 @@ pub struct Output {
   pub total: u64,
 }
 
-pub(crate) fn run(wallet: String, options: Options) -> SubcommandResult {
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct Brc20Balance {
+  pub overall: u128,
+  pub transferable: u128,
+  pub available: u128,
+}
+
+pub(crate) fn run(wallet: WalletSource, options: Options) -> SubcommandResult {
   let index = Index::open(&options)?;
   index.update()?;
 
-  let client = bitcoin_rpc_client_for_wallet_command(wallet, &options)?;
-
-  let unspent_outputs = get_unspent_outputs(&client, &index)?;
+  let unspent_outputs = wallet.get_unspent_outputs(&options, &index)?;
 
   let inscription_outputs = index
     .get_inscriptions(&unspent_outputs)?
@@ -45,7 +52,32 @@ pub(crate) fn run(wallet: String, options: Options) -> SubcommandResult {
     }
   }
 
+  let brc20 = if index.has_brc20_index() {
+    let rtx = index.begin_read()?;
+
+    let mut brc20 = BTreeMap::new();
+    for address in wallet.get_addresses(&options)? {
+      for balance in rtx.brc20_get_all_balance_by_address(ScriptKey::from_address(address))? {
+        let entry = brc20
+          .entry(balance.tick.to_lowercase().to_string())
+          .or_insert(Brc20Balance {
+            overall: 0,
+            transferable: 0,
+            available: 0,
+          });
+        entry.overall += balance.overall_balance;
+        entry.transferable += balance.transferable_balance;
+        entry.available += balance.overall_balance - balance.transferable_balance;
+      }
+    }
+
+    Some(brc20)
+  } else {
+    None
+  };
+
   Ok(Box::new(Output {
+    brc20,
     cardinal,
     ordinal,
     runes: index.has_rune_index().then_some(runes),
@@ -62,6 +94,7 @@ mod tests {
   fn runes_and_runic_fields_are_not_present_if_none() {
     assert_eq!(
       serde_json::to_string(&Output {
+        brc20: None,
         cardinal: 0,
         ordinal: 0,
         runes: None,
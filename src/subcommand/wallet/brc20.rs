@@ -0,0 +1,355 @@
+use {
+  super::*,
+  crate::{
+    okx::{
+      datastore::ScriptKey,
+      protocol::{
+        brc20::{self, Operation, Simulator},
+        ChainContext,
+      },
+    },
+    subcommand::wallet::{
+      inscribe::{Batch, Mode},
+      transaction_builder::Target,
+    },
+  },
+};
+
+#[derive(Debug, Parser)]
+pub(crate) enum Brc20 {
+  #[command(about = "Deploy BRC20 ticker")]
+  Deploy(Deploy),
+  #[command(about = "Mint BRC20 ticker")]
+  Mint(Mint),
+  #[command(about = "Inscribe BRC20 transfer inscription")]
+  InscribeTransfer(InscribeTransfer),
+  #[command(about = "Inscribe BRC20 transfer inscription and send it to recipient")]
+  Transfer(Transfer),
+}
+
+#[derive(Debug, Parser)]
+pub(crate) struct Deploy {
+  #[arg(long, help = "Deploy ticker <TICK>.")]
+  tick: String,
+  #[arg(long, help = "Set max supply to <MAX>.")]
+  max: String,
+  #[arg(long, help = "Limit each mint to <LIM>. Defaults to <MAX>.")]
+  lim: Option<String>,
+  #[arg(long, help = "Set decimals to <DEC>. Defaults to 18.")]
+  dec: Option<u8>,
+  #[arg(long, help = "Send deploy inscription to <DESTINATION>.")]
+  destination: Option<Address<NetworkUnchecked>>,
+  #[arg(long, help = "Use fee rate of <FEE_RATE> sats/vB.")]
+  fee_rate: FeeRate,
+  #[arg(
+    long,
+    help = "Amount of postage to include in the inscription. Default `10000sat`."
+  )]
+  postage: Option<Amount>,
+}
+
+#[derive(Debug, Parser)]
+pub(crate) struct Mint {
+  #[arg(long, help = "Mint ticker <TICK>.")]
+  tick: String,
+  #[arg(long, help = "Mint <AMOUNT> of ticker.")]
+  amount: String,
+  #[arg(long, help = "Send mint inscription to <DESTINATION>.")]
+  destination: Option<Address<NetworkUnchecked>>,
+  #[arg(long, help = "Use fee rate of <FEE_RATE> sats/vB.")]
+  fee_rate: FeeRate,
+  #[arg(
+    long,
+    help = "Amount of postage to include in the inscription. Default `10000sat`."
+  )]
+  postage: Option<Amount>,
+}
+
+#[derive(Debug, Parser)]
+pub(crate) struct InscribeTransfer {
+  #[arg(long, help = "Transfer ticker <TICK>.")]
+  tick: String,
+  #[arg(long, help = "Transfer <AMOUNT> of ticker.")]
+  amount: String,
+  #[arg(long, help = "Use fee rate of <FEE_RATE> sats/vB.")]
+  fee_rate: FeeRate,
+  #[arg(
+    long,
+    help = "Amount of postage to include in the inscription. Default `10000sat`."
+  )]
+  postage: Option<Amount>,
+}
+
+#[derive(Debug, Parser)]
+pub(crate) struct Transfer {
+  address: Address<NetworkUnchecked>,
+  #[command(flatten)]
+  inscribe_transfer: InscribeTransfer,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct TransferOutput {
+  pub inscription: inscribe::Output,
+  pub transaction: Txid,
+}
+
+impl Brc20 {
+  pub(crate) fn run(self, wallet: String, options: Options) -> SubcommandResult {
+    ensure!(
+      options.enable_index_brc20,
+      "`ord wallet brc20` requires index created with `--enable-index-brc20` flag",
+    );
+
+    let index = Index::open(&options)?;
+    index.update()?;
+
+    let client = bitcoin_rpc_client_for_wallet_command(wallet, &options)?;
+
+    let chain = options.chain();
+
+    match self {
+      Self::Deploy(deploy) => {
+        let destination = match deploy.destination {
+          Some(destination) => destination.require_network(chain.network())?,
+          None => get_change_address(&client, chain)?,
+        };
+
+        let operation = Operation::Deploy(brc20::Deploy {
+          tick: deploy.tick,
+          max_supply: deploy.max,
+          mint_limit: deploy.lim,
+          decimals: deploy.dec.map(|dec| dec.to_string()),
+          self_mint: None,
+        });
+
+        check_operation(&index, chain, &operation, &destination)?;
+
+        Ok(Box::new(inscribe_operation(
+          &index,
+          &client,
+          chain,
+          &operation,
+          destination,
+          deploy.fee_rate,
+          deploy.postage,
+        )?))
+      }
+      Self::Mint(mint) => {
+        // the minted balance stays with the destination after the inscription is spent, so it
+        // defaults to a receive address, which the wallet keeps listing once it holds no outputs.
+        let destination = match mint.destination {
+          Some(destination) => destination,
+          None => {
+            client.get_new_address(None, Some(bitcoincore_rpc::json::AddressType::Bech32m))?
+          }
+        }
+        .require_network(chain.network())?;
+
+        let operation = Operation::Mint {
+          mint: brc20::Mint {
+            tick: mint.tick,
+            amount: mint.amount,
+          },
+          parent: None,
+        };
+
+        check_operation(&index, chain, &operation, &destination)?;
+
+        Ok(Box::new(inscribe_operation(
+          &index,
+          &client,
+          chain,
+          &operation,
+          destination,
+          mint.fee_rate,
+          mint.postage,
+        )?))
+      }
+      Self::InscribeTransfer(inscribe_transfer) => Ok(Box::new(
+        inscribe_transfer.inscribe(&index, &client, chain)?,
+      )),
+      Self::Transfer(transfer) => {
+        let address = transfer.address.require_network(chain.network())?;
+        let fee_rate = transfer.inscribe_transfer.fee_rate;
+
+        let inscription = transfer
+          .inscribe_transfer
+          .inscribe(&index, &client, chain)?;
+
+        let transaction =
+          send_transfer_inscription(&index, &client, chain, &inscription, address, fee_rate)?;
+
+        Ok(Box::new(TransferOutput {
+          inscription,
+          transaction,
+        }))
+      }
+    }
+  }
+}
+
+impl InscribeTransfer {
+  /// Inscribes the transfer inscription to the first wallet address with enough available
+  /// balance, since only the holder of the balance can inscribe it.
+  fn inscribe(self, index: &Index, client: &Client, chain: Chain) -> Result<inscribe::Output> {
+    let operation = Operation::InscribeTransfer(brc20::Transfer {
+      tick: self.tick,
+      amount: self.amount,
+    });
+
+    let mut error = None;
+    for address in get_addresses(client, chain)? {
+      match check_operation(index, chain, &operation, &address) {
+        Ok(()) => {
+          return inscribe_operation(
+            index,
+            client,
+            chain,
+            &operation,
+            address,
+            self.fee_rate,
+            self.postage,
+          )
+        }
+        Err(err) => {
+          error.get_or_insert(err);
+        }
+      }
+    }
+
+    Err(error.unwrap_or_else(|| anyhow!("wallet contains no addresses")))
+  }
+}
+
+/// Executes the operation against the indexed BRC20 state as if it were inscribed to the
+/// destination in the next block, and fails with the error the indexer would record.
+fn check_operation(
+  index: &Index,
+  chain: Chain,
+  operation: &Operation,
+  destination: &Address,
+) -> Result {
+  let rtx = index.begin_read()?;
+
+  let receipt = Simulator::new(
+    &rtx,
+    ChainContext {
+      chain,
+      blockheight: rtx.block_count()?,
+      blocktime: u32::try_from(Utc::now().timestamp()).unwrap_or(u32::MAX),
+    },
+  )
+  .check_operation(
+    operation.clone(),
+    ScriptKey::from_address(destination.clone()),
+  )?;
+
+  receipt
+    .result
+    .map(|_| ())
+    .map_err(|error| anyhow!("{error}"))
+}
+
+fn inscribe_operation(
+  index: &Index,
+  client: &Client,
+  chain: Chain,
+  operation: &Operation,
+  destination: Address,
+  fee_rate: FeeRate,
+  postage: Option<Amount>,
+) -> Result<inscribe::Output> {
+  let utxos = get_unspent_outputs(client, index)?;
+
+  let locked_utxos = get_locked_outputs(client)?;
+
+  let runic_utxos = index.get_runic_outputs(&utxos.keys().cloned().collect::<Vec<OutPoint>>())?;
+
+  Batch {
+    commit_fee_rate: fee_rate,
+    destinations: vec![destination],
+    inscriptions: vec![Inscription {
+      body: Some(operation.to_json().into_bytes()),
+      content_type: Some("text/plain;charset=utf-8".as_bytes().to_vec()),
+      ..Default::default()
+    }],
+    mode: Mode::SeparateOutputs,
+    postage: postage.unwrap_or(TARGET_POSTAGE),
+    reveal_fee_rate: fee_rate,
+    ..Default::default()
+  }
+  .inscribe(chain, index, client, &locked_utxos, runic_utxos, &utxos)
+}
+
+/// Sends the transfer inscription revealed by the unconfirmed inscribe transactions to the
+/// recipient, funding the transaction with the wallet outputs they left unspent.
+fn send_transfer_inscription(
+  index: &Index,
+  client: &Client,
+  chain: Chain,
+  inscription: &inscribe::Output,
+  recipient: Address,
+  fee_rate: FeeRate,
+) -> Result<Txid> {
+  let transfer = &inscription.inscriptions[0];
+
+  let transactions = [
+    client.get_raw_transaction(&inscription.commit, None)?,
+    client.get_raw_transaction(&inscription.reveal, None)?,
+  ];
+
+  let mut unspent_outputs = get_unspent_outputs(client, index)?;
+
+  for transaction in &transactions {
+    for (vout, tx_out) in transaction.output.iter().enumerate() {
+      unspent_outputs.insert(
+        OutPoint {
+          txid: transaction.txid(),
+          vout: vout.try_into().unwrap(),
+        },
+        Amount::from_sat(tx_out.value),
+      );
+    }
+  }
+
+  for transaction in &transactions {
+    for tx_in in &transaction.input {
+      unspent_outputs.remove(&tx_in.previous_output);
+    }
+  }
+
+  let mut inscriptions = index.get_inscriptions(&unspent_outputs)?;
+  inscriptions.insert(transfer.location, transfer.id);
+
+  let runic_outputs =
+    index.get_runic_outputs(&unspent_outputs.keys().cloned().collect::<Vec<OutPoint>>())?;
+
+  // unlike `ord wallet send`, there is no `--force-transferable`: the transfer inscription is the
+  // outgoing sat, and the other transferable inscriptions are on inscribed outputs, which are never
+  // selected as fees, so forcing would not change the transaction.
+  let transferable_inscriptions = index
+    .get_brc20_transferable_inscriptions(&unspent_outputs.keys().cloned().collect::<Vec<_>>())?;
+
+  let unsigned_transaction = TransactionBuilder::new(
+    transfer.location,
+    inscriptions,
+    unspent_outputs,
+    get_locked_outputs(client)?,
+    runic_outputs,
+    transferable_inscriptions,
+    recipient,
+    [
+      get_change_address(client, chain)?,
+      get_change_address(client, chain)?,
+    ],
+    fee_rate,
+    Target::Postage,
+  )
+  .build_transaction()?;
+
+  let signed_transaction = client
+    .sign_raw_transaction_with_wallet(&unsigned_transaction, None, None)?
+    .hex;
+
+  Ok(client.send_raw_transaction(&signed_transaction)?)
+}
@@ -0,0 +1,40 @@
+use super::*;
+
+#[derive(Debug, Parser)]
+pub(crate) struct BroadcastPsbt {
+  #[arg(
+    help = "Finalize and broadcast signed <PSBT>s in the order given. Each may be base64 or a path to a file containing base64."
+  )]
+  psbts: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Output {
+  pub transactions: Vec<Txid>,
+}
+
+impl BroadcastPsbt {
+  pub(crate) fn run(self, options: Options) -> SubcommandResult {
+    ensure!(!self.psbts.is_empty(), "no PSBTs to broadcast");
+
+    let client = check_version(options.bitcoin_rpc_client(None)?)?;
+
+    let mut psbts = Vec::new();
+    for psbt in self.psbts {
+      psbts.push(encode_psbt(&load_psbt(&psbt)?));
+    }
+
+    let mut transactions = Vec::new();
+    for psbt in psbts {
+      let finalized = client.finalize_psbt(&psbt, Some(true))?;
+
+      let Some(hex) = finalized.hex.filter(|_| finalized.complete) else {
+        bail!("PSBT is not fully signed");
+      };
+
+      transactions.push(client.send_raw_transaction(&hex)?);
+    }
+
+    Ok(Box::new(Output { transactions }))
+  }
+}
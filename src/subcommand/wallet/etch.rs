@@ -6,6 +6,11 @@ pub(crate) struct Etch {
   divisibility: u8,
   #[clap(long, help = "Etch with fee rate of <FEE_RATE> sats/vB.")]
   fee_rate: FeeRate,
+  #[clap(
+    long,
+    help = "Don't sign or broadcast transaction, and print it as an unsigned base64 PSBT instead."
+  )]
+  psbt: bool,
   #[clap(long, help = "Etch rune <RUNE>. May contain `.` or `•`as spacers.")]
   rune: SpacedRune,
   #[clap(long, help = "Set supply to <SUPPLY>.")]
@@ -18,6 +23,8 @@ pub(crate) struct Etch {
 pub struct Output {
   pub rune: SpacedRune,
   pub transaction: Txid,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub psbt: Option<String>,
 }
 
 impl Etch {
@@ -118,6 +125,21 @@ impl Etch {
 
     let unsigned_transaction = fund_raw_transaction(&client, self.fee_rate, &unfunded_transaction)?;
 
+    if self.psbt {
+      let unsigned_transaction: Transaction =
+        consensus::encode::deserialize(&unsigned_transaction)?;
+
+      let psbt = create_psbt(&client, &unsigned_transaction, &[])?;
+
+      lock_inputs(&client, &unsigned_transaction, &[])?;
+
+      return Ok(Box::new(Output {
+        rune: self.rune,
+        transaction: unsigned_transaction.txid(),
+        psbt: Some(encode_psbt(&psbt)),
+      }));
+    }
+
     let signed_transaction = client
       .sign_raw_transaction_with_wallet(&unsigned_transaction, None, None)?
       .hex;
@@ -127,6 +149,7 @@ impl Etch {
     Ok(Box::new(Output {
       rune: self.rune,
       transaction,
+      psbt: None,
     }))
   }
 }
@@ -1,5 +1,7 @@
+pub(crate) use self::batch::{Batch, Mode};
+
 use {
-  self::batch::{Batch, Batchfile, Mode},
+  self::batch::Batchfile,
   super::*,
   crate::subcommand::wallet::transaction_builder::Target,
   bitcoin::{
@@ -27,9 +29,13 @@ pub struct InscriptionInfo {
 #[derive(Serialize, Deserialize)]
 pub struct Output {
   pub commit: Txid,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub commit_psbt: Option<String>,
   pub inscriptions: Vec<InscriptionInfo>,
   pub parent: Option<InscriptionId>,
   pub reveal: Txid,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub reveal_psbt: Option<String>,
   pub total_fees: u64,
 }
 
@@ -94,6 +100,12 @@ pub(crate) struct Inscribe {
   pub(crate) no_limit: bool,
   #[clap(long, help = "Make inscription a child of <PARENT>.")]
   pub(crate) parent: Option<InscriptionId>,
+  #[arg(
+    long,
+    help = "Don't sign or broadcast transactions, and print them as base64 PSBTs instead. The reveal PSBT includes the reveal script and control block, and is signed except for the parent input.",
+    conflicts_with = "dry_run"
+  )]
+  pub(crate) psbt: bool,
   #[arg(
     long,
     help = "Amount of postage to include in the inscription. Default `10000sat`."
@@ -200,21 +212,24 @@ impl Inscribe {
       self.satpoint
     };
 
-    Batch {
-      commit_fee_rate: self.commit_fee_rate.unwrap_or(self.fee_rate),
-      destinations,
-      dry_run: self.dry_run,
-      inscriptions,
-      mode,
-      no_backup: self.no_backup,
-      no_limit: self.no_limit,
-      parent_info,
-      postage,
-      reinscribe: self.reinscribe,
-      reveal_fee_rate: self.fee_rate,
-      satpoint,
-    }
-    .inscribe(chain, &index, &client, &locked_utxos, runic_utxos, &utxos)
+    Ok(Box::new(
+      Batch {
+        commit_fee_rate: self.commit_fee_rate.unwrap_or(self.fee_rate),
+        destinations,
+        dry_run: self.dry_run,
+        inscriptions,
+        mode,
+        no_backup: self.no_backup,
+        no_limit: self.no_limit,
+        parent_info,
+        postage,
+        psbt: self.psbt,
+        reinscribe: self.reinscribe,
+        reveal_fee_rate: self.fee_rate,
+        satpoint,
+      }
+      .inscribe(chain, &index, &client, &locked_utxos, runic_utxos, &utxos)?,
+    ))
   }
 
   fn parse_metadata(cbor: Option<PathBuf>, json: Option<PathBuf>) -> Result<Option<Vec<u8>>> {
@@ -304,6 +319,7 @@ mod tests {
       Chain::Mainnet,
       BTreeSet::new(),
       BTreeSet::new(),
+      BTreeSet::new(),
       utxos.into_iter().collect(),
       change,
     )
@@ -345,6 +361,7 @@ mod tests {
       Chain::Mainnet,
       BTreeSet::new(),
       BTreeSet::new(),
+      BTreeSet::new(),
       utxos.into_iter().collect(),
       change,
     )
@@ -389,6 +406,7 @@ mod tests {
       Chain::Mainnet,
       BTreeSet::new(),
       BTreeSet::new(),
+      BTreeSet::new(),
       utxos.into_iter().collect(),
       [commit_address, change(1)],
     )
@@ -440,6 +458,7 @@ mod tests {
       Chain::Mainnet,
       BTreeSet::new(),
       BTreeSet::new(),
+      BTreeSet::new(),
       utxos.into_iter().collect(),
       [commit_address, change(1)],
     )
@@ -485,6 +504,7 @@ mod tests {
       Chain::Signet,
       BTreeSet::new(),
       BTreeSet::new(),
+      BTreeSet::new(),
       utxos.into_iter().collect(),
       [commit_address, change(1)],
     )
@@ -568,6 +588,7 @@ mod tests {
       Chain::Signet,
       BTreeSet::new(),
       BTreeSet::new(),
+      BTreeSet::new(),
       utxos.into_iter().collect(),
       [commit_address, change(2)],
     )
@@ -650,6 +671,7 @@ mod tests {
       Chain::Signet,
       BTreeSet::new(),
       BTreeSet::new(),
+      BTreeSet::new(),
       utxos.into_iter().collect(),
       [commit_address, change(1)],
     )
@@ -708,6 +730,7 @@ mod tests {
       Chain::Mainnet,
       BTreeSet::new(),
       BTreeSet::new(),
+      BTreeSet::new(),
       utxos.into_iter().collect(),
       [commit_address, change(1)],
     )
@@ -748,6 +771,7 @@ mod tests {
       Chain::Mainnet,
       BTreeSet::new(),
       BTreeSet::new(),
+      BTreeSet::new(),
       utxos.into_iter().collect(),
       [commit_address, change(1)],
     )
@@ -921,6 +945,7 @@ inscriptions:
       Chain::Signet,
       BTreeSet::new(),
       BTreeSet::new(),
+      BTreeSet::new(),
       utxos.into_iter().collect(),
       [commit_address, change(2)],
     )
@@ -1021,6 +1046,7 @@ inscriptions:
       Chain::Signet,
       BTreeSet::new(),
       BTreeSet::new(),
+      BTreeSet::new(),
       utxos.into_iter().collect(),
       [commit_address, change(2)],
     )
@@ -1099,6 +1125,7 @@ inscriptions:
       Chain::Signet,
       BTreeSet::new(),
       BTreeSet::new(),
+      BTreeSet::new(),
       utxos.into_iter().collect(),
       [commit_address, change(2)],
     );
@@ -1137,6 +1164,7 @@ inscriptions:
       Chain::Signet,
       BTreeSet::new(),
       BTreeSet::new(),
+      BTreeSet::new(),
       utxos.into_iter().collect(),
       [commit_address, change(2)],
     )
@@ -1190,6 +1218,7 @@ inscriptions:
       Chain::Signet,
       BTreeSet::new(),
       BTreeSet::new(),
+      BTreeSet::new(),
       utxos.into_iter().collect(),
       [commit_address, change(2)],
     )
@@ -1270,6 +1299,7 @@ inscriptions:
       Chain::Signet,
       BTreeSet::new(),
       BTreeSet::new(),
+      BTreeSet::new(),
       utxos.into_iter().collect(),
       [commit_address, change(2)],
     )
@@ -1,18 +1,19 @@
 use super::*;
 
-pub(super) struct Batch {
-  pub(super) commit_fee_rate: FeeRate,
-  pub(super) destinations: Vec<Address>,
-  pub(super) dry_run: bool,
-  pub(super) inscriptions: Vec<Inscription>,
-  pub(super) mode: Mode,
-  pub(super) no_backup: bool,
-  pub(super) no_limit: bool,
-  pub(super) parent_info: Option<ParentInfo>,
-  pub(super) postage: Amount,
-  pub(super) reinscribe: bool,
-  pub(super) reveal_fee_rate: FeeRate,
-  pub(super) satpoint: Option<SatPoint>,
+pub(crate) struct Batch {
+  pub(crate) commit_fee_rate: FeeRate,
+  pub(crate) destinations: Vec<Address>,
+  pub(crate) dry_run: bool,
+  pub(crate) inscriptions: Vec<Inscription>,
+  pub(crate) mode: Mode,
+  pub(crate) no_backup: bool,
+  pub(crate) no_limit: bool,
+  pub(crate) parent_info: Option<ParentInfo>,
+  pub(crate) postage: Amount,
+  pub(crate) psbt: bool,
+  pub(crate) reinscribe: bool,
+  pub(crate) reveal_fee_rate: FeeRate,
+  pub(crate) satpoint: Option<SatPoint>,
 }
 
 impl Default for Batch {
@@ -27,6 +28,7 @@ impl Default for Batch {
       no_limit: false,
       parent_info: None,
       postage: Amount::from_sat(10_000),
+      psbt: false,
       reinscribe: false,
       reveal_fee_rate: 1.0.try_into().unwrap(),
       satpoint: None,
@@ -43,9 +45,12 @@ impl Batch {
     locked_utxos: &BTreeSet<OutPoint>,
     runic_utxos: BTreeSet<OutPoint>,
     utxos: &BTreeMap<OutPoint, Amount>,
-  ) -> SubcommandResult {
+  ) -> Result<super::Output> {
     let wallet_inscriptions = index.get_inscriptions(utxos)?;
 
+    let transferable_inscriptions = index
+      .get_brc20_transferable_inscriptions(&utxos.keys().cloned().collect::<Vec<OutPoint>>())?;
+
     let commit_tx_change = [
       get_change_address(client, chain)?,
       get_change_address(client, chain)?,
@@ -57,17 +62,41 @@ impl Batch {
         chain,
         locked_utxos.clone(),
         runic_utxos,
+        transferable_inscriptions,
         utxos.clone(),
         commit_tx_change,
       )?;
 
     if self.dry_run {
-      return Ok(Box::new(self.output(
+      return Ok(self.output(
         commit_tx.txid(),
         reveal_tx.txid(),
         total_fees,
         self.inscriptions.clone(),
-      )));
+      ));
+    }
+
+    if self.psbt {
+      if !self.no_backup {
+        Self::backup_recovery_key(client, recovery_key_pair, chain.network())?;
+      }
+
+      let commit_psbt = create_psbt(client, &commit_tx, &[])?;
+      let reveal_psbt = Self::create_reveal_psbt(client, &commit_tx, &reveal_tx)?;
+
+      lock_inputs(client, &commit_tx, &[])?;
+      lock_inputs(client, &reveal_tx, &[&commit_tx])?;
+
+      return Ok(super::Output {
+        commit_psbt: Some(encode_psbt(&commit_psbt)),
+        reveal_psbt: Some(encode_psbt(&reveal_psbt)),
+        ..self.output(
+          commit_tx.txid(),
+          reveal_tx.txid(),
+          total_fees,
+          self.inscriptions.clone(),
+        )
+      });
     }
 
     let signed_commit_tx = client
@@ -114,12 +143,7 @@ impl Batch {
       }
     };
 
-    Ok(Box::new(self.output(
-      commit,
-      reveal,
-      total_fees,
-      self.inscriptions.clone(),
-    )))
+    Ok(self.output(commit, reveal, total_fees, self.inscriptions.clone()))
   }
 
   fn output(
@@ -169,19 +193,60 @@ impl Batch {
 
     super::Output {
       commit,
+      commit_psbt: None,
       reveal,
+      reveal_psbt: None,
       total_fees,
       parent: self.parent_info.clone().map(|info| info.id),
       inscriptions: inscriptions_output,
     }
   }
 
+  /// Creates a PSBT for the reveal transaction. The commit input is already signed with the
+  /// recovery key, and carries the reveal script and control block so that signers can verify
+  /// what it reveals, while the parent input, if any, is left for the wallet to sign.
+  fn create_reveal_psbt(
+    client: &Client,
+    commit_tx: &Transaction,
+    reveal_tx: &Transaction,
+  ) -> Result<Psbt> {
+    let mut unsigned_reveal_tx = reveal_tx.clone();
+
+    let witnesses = unsigned_reveal_tx
+      .input
+      .iter_mut()
+      .map(|tx_in| mem::take(&mut tx_in.witness))
+      .collect::<Vec<Witness>>();
+
+    let mut psbt = create_psbt(client, &unsigned_reveal_tx, &[commit_tx])?;
+
+    for (input, witness) in psbt.inputs.iter_mut().zip(witnesses) {
+      let (Some(reveal_script), Some(control_block)) = (witness.nth(1), witness.nth(2)) else {
+        continue;
+      };
+
+      let reveal_script = ScriptBuf::from_bytes(reveal_script.to_vec());
+      let control_block = ControlBlock::decode(control_block)?;
+
+      input.tap_internal_key = Some(control_block.internal_key);
+      input.tap_merkle_root =
+        Some(TapLeafHash::from_script(&reveal_script, LeafVersion::TapScript).into());
+      input
+        .tap_scripts
+        .insert(control_block, (reveal_script, LeafVersion::TapScript));
+      input.final_script_witness = Some(witness);
+    }
+
+    Ok(psbt)
+  }
+
   pub(crate) fn create_batch_inscription_transactions(
     &self,
     wallet_inscriptions: BTreeMap<SatPoint, InscriptionId>,
     chain: Chain,
     locked_utxos: BTreeSet<OutPoint>,
     runic_utxos: BTreeSet<OutPoint>,
+    transferable_inscriptions: BTreeSet<SatPoint>,
     mut utxos: BTreeMap<OutPoint, Amount>,
     change: [Address; 2],
   ) -> Result<(Transaction, Transaction, TweakedKeyPair, u64)> {
@@ -233,6 +298,15 @@ impl Batch {
         .ok_or_else(|| anyhow!("wallet contains no cardinal utxos"))?
     };
 
+    // there is no `--force-transferable` as for `ord wallet send`, since the check below refuses
+    // to inscribe on any inscribed output anyway. This only gives the reason for BRC20 outputs.
+    if let Some(transferable_satpoint) = transferable_inscriptions
+      .iter()
+      .find(|transferable_satpoint| transferable_satpoint.outpoint == satpoint.outpoint)
+    {
+      bail!("cannot inscribe on {satpoint} without also spending transferable BRC20 inscription at {transferable_satpoint}");
+    }
+
     let mut reinscription = false;
 
     for (inscribed_satpoint, inscription_id) in &wallet_inscriptions {
@@ -336,6 +410,7 @@ impl Batch {
       utxos.clone(),
       locked_utxos.clone(),
       runic_utxos,
+      transferable_inscriptions,
       commit_tx_address.clone(),
       change,
       self.commit_fee_rate,
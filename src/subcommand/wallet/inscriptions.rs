@@ -8,13 +8,11 @@ pub struct Output {
   pub postage: u64,
 }
 
-pub(crate) fn run(wallet: String, options: Options) -> SubcommandResult {
+pub(crate) fn run(wallet: WalletSource, options: Options) -> SubcommandResult {
   let index = Index::open(&options)?;
   index.update()?;
 
-  let client = bitcoin_rpc_client_for_wallet_command(wallet, &options)?;
-
-  let unspent_outputs = get_unspent_outputs(&client, &index)?;
+  let unspent_outputs = wallet.get_unspent_outputs(&options, &index)?;
 
   let inscriptions = index.get_inscriptions(&unspent_outputs)?;
 
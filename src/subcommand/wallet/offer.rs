@@ -0,0 +1,353 @@
+use {
+  super::*,
+  crate::subcommand::wallet::transaction_builder::Target,
+  bitcoin::sighash::{EcdsaSighashType, TapSighashType},
+};
+
+#[derive(Debug, Parser)]
+pub(crate) enum Offer {
+  #[command(about = "Create a signed offer to sell an inscription")]
+  Create(Create),
+  #[command(about = "Accept an offer and buy its inscription")]
+  Accept(Accept),
+}
+
+#[derive(Debug, Parser)]
+pub(crate) struct Create {
+  #[arg(long, help = "Offer <INSCRIPTION> for sale.")]
+  inscription: InscriptionId,
+  #[arg(long, help = "Sell inscription for <PRICE>.")]
+  price: Amount,
+}
+
+#[derive(Debug, Parser)]
+pub(crate) struct Accept {
+  #[arg(help = "Accept offer <PSBT>, as base64 or a path to a file containing base64.")]
+  psbt: String,
+  #[arg(long, help = "Send inscription to <DESTINATION>.")]
+  destination: Option<Address<NetworkUnchecked>>,
+  #[arg(long, help = "Don't sign or broadcast transaction.")]
+  dry_run: bool,
+  #[arg(long, help = "Use fee rate of <FEE_RATE> sats/vB.")]
+  fee_rate: FeeRate,
+  #[arg(long, help = "Only accept an offer for <INSCRIPTION>.")]
+  inscription: InscriptionId,
+  #[arg(long, help = "Pay at most <MAX_PRICE> for the inscription.")]
+  max_price: Amount,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct CreateOutput {
+  pub inscription: InscriptionId,
+  pub price: u64,
+  pub psbt: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct AcceptOutput {
+  pub inscriptions: Vec<InscriptionId>,
+  #[serde(default, skip_serializing_if = "Vec::is_empty")]
+  pub brc20_transferable: Vec<Brc20Transferable>,
+  pub price: u64,
+  pub transaction: Txid,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct Brc20Transferable {
+  pub inscription: InscriptionId,
+  pub tick: String,
+  pub amount: u128,
+}
+
+impl Offer {
+  pub(crate) fn run(self, wallet: String, options: Options) -> SubcommandResult {
+    let index = Index::open(&options)?;
+    index.update()?;
+
+    let client = bitcoin_rpc_client_for_wallet_command(wallet, &options)?;
+
+    match self {
+      Self::Create(create) => Ok(Box::new(create.run(&client, &index, options.chain())?)),
+      Self::Accept(accept) => Ok(Box::new(accept.run(&client, &index, options.chain())?)),
+    }
+  }
+}
+
+impl Create {
+  fn run(self, client: &Client, index: &Index, chain: Chain) -> Result<CreateOutput> {
+    let unspent_outputs = get_unspent_outputs(client, index)?;
+
+    let satpoint = index
+      .get_inscription_satpoint_by_id(self.inscription)?
+      .ok_or_else(|| anyhow!("inscription {} not found", self.inscription))?;
+
+    let Some(output_value) = unspent_outputs.get(&satpoint.outpoint).copied() else {
+      bail!("inscription {} not in wallet", self.inscription);
+    };
+
+    // the buyer receives the whole output, so it may not hold anything else of value: the sats
+    // before the inscription would pad the buyer's alignment output and the sats above postage
+    // would go to the buyer's change
+    ensure!(
+      satpoint.offset == 0,
+      "cannot offer {} without also selling the {} sats before it in {}, split the output with `ord wallet send` first",
+      self.inscription,
+      satpoint.offset,
+      satpoint.outpoint,
+    );
+
+    ensure!(
+      output_value <= TransactionBuilder::MAX_POSTAGE,
+      "cannot offer {} without also selling the {} in {} above postage of {}, split the output with `ord wallet send` first",
+      self.inscription,
+      output_value - TransactionBuilder::MAX_POSTAGE,
+      satpoint.outpoint,
+      TransactionBuilder::MAX_POSTAGE,
+    );
+
+    for (inscribed_satpoint, inscription_id) in
+      index.get_inscriptions_on_output_with_satpoints(satpoint.outpoint)?
+    {
+      ensure!(
+        inscription_id == self.inscription,
+        "cannot offer {} without also selling inscription {inscription_id} at {inscribed_satpoint}",
+        self.inscription,
+      );
+    }
+
+    ensure!(
+      index.get_runic_outputs(&[satpoint.outpoint])?.is_empty(),
+      "cannot offer {} without also selling the runes in {}",
+      self.inscription,
+      satpoint.outpoint,
+    );
+
+    let seller_address = client
+      .get_new_address(None, Some(bitcoincore_rpc::json::AddressType::Bech32m))?
+      .require_network(chain.network())?;
+
+    let dust_value = seller_address.script_pubkey().dust_value();
+
+    ensure!(
+      self.price >= dust_value,
+      "price is below dust value: {} < {dust_value}",
+      self.price,
+    );
+
+    // the seller signs their input and the payment output at the same index, leaving the
+    // buyer free to add inputs and outputs around them
+    let unsigned_transaction = Transaction {
+      version: 2,
+      lock_time: LockTime::ZERO,
+      input: vec![TxIn {
+        previous_output: satpoint.outpoint,
+        script_sig: ScriptBuf::new(),
+        sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
+        witness: Witness::new(),
+      }],
+      output: vec![TxOut {
+        value: self.price.to_sat(),
+        script_pubkey: seller_address.script_pubkey(),
+      }],
+    };
+
+    let psbt = create_psbt(client, &unsigned_transaction, &[])?;
+
+    let signed = client.wallet_process_psbt(
+      &encode_psbt(&psbt),
+      Some(true),
+      Some(EcdsaSighashType::SinglePlusAnyoneCanPay.into()),
+      None,
+    )?;
+
+    ensure!(signed.complete, "failed to sign offer");
+
+    Ok(CreateOutput {
+      inscription: self.inscription,
+      price: self.price.to_sat(),
+      psbt: signed.psbt,
+    })
+  }
+}
+
+impl Accept {
+  fn run(self, client: &Client, index: &Index, chain: Chain) -> Result<AcceptOutput> {
+    let offer = load_psbt(&self.psbt)?;
+
+    ensure!(
+      offer.unsigned_tx.input.len() == 1 && offer.unsigned_tx.output.len() == 1,
+      "offer must have exactly one input and one output",
+    );
+
+    Self::check_signature(&offer.inputs[0])?;
+
+    let offer_input = &offer.unsigned_tx.input[0];
+    let payment = &offer.unsigned_tx.output[0];
+
+    let seller_output = offer.inputs[0]
+      .witness_utxo
+      .clone()
+      .ok_or_else(|| anyhow!("offer is missing the output it spends"))?;
+
+    ensure!(
+      payment.value <= self.max_price.to_sat(),
+      "offer price {} exceeds maximum price {}",
+      Amount::from_sat(payment.value),
+      self.max_price,
+    );
+
+    let offered_inscriptions =
+      index.get_inscriptions_on_output_with_satpoints(offer_input.previous_output)?;
+
+    // the buyer receives the whole output, so everything on it is reported, not just the expected
+    // inscription
+    let satpoint = offered_inscriptions
+      .iter()
+      .find(|(_, inscription_id)| *inscription_id == self.inscription)
+      .map(|(satpoint, _)| *satpoint)
+      .ok_or_else(|| {
+        anyhow!(
+          "offered output {} does not hold inscription {}",
+          offer_input.previous_output,
+          self.inscription,
+        )
+      })?;
+
+    ensure!(
+      index
+        .get_runic_outputs(&[offer_input.previous_output])?
+        .is_empty(),
+      "offered output {} holds runes",
+      offer_input.previous_output,
+    );
+
+    let brc20_transferable = index
+      .begin_read()?
+      .brc20_transferable_assets_on_output_with_satpoints(offer_input.previous_output)?
+      .into_iter()
+      .map(|(_, log)| Brc20Transferable {
+        inscription: log.inscription_id,
+        tick: log.tick.to_string(),
+        amount: log.amount,
+      })
+      .collect();
+
+    let offered_inscription_ids = offered_inscriptions
+      .iter()
+      .map(|(_, inscription_id)| *inscription_id)
+      .collect();
+
+    let unspent_outputs = get_unspent_outputs(client, index)?;
+
+    let outpoints = unspent_outputs
+      .keys()
+      .cloned()
+      .chain([offer_input.previous_output])
+      .collect::<Vec<OutPoint>>();
+
+    let mut inscriptions = index.get_inscriptions(&unspent_outputs)?;
+    inscriptions.extend(offered_inscriptions);
+
+    let mut amounts = unspent_outputs;
+    amounts.insert(
+      offer_input.previous_output,
+      Amount::from_sat(seller_output.value),
+    );
+
+    let recipient = match self.destination {
+      Some(destination) => destination.require_network(chain.network())?,
+      None => client
+        .get_new_address(None, Some(bitcoincore_rpc::json::AddressType::Bech32m))?
+        .require_network(chain.network())?,
+    };
+
+    let mut unsigned_transaction = TransactionBuilder::new(
+      satpoint,
+      inscriptions,
+      amounts,
+      get_locked_outputs(client)?,
+      index.get_runic_outputs(&outpoints)?,
+      index.get_brc20_transferable_inscriptions(&outpoints)?,
+      recipient,
+      [
+        get_change_address(client, chain)?,
+        get_change_address(client, chain)?,
+      ],
+      self.fee_rate,
+      Target::Postage,
+    )
+    .pay_for_outgoing(
+      chain.address_from_script(&payment.script_pubkey)?,
+      Amount::from_sat(payment.value),
+    )
+    .build_transaction()?;
+
+    // the seller's signature also commits to the transaction version, lock time and the
+    // sequence of their input
+    unsigned_transaction.version = offer.unsigned_tx.version;
+    unsigned_transaction.lock_time = offer.unsigned_tx.lock_time;
+
+    for tx_in in &mut unsigned_transaction.input {
+      if tx_in.previous_output == offer_input.previous_output {
+        tx_in.sequence = offer_input.sequence;
+      }
+    }
+
+    let mut psbt = Psbt::from_unsigned_tx(unsigned_transaction.clone())?;
+
+    for (input, tx_in) in psbt.inputs.iter_mut().zip(&unsigned_transaction.input) {
+      if tx_in.previous_output == offer_input.previous_output {
+        *input = offer.inputs[0].clone();
+      } else {
+        input.witness_utxo = Some(
+          client
+            .get_raw_transaction(&tx_in.previous_output.txid, None)?
+            .output
+            .into_iter()
+            .nth(tx_in.previous_output.vout.try_into().unwrap())
+            .ok_or_else(|| anyhow!("output {} not found", tx_in.previous_output))?,
+        );
+      }
+    }
+
+    let transaction = if self.dry_run {
+      unsigned_transaction.txid()
+    } else {
+      let signed = client.wallet_process_psbt(&encode_psbt(&psbt), Some(true), None, None)?;
+
+      let finalized = client.finalize_psbt(&signed.psbt, Some(true))?;
+
+      let Some(hex) = finalized.hex.filter(|_| finalized.complete) else {
+        bail!("failed to sign offer transaction");
+      };
+
+      client.send_raw_transaction(&hex)?
+    };
+
+    Ok(AcceptOutput {
+      inscriptions: offered_inscription_ids,
+      brc20_transferable,
+      price: payment.value,
+      transaction,
+    })
+  }
+
+  fn check_signature(input: &bitcoin::psbt::Input) -> Result {
+    let signature = match (&input.final_script_witness, input.tap_key_sig) {
+      (Some(witness), _) if witness.len() == 1 => {
+        bitcoin::taproot::Signature::from_slice(witness.nth(0).unwrap())
+          .context("offer is not signed with a key path signature")?
+      }
+      (None, Some(signature)) => signature,
+      _ => bail!("offer is not signed with a key path signature"),
+    };
+
+    ensure!(
+      signature.hash_ty == TapSighashType::SinglePlusAnyoneCanPay,
+      "offer must be signed with SIGHASH_SINGLE|ANYONECANPAY, not {}",
+      signature.hash_ty,
+    );
+
+    Ok(())
+  }
+}
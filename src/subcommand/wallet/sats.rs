@@ -24,7 +24,7 @@ pub struct OutputRare {
 }
 
 impl Sats {
-  pub(crate) fn run(&self, wallet: String, options: Options) -> SubcommandResult {
+  pub(crate) fn run(&self, wallet: WalletSource, options: Options) -> SubcommandResult {
     let index = Index::open(&options)?;
 
     if !index.has_sat_index() {
@@ -33,9 +33,7 @@ impl Sats {
 
     index.update()?;
 
-    let client = bitcoin_rpc_client_for_wallet_command(wallet, &options)?;
-
-    let utxos = get_unspent_output_ranges(&client, &index)?;
+    let utxos = get_unspent_output_ranges(wallet.get_unspent_outputs(&options, &index)?, &index)?;
 
     if let Some(path) = &self.tsv {
       let mut output = Vec::new();
@@ -11,11 +11,23 @@ pub(crate) struct Send {
     help = "Target amount of postage to include with sent inscriptions. Default `10000sat`"
   )]
   pub(crate) postage: Option<Amount>,
+  #[arg(
+    long,
+    help = "Allow spending outputs holding transferable BRC20 inscriptions as fees or change"
+  )]
+  pub(crate) force_transferable: bool,
+  #[arg(
+    long,
+    help = "Don't sign or broadcast transaction, and print it as an unsigned base64 PSBT instead"
+  )]
+  pub(crate) psbt: bool,
 }
 
 #[derive(Serialize, Deserialize)]
 pub struct Output {
   pub transaction: Txid,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub psbt: Option<String>,
 }
 
 impl Send {
@@ -42,17 +54,38 @@ impl Send {
     let runic_outputs =
       index.get_runic_outputs(&unspent_outputs.keys().cloned().collect::<Vec<OutPoint>>())?;
 
+    let transferable_inscriptions = if self.force_transferable {
+      BTreeSet::new()
+    } else {
+      index.get_brc20_transferable_inscriptions(
+        &unspent_outputs.keys().cloned().collect::<Vec<OutPoint>>(),
+      )?
+    };
+
     let satpoint = match self.outgoing {
       Outgoing::Amount(amount) => {
-        Self::lock_non_cardinal_outputs(&client, &inscriptions, &runic_outputs, unspent_outputs)?;
+        Self::lock_non_cardinal_outputs(
+          &client,
+          &inscriptions,
+          &runic_outputs,
+          &transferable_inscriptions,
+          unspent_outputs,
+        )?;
+        if self.psbt {
+          let unsigned_transaction = Self::fund_amount(&client, amount, address, self.fee_rate)?;
+          return Ok(Box::new(Self::create_psbt(&client, &unsigned_transaction)?));
+        }
         let transaction = Self::send_amount(&client, amount, address, self.fee_rate)?;
-        return Ok(Box::new(Output { transaction }));
+        return Ok(Box::new(Output {
+          transaction,
+          psbt: None,
+        }));
       }
       Outgoing::InscriptionId(id) => index
         .get_inscription_satpoint_by_id(id)?
         .ok_or_else(|| anyhow!("inscription {id} not found"))?,
       Outgoing::Rune { decimal, rune } => {
-        let transaction = Self::send_runes(
+        let unsigned_transaction = Self::create_rune_transaction(
           address,
           chain,
           &client,
@@ -62,9 +95,13 @@ impl Send {
           inscriptions,
           rune,
           runic_outputs,
+          &transferable_inscriptions,
           unspent_outputs,
         )?;
-        return Ok(Box::new(Output { transaction }));
+        return Ok(Box::new(self.sign_and_broadcast_or_create_psbt(
+          &client,
+          &unsigned_transaction,
+        )?));
       }
       Outgoing::SatPoint(satpoint) => {
         for inscription_satpoint in inscriptions.keys() {
@@ -99,6 +136,7 @@ impl Send {
       unspent_outputs,
       locked_outputs,
       runic_outputs,
+      transferable_inscriptions,
       address.clone(),
       change,
       self.fee_rate,
@@ -106,19 +144,47 @@ impl Send {
     )
     .build_transaction()?;
 
+    Ok(Box::new(self.sign_and_broadcast_or_create_psbt(
+      &client,
+      &unsigned_transaction,
+    )?))
+  }
+
+  fn sign_and_broadcast_or_create_psbt(
+    &self,
+    client: &Client,
+    unsigned_transaction: &Transaction,
+  ) -> Result<Output> {
+    if self.psbt {
+      return Self::create_psbt(client, unsigned_transaction);
+    }
+
     let signed_tx = client
-      .sign_raw_transaction_with_wallet(&unsigned_transaction, None, None)?
+      .sign_raw_transaction_with_wallet(unsigned_transaction, None, None)?
       .hex;
 
-    let txid = client.send_raw_transaction(&signed_tx)?;
+    Ok(Output {
+      transaction: client.send_raw_transaction(&signed_tx)?,
+      psbt: None,
+    })
+  }
 
-    Ok(Box::new(Output { transaction: txid }))
+  fn create_psbt(client: &Client, unsigned_transaction: &Transaction) -> Result<Output> {
+    let psbt = create_psbt(client, unsigned_transaction, &[])?;
+
+    lock_inputs(client, unsigned_transaction, &[])?;
+
+    Ok(Output {
+      transaction: unsigned_transaction.txid(),
+      psbt: Some(encode_psbt(&psbt)),
+    })
   }
 
   fn lock_non_cardinal_outputs(
     client: &Client,
     inscriptions: &BTreeMap<SatPoint, InscriptionId>,
     runic_outputs: &BTreeSet<OutPoint>,
+    transferable_inscriptions: &BTreeSet<SatPoint>,
     unspent_outputs: BTreeMap<OutPoint, bitcoin::Amount>,
   ) -> Result {
     let all_inscription_outputs = inscriptions
@@ -130,6 +196,11 @@ impl Send {
       .keys()
       .filter(|utxo| all_inscription_outputs.contains(utxo))
       .chain(runic_outputs.iter())
+      .chain(
+        transferable_inscriptions
+          .iter()
+          .map(|satpoint| &satpoint.outpoint),
+      )
       .cloned()
       .collect::<Vec<OutPoint>>();
 
@@ -163,7 +234,30 @@ impl Send {
     )?)
   }
 
-  fn send_runes(
+  fn fund_amount(
+    client: &Client,
+    amount: Amount,
+    address: Address,
+    fee_rate: FeeRate,
+  ) -> Result<Transaction> {
+    let unfunded_transaction = Transaction {
+      version: 2,
+      lock_time: LockTime::ZERO,
+      input: Vec::new(),
+      output: vec![TxOut {
+        script_pubkey: address.script_pubkey(),
+        value: amount.to_sat(),
+      }],
+    };
+
+    Ok(consensus::encode::deserialize(&fund_raw_transaction(
+      client,
+      fee_rate,
+      &unfunded_transaction,
+    )?)?)
+  }
+
+  fn create_rune_transaction(
     address: Address,
     chain: Chain,
     client: &Client,
@@ -173,14 +267,21 @@ impl Send {
     inscriptions: BTreeMap<SatPoint, InscriptionId>,
     spaced_rune: SpacedRune,
     runic_outputs: BTreeSet<OutPoint>,
+    transferable_inscriptions: &BTreeSet<SatPoint>,
     unspent_outputs: BTreeMap<OutPoint, Amount>,
-  ) -> Result<Txid> {
+  ) -> Result<Transaction> {
     ensure!(
       index.has_rune_index(),
       "sending runes with `ord send` requires index created with `--index-runes` flag",
     );
 
-    Self::lock_non_cardinal_outputs(client, &inscriptions, &runic_outputs, unspent_outputs)?;
+    Self::lock_non_cardinal_outputs(
+      client,
+      &inscriptions,
+      &runic_outputs,
+      transferable_inscriptions,
+      unspent_outputs,
+    )?;
 
     let (id, entry, _parent) = index
       .rune(spaced_rune.rune)?
@@ -261,12 +362,10 @@ impl Send {
       ],
     };
 
-    let unsigned_transaction = fund_raw_transaction(client, fee_rate, &unfunded_transaction)?;
-
-    let signed_transaction = client
-      .sign_raw_transaction_with_wallet(&unsigned_transaction, None, None)?
-      .hex;
-
-    Ok(client.send_raw_transaction(&signed_transaction)?)
+    Ok(consensus::encode::deserialize(&fund_raw_transaction(
+      client,
+      fee_rate,
+      &unfunded_transaction,
+    )?)?)
   }
 }
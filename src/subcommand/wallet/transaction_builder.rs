@@ -20,6 +20,16 @@
 //! `Target::Value(Amount)` ensures that the outgoing value is exactly the
 //! requested amount,
 //!
+//! Outputs holding transferable BRC20 inscriptions are never selected to pay
+//! fees, and may only be spent when the outgoing sat is the transferable
+//! inscription itself, since moving one anywhere else executes the transfer.
+//!
+//! `TransactionBuilder::pay_for_outgoing` adds a payment output at the same
+//! index as the input holding the outgoing sat, as required by offers whose
+//! seller signed that input with `SIGHASH_SINGLE|ANYONECANPAY`. A single
+//! padding input is placed in front of the outgoing input, which funds the
+//! payment and the alignment output that precede the recipient output.
+//!
 //! Internally, `TransactionBuilder` calls multiple methods that implement
 //! transformations responsible for individual concerns, such as ensuring that
 //! the transaction fee is paid, and that outgoing outputs aren't too large.
@@ -51,6 +61,10 @@ pub enum Error {
     inscribed_satpoint: SatPoint,
     inscription_id: InscriptionId,
   },
+  UtxoContainsTransferableInscription {
+    outgoing_satpoint: SatPoint,
+    transferable_satpoint: SatPoint,
+  },
   ValueOverflow,
 }
 
@@ -82,6 +96,13 @@ impl fmt::Display for Error {
         f,
         "cannot send {outgoing_satpoint} without also sending inscription {inscription_id} at {inscribed_satpoint}"
       ),
+      Error::UtxoContainsTransferableInscription {
+        outgoing_satpoint,
+        transferable_satpoint,
+      } => write!(
+        f,
+        "cannot send {outgoing_satpoint} without also spending transferable BRC20 inscription at {transferable_satpoint}"
+      ),
       Error::ValueOverflow => write!(f, "arithmetic overflow calculating value"),
       Error::DuplicateAddress(address) => write!(f, "duplicate input address: {address}"),
     }
@@ -100,9 +121,11 @@ pub struct TransactionBuilder {
   locked_utxos: BTreeSet<OutPoint>,
   outgoing: SatPoint,
   outputs: Vec<(Address, Amount)>,
+  payment: Option<(Address, Amount)>,
   recipient: Address,
   runic_utxos: BTreeSet<OutPoint>,
   target: Target,
+  transferable_inscriptions: BTreeSet<SatPoint>,
   unused_change_addresses: Vec<Address>,
   utxos: BTreeSet<OutPoint>,
 }
@@ -121,6 +144,7 @@ impl TransactionBuilder {
     amounts: BTreeMap<OutPoint, Amount>,
     locked_utxos: BTreeSet<OutPoint>,
     runic_utxos: BTreeSet<OutPoint>,
+    transferable_inscriptions: BTreeSet<SatPoint>,
     recipient: Address,
     change: [Address; 2],
     fee_rate: FeeRate,
@@ -136,13 +160,22 @@ impl TransactionBuilder {
       locked_utxos,
       outgoing,
       outputs: Vec::new(),
+      payment: None,
       recipient,
       runic_utxos,
       target,
+      transferable_inscriptions,
       unused_change_addresses: change.to_vec(),
     }
   }
 
+  pub fn pay_for_outgoing(self, address: Address, amount: Amount) -> Self {
+    Self {
+      payment: Some((address, amount)),
+      ..self
+    }
+  }
+
   pub fn build_transaction(self) -> Result<Transaction> {
     if self.change_addresses.len() < 2 {
       return Err(Error::DuplicateAddress(
@@ -154,6 +187,12 @@ impl TransactionBuilder {
       return Err(Error::DuplicateAddress(self.recipient));
     }
 
+    if let Some((address, _amount)) = &self.payment {
+      if self.change_addresses.contains(address) || *address == self.recipient {
+        return Err(Error::DuplicateAddress(address.clone()));
+      }
+    }
+
     match self.target {
       Target::Value(output_value) | Target::ExactPostage(output_value) => {
         let dust_value = self.recipient.script_pubkey().dust_value();
@@ -200,6 +239,17 @@ impl TransactionBuilder {
       }
     }
 
+    for transferable_satpoint in &self.transferable_inscriptions {
+      if self.outgoing.outpoint == transferable_satpoint.outpoint
+        && self.outgoing != *transferable_satpoint
+      {
+        return Err(Error::UtxoContainsTransferableInscription {
+          outgoing_satpoint: self.outgoing,
+          transferable_satpoint: *transferable_satpoint,
+        });
+      }
+    }
+
     let amount = *self
       .amounts
       .get(&self.outgoing.outpoint)
@@ -253,6 +303,10 @@ impl TransactionBuilder {
   }
 
   fn pad_alignment_output(mut self) -> Result<Self> {
+    if let Some((address, amount)) = self.payment.clone() {
+      return self.pad_payment_output(address, amount);
+    }
+
     if self.outputs[0].0 == self.recipient {
       tprintln!("no alignment output");
     } else {
@@ -283,6 +337,52 @@ impl TransactionBuilder {
     Ok(self)
   }
 
+  fn pad_payment_output(mut self, address: Address, amount: Amount) -> Result<Self> {
+    assert_eq!(self.inputs.len(), 1, "invariant: only outgoing input");
+
+    let dust_limit = self
+      .unused_change_addresses
+      .last()
+      .unwrap()
+      .script_pubkey()
+      .dust_value();
+
+    let (alignment_address, alignment) = if self.outputs[0].0 == self.recipient {
+      (
+        self
+          .unused_change_addresses
+          .pop()
+          .expect("not enough change addresses"),
+        Amount::ZERO,
+      )
+    } else {
+      self.outputs.remove(0)
+    };
+
+    let needed = (amount + dust_limit)
+      .checked_sub(alignment)
+      .unwrap_or(Amount::ZERO);
+
+    let (utxo, size) = self.select_cardinal_utxo(needed, false)?;
+
+    if size < needed {
+      return Err(Error::NotEnoughCardinalUtxos);
+    }
+
+    self.inputs.insert(0, utxo);
+    self.outputs.insert(0, (address, amount));
+    self
+      .outputs
+      .insert(0, (alignment_address, size + alignment - amount));
+
+    tprintln!(
+      "padded outgoing input with {size} sat input to pay {} sat",
+      amount.to_sat()
+    );
+
+    Ok(self)
+  }
+
   fn add_value(mut self) -> Result<Self> {
     let estimated_fee = self.estimate_fee();
 
@@ -512,6 +612,15 @@ impl TransactionBuilder {
     }
     assert!(found, "invariant: outgoing sat is found in inputs");
 
+    for transferable_satpoint in &self.transferable_inscriptions {
+      assert!(
+        transaction.input.iter().all(|tx_in| tx_in.previous_output
+          != transferable_satpoint.outpoint
+          || self.outgoing == *transferable_satpoint),
+        "invariant: transferable inscriptions are only spent when sent"
+      );
+    }
+
     let mut output_end = 0;
     let mut found = false;
     for tx_out in &transaction.output {
@@ -591,7 +700,8 @@ impl TransactionBuilder {
           self
             .change_addresses
             .iter()
-            .any(|change_address| change_address.script_pubkey() == output.script_pubkey),
+            .chain(self.payment.iter().map(|(address, _amount)| address))
+            .any(|address| address.script_pubkey() == output.script_pubkey),
           "invariant: all outputs are either change or recipient: unrecognized output {}",
           output.script_pubkey
         );
@@ -599,6 +709,23 @@ impl TransactionBuilder {
       offset += output.value;
     }
 
+    if let Some((address, amount)) = &self.payment {
+      let outgoing_input = transaction
+        .input
+        .iter()
+        .position(|tx_in| tx_in.previous_output == self.outgoing.outpoint)
+        .unwrap();
+
+      assert_eq!(
+        transaction.output.get(outgoing_input),
+        Some(&TxOut {
+          value: amount.to_sat(),
+          script_pubkey: address.script_pubkey(),
+        }),
+        "invariant: payment is at index of outgoing input",
+      );
+    }
+
     let mut actual_fee = Amount::ZERO;
     for input in &transaction.input {
       actual_fee += self.amounts[&input.previous_output];
@@ -662,10 +789,17 @@ impl TransactionBuilder {
       .map(|satpoint| satpoint.outpoint)
       .collect::<BTreeSet<OutPoint>>();
 
+    let transferable_utxos = self
+      .transferable_inscriptions
+      .iter()
+      .map(|satpoint| satpoint.outpoint)
+      .collect::<BTreeSet<OutPoint>>();
+
     let mut best_match = None;
     for utxo in &self.utxos {
       if self.runic_utxos.contains(utxo)
         || inscribed_utxos.contains(utxo)
+        || transferable_utxos.contains(utxo)
         || self.locked_utxos.contains(utxo)
       {
         continue;
@@ -734,6 +868,7 @@ mod tests {
       utxos.clone().into_iter().collect(),
       BTreeSet::new(),
       BTreeSet::new(),
+      BTreeSet::new(),
       recipient(),
       [change(0), change(1)],
       FeeRate::try_from(1.0).unwrap(),
@@ -772,6 +907,7 @@ mod tests {
       inscriptions: BTreeMap::new(),
       locked_utxos: BTreeSet::new(),
       runic_utxos: BTreeSet::new(),
+      transferable_inscriptions: BTreeSet::new(),
       recipient: recipient(),
       unused_change_addresses: vec![change(0), change(1)],
       change_addresses: vec![change(0), change(1)].into_iter().collect(),
@@ -781,6 +917,7 @@ mod tests {
         (change(0), Amount::from_sat(5_000)),
         (change(1), Amount::from_sat(1_724)),
       ],
+      payment: None,
       target: Target::Postage,
     };
 
@@ -809,6 +946,7 @@ mod tests {
       utxos.into_iter().collect(),
       BTreeSet::new(),
       BTreeSet::new(),
+      BTreeSet::new(),
       recipient(),
       [change(0), change(1)],
       FeeRate::try_from(1.0).unwrap(),
@@ -830,6 +968,7 @@ mod tests {
         utxos.into_iter().collect(),
         BTreeSet::new(),
         BTreeSet::new(),
+        BTreeSet::new(),
         recipient(),
         [change(0), change(1)],
         FeeRate::try_from(1.0).unwrap(),
@@ -856,6 +995,7 @@ mod tests {
       utxos.into_iter().collect(),
       BTreeSet::new(),
       BTreeSet::new(),
+      BTreeSet::new(),
       recipient(),
       [change(0), change(1)],
       FeeRate::try_from(1.0).unwrap(),
@@ -882,6 +1022,7 @@ mod tests {
         utxos.into_iter().collect(),
         BTreeSet::new(),
         BTreeSet::new(),
+        BTreeSet::new(),
         recipient(),
         [change(0), change(1)],
         FeeRate::try_from(1.0).unwrap(),
@@ -908,6 +1049,7 @@ mod tests {
         utxos.into_iter().collect(),
         BTreeSet::new(),
         BTreeSet::new(),
+        BTreeSet::new(),
         recipient(),
         [change(0), change(1)],
         FeeRate::try_from(1.0).unwrap(),
@@ -932,6 +1074,7 @@ mod tests {
         utxos.into_iter().collect(),
         BTreeSet::new(),
         BTreeSet::new(),
+        BTreeSet::new(),
         recipient(),
         [change(0), change(1)],
         FeeRate::try_from(1.0).unwrap(),
@@ -956,6 +1099,7 @@ mod tests {
         utxos.into_iter().collect(),
         BTreeSet::new(),
         BTreeSet::new(),
+        BTreeSet::new(),
         recipient(),
         [change(0), change(1)],
         FeeRate::try_from(1.0).unwrap(),
@@ -986,6 +1130,7 @@ mod tests {
         .collect(),
       BTreeSet::new(),
       BTreeSet::new(),
+      BTreeSet::new(),
       recipient(),
       [change(0), change(1)],
       FeeRate::try_from(1.0).unwrap(),
@@ -1006,6 +1151,7 @@ mod tests {
         .collect(),
       BTreeSet::new(),
       BTreeSet::new(),
+      BTreeSet::new(),
       recipient(),
       [change(0), change(1)],
       FeeRate::try_from(1.0).unwrap(),
@@ -1026,6 +1172,7 @@ mod tests {
         .collect(),
       BTreeSet::new(),
       BTreeSet::new(),
+      BTreeSet::new(),
       recipient(),
       [change(0), change(1)],
       FeeRate::try_from(1.0).unwrap(),
@@ -1046,6 +1193,7 @@ mod tests {
         .collect(),
       BTreeSet::new(),
       BTreeSet::new(),
+      BTreeSet::new(),
       recipient(),
       [change(0), change(1)],
       FeeRate::try_from(1.0).unwrap(),
@@ -1073,6 +1221,7 @@ mod tests {
         .collect(),
       BTreeSet::new(),
       BTreeSet::new(),
+      BTreeSet::new(),
       recipient(),
       [change(0), change(1)],
       FeeRate::try_from(1.0).unwrap(),
@@ -1097,6 +1246,7 @@ mod tests {
         utxos.into_iter().collect(),
         BTreeSet::new(),
         BTreeSet::new(),
+        BTreeSet::new(),
         recipient(),
         [change(0), change(1)],
         FeeRate::try_from(1.0).unwrap(),
@@ -1126,6 +1276,7 @@ mod tests {
       utxos.into_iter().collect(),
       BTreeSet::new(),
       BTreeSet::new(),
+      BTreeSet::new(),
       recipient(),
       [change(0), change(1)],
       FeeRate::try_from(1.0).unwrap(),
@@ -1148,6 +1299,7 @@ mod tests {
         utxos.into_iter().collect(),
         BTreeSet::new(),
         BTreeSet::new(),
+        BTreeSet::new(),
         recipient(),
         [change(0), change(1)],
         FeeRate::try_from(1.0).unwrap(),
@@ -1177,6 +1329,7 @@ mod tests {
         utxos.into_iter().collect(),
         BTreeSet::new(),
         BTreeSet::new(),
+        BTreeSet::new(),
         recipient(),
         [change(0), change(1)],
         FeeRate::try_from(1.0).unwrap(),
@@ -1203,6 +1356,7 @@ mod tests {
       utxos.into_iter().collect(),
       BTreeSet::new(),
       BTreeSet::new(),
+      BTreeSet::new(),
       recipient(),
       [change(0), change(1)],
       FeeRate::try_from(1.0).unwrap(),
@@ -1232,6 +1386,7 @@ mod tests {
       utxos.into_iter().collect(),
       BTreeSet::new(),
       BTreeSet::new(),
+      BTreeSet::new(),
       recipient(),
       [change(0), change(1)],
       FeeRate::try_from(1.0).unwrap(),
@@ -1259,6 +1414,7 @@ mod tests {
       utxos.into_iter().collect(),
       BTreeSet::new(),
       BTreeSet::new(),
+      BTreeSet::new(),
       recipient(),
       [change(0), change(1)],
       FeeRate::try_from(1.0).unwrap(),
@@ -1283,6 +1439,7 @@ mod tests {
       utxos.into_iter().collect(),
       BTreeSet::new(),
       BTreeSet::new(),
+      BTreeSet::new(),
       recipient(),
       [change(0), change(1)],
       FeeRate::try_from(1.0).unwrap(),
@@ -1309,6 +1466,7 @@ mod tests {
       utxos: BTreeSet::new(),
       locked_utxos: BTreeSet::new(),
       runic_utxos: BTreeSet::new(),
+      transferable_inscriptions: BTreeSet::new(),
       outgoing: satpoint(1, 0),
       inscriptions: BTreeMap::new(),
       recipient: recipient(),
@@ -1320,6 +1478,7 @@ mod tests {
         (recipient(), Amount::from_sat(5_000)),
         (change(1), Amount::from_sat(1_774)),
       ],
+      payment: None,
       target: Target::Postage,
     }
     .build()
@@ -1340,6 +1499,7 @@ mod tests {
       utxos: BTreeSet::new(),
       locked_utxos: BTreeSet::new(),
       runic_utxos: BTreeSet::new(),
+      transferable_inscriptions: BTreeSet::new(),
       outgoing: satpoint(1, 0),
       inscriptions: BTreeMap::new(),
       recipient: recipient(),
@@ -1351,6 +1511,7 @@ mod tests {
         (change(0), Amount::from_sat(5_000)),
         (change(0), Amount::from_sat(1_774)),
       ],
+      payment: None,
       target: Target::Postage,
     }
     .build()
@@ -1371,6 +1532,7 @@ mod tests {
         utxos.into_iter().collect(),
         BTreeSet::new(),
         BTreeSet::new(),
+        BTreeSet::new(),
         recipient(),
         [change(0), change(1)],
         FeeRate::try_from(1.0).unwrap(),
@@ -1395,6 +1557,7 @@ mod tests {
         utxos.into_iter().collect(),
         BTreeSet::new(),
         vec![outpoint(2)].into_iter().collect(),
+        BTreeSet::new(),
         recipient(),
         [change(0), change(1)],
         FeeRate::try_from(1.0).unwrap(),
@@ -1416,6 +1579,7 @@ mod tests {
         utxos.into_iter().collect(),
         BTreeSet::new(),
         BTreeSet::new(),
+        BTreeSet::new(),
         recipient(),
         [change(0), change(1)],
         FeeRate::try_from(1.0).unwrap(),
@@ -1430,6 +1594,270 @@ mod tests {
     )
   }
 
+  #[test]
+  fn do_not_select_transferable_utxos_for_cardinal_utxos() {
+    let utxos = vec![
+      (outpoint(1), Amount::from_sat(100)),
+      (outpoint(2), Amount::from_sat(49 * COIN_VALUE)),
+    ];
+
+    pretty_assert_eq!(
+      TransactionBuilder::new(
+        satpoint(1, 0),
+        BTreeMap::new(),
+        utxos.into_iter().collect(),
+        BTreeSet::new(),
+        BTreeSet::new(),
+        vec![satpoint(2, 0)].into_iter().collect(),
+        recipient(),
+        [change(0), change(1)],
+        FeeRate::try_from(1.0).unwrap(),
+        Target::Postage,
+      )
+      .build_transaction(),
+      Err(Error::NotEnoughCardinalUtxos)
+    )
+  }
+
+  #[test]
+  fn do_not_spend_transferable_inscription_when_sending_other_sat() {
+    let utxos = vec![
+      (outpoint(1), Amount::from_sat(20_000)),
+      (outpoint(2), Amount::from_sat(20_000)),
+    ];
+
+    pretty_assert_eq!(
+      TransactionBuilder::new(
+        satpoint(1, 5_000),
+        BTreeMap::from([(satpoint(1, 0), inscription_id(1))]),
+        utxos.into_iter().collect(),
+        BTreeSet::new(),
+        BTreeSet::new(),
+        vec![satpoint(1, 0)].into_iter().collect(),
+        recipient(),
+        [change(0), change(1)],
+        FeeRate::try_from(1.0).unwrap(),
+        Target::Postage,
+      )
+      .build_transaction(),
+      Err(Error::UtxoContainsTransferableInscription {
+        outgoing_satpoint: satpoint(1, 5_000),
+        transferable_satpoint: satpoint(1, 0),
+      })
+    )
+  }
+
+  #[test]
+  fn transferable_inscription_may_be_sent() {
+    let utxos = vec![
+      (outpoint(1), Amount::from_sat(10_000)),
+      (outpoint(2), Amount::from_sat(20_000)),
+    ];
+
+    pretty_assert_eq!(
+      TransactionBuilder::new(
+        satpoint(1, 0),
+        BTreeMap::from([(satpoint(1, 0), inscription_id(1))]),
+        utxos.into_iter().collect(),
+        BTreeSet::new(),
+        BTreeSet::new(),
+        vec![satpoint(1, 0)].into_iter().collect(),
+        recipient(),
+        [change(0), change(1)],
+        FeeRate::try_from(1.0).unwrap(),
+        Target::Postage,
+      )
+      .build_transaction(),
+      Ok(Transaction {
+        version: 2,
+        lock_time: LockTime::ZERO,
+        input: vec![tx_in(outpoint(1))],
+        output: vec![tx_out(9_901, recipient())],
+      })
+    )
+  }
+
+  #[test]
+  #[should_panic(expected = "invariant: transferable inscriptions are only spent when sent")]
+  fn invariant_transferable_inscriptions_are_only_spent_when_sent() {
+    let mut amounts = BTreeMap::new();
+    amounts.insert(outpoint(1), Amount::from_sat(5_000));
+    amounts.insert(outpoint(2), Amount::from_sat(5_000));
+
+    TransactionBuilder {
+      amounts,
+      fee_rate: FeeRate::try_from(1.0).unwrap(),
+      utxos: BTreeSet::new(),
+      locked_utxos: BTreeSet::new(),
+      runic_utxos: BTreeSet::new(),
+      transferable_inscriptions: vec![satpoint(2, 0)].into_iter().collect(),
+      outgoing: satpoint(1, 0),
+      inscriptions: BTreeMap::new(),
+      recipient: recipient(),
+      unused_change_addresses: vec![change(0), change(1)],
+      change_addresses: vec![change(0), change(1)].into_iter().collect(),
+      inputs: vec![outpoint(1), outpoint(2)],
+      outputs: vec![(recipient(), Amount::from_sat(9_832))],
+      payment: None,
+      target: Target::Postage,
+    }
+    .build()
+    .unwrap();
+  }
+
+  #[test]
+  fn payment_is_at_index_of_outgoing_input() {
+    let utxos = vec![
+      (outpoint(1), Amount::from_sat(10_000)),
+      (outpoint(2), Amount::from_sat(50_000)),
+      (outpoint(3), Amount::from_sat(20_000)),
+    ];
+
+    pretty_assert_eq!(
+      TransactionBuilder::new(
+        satpoint(1, 0),
+        BTreeMap::from([(satpoint(1, 0), inscription_id(1))]),
+        utxos.into_iter().collect(),
+        BTreeSet::new(),
+        BTreeSet::new(),
+        BTreeSet::new(),
+        recipient(),
+        [change(0), change(1)],
+        FeeRate::try_from(1.0).unwrap(),
+        Target::Postage,
+      )
+      .pay_for_outgoing(change(2), Amount::from_sat(30_000))
+      .build_transaction(),
+      Ok(Transaction {
+        version: 2,
+        lock_time: LockTime::ZERO,
+        input: vec![tx_in(outpoint(2)), tx_in(outpoint(1))],
+        output: vec![
+          tx_out(20_000, change(1)),
+          tx_out(30_000, change(2)),
+          tx_out(9_781, recipient()),
+        ],
+      })
+    )
+  }
+
+  #[test]
+  fn payment_pads_alignment_output() {
+    let utxos = vec![
+      (outpoint(1), Amount::from_sat(10_000)),
+      (outpoint(2), Amount::from_sat(30_000)),
+      (outpoint(3), Amount::from_sat(5_000)),
+    ];
+
+    pretty_assert_eq!(
+      TransactionBuilder::new(
+        satpoint(1, 1_000),
+        BTreeMap::from([(satpoint(1, 1_000), inscription_id(1))]),
+        utxos.into_iter().collect(),
+        BTreeSet::new(),
+        BTreeSet::new(),
+        BTreeSet::new(),
+        recipient(),
+        [change(0), change(1)],
+        FeeRate::try_from(1.0).unwrap(),
+        Target::Postage,
+      )
+      .pay_for_outgoing(change(2), Amount::from_sat(30_000))
+      .build_transaction(),
+      Ok(Transaction {
+        version: 2,
+        lock_time: LockTime::ZERO,
+        input: vec![tx_in(outpoint(2)), tx_in(outpoint(1))],
+        output: vec![
+          tx_out(1_000, change(1)),
+          tx_out(30_000, change(2)),
+          tx_out(8_781, recipient()),
+        ],
+      })
+    )
+  }
+
+  #[test]
+  fn payment_requires_padding_input_worth_payment() {
+    let utxos = vec![
+      (outpoint(1), Amount::from_sat(10_000)),
+      (outpoint(2), Amount::from_sat(20_000)),
+      (outpoint(3), Amount::from_sat(20_000)),
+    ];
+
+    pretty_assert_eq!(
+      TransactionBuilder::new(
+        satpoint(1, 0),
+        BTreeMap::from([(satpoint(1, 0), inscription_id(1))]),
+        utxos.into_iter().collect(),
+        BTreeSet::new(),
+        BTreeSet::new(),
+        BTreeSet::new(),
+        recipient(),
+        [change(0), change(1)],
+        FeeRate::try_from(1.0).unwrap(),
+        Target::Postage,
+      )
+      .pay_for_outgoing(change(2), Amount::from_sat(30_000))
+      .build_transaction(),
+      Err(Error::NotEnoughCardinalUtxos),
+    )
+  }
+
+  #[test]
+  fn payment_address_may_not_be_change_address() {
+    pretty_assert_eq!(
+      TransactionBuilder::new(
+        satpoint(1, 0),
+        BTreeMap::new(),
+        vec![(outpoint(1), Amount::from_sat(10_000))]
+          .into_iter()
+          .collect(),
+        BTreeSet::new(),
+        BTreeSet::new(),
+        BTreeSet::new(),
+        recipient(),
+        [change(0), change(1)],
+        FeeRate::try_from(1.0).unwrap(),
+        Target::Postage,
+      )
+      .pay_for_outgoing(change(1), Amount::from_sat(30_000))
+      .build_transaction(),
+      Err(Error::DuplicateAddress(change(1))),
+    )
+  }
+
+  #[test]
+  #[should_panic(expected = "invariant: payment is at index of outgoing input")]
+  fn invariant_payment_is_at_index_of_outgoing_input() {
+    let mut amounts = BTreeMap::new();
+    amounts.insert(outpoint(1), Amount::from_sat(10_000));
+    amounts.insert(outpoint(2), Amount::from_sat(50_000));
+
+    TransactionBuilder {
+      amounts,
+      fee_rate: FeeRate::try_from(1.0).unwrap(),
+      utxos: BTreeSet::new(),
+      locked_utxos: BTreeSet::new(),
+      runic_utxos: BTreeSet::new(),
+      transferable_inscriptions: BTreeSet::new(),
+      outgoing: satpoint(1, 0),
+      inscriptions: BTreeMap::new(),
+      recipient: recipient(),
+      unused_change_addresses: vec![change(0), change(1)],
+      change_addresses: vec![change(0), change(1)].into_iter().collect(),
+      inputs: vec![outpoint(2), outpoint(1)],
+      outputs: vec![
+        (change(1), Amount::from_sat(50_000)),
+        (recipient(), Amount::from_sat(9_000)),
+      ],
+      payment: Some((change(2), Amount::from_sat(30_000))),
+      target: Target::Postage,
+    }
+    .build()
+    .unwrap();
+  }
+
   #[test]
   fn build_transaction_with_custom_fee_rate() {
     let utxos = vec![(outpoint(1), Amount::from_sat(10_000))];
@@ -1442,6 +1870,7 @@ mod tests {
       utxos.into_iter().collect(),
       BTreeSet::new(),
       BTreeSet::new(),
+      BTreeSet::new(),
       recipient(),
       [change(0), change(1)],
       fee_rate,
@@ -1475,6 +1904,7 @@ mod tests {
         utxos.into_iter().collect(),
         BTreeSet::new(),
         BTreeSet::new(),
+        BTreeSet::new(),
         recipient(),
         [change(0), change(1)],
         FeeRate::try_from(1.0).unwrap(),
@@ -1504,6 +1934,7 @@ mod tests {
         utxos.into_iter().collect(),
         BTreeSet::new(),
         BTreeSet::new(),
+        BTreeSet::new(),
         recipient(),
         [change(0), change(1)],
         FeeRate::try_from(1.0).unwrap(),
@@ -1530,6 +1961,7 @@ mod tests {
         utxos.into_iter().collect(),
         BTreeSet::new(),
         BTreeSet::new(),
+        BTreeSet::new(),
         recipient(),
         [change(0), change(1)],
         FeeRate::try_from(1.0).unwrap(),
@@ -1557,6 +1989,7 @@ mod tests {
         utxos.into_iter().collect(),
         BTreeSet::new(),
         BTreeSet::new(),
+        BTreeSet::new(),
         recipient(),
         [change(0), change(1)],
         FeeRate::try_from(1.0).unwrap(),
@@ -1581,6 +2014,7 @@ mod tests {
         utxos.into_iter().collect(),
         BTreeSet::new(),
         BTreeSet::new(),
+        BTreeSet::new(),
         recipient(),
         [change(0), change(1)],
         FeeRate::try_from(4.0).unwrap(),
@@ -1624,6 +2058,7 @@ mod tests {
           .collect(),
         BTreeSet::new(),
         BTreeSet::new(),
+        BTreeSet::new(),
         recipient(),
         [change(0), change(1)],
         FeeRate::try_from(1.0).unwrap(),
@@ -1650,6 +2085,7 @@ mod tests {
           .collect(),
         BTreeSet::new(),
         BTreeSet::new(),
+        BTreeSet::new(),
         recipient(),
         [change(0), change(1)],
         FeeRate::try_from(1.0).unwrap(),
@@ -1676,6 +2112,7 @@ mod tests {
           .collect(),
         BTreeSet::new(),
         BTreeSet::new(),
+        BTreeSet::new(),
         recipient(),
         [change(0), change(1)],
         FeeRate::try_from(5.0).unwrap(),
@@ -1702,6 +2139,7 @@ mod tests {
           .collect(),
         BTreeSet::new(),
         BTreeSet::new(),
+        BTreeSet::new(),
         recipient(),
         [change(0), change(1)],
         FeeRate::try_from(6.0).unwrap(),
@@ -1723,6 +2161,7 @@ mod tests {
           .collect(),
         BTreeSet::new(),
         BTreeSet::new(),
+        BTreeSet::new(),
         recipient(),
         [recipient(), change(1)],
         FeeRate::try_from(0.0).unwrap(),
@@ -1744,6 +2183,7 @@ mod tests {
           .collect(),
         BTreeSet::new(),
         BTreeSet::new(),
+        BTreeSet::new(),
         recipient(),
         [change(0), change(0)],
         FeeRate::try_from(0.0).unwrap(),
@@ -1765,6 +2205,7 @@ mod tests {
           .collect(),
         BTreeSet::new(),
         BTreeSet::new(),
+        BTreeSet::new(),
         recipient(),
         [change(0), change(1)],
         FeeRate::try_from(2.0).unwrap(),
@@ -1791,6 +2232,7 @@ mod tests {
           .collect(),
         BTreeSet::new(),
         BTreeSet::new(),
+        BTreeSet::new(),
         recipient(),
         [change(0), change(1)],
         FeeRate::try_from(250.0).unwrap(),
@@ -1823,6 +2265,7 @@ mod tests {
       utxos.clone().into_iter().collect(),
       BTreeSet::new(),
       BTreeSet::new(),
+      BTreeSet::new(),
       recipient(),
       [change(0), change(1)],
       FeeRate::try_from(1.0).unwrap(),
@@ -1869,6 +2312,7 @@ mod tests {
       utxos.clone().into_iter().collect(),
       BTreeSet::new(),
       BTreeSet::new(),
+      BTreeSet::new(),
       recipient(),
       [change(0), change(1)],
       FeeRate::try_from(1.0).unwrap(),
@@ -1922,6 +2366,7 @@ mod tests {
       utxos.into_iter().collect(),
       BTreeSet::new(),
       BTreeSet::new(),
+      BTreeSet::new(),
       recipient(),
       [change(0), change(1)],
       FeeRate::try_from(1.0).unwrap(),
@@ -1978,6 +2423,7 @@ mod tests {
       utxos.into_iter().collect(),
       BTreeSet::new(),
       BTreeSet::new(),
+      BTreeSet::new(),
       recipient(),
       [change(0), change(1)],
       fee_rate,
@@ -2014,6 +2460,7 @@ mod tests {
       utxos.into_iter().collect(),
       locked_utxos.into_iter().collect(),
       BTreeSet::new(),
+      BTreeSet::new(),
       recipient(),
       [change(0), change(1)],
       FeeRate::try_from(1.0).unwrap(),
@@ -2040,6 +2487,7 @@ mod tests {
       utxos.into_iter().collect(),
       locked_utxos.into_iter().collect(),
       BTreeSet::new(),
+      BTreeSet::new(),
       recipient(),
       [change(0), change(1)],
       FeeRate::try_from(1.0).unwrap(),
@@ -2068,6 +2516,7 @@ mod tests {
       utxos.into_iter().collect(),
       BTreeSet::new(),
       BTreeSet::new(),
+      BTreeSet::new(),
       recipient(),
       [change(0), change(1)],
       FeeRate::try_from(1.0).unwrap(),
@@ -2096,6 +2545,7 @@ mod tests {
       utxos.into_iter().collect(),
       BTreeSet::new(),
       BTreeSet::new(),
+      BTreeSet::new(),
       recipient(),
       [change(0), change(1)],
       FeeRate::try_from(1.0).unwrap(),
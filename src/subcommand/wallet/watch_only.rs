@@ -0,0 +1,132 @@
+use {super::*, miniscript::descriptor::DescriptorPublicKey};
+
+/// A wallet described by public output descriptors. Its addresses are derived locally, and its
+/// unspent outputs are looked up in the index instead of a Bitcoin Core wallet.
+#[derive(Debug)]
+pub(crate) struct WatchOnly {
+  addresses: Vec<Address>,
+}
+
+impl WatchOnly {
+  /// Loads the descriptor `descriptor`, or the descriptors in file `descriptor`, one per line,
+  /// deriving the first `range` addresses of ranged descriptors.
+  pub(crate) fn load(descriptor: &str, range: u32, chain: Chain) -> Result<Self> {
+    let path = Path::new(descriptor);
+
+    let descriptors = if path.is_file() {
+      fs::read_to_string(path).with_context(|| format!("I/O error reading `{}`", path.display()))?
+    } else {
+      descriptor.into()
+    };
+
+    Ok(Self {
+      addresses: Self::derive_addresses(&descriptors, range, chain)?,
+    })
+  }
+
+  fn derive_addresses(descriptors: &str, range: u32, chain: Chain) -> Result<Vec<Address>> {
+    let mut addresses = Vec::new();
+
+    for line in descriptors.lines().map(str::trim) {
+      if line.is_empty() || line.starts_with('#') {
+        continue;
+      }
+
+      let descriptor = Descriptor::<DescriptorPublicKey>::from_str(line)
+        .with_context(|| format!("invalid descriptor `{line}`"))?;
+
+      for descriptor in descriptor.into_single_descriptors()? {
+        let indices = if descriptor.has_wildcard() {
+          0..range
+        } else {
+          0..1
+        };
+
+        for index in indices {
+          let address = descriptor
+            .at_derivation_index(index)?
+            .address(chain.network())?;
+
+          if !addresses.contains(&address) {
+            addresses.push(address);
+          }
+        }
+      }
+    }
+
+    if addresses.is_empty() {
+      bail!("no descriptors found");
+    }
+
+    Ok(addresses)
+  }
+
+  pub(crate) fn addresses(&self) -> &[Address] {
+    &self.addresses
+  }
+
+  pub(crate) fn get_unspent_outputs(&self, index: &Index) -> Result<BTreeMap<OutPoint, Amount>> {
+    if !index.has_address_index() {
+      bail!("watch-only wallets require index created with `--index-addresses` flag");
+    }
+
+    index.get_address_unspent_outputs(&self.addresses)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn descriptor() -> String {
+    let master_private_key = ExtendedPrivKey::new_master(Network::Regtest, &[0; 64]).unwrap();
+
+    format!(
+      "tr({}/<0;1>/*)",
+      bitcoin::bip32::ExtendedPubKey::from_priv(&Secp256k1::new(), &master_private_key),
+    )
+  }
+
+  #[test]
+  fn ranged_multipath_descriptors_derive_receive_and_change_addresses() {
+    let addresses = WatchOnly::derive_addresses(&descriptor(), 3, Chain::Regtest).unwrap();
+
+    assert_eq!(addresses.len(), 6);
+    assert!(addresses
+      .iter()
+      .all(|address| address.address_type() == Some(bitcoin::AddressType::P2tr)));
+  }
+
+  #[test]
+  fn blank_lines_and_comments_are_ignored() {
+    assert_eq!(
+      WatchOnly::derive_addresses(
+        &format!("# hot wallet\n\n{}\n", descriptor()),
+        2,
+        Chain::Regtest
+      )
+      .unwrap(),
+      WatchOnly::derive_addresses(&descriptor(), 2, Chain::Regtest).unwrap(),
+    );
+  }
+
+  #[test]
+  fn invalid_descriptor_is_an_error() {
+    assert_eq!(
+      WatchOnly::derive_addresses("foo", 1, Chain::Regtest)
+        .unwrap_err()
+        .to_string(),
+      "invalid descriptor `foo`",
+    );
+  }
+
+  #[test]
+  fn empty_descriptor_is_an_error() {
+    assert_eq!(
+      WatchOnly::derive_addresses("# nothing here\n", 1, Chain::Regtest)
+        .unwrap_err()
+        .to_string(),
+      "no descriptors found",
+    );
+  }
+}
@@ -3,7 +3,7 @@ use {super::*, boilerplate::Boilerplate};
 pub(crate) use {
   block::{BlockHtml, BlockJson},
   blocks::BlocksHtml,
-  children::{ChildrenHtml, ChildrenJson},
+  children::{ChildInscriptionJson, ChildInscriptionsJson, ChildrenHtml, ChildrenJson},
   clock::ClockSvg,
   collections::CollectionsHtml,
   home::HomeHtml,
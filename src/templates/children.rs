@@ -16,6 +16,21 @@ pub struct ChildrenJson {
   pub page: usize,
 }
 
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct ChildInscriptionJson {
+  pub id: InscriptionId,
+  pub number: i32,
+  pub satpoint: SatPoint,
+  pub content_type: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct ChildInscriptionsJson {
+  pub children: Vec<ChildInscriptionJson>,
+  pub more: bool,
+  pub page: usize,
+}
+
 impl PageContent for ChildrenHtml {
   fn title(&self) -> String {
     format!("Inscription {} Children", self.parent_number)
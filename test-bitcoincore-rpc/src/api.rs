@@ -23,7 +23,7 @@ pub trait Api {
   ) -> Result<Value, jsonrpc_core::Error>;
 
   #[rpc(name = "getblock")]
-  fn get_block(&self, blockhash: BlockHash, verbosity: u64) -> Result<String, jsonrpc_core::Error>;
+  fn get_block(&self, blockhash: BlockHash, verbosity: u64) -> Result<Value, jsonrpc_core::Error>;
 
   #[rpc(name = "getblockcount")]
   fn get_block_count(&self) -> Result<u64, jsonrpc_core::Error>;
@@ -92,6 +92,25 @@ pub trait Api {
     include_watchonly: Option<bool>,
   ) -> Result<Value, jsonrpc_core::Error>;
 
+  #[rpc(name = "finalizepsbt")]
+  fn finalize_psbt(
+    &self,
+    psbt: String,
+    extract: Option<bool>,
+  ) -> Result<Value, jsonrpc_core::Error>;
+
+  #[rpc(name = "walletprocesspsbt")]
+  fn wallet_process_psbt(
+    &self,
+    psbt: String,
+    sign: Option<bool>,
+    sighash_type: Option<String>,
+    bip32derivs: Option<bool>,
+  ) -> Result<WalletProcessPsbtResult, jsonrpc_core::Error>;
+
+  #[rpc(name = "getrawmempool")]
+  fn get_raw_mempool(&self) -> Result<Vec<Txid>, jsonrpc_core::Error>;
+
   #[rpc(name = "getrawtransaction")]
   fn get_raw_transaction(
     &self,
@@ -138,6 +157,15 @@ pub trait Api {
     address_type: Option<bitcoincore_rpc::json::AddressType>,
   ) -> Result<Address, jsonrpc_core::Error>;
 
+  #[rpc(name = "listreceivedbyaddress")]
+  fn list_received_by_address(
+    &self,
+    minconf: Option<u32>,
+    include_empty: Option<bool>,
+    include_watchonly: Option<bool>,
+    address_filter: Option<Address<NetworkUnchecked>>,
+  ) -> Result<Vec<ListReceivedByAddressResult>, jsonrpc_core::Error>;
+
   #[rpc(name = "listtransactions")]
   fn list_transactions(
     &self,
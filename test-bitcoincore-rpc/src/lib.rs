@@ -2,6 +2,7 @@
 
 use {
   api::Api,
+  base64::Engine,
   bitcoin::{
     address::{Address, NetworkUnchecked},
     amount::SignedAmount,
@@ -18,12 +19,12 @@ use {
   },
   bitcoincore_rpc::json::{
     Bip125Replaceable, CreateRawTransactionInput, Descriptor, EstimateMode, GetBalancesResult,
-    GetBalancesResultEntry, GetBlockHeaderResult, GetBlockchainInfoResult, GetDescriptorInfoResult,
-    GetNetworkInfoResult, GetRawTransactionResult, GetTransactionResult,
+    GetBalancesResultEntry, GetBlockHeaderResult, GetBlockResult, GetBlockchainInfoResult,
+    GetDescriptorInfoResult, GetNetworkInfoResult, GetRawTransactionResult, GetTransactionResult,
     GetTransactionResultDetail, GetTransactionResultDetailCategory, GetWalletInfoResult,
-    ImportDescriptors, ImportMultiResult, ListDescriptorsResult, ListTransactionResult,
-    ListUnspentResultEntry, LoadWalletResult, SignRawTransactionInput, SignRawTransactionResult,
-    Timestamp, WalletTxInfo,
+    ImportDescriptors, ImportMultiResult, ListDescriptorsResult, ListReceivedByAddressResult,
+    ListTransactionResult, ListUnspentResultEntry, LoadWalletResult, SignRawTransactionInput,
+    SignRawTransactionResult, Timestamp, WalletProcessPsbtResult, WalletTxInfo,
   },
   jsonrpc_core::{IoHandler, Value},
   jsonrpc_http_server::{CloseHandle, ServerBuilder},
@@ -266,6 +267,10 @@ impl Handle {
     self.state().locked.insert(output);
   }
 
+  pub fn locked(&self) -> BTreeSet<OutPoint> {
+    self.state().locked.clone()
+  }
+
   pub fn network(&self) -> String {
     match self.state().network {
       Network::Bitcoin => "mainnet".to_string(),
@@ -45,6 +45,7 @@ impl Api for Server {
   }
 
   fn get_blockchain_info(&self) -> Result<GetBlockchainInfoResult, jsonrpc_core::Error> {
+    let height = u64::try_from(self.state().hashes.len()).unwrap() - 1;
     Ok(GetBlockchainInfoResult {
       chain: String::from(match self.network {
         Network::Bitcoin => "main",
@@ -53,8 +54,8 @@ impl Api for Server {
         Network::Regtest => "regtest",
         _ => panic!(),
       }),
-      blocks: 0,
-      headers: 0,
+      blocks: height,
+      headers: height,
       best_block_hash: self.state().hashes[0],
       difficulty: 0.0,
       median_time: 0,
@@ -142,15 +143,44 @@ impl Api for Server {
     }
   }
 
-  fn get_block(
-    &self,
-    block_hash: BlockHash,
-    verbosity: u64,
-  ) -> Result<String, jsonrpc_core::Error> {
-    assert_eq!(verbosity, 0, "Verbosity level {verbosity} is unsupported");
-    match self.state().blocks.get(&block_hash) {
-      Some(block) => Ok(hex::encode(serialize(block))),
-      None => Err(Self::not_found()),
+  fn get_block(&self, block_hash: BlockHash, verbosity: u64) -> Result<Value, jsonrpc_core::Error> {
+    let state = self.state();
+
+    let Some(block) = state.blocks.get(&block_hash) else {
+      return Err(Self::not_found());
+    };
+
+    match verbosity {
+      0 => Ok(serde_json::to_value(hex::encode(serialize(block))).unwrap()),
+      1 => Ok(
+        serde_json::to_value(GetBlockResult {
+          bits: String::new(),
+          chainwork: Vec::new(),
+          confirmations: 0,
+          difficulty: 0.0,
+          hash: block_hash,
+          height: state
+            .hashes
+            .iter()
+            .position(|hash| *hash == block_hash)
+            .unwrap(),
+          mediantime: None,
+          merkleroot: block.header.merkle_root,
+          n_tx: block.txdata.len(),
+          nextblockhash: None,
+          nonce: block.header.nonce,
+          previousblockhash: None,
+          size: serialize(block).len(),
+          strippedsize: None,
+          time: block.header.time.try_into().unwrap(),
+          tx: block.txdata.iter().map(|tx| tx.txid()).collect(),
+          version: block.header.version.to_consensus(),
+          version_hex: Some(block.header.version.to_consensus().to_be_bytes().to_vec()),
+          weight: block.weight().to_wu().try_into().unwrap(),
+        })
+        .unwrap(),
+      ),
+      _ => panic!("Verbosity level {verbosity} is unsupported"),
     }
   }
 
@@ -475,6 +505,132 @@ impl Api for Server {
     }
   }
 
+  fn finalize_psbt(
+    &self,
+    psbt: String,
+    extract: Option<bool>,
+  ) -> Result<Value, jsonrpc_core::Error> {
+    assert_eq!(extract, Some(true), "extract param not supported");
+
+    let encoded = psbt;
+
+    let psbt = bitcoin::psbt::Psbt::deserialize(
+      &base64::engine::general_purpose::STANDARD
+        .decode(&encoded)
+        .unwrap(),
+    )
+    .unwrap();
+
+    let mut transaction = psbt.unsigned_tx.clone();
+    let mut complete = true;
+
+    for (tx_in, input) in transaction.input.iter_mut().zip(&psbt.inputs) {
+      tx_in.witness = if let Some(witness) = &input.final_script_witness {
+        witness.clone()
+      } else if let Some(signature) = input.tap_key_sig {
+        Witness::from_slice(&[signature.to_vec()])
+      } else if let Some((public_key, signature)) = input.partial_sigs.iter().next() {
+        Witness::from_slice(&[signature.to_vec(), public_key.to_bytes()])
+      } else {
+        complete = false;
+        Witness::new()
+      };
+    }
+
+    // like bitcoind, only include the extracted transaction if the PSBT is complete
+    Ok(if complete {
+      serde_json::json!({ "hex": hex::encode(serialize(&transaction)), "complete": true })
+    } else {
+      serde_json::json!({ "psbt": encoded, "complete": false })
+    })
+  }
+
+  fn wallet_process_psbt(
+    &self,
+    psbt: String,
+    sign: Option<bool>,
+    sighash_type: Option<String>,
+    bip32derivs: Option<bool>,
+  ) -> Result<WalletProcessPsbtResult, jsonrpc_core::Error> {
+    let hash_ty = match sighash_type.as_deref() {
+      None | Some("DEFAULT") => bitcoin::sighash::TapSighashType::Default,
+      Some("ALL") => bitcoin::sighash::TapSighashType::All,
+      Some("SINGLE|ANYONECANPAY") => bitcoin::sighash::TapSighashType::SinglePlusAnyoneCanPay,
+      Some(sighash_type) => panic!("unsupported sighash type {sighash_type}"),
+    };
+
+    let mut psbt = bitcoin::psbt::Psbt::deserialize(
+      &base64::engine::general_purpose::STANDARD
+        .decode(psbt)
+        .unwrap(),
+    )
+    .unwrap();
+
+    let state = self.state();
+
+    if sign == Some(false) {
+      assert_eq!(
+        bip32derivs,
+        Some(true),
+        "only bip32derivs=true is supported"
+      );
+
+      // add placeholder key origins to the inputs spending wallet outputs
+      for (tx_in, input) in psbt.unsigned_tx.input.iter().zip(&mut psbt.inputs) {
+        if state.utxos.contains_key(&tx_in.previous_output) {
+          let secp256k1 = Secp256k1::new();
+          let key_pair = KeyPair::new(&secp256k1, &mut rand::thread_rng());
+          let (public_key, _parity) = XOnlyPublicKey::from_keypair(&key_pair);
+          input.tap_key_origins.insert(
+            public_key,
+            (
+              Vec::new(),
+              (
+                bitcoin::bip32::Fingerprint::default(),
+                bitcoin::bip32::DerivationPath::master(),
+              ),
+            ),
+          );
+        }
+      }
+
+      return Ok(WalletProcessPsbtResult {
+        complete: false,
+        psbt: base64::engine::general_purpose::STANDARD.encode(psbt.serialize()),
+      });
+    }
+
+    assert_eq!(sign, Some(true), "sign param not supported");
+    assert_eq!(bip32derivs, None, "bip32derivs param not supported");
+
+    // sign and finalize the inputs spending wallet outputs with placeholder key path signatures
+    for (tx_in, input) in psbt.unsigned_tx.input.iter().zip(&mut psbt.inputs) {
+      if input.final_script_witness.is_none()
+        && input.tap_key_sig.is_none()
+        && state.utxos.contains_key(&tx_in.previous_output)
+      {
+        let signature = bitcoin::taproot::Signature {
+          sig: bitcoin::secp256k1::schnorr::Signature::from_slice(&[1; 64]).unwrap(),
+          hash_ty,
+        };
+
+        input.final_script_witness = Some(Witness::from_slice(&[signature.to_vec()]));
+      }
+    }
+
+    Ok(WalletProcessPsbtResult {
+      complete: psbt
+        .inputs
+        .iter()
+        .all(|input| input.final_script_witness.is_some()),
+      psbt: base64::engine::general_purpose::STANDARD.encode(psbt.serialize()),
+    })
+  }
+
+  fn get_raw_mempool(&self) -> Result<Vec<Txid>, jsonrpc_core::Error> {
+    Ok(self.state().mempool().iter().map(|tx| tx.txid()).collect())
+  }
+
   fn get_raw_transaction(
     &self,
     txid: Txid,
@@ -506,7 +662,12 @@ impl Api for Server {
         None => Err(Self::not_found()),
       }
     } else {
-      match self.state().transactions.get(&txid) {
+      let state = self.state();
+      match state
+        .transactions
+        .get(&txid)
+        .or_else(|| state.mempool().iter().find(|tx| tx.txid() == txid))
+      {
         Some(tx) => Ok(Value::String(hex::encode(serialize(tx)))),
         None => Err(Self::not_found()),
       }
@@ -541,7 +702,15 @@ impl Api for Server {
           label: None,
           redeem_script: None,
           witness_script: None,
-          script_pub_key: ScriptBuf::new(),
+          script_pub_key: state
+            .transactions
+            .get(&outpoint.txid)
+            .map(|tx| {
+              tx.output[usize::try_from(outpoint.vout).unwrap()]
+                .script_pubkey
+                .clone()
+            })
+            .unwrap_or_default(),
           amount,
           confirmations: 0,
           spendable: true,
@@ -615,10 +784,46 @@ impl Api for Server {
     let key_pair = KeyPair::new(&secp256k1, &mut rand::thread_rng());
     let (public_key, _parity) = XOnlyPublicKey::from_keypair(&key_pair);
     let address = Address::p2tr(&secp256k1, public_key, None, self.network);
+    self.state().receive_addresses.push(address.clone());
 
     Ok(address)
   }
 
+  fn list_received_by_address(
+    &self,
+    _minconf: Option<u32>,
+    include_empty: Option<bool>,
+    include_watchonly: Option<bool>,
+    address_filter: Option<Address<NetworkUnchecked>>,
+  ) -> Result<Vec<ListReceivedByAddressResult>, jsonrpc_core::Error> {
+    assert_eq!(
+      include_empty,
+      Some(true),
+      "only include_empty=true is supported"
+    );
+    assert_eq!(
+      include_watchonly, None,
+      "include_watchonly param not supported"
+    );
+    assert_eq!(address_filter, None, "address_filter param not supported");
+
+    Ok(
+      self
+        .state()
+        .receive_addresses
+        .iter()
+        .map(|address| ListReceivedByAddressResult {
+          involved_watch_only: false,
+          address: address.to_string().parse().unwrap(),
+          amount: Amount::ZERO,
+          confirmations: 0,
+          label: String::new(),
+          txids: Vec::new(),
+        })
+        .collect(),
+    )
+  }
+
   fn list_transactions(
     &self,
     _label: Option<String>,
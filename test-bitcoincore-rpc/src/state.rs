@@ -11,6 +11,7 @@ pub(crate) struct State {
   pub(crate) mempool: Vec<Transaction>,
   pub(crate) network: Network,
   pub(crate) nonce: u32,
+  pub(crate) receive_addresses: Vec<Address>,
   pub(crate) sent: Vec<Sent>,
   pub(crate) transactions: BTreeMap<Txid, Transaction>,
   pub(crate) utxos: BTreeMap<OutPoint, Amount>,
@@ -38,6 +39,7 @@ impl State {
       mempool: Vec::new(),
       network,
       nonce: 0,
+      receive_addresses: Vec::new(),
       sent: Vec::new(),
       transactions: BTreeMap::new(),
       utxos: BTreeMap::new(),
@@ -107,6 +109,7 @@ impl State {
     for tx in block.txdata.iter() {
       for input in tx.input.iter() {
         self.utxos.remove(&input.previous_output);
+        self.locked.remove(&input.previous_output);
       }
 
       for (vout, txout) in tx.output.iter().enumerate() {
@@ -411,3 +411,34 @@ fn send_inscription_does_not_select_runic_utxos() {
     .expected_exit_code(1)
     .run_and_extract_stdout();
 }
+
+#[test]
+fn etch_with_psbt_is_broadcast_after_signing() {
+  let rpc_server = test_bitcoincore_rpc::builder()
+    .network(Network::Regtest)
+    .build();
+
+  create_wallet(&rpc_server);
+
+  rpc_server.mine_blocks(1);
+
+  let output = CommandBuilder::new(format!(
+    "--index-runes --regtest wallet etch --rune {} --divisibility 0 --fee-rate 0 --supply 1000 --symbol ¢ --psbt",
+    Rune(RUNE),
+  ))
+  .rpc_server(&rpc_server)
+  .run_and_deserialize_output::<Output>();
+
+  assert_eq!(rpc_server.mempool().len(), 0);
+
+  CommandBuilder::new(format!(
+    "--regtest wallet broadcast-psbt {}",
+    sign_psbt(&output.psbt.unwrap())
+  ))
+  .rpc_server(&rpc_server)
+  .run_and_deserialize_output::<ord::subcommand::wallet::broadcast_psbt::Output>();
+
+  rpc_server.mine_blocks(1);
+
+  assert!(runes(&rpc_server).contains_key(&Rune(RUNE)));
+}
@@ -116,3 +116,115 @@ fn export_inscription_number_to_id_tsv() {
     &ord::Object::InscriptionId(inscription),
   );
 }
+
+#[test]
+fn rollback_to_height() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+
+  let tempdir = TempDir::new().unwrap();
+
+  let index_path = tempdir.path().join("foo.redb");
+
+  for _ in 0..5 {
+    rpc_server.mine_blocks(1);
+
+    CommandBuilder::new(format!(
+      "--index {} --max-reorg-depth 30 index update",
+      index_path.display()
+    ))
+    .rpc_server(&rpc_server)
+    .run_and_deserialize_output::<Empty>();
+  }
+
+  assert_eq!(
+    CommandBuilder::new(format!(
+      "--index {} --max-reorg-depth 30 index rollback --height 3",
+      index_path.display()
+    ))
+    .rpc_server(&rpc_server)
+    .run_and_deserialize_output::<ord::subcommand::index::rollback::Output>(),
+    ord::subcommand::index::rollback::Output { height: 3 }
+  );
+
+  CommandBuilder::new(format!(
+    "--index {} --max-reorg-depth 30 index rollback --height 3",
+    index_path.display()
+  ))
+  .rpc_server(&rpc_server)
+  .expected_exit_code(1)
+  .expected_stderr("error: index is at height 3, which is not above 3\n")
+  .run_and_extract_stdout();
+}
+
+#[test]
+fn brc20_snapshot_is_imported_and_indexing_continues() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  create_wallet(&rpc_server);
+  rpc_server.mine_blocks(1);
+
+  let tempdir = TempDir::new().unwrap();
+  let snapshot_path = tempdir.path().join("snapshot.json");
+  let index_path = tempdir.path().join("foo.redb");
+
+  let mint = |rpc_server: &test_bitcoincore_rpc::Handle| {
+    CommandBuilder::new(
+      "--enable-index-brc20 wallet brc20 mint --tick ordi --amount 10 --fee-rate 1",
+    )
+    .rpc_server(rpc_server)
+    .run_and_deserialize_output::<Inscribe>();
+
+    rpc_server.mine_blocks(1);
+  };
+
+  CommandBuilder::new(
+    "--enable-index-brc20 wallet brc20 deploy --tick ordi --max 1000 --lim 10 --fee-rate 1",
+  )
+  .rpc_server(&rpc_server)
+  .run_and_deserialize_output::<Inscribe>();
+
+  rpc_server.mine_blocks(1);
+
+  mint(&rpc_server);
+
+  let exported = CommandBuilder::new(format!(
+    "--enable-index-brc20 index export-brc20 --height {} --output {}",
+    rpc_server.height(),
+    snapshot_path.display()
+  ))
+  .rpc_server(&rpc_server)
+  .run_and_deserialize_output::<ord::subcommand::index::export_brc20::Output>();
+
+  assert_eq!(exported.tokens, 1);
+
+  assert_eq!(
+    CommandBuilder::new(format!(
+      "--index {} --enable-index-brc20 index import-brc20 --input {}",
+      index_path.display(),
+      snapshot_path.display()
+    ))
+    .rpc_server(&rpc_server)
+    .run_and_deserialize_output::<ord::subcommand::index::export_brc20::Output>(),
+    exported
+  );
+
+  mint(&rpc_server);
+
+  let balance = CommandBuilder::new(format!(
+    "--index {} --enable-index-brc20 wallet balance",
+    index_path.display()
+  ))
+  .rpc_server(&rpc_server)
+  .run_and_deserialize_output::<ord::subcommand::wallet::balance::Output>()
+  .brc20
+  .unwrap();
+
+  assert_eq!(balance["ordi"].overall, 20_000_000_000_000_000_000);
+
+  assert_eq!(
+    Some(balance),
+    CommandBuilder::new("--enable-index-brc20 wallet balance")
+      .rpc_server(&rpc_server)
+      .run_and_deserialize_output::<ord::subcommand::wallet::balance::Output>()
+      .brc20
+  );
+}
@@ -123,6 +123,32 @@ fn inscribe(rpc_server: &test_bitcoincore_rpc::Handle) -> (InscriptionId, Txid)
   (output.inscriptions[0].id, output.reveal)
 }
 
+/// Stands in for an external signer by adding a placeholder key path signature to every input
+/// that hasn't been finalized.
+fn sign_psbt(psbt: &str) -> String {
+  use base64::Engine;
+
+  let mut psbt = bitcoin::psbt::Psbt::deserialize(
+    &base64::engine::general_purpose::STANDARD
+      .decode(psbt)
+      .unwrap(),
+  )
+  .unwrap();
+
+  for input in &mut psbt.inputs {
+    if input.final_script_witness.is_none() {
+      input.tap_key_sig = Some(
+        bitcoin::taproot::Signature::from_slice(
+          &[1; bitcoin::key::constants::SCHNORR_SIGNATURE_SIZE],
+        )
+        .unwrap(),
+      );
+    }
+  }
+
+  base64::engine::general_purpose::STANDARD.encode(psbt.serialize())
+}
+
 mod command_builder;
 mod expected;
 mod test_server;
@@ -1,13 +1,17 @@
 use super::*;
 
 mod balance;
+mod brc20;
+mod broadcast_psbt;
 mod cardinals;
 mod create;
 mod inscribe;
 mod inscriptions;
+mod offer;
 mod outputs;
 mod receive;
 mod restore;
 mod sats;
 mod send;
 mod transactions;
+mod watch_only;
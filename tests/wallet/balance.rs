@@ -20,6 +20,7 @@ fn wallet_balance() {
       .rpc_server(&rpc_server)
       .run_and_deserialize_output::<Output>(),
     Output {
+      brc20: None,
       cardinal: 50 * COIN_VALUE,
       ordinal: 0,
       runic: None,
@@ -40,6 +41,7 @@ fn inscribed_utxos_are_deducted_from_cardinal() {
       .rpc_server(&rpc_server)
       .run_and_deserialize_output::<Output>(),
     Output {
+      brc20: None,
       cardinal: 0,
       ordinal: 0,
       runic: None,
@@ -55,6 +57,7 @@ fn inscribed_utxos_are_deducted_from_cardinal() {
       .rpc_server(&rpc_server)
       .run_and_deserialize_output::<Output>(),
     Output {
+      brc20: None,
       cardinal: 100 * COIN_VALUE - 10_000,
       ordinal: 10_000,
       runic: None,
@@ -77,6 +80,7 @@ fn runic_utxos_are_deducted_from_cardinal() {
       .rpc_server(&rpc_server)
       .run_and_deserialize_output::<Output>(),
     Output {
+      brc20: None,
       cardinal: 0,
       ordinal: 0,
       runic: Some(0),
@@ -92,6 +96,7 @@ fn runic_utxos_are_deducted_from_cardinal() {
       .rpc_server(&rpc_server)
       .run_and_deserialize_output::<Output>(),
     Output {
+      brc20: None,
       cardinal: 100 * COIN_VALUE - 10_000,
       ordinal: 0,
       runic: Some(10_000),
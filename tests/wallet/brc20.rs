@@ -0,0 +1,234 @@
+use {
+  super::*,
+  ord::subcommand::wallet::{
+    balance::{Brc20Balance, Output as Balance},
+    brc20::TransferOutput,
+    send::Output as Send,
+  },
+};
+
+const RECIPIENT: &str = "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4";
+
+fn deploy_and_mint(rpc_server: &test_bitcoincore_rpc::Handle) -> Inscribe {
+  CommandBuilder::new(
+    "--enable-index-brc20 wallet brc20 deploy --tick ordi --max 1000 --lim 10 --fee-rate 1",
+  )
+  .rpc_server(rpc_server)
+  .run_and_deserialize_output::<Inscribe>();
+
+  rpc_server.mine_blocks(1);
+
+  let mint = CommandBuilder::new(
+    "--enable-index-brc20 wallet brc20 mint --tick ordi --amount 10 --fee-rate 1",
+  )
+  .rpc_server(rpc_server)
+  .run_and_deserialize_output::<Inscribe>();
+
+  rpc_server.mine_blocks(1);
+
+  mint
+}
+
+#[test]
+fn brc20_requires_brc20_index() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  create_wallet(&rpc_server);
+
+  CommandBuilder::new("wallet brc20 mint --tick ordi --amount 10 --fee-rate 1")
+    .rpc_server(&rpc_server)
+    .expected_stderr(
+      "error: `ord wallet brc20` requires index created with `--enable-index-brc20` flag\n",
+    )
+    .expected_exit_code(1)
+    .run_and_extract_stdout();
+}
+
+#[test]
+fn brc20_deploy_and_mint_are_checked_against_index() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  create_wallet(&rpc_server);
+  rpc_server.mine_blocks(1);
+
+  CommandBuilder::new(
+    "--enable-index-brc20 wallet brc20 mint --tick ordi --amount 10 --fee-rate 1",
+  )
+  .rpc_server(&rpc_server)
+  .expected_stderr("error: tick: ordi not found\n")
+  .expected_exit_code(1)
+  .run_and_extract_stdout();
+
+  deploy_and_mint(&rpc_server);
+
+  CommandBuilder::new(
+    "--enable-index-brc20 wallet brc20 deploy --tick ORDI --max 1000 --lim 10 --fee-rate 1",
+  )
+  .rpc_server(&rpc_server)
+  .expected_stderr("error: tick: ordi has been existed\n")
+  .expected_exit_code(1)
+  .run_and_extract_stdout();
+
+  CommandBuilder::new(
+    "--enable-index-brc20 wallet brc20 mint --tick ordi --amount 11 --fee-rate 1",
+  )
+  .rpc_server(&rpc_server)
+  .stderr_regex("error: amount exceed limit: .*\n")
+  .expected_exit_code(1)
+  .run_and_extract_stdout();
+
+  assert_eq!(rpc_server.mempool().len(), 0);
+}
+
+#[test]
+fn brc20_transfer_sends_transfer_inscription_to_recipient() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  create_wallet(&rpc_server);
+  rpc_server.mine_blocks(1);
+
+  deploy_and_mint(&rpc_server);
+
+  CommandBuilder::new(format!(
+    "--enable-index-brc20 wallet brc20 transfer --tick ordi --amount 11 --fee-rate 1 {RECIPIENT}"
+  ))
+  .rpc_server(&rpc_server)
+  .stderr_regex("error: insufficient balance: .*\n")
+  .expected_exit_code(1)
+  .run_and_extract_stdout();
+
+  let output = CommandBuilder::new(format!(
+    "--enable-index-brc20 wallet brc20 transfer --tick ordi --amount 4 --fee-rate 1 {RECIPIENT}"
+  ))
+  .rpc_server(&rpc_server)
+  .run_and_deserialize_output::<TransferOutput>();
+
+  let mempool = rpc_server.mempool();
+  assert_eq!(mempool.len(), 3);
+  assert_eq!(mempool[1].txid(), output.inscription.reveal);
+  assert_eq!(mempool[2].txid(), output.transaction);
+  assert_eq!(
+    mempool[2].input[0].previous_output,
+    output.inscription.inscriptions[0].location.outpoint
+  );
+
+  rpc_server.mine_blocks(1);
+
+  let ord_server = TestServer::spawn_with_args(&rpc_server, &["--enable-index-brc20"]);
+
+  let balance = ord_server
+    .json_request(format!(
+      "/api/v1/brc20/tick/ordi/address/{RECIPIENT}/balance"
+    ))
+    .json::<serde_json::Value>()
+    .unwrap();
+
+  assert_eq!(
+    balance["data"]["overallBalance"],
+    serde_json::json!("4000000000000000000")
+  );
+}
+
+#[test]
+fn brc20_balance_and_transferable_inscriptions_are_protected() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  create_wallet(&rpc_server);
+  rpc_server.mine_blocks(1);
+
+  deploy_and_mint(&rpc_server);
+
+  let output = CommandBuilder::new(
+    "--enable-index-brc20 wallet brc20 inscribe-transfer --tick ordi --amount 4 --fee-rate 1",
+  )
+  .rpc_server(&rpc_server)
+  .run_and_deserialize_output::<Inscribe>();
+
+  rpc_server.mine_blocks(1);
+
+  assert_eq!(
+    CommandBuilder::new("--enable-index-brc20 wallet balance")
+      .rpc_server(&rpc_server)
+      .run_and_deserialize_output::<Balance>()
+      .brc20,
+    Some(
+      vec![(
+        "ordi".into(),
+        Brc20Balance {
+          overall: 10_000_000_000_000_000_000,
+          transferable: 4_000_000_000_000_000_000,
+          available: 6_000_000_000_000_000_000,
+        }
+      )]
+      .into_iter()
+      .collect()
+    )
+  );
+
+  let location = output.inscriptions[0].location;
+
+  CommandBuilder::new(format!(
+    "--enable-index-brc20 wallet inscribe --fee-rate 1 --file foo.txt --satpoint {}:5000",
+    location.outpoint
+  ))
+  .write("foo.txt", "FOO")
+  .rpc_server(&rpc_server)
+  .expected_stderr(format!(
+    "error: cannot inscribe on {}:5000 without also spending transferable BRC20 inscription at {location}\n",
+    location.outpoint
+  ))
+  .expected_exit_code(1)
+  .run_and_extract_stdout();
+
+  CommandBuilder::new(format!(
+    "--enable-index-brc20 wallet send --fee-rate 1 {RECIPIENT} {}:5000",
+    location.outpoint
+  ))
+  .rpc_server(&rpc_server)
+  .expected_stderr(format!(
+    "error: cannot send {}:5000 without also spending transferable BRC20 inscription at {location}\n",
+    location.outpoint
+  ))
+  .expected_exit_code(1)
+  .run_and_extract_stdout();
+
+  CommandBuilder::new(format!(
+    "--enable-index-brc20 wallet send --fee-rate 1 --force-transferable {RECIPIENT} {}:5000",
+    location.outpoint
+  ))
+  .rpc_server(&rpc_server)
+  .run_and_deserialize_output::<Send>();
+}
+
+#[test]
+fn brc20_balance_includes_addresses_without_outputs() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  create_wallet(&rpc_server);
+  rpc_server.mine_blocks(1);
+
+  let mint = deploy_and_mint(&rpc_server);
+
+  CommandBuilder::new(format!(
+    "--enable-index-brc20 wallet send --fee-rate 1 {RECIPIENT} {}",
+    mint.inscriptions[0].id
+  ))
+  .rpc_server(&rpc_server)
+  .run_and_deserialize_output::<Send>();
+
+  rpc_server.mine_blocks(1);
+
+  assert_eq!(
+    CommandBuilder::new("--enable-index-brc20 wallet balance")
+      .rpc_server(&rpc_server)
+      .run_and_deserialize_output::<Balance>()
+      .brc20,
+    Some(
+      vec![(
+        "ordi".into(),
+        Brc20Balance {
+          overall: 10_000_000_000_000_000_000,
+          transferable: 0,
+          available: 10_000_000_000_000_000_000,
+        }
+      )]
+      .into_iter()
+      .collect()
+    )
+  );
+}
@@ -0,0 +1,54 @@
+use {super::*, ord::subcommand::wallet::send::Output};
+
+#[test]
+fn unsigned_psbt_is_not_broadcast() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  create_wallet(&rpc_server);
+  let txid = rpc_server.mine_blocks(1)[0].txdata[0].txid();
+
+  let output = CommandBuilder::new(format!(
+    "wallet send --fee-rate 1 --psbt bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4 {txid}:0:0"
+  ))
+  .rpc_server(&rpc_server)
+  .run_and_deserialize_output::<Output>();
+
+  CommandBuilder::new(format!("wallet broadcast-psbt {}", output.psbt.unwrap()))
+    .rpc_server(&rpc_server)
+    .expected_stderr("error: PSBT is not fully signed\n")
+    .expected_exit_code(1)
+    .run_and_extract_stdout();
+
+  assert_eq!(rpc_server.mempool().len(), 0);
+}
+
+#[test]
+fn invalid_psbt_is_an_error() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+
+  CommandBuilder::new("wallet broadcast-psbt foo")
+    .rpc_server(&rpc_server)
+    .stderr_regex("error: PSBT is not valid base64\n.*")
+    .expected_exit_code(1)
+    .run_and_extract_stdout();
+}
+
+#[test]
+fn signed_psbt_can_be_read_from_file() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  create_wallet(&rpc_server);
+  let txid = rpc_server.mine_blocks(1)[0].txdata[0].txid();
+
+  let output = CommandBuilder::new(format!(
+    "wallet send --fee-rate 1 --psbt bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4 {txid}:0:0"
+  ))
+  .rpc_server(&rpc_server)
+  .run_and_deserialize_output::<Output>();
+
+  let broadcast = CommandBuilder::new("wallet broadcast-psbt signed.psbt")
+    .write("signed.psbt", sign_psbt(&output.psbt.unwrap()))
+    .rpc_server(&rpc_server)
+    .run_and_deserialize_output::<ord::subcommand::wallet::broadcast_psbt::Output>();
+
+  assert_eq!(broadcast.transactions, vec![output.transaction]);
+  assert_eq!(rpc_server.mempool()[0].txid(), output.transaction);
+}
@@ -1800,3 +1800,73 @@ fn server_can_decompress_brotli() {
   assert_eq!(response.status(), StatusCode::OK);
   assert_eq!(response.bytes().unwrap().deref(), [0; 350_000]);
 }
+
+#[test]
+fn inscribe_with_psbt_leaves_signing_and_broadcasting_to_signer() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  create_wallet(&rpc_server);
+  rpc_server.mine_blocks(1);
+
+  let output = CommandBuilder::new("wallet inscribe --fee-rate 1 --file foo.txt --psbt")
+    .write("foo.txt", "FOO")
+    .rpc_server(&rpc_server)
+    .run_and_deserialize_output::<Inscribe>();
+
+  assert_eq!(rpc_server.mempool().len(), 0);
+
+  let commit_psbt = output.commit_psbt.unwrap();
+  let reveal_psbt = output.reveal_psbt.unwrap();
+
+  {
+    use base64::Engine;
+
+    let psbt = bitcoin::psbt::Psbt::deserialize(
+      &base64::engine::general_purpose::STANDARD
+        .decode(&commit_psbt)
+        .unwrap(),
+    )
+    .unwrap();
+
+    // the signer is told which wallet keys to sign with, and the inputs aren't spent meanwhile
+    for (tx_in, input) in psbt.unsigned_tx.input.iter().zip(&psbt.inputs) {
+      assert!(!input.tap_key_origins.is_empty());
+      assert!(rpc_server.locked().contains(&tx_in.previous_output));
+    }
+
+    let psbt = bitcoin::psbt::Psbt::deserialize(
+      &base64::engine::general_purpose::STANDARD
+        .decode(&reveal_psbt)
+        .unwrap(),
+    )
+    .unwrap();
+
+    let input = &psbt.inputs[0];
+    let (control_block, (reveal_script, _)) = input.tap_scripts.iter().next().unwrap();
+
+    assert!(reveal_script
+      .as_bytes()
+      .windows(3)
+      .any(|window| window == b"FOO"));
+
+    let witness = input.final_script_witness.as_ref().unwrap();
+    assert_eq!(witness.nth(1).unwrap(), reveal_script.as_bytes());
+    assert_eq!(witness.nth(2).unwrap(), control_block.serialize());
+  }
+
+  let broadcast = CommandBuilder::new(format!(
+    "wallet broadcast-psbt {} {reveal_psbt}",
+    sign_psbt(&commit_psbt)
+  ))
+  .rpc_server(&rpc_server)
+  .run_and_deserialize_output::<ord::subcommand::wallet::broadcast_psbt::Output>();
+
+  assert_eq!(broadcast.transactions, vec![output.commit, output.reveal]);
+
+  rpc_server.mine_blocks(1);
+
+  let request = TestServer::spawn_with_args(&rpc_server, &[])
+    .request(format!("/content/{}", output.inscriptions[0].id));
+
+  assert_eq!(request.status(), 200);
+  assert_eq!(request.text().unwrap(), "FOO");
+}
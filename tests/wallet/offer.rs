@@ -0,0 +1,301 @@
+use {
+  super::*,
+  base64::Engine,
+  bitcoin::{psbt::Psbt, Witness},
+  ord::subcommand::wallet::offer::{AcceptOutput, Brc20Transferable, CreateOutput},
+};
+
+fn decode(psbt: &str) -> Psbt {
+  Psbt::deserialize(
+    &base64::engine::general_purpose::STANDARD
+      .decode(psbt)
+      .unwrap(),
+  )
+  .unwrap()
+}
+
+#[test]
+fn offer_is_signed_with_sighash_single_anyonecanpay() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  create_wallet(&rpc_server);
+
+  let (inscription, reveal) = inscribe(&rpc_server);
+
+  let output = CommandBuilder::new(format!(
+    "wallet offer create --inscription {inscription} --price 20000sat"
+  ))
+  .rpc_server(&rpc_server)
+  .run_and_deserialize_output::<CreateOutput>();
+
+  assert_eq!(output.inscription, inscription);
+  assert_eq!(output.price, 20_000);
+
+  let offer = decode(&output.psbt);
+
+  assert_eq!(offer.unsigned_tx.input.len(), 1);
+  assert_eq!(
+    offer.unsigned_tx.input[0].previous_output,
+    OutPoint::new(reveal, 0)
+  );
+  assert_eq!(offer.unsigned_tx.output.len(), 1);
+  assert_eq!(offer.unsigned_tx.output[0].value, 20_000);
+
+  let witness = offer.inputs[0].final_script_witness.clone().unwrap();
+  assert_eq!(witness.len(), 1);
+  assert_eq!(witness.nth(0).unwrap().last(), Some(&0x83));
+}
+
+#[test]
+fn accepted_offer_pays_seller_and_sends_inscription_to_buyer() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  create_wallet(&rpc_server);
+
+  let (inscription, reveal) = inscribe(&rpc_server);
+
+  let offer = CommandBuilder::new(format!(
+    "wallet offer create --inscription {inscription} --price 20000sat"
+  ))
+  .rpc_server(&rpc_server)
+  .run_and_deserialize_output::<CreateOutput>();
+
+  let payment = decode(&offer.psbt).unsigned_tx.output[0].clone();
+
+  rpc_server.mine_blocks(1);
+
+  let output = CommandBuilder::new(format!(
+    "wallet offer accept --fee-rate 1 --inscription {inscription} --max-price 20000sat --destination bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4 {}",
+    offer.psbt
+  ))
+  .rpc_server(&rpc_server)
+  .run_and_deserialize_output::<AcceptOutput>();
+
+  assert_eq!(output.inscriptions, vec![inscription]);
+  assert_eq!(output.brc20_transferable, Vec::new());
+  assert_eq!(output.price, 20_000);
+
+  let transaction = rpc_server.mempool()[0].clone();
+
+  assert_eq!(transaction.txid(), output.transaction);
+  assert_eq!(
+    transaction.input[1].previous_output,
+    OutPoint::new(reveal, 0)
+  );
+  assert_eq!(transaction.output[1], payment);
+  assert_eq!(
+    transaction.output[2].script_pubkey,
+    "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4"
+      .parse::<Address<NetworkUnchecked>>()
+      .unwrap()
+      .assume_checked()
+      .script_pubkey()
+  );
+  assert_eq!(transaction.output[2].value, 9_757);
+
+  rpc_server.mine_blocks(1);
+
+  let inscriptions = CommandBuilder::new("wallet inscriptions")
+    .rpc_server(&rpc_server)
+    .run_and_deserialize_output::<Vec<ord::subcommand::wallet::inscriptions::Output>>();
+
+  assert_eq!(inscriptions[0].inscription, inscription);
+  assert_eq!(
+    inscriptions[0].location,
+    SatPoint {
+      outpoint: OutPoint::new(output.transaction, 2),
+      offset: 0,
+    }
+  );
+}
+
+#[test]
+fn offer_for_unknown_inscription_is_an_error() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  create_wallet(&rpc_server);
+
+  let inscription = format!("{}i0", "0".repeat(64));
+
+  CommandBuilder::new(format!(
+    "wallet offer create --inscription {inscription} --price 20000sat"
+  ))
+  .rpc_server(&rpc_server)
+  .expected_stderr(format!("error: inscription {inscription} not found\n"))
+  .expected_exit_code(1)
+  .run_and_extract_stdout();
+}
+
+#[test]
+fn offer_price_must_not_be_dust() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  create_wallet(&rpc_server);
+
+  let (inscription, _) = inscribe(&rpc_server);
+
+  CommandBuilder::new(format!(
+    "wallet offer create --inscription {inscription} --price 1sat"
+  ))
+  .rpc_server(&rpc_server)
+  .expected_stderr("error: price is below dust value: 0.00000001 BTC < 0.0000033 BTC\n")
+  .expected_exit_code(1)
+  .run_and_extract_stdout();
+}
+
+#[test]
+fn offer_of_output_above_postage_is_an_error() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  create_wallet(&rpc_server);
+  rpc_server.mine_blocks(1);
+
+  let output =
+    CommandBuilder::new("wallet inscribe --fee-rate 1 --file foo.txt --postage 30000sat")
+      .write("foo.txt", "FOO")
+      .rpc_server(&rpc_server)
+      .run_and_deserialize_output::<Inscribe>();
+
+  rpc_server.mine_blocks(1);
+
+  let inscription = output.inscriptions[0].id;
+
+  CommandBuilder::new(format!(
+    "wallet offer create --inscription {inscription} --price 20000sat"
+  ))
+  .rpc_server(&rpc_server)
+  .expected_stderr(format!(
+    "error: cannot offer {inscription} without also selling the 0.0001 BTC in {}:0 above postage of 0.0002 BTC, split the output with `ord wallet send` first\n",
+    output.reveal
+  ))
+  .expected_exit_code(1)
+  .run_and_extract_stdout();
+}
+
+#[test]
+fn offer_signed_with_sighash_default_is_rejected() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  create_wallet(&rpc_server);
+
+  let (inscription, _) = inscribe(&rpc_server);
+
+  let offer = CommandBuilder::new(format!(
+    "wallet offer create --inscription {inscription} --price 20000sat"
+  ))
+  .rpc_server(&rpc_server)
+  .run_and_deserialize_output::<CreateOutput>();
+
+  let mut psbt = decode(&offer.psbt);
+  psbt.inputs[0].final_script_witness = Some(Witness::from_slice(&[&[1; 64]]));
+
+  CommandBuilder::new(format!(
+    "wallet offer accept --fee-rate 1 --inscription {inscription} --max-price 20000sat offer.psbt"
+  ))
+  .write(
+    "offer.psbt",
+    base64::engine::general_purpose::STANDARD.encode(psbt.serialize()),
+  )
+  .rpc_server(&rpc_server)
+  .expected_stderr(
+    "error: offer must be signed with SIGHASH_SINGLE|ANYONECANPAY, not SIGHASH_DEFAULT\n",
+  )
+  .expected_exit_code(1)
+  .run_and_extract_stdout();
+}
+
+#[test]
+fn accepted_offer_must_match_expected_inscription_and_price() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  create_wallet(&rpc_server);
+
+  let (inscription, reveal) = inscribe(&rpc_server);
+  let (other, _) = inscribe(&rpc_server);
+
+  let offer = CommandBuilder::new(format!(
+    "wallet offer create --inscription {inscription} --price 20000sat"
+  ))
+  .rpc_server(&rpc_server)
+  .run_and_deserialize_output::<CreateOutput>();
+
+  CommandBuilder::new(format!(
+    "wallet offer accept --fee-rate 1 --inscription {other} --max-price 20000sat {}",
+    offer.psbt
+  ))
+  .rpc_server(&rpc_server)
+  .expected_stderr(format!(
+    "error: offered output {reveal}:0 does not hold inscription {other}\n"
+  ))
+  .expected_exit_code(1)
+  .run_and_extract_stdout();
+
+  CommandBuilder::new(format!(
+    "wallet offer accept --fee-rate 1 --inscription {inscription} --max-price 19999sat {}",
+    offer.psbt
+  ))
+  .rpc_server(&rpc_server)
+  .expected_stderr("error: offer price 0.0002 BTC exceeds maximum price 0.00019999 BTC\n")
+  .expected_exit_code(1)
+  .run_and_extract_stdout();
+
+  let output = CommandBuilder::new(format!(
+    "wallet offer accept --fee-rate 1 --inscription {inscription} --max-price 20000sat --dry-run {}",
+    offer.psbt
+  ))
+  .rpc_server(&rpc_server)
+  .run_and_deserialize_output::<AcceptOutput>();
+
+  assert_eq!(output.inscriptions, vec![inscription]);
+  assert_eq!(output.price, 20_000);
+  assert!(rpc_server.mempool().is_empty());
+}
+
+#[test]
+fn accepted_offer_reports_transferable_brc20_inscription() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  create_wallet(&rpc_server);
+  rpc_server.mine_blocks(1);
+
+  CommandBuilder::new(
+    "--enable-index-brc20 wallet brc20 deploy --tick ordi --max 1000 --lim 10 --fee-rate 1",
+  )
+  .rpc_server(&rpc_server)
+  .run_and_deserialize_output::<Inscribe>();
+
+  rpc_server.mine_blocks(1);
+
+  CommandBuilder::new(
+    "--enable-index-brc20 wallet brc20 mint --tick ordi --amount 10 --fee-rate 1",
+  )
+  .rpc_server(&rpc_server)
+  .run_and_deserialize_output::<Inscribe>();
+
+  rpc_server.mine_blocks(1);
+
+  let transfer = CommandBuilder::new(
+    "--enable-index-brc20 wallet brc20 inscribe-transfer --tick ordi --amount 4 --fee-rate 1",
+  )
+  .rpc_server(&rpc_server)
+  .run_and_deserialize_output::<Inscribe>();
+
+  rpc_server.mine_blocks(1);
+
+  let inscription = transfer.inscriptions[0].id;
+
+  let offer = CommandBuilder::new(format!(
+    "--enable-index-brc20 wallet offer create --inscription {inscription} --price 20000sat"
+  ))
+  .rpc_server(&rpc_server)
+  .run_and_deserialize_output::<CreateOutput>();
+
+  let output = CommandBuilder::new(format!(
+    "--enable-index-brc20 wallet offer accept --fee-rate 1 --inscription {inscription} --max-price 20000sat --dry-run {}",
+    offer.psbt
+  ))
+  .rpc_server(&rpc_server)
+  .run_and_deserialize_output::<AcceptOutput>();
+
+  assert_eq!(output.inscriptions, vec![inscription]);
+  assert_eq!(
+    output.brc20_transferable,
+    vec![Brc20Transferable {
+      inscription,
+      tick: "ordi".into(),
+      amount: 4_000_000_000_000_000_000,
+    }]
+  );
+}
@@ -365,6 +365,50 @@ fn send_btc_with_fee_rate() {
   );
 }
 
+#[test]
+fn send_btc_with_psbt_is_funded_but_not_broadcast() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  create_wallet(&rpc_server);
+
+  rpc_server.mine_blocks(1);
+
+  let output = CommandBuilder::new(
+    "wallet send --fee-rate 1 --psbt bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4 1btc",
+  )
+  .rpc_server(&rpc_server)
+  .run_and_deserialize_output::<Output>();
+
+  assert!(rpc_server.mempool().is_empty());
+  assert!(rpc_server.sent().is_empty());
+
+  {
+    use base64::Engine;
+
+    let psbt = bitcoin::psbt::Psbt::deserialize(
+      &base64::engine::general_purpose::STANDARD
+        .decode(output.psbt.as_ref().unwrap())
+        .unwrap(),
+    )
+    .unwrap();
+
+    for (tx_in, input) in psbt.unsigned_tx.input.iter().zip(&psbt.inputs) {
+      assert!(!input.tap_key_origins.is_empty());
+      assert!(rpc_server.locked().contains(&tx_in.previous_output));
+    }
+  }
+
+  CommandBuilder::new(format!(
+    "wallet broadcast-psbt {}",
+    sign_psbt(&output.psbt.unwrap())
+  ))
+  .rpc_server(&rpc_server)
+  .run_and_deserialize_output::<ord::subcommand::wallet::broadcast_psbt::Output>();
+
+  let tx = &rpc_server.mempool()[0];
+  assert_eq!(tx.txid(), output.transaction);
+  assert_eq!(tx.output[0].value, COIN_VALUE);
+}
+
 #[test]
 fn send_btc_locks_inscriptions() {
   let rpc_server = test_bitcoincore_rpc::spawn();
@@ -913,6 +957,7 @@ fn sending_rune_does_not_send_inscription() {
       .rpc_server(&rpc_server)
       .run_and_deserialize_output::<ord::subcommand::wallet::balance::Output>(),
     ord::subcommand::wallet::balance::Output {
+      brc20: None,
       cardinal: 10000,
       ordinal: 10000,
       runic: Some(0),
@@ -937,6 +982,7 @@ fn sending_rune_does_not_send_inscription() {
       .rpc_server(&rpc_server)
       .run_and_deserialize_output::<ord::subcommand::wallet::balance::Output>(),
     ord::subcommand::wallet::balance::Output {
+      brc20: None,
       cardinal: 0,
       ordinal: 10000,
       runic: Some(10000),
@@ -0,0 +1,153 @@
+use {
+  super::*,
+  bitcoin::{
+    bip32::{ExtendedPrivKey, ExtendedPubKey},
+    secp256k1::Secp256k1,
+  },
+  miniscript::descriptor::{Descriptor, DescriptorPublicKey},
+  ord::subcommand::wallet::{balance, inscriptions, outputs, send},
+};
+
+fn descriptor() -> String {
+  let master_private_key = ExtendedPrivKey::new_master(Network::Bitcoin, &[1; 64]).unwrap();
+
+  format!(
+    "tr({}/0/*)",
+    ExtendedPubKey::from_priv(&Secp256k1::new(), &master_private_key)
+  )
+}
+
+fn derive_address(descriptor: &str, index: u32) -> Address {
+  Descriptor::<DescriptorPublicKey>::from_str(descriptor)
+    .unwrap()
+    .at_derivation_index(index)
+    .unwrap()
+    .address(Network::Bitcoin)
+    .unwrap()
+}
+
+#[test]
+fn watch_only_wallet_lists_holdings_of_derived_addresses() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  create_wallet(&rpc_server);
+  rpc_server.mine_blocks(1);
+
+  let descriptor = descriptor();
+  let address = derive_address(&descriptor, 5);
+
+  let inscribe = CommandBuilder::new(format!(
+    "wallet inscribe --fee-rate 1 --file foo.txt --destination {address}"
+  ))
+  .write("foo.txt", "FOO")
+  .rpc_server(&rpc_server)
+  .run_and_deserialize_output::<Inscribe>();
+
+  rpc_server.mine_blocks(1);
+
+  let send = CommandBuilder::new(format!("wallet send --fee-rate 1 {address} 1btc"))
+    .rpc_server(&rpc_server)
+    .run_and_deserialize_output::<send::Output>();
+
+  rpc_server.mine_blocks(1);
+
+  let inscription = inscribe.inscriptions[0].id;
+  let location = inscribe.inscriptions[0].location;
+
+  let output = CommandBuilder::new(format!(
+    "--index-addresses wallet --descriptor {descriptor} inscriptions"
+  ))
+  .rpc_server(&rpc_server)
+  .run_and_deserialize_output::<Vec<inscriptions::Output>>();
+
+  assert_eq!(output.len(), 1);
+  assert_eq!(output[0].inscription, inscription);
+  assert_eq!(output[0].location, location);
+  assert_eq!(output[0].postage, 10_000);
+
+  let output = CommandBuilder::new(format!(
+    "--index-addresses wallet --descriptor {descriptor} outputs"
+  ))
+  .rpc_server(&rpc_server)
+  .run_and_deserialize_output::<Vec<outputs::Output>>();
+
+  let mut expected = vec![
+    location.outpoint,
+    OutPoint::new(send.transaction, 0),
+    OutPoint::new(send.transaction, 1),
+  ];
+  expected.sort();
+
+  assert_eq!(
+    output
+      .iter()
+      .map(|output| output.output)
+      .collect::<Vec<OutPoint>>(),
+    expected,
+  );
+
+  let balance = CommandBuilder::new(format!(
+    "--index-addresses wallet --descriptor {descriptor} balance"
+  ))
+  .rpc_server(&rpc_server)
+  .run_and_deserialize_output::<balance::Output>();
+
+  assert_eq!(balance.ordinal, 10_000);
+  assert_eq!(
+    balance.total,
+    output.iter().map(|output| output.amount).sum::<u64>()
+  );
+}
+
+#[test]
+fn watch_only_wallet_reads_descriptors_from_file() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+  create_wallet(&rpc_server);
+  rpc_server.mine_blocks(1);
+
+  let descriptor = descriptor();
+  let address = derive_address(&descriptor, 0);
+
+  let send = CommandBuilder::new(format!("wallet send --fee-rate 1 {address} 1btc"))
+    .rpc_server(&rpc_server)
+    .run_and_deserialize_output::<send::Output>();
+
+  rpc_server.mine_blocks(1);
+
+  let output = CommandBuilder::new("--index-addresses wallet --descriptor descriptors.txt outputs")
+    .write("descriptors.txt", format!("# hot wallet\n{descriptor}\n"))
+    .rpc_server(&rpc_server)
+    .run_and_deserialize_output::<Vec<outputs::Output>>();
+
+  assert_eq!(output.len(), 2);
+  assert_eq!(output[0].output, OutPoint::new(send.transaction, 0));
+  assert_eq!(output[0].amount, COIN_VALUE);
+}
+
+#[test]
+fn watch_only_wallet_requires_address_index() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+
+  CommandBuilder::new(format!("wallet --descriptor {} outputs", descriptor()))
+    .rpc_server(&rpc_server)
+    .expected_stderr(
+      "error: watch-only wallets require index created with `--index-addresses` flag\n",
+    )
+    .expected_exit_code(1)
+    .run_and_extract_stdout();
+}
+
+#[test]
+fn watch_only_wallet_does_not_support_commands_that_spend() {
+  let rpc_server = test_bitcoincore_rpc::spawn();
+
+  CommandBuilder::new(format!(
+    "--index-addresses wallet --descriptor {} send --fee-rate 1 bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4 1btc",
+    descriptor()
+  ))
+  .rpc_server(&rpc_server)
+  .expected_stderr(
+    "error: watch-only wallets only support the `balance`, `cardinals`, `inscriptions`, `outputs` and `sats` commands\n",
+  )
+  .expected_exit_code(1)
+  .run_and_extract_stdout();
+}